@@ -5,18 +5,22 @@ use platform::{
     dispatcher::{AlarmsDispatcher, Id},
     message::Response as MessageResponse,
 };
-use sdk::cosmwasm_std::{Addr, Env, QuerierWrapper, Storage, Timestamp};
-use time_oracle::Alarms;
+use sdk::cosmwasm_std::{Addr, Binary, Env, QuerierWrapper, Storage, Timestamp};
+use time_oracle::{Alarm, Alarms, Recurrence};
 
 use crate::{
-    msg::{AlarmsCount, AlarmsStatusResponse, ExecuteAlarmMsg},
+    msg::{
+        AlarmId, AlarmsCount, AlarmsResponse, AlarmsStatusResponse, ExecuteAlarmMsg,
+        RecurringAlarm, ScheduledAlarm,
+    },
     result::ContractResult,
     ContractError,
 };
 
-const ALARMS_NAMESPACE: &str = "alarms";
-const ALARMS_IDX_NAMESPACE: &str = "alarms_idx";
-const IN_DELIVERY_NAMESPACE: &str = "in_delivery";
+pub(super) const ALARMS_NAMESPACE: &str = "alarms";
+pub(super) const ALARMS_IDX_NAMESPACE: &str = "alarms_idx";
+pub(super) const IN_DELIVERY_NAMESPACE: &str = "in_delivery";
+pub(super) const MIGRATION_CURSOR_NAMESPACE: &str = "alarms_migration";
 const REPLY_ID: Id = 0;
 const EVENT_TYPE: &str = "timealarm";
 
@@ -52,6 +56,17 @@ where
 
         Ok(AlarmsStatusResponse { remaining_alarms })
     }
+
+    pub fn try_query_alarms(&self, subscriber: Addr) -> ContractResult<AlarmsResponse> {
+        self.time_alarms
+            .subscriber_alarms(subscriber)
+            .map(|alarm| {
+                alarm.map(|(time, Alarm { id, payload, .. })| ScheduledAlarm { id, time, payload })
+            })
+            .collect::<Result<_, _>>()
+            .map(|alarms| AlarmsResponse { alarms })
+            .map_err(Into::into)
+    }
 }
 
 impl<'storage, S> TimeAlarms<'storage, S>
@@ -63,7 +78,9 @@ where
         querier: QuerierWrapper<'_>,
         env: &Env,
         subscriber: Addr,
+        id: AlarmId,
         time: Timestamp,
+        payload: Option<Binary>,
     ) -> ContractResult<MessageResponse> {
         if time < env.block.time {
             return Err(ContractError::InvalidAlarm(time));
@@ -71,10 +88,59 @@ where
 
         contract::validate_addr(querier, &subscriber)
             .map_err(ContractError::from)
-            .and_then(|()| self.time_alarms.add(subscriber, time).map_err(Into::into))
+            .and_then(|()| {
+                self.time_alarms
+                    .add(subscriber, id, time, payload)
+                    .map_err(Into::into)
+            })
+            .map(|()| Default::default())
+    }
+
+    pub fn try_add_recurring(
+        &mut self,
+        querier: QuerierWrapper<'_>,
+        env: &Env,
+        subscriber: Addr,
+        RecurringAlarm {
+            start,
+            period,
+            until,
+            id,
+            payload,
+        }: RecurringAlarm,
+    ) -> ContractResult<MessageResponse> {
+        if start < env.block.time {
+            return Err(ContractError::InvalidAlarm(start));
+        }
+        if period.secs() == 0 {
+            return Err(ContractError::InvalidAlarmPeriod(period));
+        }
+        if let Some(until) = until.filter(|until| until < &start) {
+            return Err(ContractError::InvalidAlarm(until));
+        }
+
+        contract::validate_addr(querier, &subscriber)
+            .map_err(ContractError::from)
+            .and_then(|()| {
+                self.time_alarms
+                    .add_recurring(
+                        subscriber,
+                        id,
+                        Recurrence::new(start, period.secs(), until),
+                        payload,
+                    )
+                    .map_err(Into::into)
+            })
             .map(|()| Default::default())
     }
 
+    pub fn try_remove(&mut self, subscriber: Addr, id: AlarmId) -> ContractResult<MessageResponse> {
+        self.time_alarms
+            .remove(subscriber, id)
+            .map(|()| Default::default())
+            .map_err(Into::into)
+    }
+
     pub fn try_notify(
         &mut self,
         ctime: Timestamp,
@@ -87,16 +153,29 @@ where
             .collect::<Result<Vec<_>, _>>()?
             .into_iter()
             .try_fold(
-                AlarmsDispatcher::new(ExecuteAlarmMsg::TimeAlarm {}, EVENT_TYPE),
+                AlarmsDispatcher::new(
+                    ExecuteAlarmMsg::TimeAlarm {
+                        id: AlarmId::default(),
+                        payload: None,
+                    },
+                    EVENT_TYPE,
+                ),
                 |dispatcher: AlarmsDispatcher<ExecuteAlarmMsg>,
-                 subscriber: Addr|
+                 alarm: Alarm|
                  -> ContractResult<_> {
                     dispatcher
-                        .send_to(subscriber.clone(), REPLY_ID)
+                        .send_message_to(
+                            alarm.subscriber.clone(),
+                            &ExecuteAlarmMsg::TimeAlarm {
+                                id: alarm.id.clone(),
+                                payload: alarm.payload.clone(),
+                            },
+                            REPLY_ID,
+                        )
                         .map_err(Into::into)
                         .and_then(|dispatcher| {
                             self.time_alarms
-                                .out_for_delivery(subscriber)
+                                .out_for_delivery(alarm, ctime)
                                 .map(|()| dispatcher)
                                 .map_err(Into::into)
                         })
@@ -105,7 +184,7 @@ where
             .map(|dispatcher| (dispatcher.nb_sent(), dispatcher.into()))
     }
 
-    pub fn last_delivered(&mut self) -> ContractResult<()> {
+    pub fn last_delivered(&mut self) -> ContractResult<Addr> {
         self.time_alarms.last_delivered().map_err(Into::into)
     }
 
@@ -121,10 +200,15 @@ mod tests {
     use platform::contract;
     use sdk::cosmwasm_std::{
         testing::{self, mock_dependencies, MockQuerier},
-        Addr, QuerierWrapper, Timestamp,
+        Addr, Binary, QuerierWrapper, Timestamp,
     };
 
-    use crate::ContractError;
+    use finance::duration::Duration;
+
+    use crate::{
+        msg::{AlarmId, AlarmsResponse, RecurringAlarm, ScheduledAlarm},
+        ContractError,
+    };
 
     use super::TimeAlarms;
 
@@ -141,7 +225,9 @@ mod tests {
                 deps.querier,
                 &env,
                 msg_sender.clone(),
+                AlarmId::default(),
                 Timestamp::from_nanos(8),
+                None,
             )
             .is_err());
 
@@ -150,7 +236,14 @@ mod tests {
             .into();
 
         let result = TimeAlarms::new(deps.storage)
-            .try_add(deps.querier, &env, msg_sender, Timestamp::from_nanos(8))
+            .try_add(
+                deps.querier,
+                &env,
+                msg_sender,
+                AlarmId::default(),
+                Timestamp::from_nanos(8),
+                None,
+            )
             .unwrap_err();
 
         assert_eq!(expected_error, result);
@@ -169,7 +262,14 @@ mod tests {
 
         let msg_sender = Addr::unchecked("some address");
         assert!(TimeAlarms::new(deps.storage)
-            .try_add(deps.querier, &env, msg_sender, Timestamp::from_nanos(4),)
+            .try_add(
+                deps.querier,
+                &env,
+                msg_sender,
+                AlarmId::default(),
+                Timestamp::from_nanos(4),
+                None,
+            )
             .is_ok());
     }
 
@@ -187,7 +287,174 @@ mod tests {
 
         let msg_sender = Addr::unchecked("some address");
         TimeAlarms::new(deps.storage)
-            .try_add(deps.querier, &env, msg_sender, Timestamp::from_nanos(4))
+            .try_add(
+                deps.querier,
+                &env,
+                msg_sender,
+                AlarmId::default(),
+                Timestamp::from_nanos(4),
+                None,
+            )
             .unwrap_err();
     }
+
+    #[test]
+    fn add_remove_keyed_alarms() {
+        let mut mock_querier = MockQuerier::default();
+        mock_querier.update_wasm(contract::testing::valid_contract_handler);
+        let querier = QuerierWrapper::new(&mock_querier);
+        let mut deps_temp = mock_dependencies();
+        let mut deps = deps_temp.as_mut();
+        deps.querier = querier;
+        let mut env = testing::mock_env();
+        env.block.time = Timestamp::from_seconds(0);
+
+        let subscriber = Addr::unchecked("some address");
+        let due = Timestamp::from_seconds(10);
+        let overdue = Timestamp::from_seconds(20);
+        let payload = Binary::from(b"payload");
+        let mut time_alarms = TimeAlarms::new(deps.storage);
+
+        time_alarms
+            .try_add(
+                deps.querier,
+                &env,
+                subscriber.clone(),
+                "due".into(),
+                due,
+                Some(payload.clone()),
+            )
+            .unwrap();
+        time_alarms
+            .try_add(
+                deps.querier,
+                &env,
+                subscriber.clone(),
+                "overdue".into(),
+                overdue,
+                None,
+            )
+            .unwrap();
+        assert_eq!(
+            AlarmsResponse {
+                alarms: vec![
+                    ScheduledAlarm {
+                        id: "due".into(),
+                        time: due,
+                        payload: Some(payload),
+                    },
+                    ScheduledAlarm {
+                        id: "overdue".into(),
+                        time: overdue,
+                        payload: None,
+                    }
+                ]
+            },
+            time_alarms.try_query_alarms(subscriber.clone()).unwrap()
+        );
+
+        time_alarms
+            .try_remove(subscriber.clone(), "due".into())
+            .unwrap();
+        assert_eq!(
+            AlarmsResponse {
+                alarms: vec![ScheduledAlarm {
+                    id: "overdue".into(),
+                    time: overdue,
+                    payload: None,
+                }]
+            },
+            time_alarms.try_query_alarms(subscriber).unwrap()
+        );
+    }
+
+    #[test]
+    fn add_recurring_alarm() {
+        let mut mock_querier = MockQuerier::default();
+        mock_querier.update_wasm(contract::testing::valid_contract_handler);
+        let querier = QuerierWrapper::new(&mock_querier);
+        let mut deps_temp = mock_dependencies();
+        let mut deps = deps_temp.as_mut();
+        deps.querier = querier;
+        let mut env = testing::mock_env();
+        env.block.time = Timestamp::from_seconds(100);
+
+        let subscriber = Addr::unchecked("some address");
+        let alarm = RecurringAlarm {
+            start: Timestamp::from_seconds(110),
+            period: Duration::from_secs(10),
+            until: None,
+            id: "cadence".into(),
+            payload: None,
+        };
+        let mut time_alarms = TimeAlarms::new(deps.storage);
+
+        assert_eq!(
+            Err(ContractError::InvalidAlarm(Timestamp::from_seconds(90))),
+            time_alarms.try_add_recurring(
+                deps.querier,
+                &env,
+                subscriber.clone(),
+                RecurringAlarm {
+                    start: Timestamp::from_seconds(90),
+                    ..alarm.clone()
+                },
+            )
+        );
+        assert_eq!(
+            Err(ContractError::InvalidAlarmPeriod(Duration::from_nanos(999))),
+            time_alarms.try_add_recurring(
+                deps.querier,
+                &env,
+                subscriber.clone(),
+                RecurringAlarm {
+                    period: Duration::from_nanos(999),
+                    ..alarm.clone()
+                },
+            )
+        );
+        assert_eq!(
+            Err(ContractError::InvalidAlarm(Timestamp::from_seconds(105))),
+            time_alarms.try_add_recurring(
+                deps.querier,
+                &env,
+                subscriber.clone(),
+                RecurringAlarm {
+                    until: Some(Timestamp::from_seconds(105)),
+                    ..alarm.clone()
+                },
+            )
+        );
+
+        time_alarms
+            .try_add_recurring(deps.querier, &env, subscriber.clone(), alarm)
+            .unwrap();
+        assert_eq!(
+            AlarmsResponse {
+                alarms: vec![ScheduledAlarm {
+                    id: "cadence".into(),
+                    time: Timestamp::from_seconds(110),
+                    payload: None,
+                }]
+            },
+            time_alarms.try_query_alarms(subscriber.clone()).unwrap()
+        );
+
+        // re-armed on dispatch
+        let (sent, _) = time_alarms
+            .try_notify(Timestamp::from_seconds(111), 10)
+            .unwrap();
+        assert_eq!(1, sent);
+        time_alarms.last_delivered().unwrap();
+        assert_eq!(
+            AlarmsResponse {
+                alarms: vec![ScheduledAlarm {
+                    id: "cadence".into(),
+                    time: Timestamp::from_seconds(120),
+                    payload: None,
+                }]
+            },
+            time_alarms.try_query_alarms(subscriber).unwrap()
+        );
+    }
 }
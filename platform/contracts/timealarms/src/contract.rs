@@ -1,27 +1,45 @@
+use std::ops::DerefMut;
+
 use platform::{
     batch::{Emit, Emitter},
+    dispatch_rewards,
+    message::Response as MessageResponse,
     response,
 };
 use sdk::{
     cosmwasm_ext::Response as CwResponse,
     cosmwasm_std::{
-        entry_point, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Storage,
-        SubMsgResult,
+        entry_point, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, SubMsgResult,
     },
 };
-use versioning::{package_version, version, SemVer, Version, VersionSegment};
+use time_oracle::migrate_v2::AlarmsOld;
+use versioning::{package_version, version, FullUpdateOutput, SemVer, Version, VersionSegment};
 
 use crate::{
-    alarms::TimeAlarms,
-    msg::{DispatchAlarmsResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, SudoMsg},
+    alarms::{self, TimeAlarms},
+    msg::{
+        AlarmsStatusResponse, DispatchAlarmsResponse, DispatchRewardsResponse, ExecuteMsg,
+        InstantiateMsg, MigrateMsg, QueryMsg, SudoMsg,
+    },
     result::ContractResult,
 };
 
-// const CONTRACT_STORAGE_VERSION_FROM: VersionSegment = 0;
-const CONTRACT_STORAGE_VERSION: VersionSegment = 1;
+const CONTRACT_STORAGE_VERSION_FROM: VersionSegment = 1;
+const CONTRACT_STORAGE_VERSION: VersionSegment = 2;
 const PACKAGE_VERSION: SemVer = package_version!();
 const CONTRACT_VERSION: Version = version!(CONTRACT_STORAGE_VERSION, PACKAGE_VERSION);
 
+/// The alarms migrated on the contract migration
+///
+/// The rest are migrated in place of dispatching on the next [`ExecuteMsg::DispatchAlarms`].
+const MIGRATE_BATCH_SIZE: usize = 64;
+const ALARMS_OLD: AlarmsOld = AlarmsOld::new(
+    alarms::ALARMS_NAMESPACE,
+    alarms::ALARMS_IDX_NAMESPACE,
+    alarms::IN_DELIVERY_NAMESPACE,
+    alarms::MIGRATION_CURSOR_NAMESPACE,
+);
+
 #[entry_point]
 pub fn instantiate(
     deps: DepsMut<'_>,
@@ -40,66 +58,128 @@ pub fn migrate(
     _env: Env,
     MigrateMsg {}: MigrateMsg,
 ) -> ContractResult<CwResponse> {
-    versioning::update_software(deps.storage, CONTRACT_VERSION, Into::into)
-        .and_then(response::response)
+    versioning::update_software_and_storage::<CONTRACT_STORAGE_VERSION_FROM, _, _, _, _>(
+        deps.storage,
+        CONTRACT_VERSION,
+        |storage| {
+            ALARMS_OLD
+                .start(storage)
+                .and_then(|()| ALARMS_OLD.migrate(storage, MIGRATE_BATCH_SIZE))
+                .map(|_completed| ())
+                .map_err(Into::into)
+        },
+        Into::into,
+    )
+    .and_then(
+        |FullUpdateOutput {
+             release_label,
+             storage_migration_output: (),
+         }| response::response(release_label),
+    )
 }
 
 #[entry_point]
 pub fn execute(
-    deps: DepsMut<'_>,
+    mut deps: DepsMut<'_>,
     env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> ContractResult<CwResponse> {
-    let mut time_alarms: TimeAlarms<'_, &mut dyn Storage> = TimeAlarms::new(deps.storage);
-
     match msg {
-        ExecuteMsg::AddAlarm { time } => time_alarms
-            .try_add(deps.querier, &env, info.sender, time)
+        ExecuteMsg::AddAlarm { time, id, payload } => TimeAlarms::new(deps.storage)
+            .try_add(deps.querier, &env, info.sender, id, time, payload)
+            .map(response::response_only_messages),
+        ExecuteMsg::AddRecurringAlarm(alarm) => TimeAlarms::new(deps.storage)
+            .try_add_recurring(deps.querier, &env, info.sender, alarm)
             .map(response::response_only_messages),
-        ExecuteMsg::DispatchAlarms { max_count } => time_alarms
-            .try_notify(env.block.time, max_count)
-            .and_then(|(total, resp)| {
-                response::response_with_messages(DispatchAlarmsResponse(total), resp)
-            }),
+        ExecuteMsg::RemoveAlarm { id } => TimeAlarms::new(deps.storage)
+            .try_remove(info.sender, id)
+            .map(response::response_only_messages),
+        ExecuteMsg::DispatchAlarms { max_count } if ALARMS_OLD.in_progress(deps.storage)? => {
+            usize::try_from(max_count)
+                .map_err(Into::into)
+                .and_then(|max_count| {
+                    ALARMS_OLD
+                        .migrate(deps.storage, max_count)
+                        .map_err(Into::into)
+                })
+                .and_then(|_completed| response::response(DispatchAlarmsResponse(0)))
+        }
+        ExecuteMsg::DispatchAlarms { max_count } => {
+            let (total, resp) =
+                TimeAlarms::new(deps.storage.deref_mut()).try_notify(env.block.time, max_count)?;
+
+            dispatch_rewards::dispatched(deps.storage, info.sender, total)
+                .map_err(Into::into)
+                .and_then(|()| {
+                    response::response_with_messages(DispatchAlarmsResponse(total), resp)
+                })
+        }
     }
 }
 
 #[entry_point]
-pub fn sudo(_deps: DepsMut<'_>, _env: Env, msg: SudoMsg) -> ContractResult<CwResponse> {
-    match msg {}
+pub fn sudo(deps: DepsMut<'_>, env: Env, msg: SudoMsg) -> ContractResult<CwResponse> {
+    match msg {
+        SudoMsg::DispatchRewards {
+            per_alarm,
+            top_up,
+            leasers,
+        } => {
+            dispatch_rewards::try_fund(deps.storage, deps.querier, &env, per_alarm, top_up, leasers)
+                .map(|()| response::empty_response())
+                .map_err(Into::into)
+        }
+    }
 }
 
 #[entry_point]
 pub fn query(deps: Deps<'_>, env: Env, msg: QueryMsg) -> ContractResult<Binary> {
     match msg {
         QueryMsg::ContractVersion {} => Ok(to_json_binary(&PACKAGE_VERSION)?),
-        QueryMsg::AlarmsStatus {} => Ok(to_json_binary(
-            &TimeAlarms::new(deps.storage).try_any_alarm(env.block.time)?,
+        QueryMsg::AlarmsStatus {} => {
+            Ok(to_json_binary(&if ALARMS_OLD.in_progress(deps.storage)? {
+                // keep the dispatcher going until the migration completes
+                AlarmsStatusResponse {
+                    remaining_alarms: true,
+                }
+            } else {
+                TimeAlarms::new(deps.storage).try_any_alarm(env.block.time)?
+            })?)
+        }
+        QueryMsg::Alarms { subscriber } => Ok(to_json_binary(
+            &TimeAlarms::new(deps.storage).try_query_alarms(subscriber)?,
         )?),
+        QueryMsg::DispatchRewards {} => Ok(to_json_binary(&DispatchRewardsResponse::from(
+            dispatch_rewards::load(deps.storage)?,
+        ))?),
     }
 }
 
 #[entry_point]
-pub fn reply(deps: DepsMut<'_>, env: Env, msg: Reply) -> ContractResult<CwResponse> {
+pub fn reply(mut deps: DepsMut<'_>, env: Env, msg: Reply) -> ContractResult<CwResponse> {
     const EVENT_TYPE: &str = "time-alarm";
     const KEY_DELIVERED: &str = "delivered";
     const KEY_DETAILS: &str = "details";
 
     let emitter: Emitter = Emitter::of_type(EVENT_TYPE);
 
-    let mut time_alarms: TimeAlarms<'_, &mut dyn Storage> = TimeAlarms::new(deps.storage);
-
     Ok(response::response_only_messages(match msg.result {
         SubMsgResult::Ok(_) => {
-            time_alarms.last_delivered()?;
-
-            emitter.emit(KEY_DELIVERED, "success")
+            let subscriber = TimeAlarms::new(deps.storage.deref_mut()).last_delivered()?;
+
+            dispatch_rewards::delivered(
+                deps.storage,
+                deps.querier,
+                &env,
+                &subscriber,
+                emitter.emit(KEY_DELIVERED, "success"),
+            )?
         }
         SubMsgResult::Err(err) => {
-            time_alarms.last_failed(env.block.time)?;
+            TimeAlarms::new(deps.storage).last_failed(env.block.time)?;
 
-            emitter.emit(KEY_DELIVERED, "error").emit(KEY_DETAILS, err)
+            MessageResponse::from(emitter.emit(KEY_DELIVERED, "error").emit(KEY_DETAILS, err))
         }
     }))
 }
@@ -2,6 +2,7 @@ use std::num::TryFromIntError;
 
 use thiserror::Error;
 
+use finance::duration::Duration;
 use sdk::cosmwasm_std::{Addr, StdError, Timestamp};
 use time_oracle::AlarmError;
 
@@ -19,6 +20,9 @@ pub enum ContractError {
     #[error("[TimeAlarms] Alarm is in the past: {0:?}")]
     InvalidAlarm(Timestamp),
 
+    #[error("[TimeAlarms] Alarm period is shorter than a second: {0:?}")]
+    InvalidAlarmPeriod(Duration),
+
     #[error("[TimeAlarms] {0}")]
     Platform(#[from] platform::error::Error),
 
@@ -1,11 +1,15 @@
 use serde::{Deserialize, Serialize};
 
+use currency::NlsPlatform;
+use finance::{coin::Coin, duration::Duration};
 use sdk::{
-    cosmwasm_std::Timestamp,
+    cosmwasm_std::{Addr, Binary, Timestamp},
     schemars::{self, JsonSchema},
 };
 
 pub type AlarmsCount = platform::dispatcher::AlarmsCount;
+pub type AlarmId = time_oracle::AlarmId;
+pub type DispatchRewardsResponse = platform::dispatch_rewards::DispatchRewardsResponse;
 
 #[derive(Serialize, Deserialize, Clone, Eq, PartialEq, JsonSchema)]
 #[cfg_attr(any(test, feature = "testing"), derive(Debug))]
@@ -20,32 +24,87 @@ pub struct MigrateMsg {}
 #[cfg_attr(any(test, feature = "testing"), derive(Debug))]
 #[serde(deny_unknown_fields, rename_all = "snake_case")]
 pub enum ExecuteMsg {
+    /// Schedule an alarm replacing any pending one of the sender with the same id
+    ///
+    /// The id and the payload are echoed back in [`ExecuteAlarmMsg::TimeAlarm`].
     AddAlarm {
         time: Timestamp,
+        #[serde(default, skip_serializing_if = "AlarmId::is_empty")]
+        id: AlarmId,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        payload: Option<Binary>,
     },
-    /// Returns [`DispatchAlarmsResponse`] as response data.
-    DispatchAlarms {
-        max_count: AlarmsCount,
+    /// Schedule a recurring alarm replacing any pending one of the sender with the same id
+    AddRecurringAlarm(RecurringAlarm),
+    /// Remove a pending alarm of the sender, if any
+    RemoveAlarm {
+        #[serde(default)]
+        id: AlarmId,
     },
+    /// Returns [`DispatchAlarmsResponse`] as response data.
+    ///
+    /// While the alarms migration is in progress, up to `max_count` alarms are migrated
+    /// instead and none are dispatched.
+    DispatchAlarms { max_count: AlarmsCount },
+}
+
+/// An alarm going off at `start`, and then every `period`, until `until`, if set
+///
+/// It is re-armed on each delivery so a failed delivery does not stop the recurrence.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Debug))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct RecurringAlarm {
+    pub start: Timestamp,
+    pub period: Duration,
+    #[serde(default)]
+    pub until: Option<Timestamp>,
+    #[serde(default, skip_serializing_if = "AlarmId::is_empty")]
+    pub id: AlarmId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload: Option<Binary>,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
 #[cfg_attr(any(test, feature = "testing"), derive(Debug))]
 #[serde(deny_unknown_fields, rename_all = "snake_case")]
-pub enum SudoMsg {}
+pub enum SudoMsg {
+    /// Set the reward paid per alarm delivered successfully to a lease and top up the budget
+    ///
+    /// The contract NLS balance should cover the resulting budget. Only the alarms
+    /// of the leases verified by one of the `leasers` are rewarded.
+    DispatchRewards {
+        per_alarm: Coin<NlsPlatform>,
+        top_up: Coin<NlsPlatform>,
+        leasers: Vec<Addr>,
+    },
+}
 
 #[derive(Serialize, Deserialize)]
 #[serde(deny_unknown_fields, rename_all = "snake_case")]
 pub enum QueryMsg {
     ContractVersion {},
     AlarmsStatus {},
+    /// Returns [`AlarmsResponse`]
+    Alarms {
+        subscriber: Addr,
+    },
+    /// Returns [`DispatchRewardsResponse`]
+    DispatchRewards {},
 }
 
-#[derive(Serialize, Deserialize, Clone, Copy, Eq, PartialEq, JsonSchema)]
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, JsonSchema)]
 #[cfg_attr(any(test, feature = "testing"), derive(Debug))]
 #[serde(deny_unknown_fields, rename_all = "snake_case")]
 pub enum ExecuteAlarmMsg {
-    TimeAlarm {},
+    /// The default id and an absent payload are not serialized
+    /// to keep the message intact for subscribers of a single alarm
+    TimeAlarm {
+        #[serde(default, skip_serializing_if = "AlarmId::is_empty")]
+        id: AlarmId,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        payload: Option<Binary>,
+    },
 }
 
 #[derive(Serialize, Deserialize, JsonSchema)]
@@ -60,3 +119,53 @@ pub struct DispatchAlarmsResponse(pub AlarmsCount);
 pub struct AlarmsStatusResponse {
     pub remaining_alarms: bool,
 }
+
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Debug))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct ScheduledAlarm {
+    pub id: AlarmId,
+    pub time: Timestamp,
+    pub payload: Option<Binary>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(any(test, feature = "testing"), derive(PartialEq, Debug))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct AlarmsResponse {
+    pub alarms: Vec<ScheduledAlarm>,
+}
+
+#[cfg(test)]
+mod test {
+    use sdk::cosmwasm_std::{self, Binary};
+
+    use super::{AlarmId, ExecuteAlarmMsg};
+
+    #[test]
+    fn time_alarm_default() {
+        let msg = ExecuteAlarmMsg::TimeAlarm {
+            id: AlarmId::default(),
+            payload: None,
+        };
+        let serialized = cosmwasm_std::to_json_vec(&msg).unwrap();
+
+        assert_eq!(br#"{"time_alarm":{}}"#.as_slice(), serialized.as_slice());
+        assert_eq!(msg, cosmwasm_std::from_json(serialized).unwrap());
+    }
+
+    #[test]
+    fn time_alarm_keyed() {
+        let msg = ExecuteAlarmMsg::TimeAlarm {
+            id: "due".into(),
+            payload: Some(Binary::from(b"{}")),
+        };
+        let serialized = cosmwasm_std::to_json_vec(&msg).unwrap();
+
+        assert_eq!(
+            br#"{"time_alarm":{"id":"due","payload":"e30="}}"#.as_slice(),
+            serialized.as_slice()
+        );
+        assert_eq!(msg, cosmwasm_std::from_json(serialized).unwrap());
+    }
+}
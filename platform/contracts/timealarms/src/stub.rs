@@ -3,9 +3,12 @@ use std::result::Result as StdResult;
 use serde::{Deserialize, Serialize};
 
 use platform::{batch::Batch, contract};
-use sdk::cosmwasm_std::{wasm_execute, Addr, QuerierWrapper, Timestamp};
+use sdk::cosmwasm_std::{wasm_execute, Addr, Binary, QuerierWrapper, Timestamp};
 
-use crate::{msg::ExecuteMsg, ContractError};
+use crate::{
+    msg::{AlarmId, ExecuteMsg, RecurringAlarm},
+    ContractError,
+};
 
 pub type Result<T> = StdResult<T, ContractError>;
 
@@ -14,6 +17,17 @@ where
     Self: Into<Batch>,
 {
     fn add_alarm(&mut self, time: Timestamp) -> Result<()>;
+
+    fn add_keyed_alarm(
+        &mut self,
+        id: AlarmId,
+        time: Timestamp,
+        payload: Option<Binary>,
+    ) -> Result<()>;
+
+    fn add_recurring_alarm(&mut self, alarm: RecurringAlarm) -> Result<()>;
+
+    fn remove_alarm(&mut self, id: AlarmId) -> Result<()>;
 }
 
 pub trait WithTimeAlarms {
@@ -48,6 +62,29 @@ impl TimeAlarmsRef {
         Ok(stub.into())
     }
 
+    pub fn setup_keyed_alarm(
+        &self,
+        id: AlarmId,
+        when: Timestamp,
+        payload: Option<Binary>,
+    ) -> Result<Batch> {
+        let mut stub = self.as_stub();
+        stub.add_keyed_alarm(id, when, payload)?;
+        Ok(stub.into())
+    }
+
+    pub fn setup_recurring_alarm(&self, alarm: RecurringAlarm) -> Result<Batch> {
+        let mut stub = self.as_stub();
+        stub.add_recurring_alarm(alarm)?;
+        Ok(stub.into())
+    }
+
+    pub fn cancel_alarm(&self, id: AlarmId) -> Result<Batch> {
+        let mut stub = self.as_stub();
+        stub.remove_alarm(id)?;
+        Ok(stub.into())
+    }
+
     /// It would be overengineering to hide the `TimeAlarms` implementation
     fn as_stub(&self) -> TimeAlarmsStub<'_> {
         TimeAlarmsStub {
@@ -82,9 +119,38 @@ impl<'a> TimeAlarmsStub<'a> {
 
 impl<'a> TimeAlarms for TimeAlarmsStub<'a> {
     fn add_alarm(&mut self, time: Timestamp) -> Result<()> {
+        self.add_keyed_alarm(AlarmId::default(), time, None)
+    }
+
+    fn add_keyed_alarm(
+        &mut self,
+        id: AlarmId,
+        time: Timestamp,
+        payload: Option<Binary>,
+    ) -> Result<()> {
+        self.batch.schedule_execute_no_reply(wasm_execute(
+            self.addr().clone(),
+            &ExecuteMsg::AddAlarm { time, id, payload },
+            vec![],
+        )?);
+
+        Ok(())
+    }
+
+    fn add_recurring_alarm(&mut self, alarm: RecurringAlarm) -> Result<()> {
+        self.batch.schedule_execute_no_reply(wasm_execute(
+            self.addr().clone(),
+            &ExecuteMsg::AddRecurringAlarm(alarm),
+            vec![],
+        )?);
+
+        Ok(())
+    }
+
+    fn remove_alarm(&mut self, id: AlarmId) -> Result<()> {
         self.batch.schedule_execute_no_reply(wasm_execute(
             self.addr().clone(),
-            &ExecuteMsg::AddAlarm { time },
+            &ExecuteMsg::RemoveAlarm { id },
             vec![],
         )?);
 
@@ -14,7 +14,10 @@ use sdk::{
         QuerierWrapper, Storage, Timestamp,
     },
 };
-use timealarms::stub::TimeAlarmsRef;
+use timealarms::{
+    msg::{AlarmId, RecurringAlarm},
+    stub::TimeAlarmsRef,
+};
 use versioning::{package_version, version, SemVer, Version, VersionSegment};
 
 use crate::{
@@ -44,9 +47,18 @@ pub fn instantiate(
 }
 
 #[entry_point]
-pub fn migrate(deps: DepsMut<'_>, _env: Env, _msg: MigrateMsg) -> ContractResult<CwResponse> {
-    versioning::update_software(deps.storage, CONTRACT_VERSION, Into::into)
-        .and_then(response::response)
+pub fn migrate(deps: DepsMut<'_>, env: Env, _msg: MigrateMsg) -> ContractResult<CwResponse> {
+    versioning::update_software(deps.storage, CONTRACT_VERSION, Into::into).and_then(
+        |release_label| {
+            // replace the pending one-shot alarm with a recurring one keeping the cadence
+            let cadence = try_load_config(deps.storage)
+                .map(|config| Duration::from_hours(config.cadence_hours))?;
+            let start = (DispatchLog::last_dispatch(deps.storage) + cadence).max(env.block.time);
+
+            setup_alarm(timealarms(deps.storage)?, start, cadence, deps.querier)
+                .and_then(|alarm| response::response_with_messages(release_label, alarm))
+        },
+    )
 }
 
 #[entry_point]
@@ -64,17 +76,23 @@ pub fn execute(
             )
             .check(&info.sender)?;
 
-            try_dispatch(deps, &env, info.sender).map(response::response_only_messages)
+            try_dispatch(deps, &env).map(response::response_only_messages)
         }
     }
 }
 
 #[entry_point]
-pub fn sudo(deps: DepsMut<'_>, _env: Env, msg: SudoMsg) -> ContractResult<CwResponse> {
+pub fn sudo(deps: DepsMut<'_>, env: Env, msg: SudoMsg) -> ContractResult<CwResponse> {
     match msg {
         SudoMsg::Config { cadence_hours } => {
             Config::update_cadence_hours(deps.storage, cadence_hours)
-                .map(|()| response::empty_response())
+                .and_then(|()| timealarms(deps.storage))
+                .and_then(|timealarms| {
+                    let cadence = Duration::from_hours(cadence_hours);
+
+                    setup_alarm(timealarms, env.block.time + cadence, cadence, deps.querier)
+                })
+                .map(response::response_only_messages)
         }
         SudoMsg::Rewards { tvl_to_apr } => {
             Config::update_tvl_to_apr(deps.storage, tvl_to_apr).map(|()| response::empty_response())
@@ -134,24 +152,16 @@ fn query_reward_apr(
         .map(|rewards| rewards.apr())
 }
 
-fn try_dispatch(deps: DepsMut<'_>, env: &Env, timealarm: Addr) -> ContractResult<MessageResponse> {
+fn try_dispatch(deps: DepsMut<'_>, env: &Env) -> ContractResult<MessageResponse> {
     let now = env.block.time;
 
     let config = try_load_config(deps.storage)?;
-    let setup_alarm = setup_alarm(
-        timealarm,
-        &now,
-        Duration::from_hours(config.cadence_hours),
-        deps.querier,
-    )?;
 
     let last_dispatch = DispatchLog::last_dispatch(deps.storage);
     DispatchLog::update(deps.storage, env.block.time)?;
     let rewards_span = Duration::between(&last_dispatch, &now);
 
-    try_build_reward(config, deps.querier, env)
-        .and_then(|reward| reward.distribute(rewards_span))
-        .map(|dispatch_res| dispatch_res.merge_with(MessageResponse::messages_only(setup_alarm)))
+    try_build_reward(config, deps.querier, env).and_then(|reward| reward.distribute(rewards_span))
 }
 
 fn protocols(
@@ -177,17 +187,29 @@ fn protocols(
         .map_err(Into::into)
 }
 
+fn timealarms(storage: &dyn Storage) -> ContractResult<Addr> {
+    SingleUserAccess::new(storage, crate::access_control::TIMEALARMS_NAMESPACE)
+        .granted_to()
+        .map_err(Into::into)
+}
+
 fn setup_alarm(
     timealarm: Addr,
-    now: &Timestamp,
-    alarm_in: Duration,
+    start: Timestamp,
+    cadence: Duration,
     querier: QuerierWrapper<'_>,
 ) -> ContractResult<Batch> {
     TimeAlarmsRef::new(timealarm, querier)
         .map_err(ContractError::SetupTimeAlarmStub)
         .and_then(|stub| {
-            stub.setup_alarm(now + alarm_in)
-                .map_err(ContractError::SetupTimeAlarm)
+            stub.setup_recurring_alarm(RecurringAlarm {
+                start,
+                period: cadence,
+                until: None,
+                id: AlarmId::default(),
+                payload: None,
+            })
+            .map_err(ContractError::SetupTimeAlarm)
         })
 }
 
@@ -216,12 +238,9 @@ fn setup_dispatching(
         .map_err(ContractError::SaveConfig)?;
     DispatchLog::update(storage, env.block.time)?;
 
-    setup_alarm(
-        msg.timealarms,
-        &env.block.time,
-        Duration::from_hours(msg.cadence_hours),
-        querier,
-    )
+    let cadence = Duration::from_hours(msg.cadence_hours);
+
+    setup_alarm(msg.timealarms, env.block.time + cadence, cadence, querier)
 }
 
 #[cfg(test)]
@@ -307,7 +326,8 @@ mod tests {
         )
         .unwrap();
 
-        assert_eq!(messages.len(), 0);
+        // the recurring alarm is re-registered with the new cadence
+        assert_eq!(messages.len(), 1);
         assert_eq!(attributes.len(), 0);
         assert_eq!(events.len(), 0);
         assert_eq!(data, None);
@@ -330,7 +350,7 @@ mod tests {
         )
         .unwrap();
 
-        assert_eq!(messages.len(), 0);
+        assert_eq!(messages.len(), 1);
         assert_eq!(attributes.len(), 0);
         assert_eq!(events.len(), 0);
         assert_eq!(data, None);
@@ -39,10 +39,14 @@ where
     }
 
     pub fn check(&self, user: &Addr) -> Result {
+        self.granted_to()
+            .and_then(|granted_to| check(&granted_to, user))
+    }
+
+    pub fn granted_to(&self) -> std::result::Result<Addr, Error> {
         self.storage_item
             .load(self.storage.deref())
             .map_err(Into::into)
-            .and_then(|granted_to| check(&granted_to, user))
     }
 }
 
@@ -80,8 +84,10 @@ mod tests {
         let user = Addr::unchecked("cosmic address");
 
         assert!(access.check(&user).is_err());
+        assert!(access.granted_to().is_err());
         access.grant_to(&user).unwrap();
         access.check(&user).unwrap();
+        assert_eq!(Ok(user), access.granted_to());
     }
 
     #[test]
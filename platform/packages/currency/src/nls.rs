@@ -12,6 +12,7 @@ use crate::{AnyVisitor, Currency, Group, Matcher, MaybeAnyVisitResult, SymbolSli
 /// Intended to be used *only* until the TODO below gets done, and *only* in dex-independent usecases:
 /// - LP rewards
 /// - Relayers' tips
+/// - Alarm dispatchers' rewards
 pub struct NlsPlatform;
 
 impl Currency for NlsPlatform {
@@ -78,6 +78,14 @@ impl Liability {
         self.healthy
     }
 
+    pub const fn first_liq_warn(&self) -> Percent {
+        self.first_liq_warn
+    }
+
+    pub const fn second_liq_warn(&self) -> Percent {
+        self.second_liq_warn
+    }
+
     pub const fn third_liq_warn(&self) -> Percent {
         self.third_liq_warn
     }
@@ -119,6 +127,25 @@ impl Liability {
             .unwrap_or(default_borrow)
     }
 
+    /// The maximum amount that may be additionally borrowed against an open position
+    ///
+    /// Post-assert: (total_due + max_borrow_amount) / (lease_amount + max_borrow_amount) ~= self.initial, if total_due < self.initial% of lease_amount.
+    /// Otherwise, max_borrow_amount == 0
+    pub fn max_borrow_amount<P>(&self, lease_amount: P, total_due: P) -> P
+    where
+        P: Percentable + Copy + Ord + Sub<Output = P> + Zero,
+    {
+        debug_assert!(self.initial > Percent::ZERO);
+        debug_assert!(self.initial < Percent::HUNDRED);
+
+        // from 'due + borrow = initial% of (lease + borrow)' follows
+        // borrow = 100% / (100% - initial%) of (initial% of lease - due)
+        let multiplier = Rational::new(Percent::HUNDRED, Percent::HUNDRED - self.initial);
+        let initial_liability = self.initial.of(lease_amount);
+        let free_liability = initial_liability - initial_liability.min(total_due);
+        Fraction::<Units>::of(&multiplier, free_liability)
+    }
+
     /// Post-assert: (total_due - amount_to_liquidate) / (lease_amount - amount_to_liquidate) ~= self.healthy_percent(), if total_due < lease_amount.
     /// Otherwise, amount_to_liquidate == total_due
     pub fn amount_to_liquidate<P>(&self, lease_amount: P, total_due: P) -> P
@@ -372,6 +399,40 @@ mod test {
         amount_to_liquidate_int(liability, lease_amount, lease_amount + 10, lease_amount);
     }
 
+    #[test]
+    fn max_borrow_amount() {
+        let initial = 60;
+        let liability = Liability {
+            initial: Percent::from_percent(initial),
+            healthy: Percent::from_percent(85),
+            max: Percent::from_percent(90),
+            first_liq_warn: Percent::from_permille(860),
+            second_liq_warn: Percent::from_permille(865),
+            third_liq_warn: Percent::from_permille(870),
+            recalc_time: Duration::from_secs(20000),
+        };
+        let lease_amount: Amount = 100;
+        let initial_amount = Percent::from_percent(initial).of(lease_amount);
+        max_borrow_amount_int(liability, lease_amount, Amount::ZERO, 150);
+        max_borrow_amount_int(liability, lease_amount, 30, 75);
+        max_borrow_amount_int(liability, lease_amount, initial_amount - 1, 2);
+        max_borrow_amount_int(liability, lease_amount, initial_amount, Amount::ZERO);
+        max_borrow_amount_int(liability, lease_amount, initial_amount + 1, Amount::ZERO);
+        max_borrow_amount_int(liability, lease_amount, lease_amount, Amount::ZERO);
+    }
+
+    #[track_caller]
+    fn max_borrow_amount_int(liability: Liability, lease: Amount, due: Amount, exp: Amount) {
+        let borrow = liability.max_borrow_amount(lease, due);
+        assert_eq!(exp, borrow);
+        if due < liability.initial.of(lease) {
+            assert!(
+                liability.initial.of(lease + exp).abs_diff(due + exp) <= 1,
+                "Lease = {lease}, due = {due}, exp = {exp}"
+            );
+        }
+    }
+
     #[track_caller]
     fn amount_to_liquidate_int(liability: Liability, lease: Amount, due: Amount, exp: Amount) {
         let liq = liability.amount_to_liquidate(lease, due);
@@ -0,0 +1,510 @@
+use serde::{Deserialize, Serialize};
+
+use currency::NlsPlatform;
+use finance::coin::Coin;
+use sdk::{
+    cosmwasm_std::{Addr, Env, QuerierWrapper, Storage},
+    cw_storage_plus::Item,
+    schemars::{self, JsonSchema},
+};
+
+use crate::{
+    bank::{self, FixedAddressSender as _, LazySenderStub},
+    batch::{Batch, Emit, Emitter},
+    contract,
+    dispatcher::AlarmsCount,
+    error::Error,
+    message::Response as MessageResponse,
+    result::Result,
+};
+
+const STORAGE: Item<'static, DispatchRewards> = Item::new("dispatch_rewards");
+const KEY_REWARD_ERROR: &str = "reward-error";
+
+/// A budget of NLS paying a fixed reward per alarm delivered successfully to a lease
+///
+/// The keeper of the last dispatch is rewarded for up to as many deliveries
+/// as the number of alarms it has sent. Since anyone may subscribe for alarms,
+/// only the deliveries to leases, as verified by one of the leasers, are rewarded.
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(any(test, feature = "testing"), derive(PartialEq, Eq, Debug))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct DispatchRewards {
+    per_alarm: Coin<NlsPlatform>,
+    budget: Coin<NlsPlatform>,
+    #[serde(default)]
+    leasers: Vec<Addr>,
+    #[serde(default)]
+    keeper: Option<Keeper>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(any(test, feature = "testing"), derive(PartialEq, Eq, Debug))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+struct Keeper {
+    addr: Addr,
+    rewardable: AlarmsCount,
+    /// The subscribers verified during the dispatch and whether they are leases
+    #[serde(default)]
+    verified: Vec<(Addr, bool)>,
+}
+
+/// The reward for a delivery along with the leaser query failures, if any
+pub struct Reward {
+    messages: Batch,
+    errors: Vec<String>,
+}
+
+impl Reward {
+    fn none(errors: Vec<String>) -> Self {
+        Self {
+            messages: Batch::default(),
+            errors,
+        }
+    }
+
+    fn into_response(self, emitter: Emitter) -> MessageResponse {
+        MessageResponse::messages_with_events(
+            self.messages,
+            self.errors.into_iter().fold(emitter, |emitter, error| {
+                emitter.emit(KEY_REWARD_ERROR, error)
+            }),
+        )
+    }
+}
+
+/// Mirrors the leaser query verifying a lease
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum LeaserQueryMsg<'addr> {
+    IsLease { address: &'addr Addr },
+}
+
+impl DispatchRewards {
+    pub const fn budget(&self) -> Coin<NlsPlatform> {
+        self.budget
+    }
+
+    /// Set the reward per alarm and the leasers, and top up the budget
+    ///
+    /// The caller is responsible for ensuring the contract balance covers the budget.
+    /// The subscribers verified so far are to be verified anew by the new leasers.
+    pub fn fund(
+        self,
+        per_alarm: Coin<NlsPlatform>,
+        top_up: Coin<NlsPlatform>,
+        leasers: Vec<Addr>,
+    ) -> Self {
+        Self {
+            per_alarm,
+            budget: self.budget + top_up,
+            leasers,
+            keeper: self.keeper.map(|keeper| Keeper {
+                verified: vec![],
+                ..keeper
+            }),
+        }
+    }
+
+    pub fn dispatched(self, keeper: Addr, nb_sent: AlarmsCount) -> Self {
+        Self {
+            keeper: (nb_sent != 0).then_some(Keeper {
+                addr: keeper,
+                rewardable: nb_sent,
+                verified: vec![],
+            }),
+            ..self
+        }
+    }
+
+    /// Reward the keeper for a successful delivery to a lease if still rewardable and funded
+    ///
+    /// The reward is paid out of the balance of `account` only if it still covers it.
+    pub fn delivered(
+        self,
+        subscriber: &Addr,
+        querier: QuerierWrapper<'_>,
+        account: &Addr,
+    ) -> Result<(Self, Reward)> {
+        let Some(mut keeper) = self.keeper.clone() else {
+            return Ok((self, Reward::none(vec![])));
+        };
+        if keeper.rewardable == 0 || self.per_alarm.is_zero() || self.budget < self.per_alarm {
+            return Ok((self, Reward::none(vec![])));
+        }
+
+        let (is_lease, errors) = keeper.verify(subscriber, &self.leasers, querier);
+        let rewards = Self {
+            keeper: Some(keeper.clone()),
+            ..self
+        };
+        if !is_lease {
+            return Ok((rewards, Reward::none(errors)));
+        }
+
+        bank::balance::<NlsPlatform>(account, querier).map(|balance| {
+            if balance < rewards.per_alarm {
+                (rewards, Reward::none(errors))
+            } else {
+                let mut sender = LazySenderStub::new(keeper.addr.clone());
+                sender.send(rewards.per_alarm);
+
+                (
+                    Self {
+                        budget: rewards.budget - rewards.per_alarm,
+                        keeper: Some(Keeper {
+                            rewardable: keeper.rewardable - 1,
+                            ..keeper
+                        }),
+                        ..rewards
+                    },
+                    Reward {
+                        messages: sender.into(),
+                        errors,
+                    },
+                )
+            }
+        })
+    }
+}
+
+impl Keeper {
+    /// Return whether one of the leasers vouches for the subscriber, and the query failures
+    ///
+    /// A leaser failing the query does not vouch for the subscriber. That way a misconfigured
+    /// leaser cannot block the alarm deliveries. Only the outcomes free of failures are kept
+    /// for the subsequent deliveries of the dispatch.
+    fn verify(
+        &mut self,
+        subscriber: &Addr,
+        leasers: &[Addr],
+        querier: QuerierWrapper<'_>,
+    ) -> (bool, Vec<String>) {
+        if let Some(&(_, is_lease)) = self
+            .verified
+            .iter()
+            .find(|(verified, _)| verified == subscriber)
+        {
+            return (is_lease, vec![]);
+        }
+
+        let mut errors = vec![];
+        let is_lease = leasers.iter().any(|leaser| {
+            querier
+                .query_wasm_smart(
+                    leaser,
+                    &LeaserQueryMsg::IsLease {
+                        address: subscriber,
+                    },
+                )
+                .unwrap_or_else(|error| {
+                    errors.push(format!("leaser {leaser}: {error}"));
+                    false
+                })
+        });
+        if is_lease || errors.is_empty() {
+            self.verified.push((subscriber.clone(), is_lease));
+        }
+        (is_lease, errors)
+    }
+}
+
+/// The reward settings and the remaining budget
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Debug))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct DispatchRewardsResponse {
+    pub per_alarm: Coin<NlsPlatform>,
+    pub budget: Coin<NlsPlatform>,
+    pub leasers: Vec<Addr>,
+}
+
+impl From<DispatchRewards> for DispatchRewardsResponse {
+    fn from(value: DispatchRewards) -> Self {
+        Self {
+            per_alarm: value.per_alarm,
+            budget: value.budget,
+            leasers: value.leasers,
+        }
+    }
+}
+
+pub fn load(storage: &dyn Storage) -> Result<DispatchRewards> {
+    STORAGE
+        .may_load(storage)
+        .map(Option::unwrap_or_default)
+        .map_err(Into::into)
+}
+
+pub fn try_fund(
+    storage: &mut dyn Storage,
+    querier: QuerierWrapper<'_>,
+    env: &Env,
+    per_alarm: Coin<NlsPlatform>,
+    top_up: Coin<NlsPlatform>,
+    leasers: Vec<Addr>,
+) -> Result<()> {
+    leasers
+        .iter()
+        .try_for_each(|leaser| contract::validate_addr(querier, leaser))?;
+
+    let rewards = load(storage)?.fund(per_alarm, top_up, leasers);
+    let balance: Coin<NlsPlatform> = bank::balance(&env.contract.address, querier)?;
+
+    if rewards.budget() <= balance {
+        store(storage, &rewards)
+    } else {
+        Err(Error::insufficient_rewards_balance(
+            rewards.budget(),
+            balance,
+        ))
+    }
+}
+
+pub fn dispatched(storage: &mut dyn Storage, keeper: Addr, nb_sent: AlarmsCount) -> Result<()> {
+    load(storage).and_then(|rewards| store(storage, &rewards.dispatched(keeper, nb_sent)))
+}
+
+pub fn delivered(
+    storage: &mut dyn Storage,
+    querier: QuerierWrapper<'_>,
+    env: &Env,
+    subscriber: &Addr,
+    emitter: Emitter,
+) -> Result<MessageResponse> {
+    load(storage)?
+        .delivered(subscriber, querier, &env.contract.address)
+        .and_then(|(rewards, reward)| {
+            store(storage, &rewards).map(|()| reward.into_response(emitter))
+        })
+}
+
+fn store(storage: &mut dyn Storage, rewards: &DispatchRewards) -> Result<()> {
+    STORAGE.save(storage, rewards).map_err(Into::into)
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+
+    use sdk::cosmwasm_std::{
+        self, testing::MockQuerier, Addr, ContractResult, QuerierResult, QuerierWrapper,
+        SystemError, SystemResult, WasmQuery,
+    };
+
+    use currency::NlsPlatform;
+    use finance::coin::Coin;
+
+    use crate::coin_legacy;
+
+    use super::{DispatchRewards, LeaserQueryMsg, Reward};
+
+    const LEASER: &str = "leaser";
+    const BROKEN_LEASER: &str = "broken_leaser";
+    const LEASE: &str = "lease";
+    const ACCOUNT: &str = "timealarms";
+
+    thread_local! {
+        static LEASER_QUERIES: Cell<usize> = const { Cell::new(0) };
+    }
+
+    fn leaser_handler(query: &WasmQuery) -> QuerierResult {
+        LEASER_QUERIES.with(|queries| queries.set(queries.get() + 1));
+        match query {
+            WasmQuery::Smart { contract_addr, msg } if contract_addr == LEASER => {
+                SystemResult::Ok(ContractResult::Ok(
+                    cosmwasm_std::to_json_binary(
+                        &(msg
+                            == &cosmwasm_std::to_json_binary(&LeaserQueryMsg::IsLease {
+                                address: &Addr::unchecked(LEASE),
+                            })
+                            .unwrap()),
+                    )
+                    .unwrap(),
+                ))
+            }
+            WasmQuery::Smart { contract_addr, .. } => {
+                SystemResult::Err(SystemError::NoSuchContract {
+                    addr: contract_addr.clone(),
+                })
+            }
+            _ => unimplemented!(),
+        }
+    }
+
+    fn querier(balance: u128) -> MockQuerier {
+        let mut mock_querier = MockQuerier::new(&[(
+            ACCOUNT,
+            &[coin_legacy::to_cosmwasm(Coin::<NlsPlatform>::new(balance))],
+        )]);
+        mock_querier.update_wasm(leaser_handler);
+        mock_querier
+    }
+
+    fn funded(per_alarm: u128, budget: u128) -> DispatchRewards {
+        DispatchRewards::default().fund(
+            Coin::new(per_alarm),
+            Coin::new(budget),
+            vec![Addr::unchecked(LEASER)],
+        )
+    }
+
+    fn deliver(
+        rewards: DispatchRewards,
+        subscriber: &str,
+        querier: QuerierWrapper<'_>,
+    ) -> (DispatchRewards, Reward) {
+        rewards
+            .delivered(
+                &Addr::unchecked(subscriber),
+                querier,
+                &Addr::unchecked(ACCOUNT),
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn rewards_funded() {
+        let mock_querier = querier(100);
+        let querier = QuerierWrapper::new(&mock_querier);
+
+        let rewards = funded(10, 25).dispatched(Addr::unchecked("keeper"), 3);
+
+        let (rewards, reward) = deliver(rewards, LEASE, querier);
+        assert_eq!(Coin::new(15), rewards.budget());
+        assert_eq!(1, reward.messages.len());
+
+        let (rewards, reward) = deliver(rewards, LEASE, querier);
+        assert_eq!(Coin::new(5), rewards.budget());
+        assert_eq!(1, reward.messages.len());
+
+        // the budget does not cover the third one
+        let (rewards, reward) = deliver(rewards, LEASE, querier);
+        assert_eq!(Coin::new(5), rewards.budget());
+        assert!(reward.messages.is_empty());
+
+        let rewards = rewards.fund(Coin::new(5), Coin::new(0), vec![Addr::unchecked(LEASER)]);
+        let (rewards, reward) = deliver(rewards, LEASE, querier);
+        assert_eq!(Coin::<NlsPlatform>::default(), rewards.budget());
+        assert_eq!(1, reward.messages.len());
+    }
+
+    #[test]
+    fn rewards_up_to_balance() {
+        let mock_querier = querier(15);
+        let querier = QuerierWrapper::new(&mock_querier);
+
+        let rewards = funded(10, 100).dispatched(Addr::unchecked("keeper"), 2);
+
+        let (rewards, reward) = deliver(rewards, LEASE, querier);
+        assert_eq!(1, reward.messages.len());
+        assert_eq!(Coin::new(90), rewards.budget());
+
+        // the balance does not cover the reward any more
+        let mock_querier = self::querier(5);
+        let (rewards, reward) = deliver(rewards, LEASE, QuerierWrapper::new(&mock_querier));
+        assert!(reward.messages.is_empty());
+        assert_eq!(Coin::new(90), rewards.budget());
+    }
+
+    #[test]
+    fn rewards_up_to_nb_sent() {
+        let mock_querier = querier(100);
+        let querier = QuerierWrapper::new(&mock_querier);
+
+        let rewards = funded(10, 100).dispatched(Addr::unchecked("keeper"), 1);
+
+        let (rewards, reward) = deliver(rewards, LEASE, querier);
+        assert_eq!(1, reward.messages.len());
+
+        let (rewards, reward) = deliver(rewards, LEASE, querier);
+        assert!(reward.messages.is_empty());
+        assert_eq!(Coin::new(90), rewards.budget());
+
+        let (rewards, reward) = deliver(
+            rewards.dispatched(Addr::unchecked("keeper"), 0),
+            LEASE,
+            querier,
+        );
+        assert!(reward.messages.is_empty());
+        assert_eq!(Coin::new(90), rewards.budget());
+    }
+
+    #[test]
+    fn rewards_leases_only() {
+        let mock_querier = querier(100);
+        let querier = QuerierWrapper::new(&mock_querier);
+
+        let rewards = funded(10, 100).dispatched(Addr::unchecked("keeper"), 2);
+
+        let (rewards, reward) = deliver(rewards, "customer", querier);
+        assert!(reward.messages.is_empty());
+        assert_eq!(Coin::new(100), rewards.budget());
+
+        let (rewards, reward) = deliver(rewards, LEASE, querier);
+        assert_eq!(1, reward.messages.len());
+        assert_eq!(Coin::new(90), rewards.budget());
+
+        // no leasers to vouch for the subscriber
+        let (rewards, reward) = deliver(
+            rewards.fund(Coin::new(10), Coin::new(0), vec![]),
+            LEASE,
+            querier,
+        );
+        assert!(reward.messages.is_empty());
+        assert_eq!(Coin::new(90), rewards.budget());
+    }
+
+    #[test]
+    fn verified_once_per_dispatch() {
+        let mock_querier = querier(100);
+        let querier = QuerierWrapper::new(&mock_querier);
+        let queries = || LEASER_QUERIES.with(Cell::get);
+
+        let rewards = funded(10, 100).dispatched(Addr::unchecked("keeper"), 3);
+
+        let before = queries();
+        let (rewards, _) = deliver(rewards, LEASE, querier);
+        let (rewards, _) = deliver(rewards, LEASE, querier);
+        let (rewards, _) = deliver(rewards, "customer", querier);
+        assert_eq!(before + 2, queries());
+        assert_eq!(Coin::new(80), rewards.budget());
+
+        // the next dispatch verifies anew
+        let rewards = rewards.dispatched(Addr::unchecked("keeper"), 1);
+        let (_, reward) = deliver(rewards, LEASE, querier);
+        assert_eq!(before + 3, queries());
+        assert_eq!(1, reward.messages.len());
+    }
+
+    #[test]
+    fn leaser_failures_reported() {
+        let mock_querier = querier(100);
+        let querier = QuerierWrapper::new(&mock_querier);
+
+        let rewards = DispatchRewards::default()
+            .fund(
+                Coin::new(10),
+                Coin::new(100),
+                vec![Addr::unchecked(BROKEN_LEASER), Addr::unchecked(LEASER)],
+            )
+            .dispatched(Addr::unchecked("keeper"), 3);
+
+        let (rewards, reward) = deliver(rewards, LEASE, querier);
+        assert_eq!(1, reward.messages.len());
+        assert_eq!(1, reward.errors.len());
+        assert!(reward.errors[0].contains(BROKEN_LEASER));
+
+        // a verified lease is not queried again
+        let (rewards, reward) = deliver(rewards, LEASE, querier);
+        assert_eq!(1, reward.messages.len());
+        assert!(reward.errors.is_empty());
+
+        // a failed verification is not kept
+        let (rewards, reward) = deliver(rewards, "customer", querier);
+        assert!(reward.messages.is_empty());
+        assert_eq!(1, reward.errors.len());
+        let (_, reward) = deliver(rewards, "customer", querier);
+        assert_eq!(1, reward.errors.len());
+    }
+}
@@ -19,7 +19,7 @@ const EVENT_KEY: &str = "receiver";
 
 impl<M> AlarmsDispatcher<M>
 where
-    M: Serialize + Copy,
+    M: Serialize,
 {
     pub fn new<T>(message: M, event_type: T) -> Self
     where
@@ -40,6 +40,20 @@ where
             .map(|()| self)
     }
 
+    /// Send a receiver specific message instead of the one the dispatcher is created with
+    pub fn send_message_to(
+        mut self,
+        receiver: Addr,
+        message: &M,
+        reply_id: Id,
+    ) -> Result<Self, Error> {
+        self.emitter = self.emitter.emit(EVENT_KEY, receiver.clone());
+
+        self.batch
+            .schedule_execute_wasm_reply_always_no_funds(receiver, message, reply_id)
+            .map(|()| self)
+    }
+
     pub fn nb_sent(&self) -> AlarmsCount {
         self.batch
             .len()
@@ -97,4 +111,28 @@ mod test {
         let msg = &r.messages[0];
         assert_eq!(ReplyOn::Always, msg.reply_on);
     }
+
+    #[test]
+    fn receiver_specific_alarm() {
+        let d = AlarmsDispatcher::new(1, EVENT_TYPE);
+        let receiver1 = Addr::unchecked("time_alarm receiver 1");
+        let receiver2 = Addr::unchecked("time_alarm receiver 2");
+
+        let d = d
+            .send_to(receiver1.clone(), Id::MAX)
+            .unwrap()
+            .send_message_to(receiver2.clone(), &2, Id::MAX)
+            .unwrap();
+        assert_eq!(d.nb_sent(), 2);
+
+        let r: CwResponse = response::response_only_messages(d);
+        assert_eq!(
+            Event::new(EVENT_TYPE)
+                .add_attribute(EVENT_KEY, receiver1)
+                .add_attribute(EVENT_KEY, receiver2),
+            r.events[0]
+        );
+        assert_eq!(2, r.messages.len());
+        assert_ne!(r.messages[0].msg, r.messages[1].msg);
+    }
 }
@@ -3,7 +3,8 @@ use std::fmt::Debug;
 use prost::DecodeError;
 use thiserror::Error;
 
-use currency::Currency;
+use currency::{Currency, NlsPlatform};
+use finance::coin::Coin;
 use sdk::cosmwasm_std::{Addr, Api, StdError};
 
 use crate::contract::CodeId;
@@ -48,6 +49,9 @@ pub enum Error {
 
     #[error("[Platform] Reply is empty!")]
     EmptyReply(),
+
+    #[error("[Platform] The dispatch rewards budget {0} exceeds the contract balance {1}")]
+    InsufficientRewardsBalance(String, String),
 }
 
 impl Error {
@@ -71,6 +75,13 @@ impl Error {
     {
         Self::UnexpectedCode(exp_code_id.to_string(), instance.into().into())
     }
+
+    pub fn insufficient_rewards_balance(
+        budget: Coin<NlsPlatform>,
+        balance: Coin<NlsPlatform>,
+    ) -> Self {
+        Self::InsufficientRewardsBalance(budget.to_string(), balance.to_string())
+    }
 }
 
 pub fn log<Err>(api: &dyn Api) -> impl FnOnce(&Err) + '_
@@ -3,6 +3,7 @@ pub mod bank_ibc;
 pub mod batch;
 pub mod coin_legacy;
 pub mod contract;
+pub mod dispatch_rewards;
 pub mod dispatcher;
 mod emit;
 pub mod error;
@@ -1,28 +1,104 @@
-use std::{
-    iter,
-    ops::{Deref, DerefMut},
-};
+use std::ops::{Deref, DerefMut};
+
+use serde::{Deserialize, Serialize};
 
 use sdk::{
-    cosmwasm_std::{Addr, Order, StdResult as CwResult, Storage, Timestamp},
+    cosmwasm_std::{Addr, Binary, Order, Storage, Timestamp},
     cw_storage_plus::{Bound, Deque, Index, IndexList, IndexedMap as CwIndexedMap, MultiIndex},
 };
 
 use crate::AlarmError;
 
+/// Distinguishes the alarms of a subscriber
+///
+/// The default, empty, id is the one used by subscribers that keep a single alarm.
+pub type AlarmId = String;
+
 type TimeSeconds = u64;
+type AlarmKey = (Addr, AlarmId);
 
 fn as_seconds(from: Timestamp) -> TimeSeconds {
     from.seconds()
 }
 
+/// An alarm as delivered to its subscriber
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct Alarm {
+    pub subscriber: Addr,
+    pub id: AlarmId,
+    pub payload: Option<Binary>,
+    #[serde(default)]
+    pub recurrence: Option<Recurrence>,
+}
+
+/// The schedule of a recurring alarm
+///
+/// The alarm goes off at `start + k * period`, for k = 0, 1, ..., until `until`, if set.
+/// It is re-armed for the next occurrence each time it goes out for delivery.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct Recurrence {
+    start: TimeSeconds,
+    period: TimeSeconds,
+    until: Option<TimeSeconds>,
+}
+
+impl Recurrence {
+    pub fn new(start: Timestamp, period: TimeSeconds, until: Option<Timestamp>) -> Self {
+        debug_assert_ne!(0, period);
+        debug_assert!(until.is_none_or(|until| start <= until));
+
+        Self {
+            start: as_seconds(start),
+            period,
+            until: until.map(as_seconds),
+        }
+    }
+
+    pub fn start(&self) -> Timestamp {
+        Timestamp::from_seconds(self.start)
+    }
+
+    /// The first occurrence strictly after `time`, if any
+    fn next_after(&self, time: TimeSeconds) -> Option<TimeSeconds> {
+        time.checked_sub(self.start)
+            .map_or(Some(self.start), |elapsed| {
+                (elapsed / self.period + 1)
+                    .checked_mul(self.period)
+                    .and_then(|since_start| self.start.checked_add(since_start))
+            })
+            .filter(|next| self.until.is_none_or(|until| *next <= until))
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+struct Schedule {
+    time: TimeSeconds,
+    payload: Option<Binary>,
+    #[serde(default)]
+    recurrence: Option<Recurrence>,
+}
+
+impl Schedule {
+    fn into_alarm(self, (subscriber, id): AlarmKey) -> Alarm {
+        Alarm {
+            subscriber,
+            id,
+            payload: self.payload,
+            recurrence: self.recurrence,
+        }
+    }
+}
+
 struct AlarmIndexes {
-    alarms: MultiIndex<'static, TimeSeconds, TimeSeconds, Addr>,
+    alarms: MultiIndex<'static, TimeSeconds, Schedule, AlarmKey>,
 }
 
-impl IndexList<TimeSeconds> for AlarmIndexes {
-    fn get_indexes(&self) -> Box<dyn Iterator<Item = &'_ dyn Index<TimeSeconds>> + '_> {
-        let v: Vec<&dyn Index<TimeSeconds>> = vec![&self.alarms];
+impl IndexList<Schedule> for AlarmIndexes {
+    fn get_indexes(&self) -> Box<dyn Iterator<Item = &'_ dyn Index<Schedule>> + '_> {
+        let v: Vec<&dyn Index<Schedule>> = vec![&self.alarms];
 
         Box::new(v.into_iter())
     }
@@ -30,18 +106,13 @@ impl IndexList<TimeSeconds> for AlarmIndexes {
 
 fn indexed_map(namespace_alarms: &'static str, namespace_index: &'static str) -> IndexedMap {
     let indexes = AlarmIndexes {
-        alarms: MultiIndex::new(|_, d| *d, namespace_alarms, namespace_index),
+        alarms: MultiIndex::new(|_, d| d.time, namespace_alarms, namespace_index),
     };
 
     IndexedMap::new(namespace_alarms, indexes)
 }
 
-type IndexedMap = CwIndexedMap<'static, Addr, TimeSeconds, AlarmIndexes>;
-
-type AlarmsSelectionIterator<'storage> = iter::Map<
-    Box<dyn Iterator<Item = CwResult<(Addr, TimeSeconds)>> + 'storage>,
-    fn(CwResult<(Addr, TimeSeconds)>) -> Result<Addr, AlarmError>,
->;
+type IndexedMap = CwIndexedMap<'static, AlarmKey, Schedule, AlarmIndexes>;
 
 pub struct Alarms<'storage, S>
 where
@@ -49,7 +120,7 @@ where
 {
     storage: S,
     alarms: IndexedMap,
-    in_delivery: Deque<'static, Addr>,
+    in_delivery: Deque<'static, Alarm>,
 }
 
 impl<'storage, S> Alarms<'storage, S>
@@ -69,29 +140,95 @@ where
         }
     }
 
-    pub fn alarms_selection(&self, ctime: Timestamp) -> AlarmsSelectionIterator<'_> {
+    pub fn alarms_selection(
+        &self,
+        ctime: Timestamp,
+    ) -> impl Iterator<Item = Result<Alarm, AlarmError>> + '_ {
         self.alarms
             .idx
             .alarms
             .range(
                 self.storage.deref(),
                 None,
-                Some(Bound::inclusive((as_seconds(ctime), Addr::unchecked("")))),
+                Some(Bound::inclusive((
+                    as_seconds(ctime),
+                    (Addr::unchecked(""), AlarmId::new()),
+                ))),
                 Order::Ascending,
             )
             .map(|res| {
-                res.map(|(subscriber, _): (Addr, TimeSeconds)| subscriber)
+                res.map(|(key, schedule): (AlarmKey, Schedule)| schedule.into_alarm(key))
                     .map_err(AlarmError::from)
             })
     }
+
+    /// Iterate over the pending alarms of a subscriber, ordered by their ids
+    pub fn subscriber_alarms(
+        &self,
+        subscriber: Addr,
+    ) -> impl Iterator<Item = Result<(Timestamp, Alarm), AlarmError>> + '_ {
+        self.alarms
+            .prefix(subscriber.clone())
+            .range(self.storage.deref(), None, None, Order::Ascending)
+            .map(move |res| {
+                res.map(|(id, schedule): (AlarmId, Schedule)| {
+                    (
+                        Timestamp::from_seconds(schedule.time),
+                        schedule.into_alarm((subscriber.clone(), id)),
+                    )
+                })
+                .map_err(AlarmError::from)
+            })
+    }
 }
 
 impl<'storage, S> Alarms<'storage, S>
 where
     S: Deref<Target = dyn Storage + 'storage> + DerefMut,
 {
-    pub fn add(&mut self, subscriber: Addr, time: Timestamp) -> Result<(), AlarmError> {
-        self.add_internal(subscriber, as_seconds(time))
+    /// Schedule an alarm replacing any pending one with the same subscriber and id
+    pub fn add(
+        &mut self,
+        subscriber: Addr,
+        id: AlarmId,
+        time: Timestamp,
+        payload: Option<Binary>,
+    ) -> Result<(), AlarmError> {
+        self.add_internal(
+            Alarm {
+                subscriber,
+                id,
+                payload,
+                recurrence: None,
+            },
+            as_seconds(time),
+        )
+    }
+
+    /// Schedule a recurring alarm replacing any pending one with the same subscriber and id
+    pub fn add_recurring(
+        &mut self,
+        subscriber: Addr,
+        id: AlarmId,
+        recurrence: Recurrence,
+        payload: Option<Binary>,
+    ) -> Result<(), AlarmError> {
+        self.add_internal(
+            Alarm {
+                subscriber,
+                id,
+                payload,
+                recurrence: Some(recurrence),
+            },
+            recurrence.start,
+        )
+    }
+
+    /// Remove a pending alarm, if any
+    pub fn remove(&mut self, subscriber: Addr, id: AlarmId) -> Result<(), AlarmError> {
+        self.alarms
+            .remove(self.storage.deref_mut(), (subscriber, id))
+            .map_err(Into::into)
     }
 
     pub fn ensure_no_in_delivery(&mut self) -> Result<&mut Self, AlarmError> {
@@ -103,43 +240,72 @@ where
             })
     }
 
-    pub fn out_for_delivery(&mut self, subscriber: Addr) -> Result<(), AlarmError> {
-        self.alarms
-            .remove(self.storage.deref_mut(), subscriber.clone())?;
+    /// Remove the alarm until its delivery is confirmed, re-arming it if recurring
+    pub fn out_for_delivery(&mut self, alarm: Alarm, now: Timestamp) -> Result<(), AlarmError> {
+        if let Some(next) = alarm
+            .recurrence
+            .and_then(|recurrence| recurrence.next_after(as_seconds(now)))
+        {
+            self.add_internal(alarm.clone(), next)?;
+        } else {
+            self.alarms.remove(
+                self.storage.deref_mut(),
+                (alarm.subscriber.clone(), alarm.id.clone()),
+            )?;
+        }
 
         self.in_delivery
-            .push_back(self.storage.deref_mut(), &subscriber)
+            .push_back(self.storage.deref_mut(), &alarm)
             .map_err(Into::into)
     }
 
-    pub fn last_delivered(&mut self) -> Result<(), AlarmError> {
+    /// Confirm the delivery of the first alarm out for delivery, returning its subscriber
+    pub fn last_delivered(&mut self) -> Result<Addr, AlarmError> {
         self.in_delivery
             .pop_front(self.storage.deref_mut())
             .map_err(Into::into)
-            .and_then(|maybe_alarm: Option<Addr>| {
-                if maybe_alarm.is_some() {
-                    Ok(())
-                } else {
-                    Err(AlarmError::EmptyAlarmsInDeliveryQueue(String::from(
+            .and_then(|maybe_alarm: Option<Alarm>| {
+                maybe_alarm.map(|alarm| alarm.subscriber).ok_or_else(|| {
+                    AlarmError::EmptyAlarmsInDeliveryQueue(String::from(
                         "Received success reply status",
-                    )))
-                }
+                    ))
+                })
             })
     }
 
+    /// Re-schedule the first alarm out for delivery for an immediate retry
+    ///
+    /// The retry replaces the next occurrence of a recurring alarm re-armed on
+    /// [`Alarms::out_for_delivery`]. The retry keeps the recurrence, so the alarm
+    /// gets re-armed again on its delivery.
     pub fn last_failed(&mut self, now: Timestamp) -> Result<(), AlarmError> {
         self.in_delivery
             .pop_front(self.storage.deref_mut())
             .map_err(Into::into)
-            .and_then(|maybe_alarm: Option<Addr>| maybe_alarm.ok_or_else(|| AlarmError::EmptyAlarmsInDeliveryQueue(
-                String::from("Received failure reply status"))
-            ))
-            .and_then(|subscriber: Addr| self.add_internal(subscriber, as_seconds(now) - /* Minus one second, to ensure it can be run within the same block */ 1))
+            .and_then(|maybe_alarm: Option<Alarm>| {
+                maybe_alarm.ok_or_else(|| {
+                    AlarmError::EmptyAlarmsInDeliveryQueue(String::from(
+                        "Received failure reply status",
+                    ))
+                })
+            })
+            .and_then(|alarm: Alarm| {
+                // Minus one second, to ensure it can be run within the same block
+                self.add_internal(alarm, as_seconds(now) - 1)
+            })
     }
 
-    fn add_internal(&mut self, subscriber: Addr, time: TimeSeconds) -> Result<(), AlarmError> {
+    fn add_internal(&mut self, alarm: Alarm, time: TimeSeconds) -> Result<(), AlarmError> {
         self.alarms
-            .save(self.storage.deref_mut(), subscriber, &time)
+            .save(
+                self.storage.deref_mut(),
+                (alarm.subscriber, alarm.id),
+                &Schedule {
+                    time,
+                    payload: alarm.payload,
+                    recurrence: alarm.recurrence,
+                },
+            )
             .map_err(Into::into)
     }
 }
@@ -156,8 +322,17 @@ pub mod tests {
         Alarms::new(storage, "alarms", "alarms_idx", "in_delivery")
     }
 
+    fn alarm(subscriber: &Addr, id: &str, payload: Option<Binary>) -> Alarm {
+        Alarm {
+            subscriber: subscriber.clone(),
+            id: id.into(),
+            payload,
+            recurrence: None,
+        }
+    }
+
     #[allow(clippy::needless_lifetimes)] // cannot rely on eliding lifetimes due to a known limitattion, look at the clippy lint description
-    fn query_alarms<'r, S>(alarms: &Alarms<'r, S>, t_sec: TimeSeconds) -> Vec<Addr>
+    fn query_alarms<'r, S>(alarms: &Alarms<'r, S>, t_sec: TimeSeconds) -> Vec<Alarm>
     where
         S: Deref<Target = dyn Storage + 'r>,
     {
@@ -177,18 +352,93 @@ pub mod tests {
         let addr1 = Addr::unchecked("addr1");
         let addr2 = Addr::unchecked("addr2");
 
-        alarms.add(addr1.clone(), t1).unwrap();
+        alarms
+            .add(addr1.clone(), AlarmId::default(), t1, None)
+            .unwrap();
+
+        assert_eq!(query_alarms(&alarms, 10), vec![alarm(&addr1, "", None)]);
 
-        assert_eq!(query_alarms(&alarms, 10), vec![addr1.clone()]);
+        // single alarm per addr and id
+        alarms
+            .add(addr1.clone(), AlarmId::default(), t2, None)
+            .unwrap();
+
+        assert_eq!(query_alarms(&alarms, 10), vec![alarm(&addr1, "", None)]);
+        assert_eq!(query_alarms(&alarms, 2), vec![]);
 
-        // single alarm per addr
-        alarms.add(addr1.clone(), t2).unwrap();
+        alarms
+            .add(addr2.clone(), AlarmId::default(), t2, None)
+            .unwrap();
 
-        assert_eq!(query_alarms(&alarms, 10), vec![addr1.clone()]);
+        assert_eq!(
+            query_alarms(&alarms, 10),
+            vec![alarm(&addr1, "", None), alarm(&addr2, "", None)]
+        );
+    }
 
-        alarms.add(addr2.clone(), t2).unwrap();
+    #[test]
+    fn test_add_keyed() {
+        let mut storage = MockStorage::new();
+        let mut alarms = alarms(&mut storage);
 
-        assert_eq!(query_alarms(&alarms, 10), vec![addr1, addr2]);
+        let t1 = Timestamp::from_seconds(1);
+        let t2 = Timestamp::from_seconds(3);
+        let addr1 = Addr::unchecked("addr1");
+        let payload = Binary::from(b"payload");
+
+        alarms
+            .add(addr1.clone(), "due".into(), t2, Some(payload.clone()))
+            .unwrap();
+        alarms
+            .add(addr1.clone(), "overdue".into(), t1, None)
+            .unwrap();
+
+        assert_eq!(
+            query_alarms(&alarms, 10),
+            vec![
+                alarm(&addr1, "overdue", None),
+                alarm(&addr1, "due", Some(payload.clone()))
+            ]
+        );
+        assert_eq!(
+            alarms
+                .subscriber_alarms(addr1.clone())
+                .map(Result::unwrap)
+                .collect::<Vec<_>>(),
+            vec![
+                (t2, alarm(&addr1, "due", Some(payload))),
+                (t1, alarm(&addr1, "overdue", None))
+            ]
+        );
+        assert_eq!(
+            alarms.subscriber_alarms(Addr::unchecked("addr2")).count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut storage = MockStorage::new();
+        let mut alarms = alarms(&mut storage);
+
+        let t1 = Timestamp::from_seconds(1);
+        let addr1 = Addr::unchecked("addr1");
+
+        alarms.add(addr1.clone(), "due".into(), t1, None).unwrap();
+        alarms
+            .add(addr1.clone(), "overdue".into(), t1, None)
+            .unwrap();
+
+        alarms.remove(addr1.clone(), "due".into()).unwrap();
+        assert_eq!(
+            query_alarms(&alarms, 10),
+            vec![alarm(&addr1, "overdue", None)]
+        );
+
+        // removing a non-existent alarm is a no-op
+        alarms.remove(addr1.clone(), "due".into()).unwrap();
+        alarms.remove(addr1, "overdue".into()).unwrap();
+        assert_eq!(query_alarms(&alarms, 10), vec![]);
     }
 
     #[test]
@@ -206,13 +456,191 @@ pub mod tests {
         let addr4 = Addr::unchecked("addr4");
 
         // same timestamp
-        alarms.add(addr1.clone(), t1).unwrap();
-        alarms.add(addr2.clone(), t1).unwrap();
+        alarms
+            .add(addr1.clone(), AlarmId::default(), t1, None)
+            .unwrap();
+        alarms
+            .add(addr2.clone(), AlarmId::default(), t1, None)
+            .unwrap();
         // different timestamp
-        alarms.add(addr3.clone(), t2).unwrap();
+        alarms
+            .add(addr3.clone(), AlarmId::default(), t2, None)
+            .unwrap();
         // rest
-        alarms.add(addr4, t4).unwrap();
+        alarms.add(addr4, AlarmId::default(), t4, None).unwrap();
+
+        assert_eq!(
+            query_alarms(&alarms, t3_sec),
+            vec![
+                alarm(&addr1, "", None),
+                alarm(&addr2, "", None),
+                alarm(&addr3, "", None)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_delivery() {
+        let mut storage = MockStorage::new();
+        let mut alarms = alarms(&mut storage);
+
+        let t1 = Timestamp::from_seconds(1);
+        let addr1 = Addr::unchecked("addr1");
+        let payload = Binary::from(b"payload");
+        let delivered = alarm(&addr1, "due", Some(payload.clone()));
+        let failed = alarm(&addr1, "overdue", Some(payload));
+
+        alarms
+            .add(
+                addr1.clone(),
+                delivered.id.clone(),
+                t1,
+                delivered.payload.clone(),
+            )
+            .unwrap();
+        alarms
+            .add(addr1, failed.id.clone(), t1, failed.payload.clone())
+            .unwrap();
+
+        alarms
+            .out_for_delivery(delivered.clone(), Timestamp::from_seconds(5))
+            .unwrap();
+        alarms
+            .out_for_delivery(failed.clone(), Timestamp::from_seconds(5))
+            .unwrap();
+        assert_eq!(query_alarms(&alarms, 10), vec![]);
+        assert!(alarms.ensure_no_in_delivery().is_err());
+
+        alarms.last_delivered().unwrap();
+        alarms.last_failed(Timestamp::from_seconds(5)).unwrap();
+        alarms.ensure_no_in_delivery().unwrap();
+
+        assert_eq!(query_alarms(&alarms, 5), vec![failed]);
+        assert_eq!(
+            alarms.last_delivered(),
+            Err(AlarmError::EmptyAlarmsInDeliveryQueue(String::from(
+                "Received success reply status"
+            )))
+        );
+    }
+
+    #[test]
+    fn test_recurrence() {
+        let recurrence = Recurrence::new(
+            Timestamp::from_seconds(10),
+            5,
+            Some(Timestamp::from_seconds(20)),
+        );
+
+        assert_eq!(Some(10), recurrence.next_after(0));
+        assert_eq!(Some(10), recurrence.next_after(9));
+        assert_eq!(Some(15), recurrence.next_after(10));
+        assert_eq!(Some(15), recurrence.next_after(14));
+        assert_eq!(Some(20), recurrence.next_after(17));
+        assert_eq!(None, recurrence.next_after(20));
+
+        let unbound = Recurrence::new(Timestamp::from_seconds(10), 5, None);
+        assert_eq!(Some(1_000_005), unbound.next_after(1_000_000));
+        assert_eq!(None, unbound.next_after(TimeSeconds::MAX));
+    }
 
-        assert_eq!(query_alarms(&alarms, t3_sec), vec![addr1, addr2, addr3]);
+    #[test]
+    fn test_recurring_delivery() {
+        let mut storage = MockStorage::new();
+        let mut alarms = alarms(&mut storage);
+
+        let addr1 = Addr::unchecked("addr1");
+        let recurrence = Recurrence::new(
+            Timestamp::from_seconds(10),
+            10,
+            Some(Timestamp::from_seconds(30)),
+        );
+        let recurring = Alarm {
+            recurrence: Some(recurrence),
+            ..alarm(&addr1, "cadence", None)
+        };
+
+        alarms
+            .add_recurring(addr1.clone(), recurring.id.clone(), recurrence, None)
+            .unwrap();
+        assert_eq!(query_alarms(&alarms, 10), vec![]);
+        assert_eq!(query_alarms(&alarms, 11), vec![recurring.clone()]);
+
+        // re-armed on delivery
+        alarms
+            .out_for_delivery(recurring.clone(), Timestamp::from_seconds(11))
+            .unwrap();
+        alarms.last_delivered().unwrap();
+        assert_eq!(query_alarms(&alarms, 20), vec![]);
+        assert_eq!(query_alarms(&alarms, 21), vec![recurring.clone()]);
+
+        // missed occurrences are skipped
+        alarms
+            .out_for_delivery(recurring.clone(), Timestamp::from_seconds(25))
+            .unwrap();
+        alarms.last_delivered().unwrap();
+        assert_eq!(query_alarms(&alarms, 30), vec![]);
+        assert_eq!(query_alarms(&alarms, 31), vec![recurring.clone()]);
+
+        // retried on failure
+        alarms
+            .out_for_delivery(recurring.clone(), Timestamp::from_seconds(31))
+            .unwrap();
+        alarms.last_failed(Timestamp::from_seconds(31)).unwrap();
+        assert_eq!(query_alarms(&alarms, 31), vec![recurring.clone()]);
+
+        // done after the last occurrence
+        alarms
+            .out_for_delivery(recurring, Timestamp::from_seconds(32))
+            .unwrap();
+        alarms.last_delivered().unwrap();
+        assert_eq!(query_alarms(&alarms, 100), vec![]);
+        assert_eq!(alarms.subscriber_alarms(addr1).count(), 0);
+    }
+
+    #[test]
+    fn test_recurring_failed_delivery() {
+        let mut storage = MockStorage::new();
+        let mut alarms = alarms(&mut storage);
+
+        let addr1 = Addr::unchecked("addr1");
+        let recurrence = Recurrence::new(Timestamp::from_seconds(10), 10, None);
+        let recurring = Alarm {
+            recurrence: Some(recurrence),
+            ..alarm(&addr1, "cadence", None)
+        };
+
+        alarms
+            .add_recurring(addr1.clone(), recurring.id.clone(), recurrence, None)
+            .unwrap();
+
+        // the retry replaces the re-armed occurrence keeping the recurrence
+        alarms
+            .out_for_delivery(recurring.clone(), Timestamp::from_seconds(12))
+            .unwrap();
+        alarms.last_failed(Timestamp::from_seconds(12)).unwrap();
+        assert_eq!(
+            vec![(Timestamp::from_seconds(11), recurring.clone())],
+            alarms
+                .subscriber_alarms(addr1.clone())
+                .map(Result::unwrap)
+                .collect::<Vec<_>>()
+        );
+
+        // re-armed on the retry delivery
+        alarms
+            .out_for_delivery(recurring.clone(), Timestamp::from_seconds(13))
+            .unwrap();
+        alarms.last_delivered().unwrap();
+        assert_eq!(query_alarms(&alarms, 20), vec![]);
+        assert_eq!(query_alarms(&alarms, 21), vec![recurring.clone()]);
+
+        // and so on
+        alarms
+            .out_for_delivery(recurring.clone(), Timestamp::from_seconds(21))
+            .unwrap();
+        alarms.last_delivered().unwrap();
+        assert_eq!(query_alarms(&alarms, 30), vec![]);
+        assert_eq!(query_alarms(&alarms, 31), vec![recurring]);
     }
 }
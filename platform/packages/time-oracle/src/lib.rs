@@ -2,12 +2,12 @@ use thiserror::Error;
 
 use sdk::cosmwasm_std::StdError;
 
-pub use crate::alarms::Alarms;
+pub use crate::alarms::{Alarm, AlarmId, Alarms, Recurrence};
 
 mod alarms;
 
 #[cfg(feature = "migrate")]
-pub mod migrate_v1;
+pub mod migrate_v2;
 
 #[derive(Error, Debug, PartialEq)]
 pub enum AlarmError {
@@ -0,0 +1,332 @@
+use sdk::{
+    cosmwasm_std::{Addr, Order, StdError, Storage, Timestamp},
+    cw_storage_plus::{Bound, Deque, Index, IndexList, IndexedMap, Item, MultiIndex},
+};
+
+use crate::AlarmError;
+
+use super::{AlarmId, Alarms};
+
+type TimeSeconds = u64;
+
+struct AlarmIndexes {
+    alarms: MultiIndex<'static, TimeSeconds, TimeSeconds, Addr>,
+}
+
+impl IndexList<TimeSeconds> for AlarmIndexes {
+    fn get_indexes(&self) -> Box<dyn Iterator<Item = &'_ dyn Index<TimeSeconds>> + '_> {
+        let v: Vec<&dyn Index<TimeSeconds>> = vec![&self.alarms];
+
+        Box::new(v.into_iter())
+    }
+}
+
+/// The single alarm per subscriber storage layout
+///
+/// The alarms are migrated to keyed ones with the default id and no payload.
+/// The migration is started once and then goes in bounded steps, each one resuming
+/// after the last migrated subscriber kept as a cursor in `namespace_cursor`.
+///
+/// The new alarms share the namespaces of the old ones. The range of the remaining
+/// old alarms does not hit the new ones because the new keys start with the length
+/// of the subscriber address, a big-endian `u16`, whose first byte is zero for
+/// any address shorter than 256 bytes, while the old keys, the addresses themselves,
+/// start with a printable character. Hence, the new keys sort before the cursor.
+///
+/// An alarm added by the subscriber in the meantime takes precedence over
+/// the old one.
+pub struct AlarmsOld {
+    namespace_alarms: &'static str,
+    namespace_index: &'static str,
+    namespace_in_delivery: &'static str,
+    namespace_cursor: &'static str,
+}
+
+impl AlarmsOld {
+    pub const fn new(
+        namespace_alarms: &'static str,
+        namespace_index: &'static str,
+        namespace_in_delivery: &'static str,
+        namespace_cursor: &'static str,
+    ) -> Self {
+        Self {
+            namespace_alarms,
+            namespace_index,
+            namespace_in_delivery,
+            namespace_cursor,
+        }
+    }
+
+    /// Start a migration carried out by the subsequent calls to [`AlarmsOld::migrate`]
+    pub fn start(&self, storage: &mut dyn Storage) -> Result<(), AlarmError> {
+        Deque::<'static, Addr>::new(self.namespace_in_delivery)
+            .is_empty(storage)?
+            .then_some(())
+            .ok_or_else(|| {
+                AlarmError::NonEmptyAlarmsInDeliveryQueue(String::from("Alarms migration"))
+            })?;
+
+        self.cursor().save(storage, &None).map_err(Into::into)
+    }
+
+    /// Return whether a started migration has not completed yet
+    pub fn in_progress(&self, storage: &dyn Storage) -> Result<bool, AlarmError> {
+        self.cursor()
+            .may_load(storage)
+            .map(|cursor| cursor.is_some())
+            .map_err(Into::into)
+    }
+
+    /// Migrate up to `max_count` alarms resuming after the last migrated subscriber
+    ///
+    /// Return whether the migration has completed. The call is a no-op if there is
+    /// no migration in progress.
+    pub fn migrate(&self, storage: &mut dyn Storage, max_count: usize) -> Result<bool, AlarmError> {
+        let Some(start_after) = self.cursor().may_load(storage)? else {
+            return Ok(true);
+        };
+
+        let old_alarms = self.alarms();
+        let batch = old_alarms
+            .range(
+                storage,
+                start_after.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(max_count)
+            .collect::<Result<Vec<(Addr, TimeSeconds)>, StdError>>()?;
+
+        let completed = batch.len() < max_count;
+        let last_subscriber = batch.last().map(|(subscriber, _)| subscriber.clone());
+
+        self.migrate_batch(&old_alarms, storage, batch)?;
+
+        if completed {
+            self.cursor().remove(storage);
+            Ok(true)
+        } else {
+            self.cursor()
+                .save(storage, &last_subscriber)
+                .map(|()| false)
+                .map_err(Into::into)
+        }
+    }
+
+    fn migrate_batch(
+        &self,
+        old_alarms: &IndexedMap<'static, Addr, TimeSeconds, AlarmIndexes>,
+        storage: &mut dyn Storage,
+        batch: Vec<(Addr, TimeSeconds)>,
+    ) -> Result<(), AlarmError> {
+        // purge the batch data
+        for (subscriber, _) in &batch {
+            old_alarms.remove(storage, subscriber.clone())?;
+        }
+
+        let mut alarms_new = Alarms::new(
+            storage,
+            self.namespace_alarms,
+            self.namespace_index,
+            self.namespace_in_delivery,
+        );
+
+        // restore to new alarms unless replaced meanwhile
+        batch.into_iter().try_for_each(|(subscriber, time)| {
+            let replaced = alarms_new
+                .subscriber_alarms(subscriber.clone())
+                .try_fold(false, |replaced, alarm| {
+                    alarm.map(|(_time, alarm)| replaced || alarm.id.is_empty())
+                })?;
+
+            if replaced {
+                Ok(())
+            } else {
+                alarms_new.add(
+                    subscriber,
+                    AlarmId::default(),
+                    Timestamp::from_seconds(time),
+                    None,
+                )
+            }
+        })
+    }
+
+    #[cfg(test)]
+    fn add(
+        &self,
+        storage: &mut dyn Storage,
+        subscriber: Addr,
+        time: Timestamp,
+    ) -> Result<(), AlarmError> {
+        self.alarms()
+            .save(storage, subscriber, &time.seconds())
+            .map_err(Into::into)
+    }
+
+    fn alarms(&self) -> IndexedMap<'static, Addr, TimeSeconds, AlarmIndexes> {
+        let indexes = AlarmIndexes {
+            alarms: MultiIndex::new(|_, d| *d, self.namespace_alarms, self.namespace_index),
+        };
+
+        IndexedMap::new(self.namespace_alarms, indexes)
+    }
+
+    const fn cursor(&self) -> Item<'static, Option<Addr>> {
+        Item::new(self.namespace_cursor)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use sdk::cosmwasm_std::testing::MockStorage;
+
+    use crate::Alarm;
+
+    use super::{super::Alarms, *};
+
+    const MIGRATION_NAMESPACES: (&str, &str, &str) = ("alarms", "alarms_idx", "in_delivery");
+
+    #[test]
+    fn test_migration() {
+        let mut storage = MockStorage::new();
+        let alarms = alarms_old();
+
+        let addr1 = Addr::unchecked("addr1");
+        let addr2 = Addr::unchecked("addr2");
+        let addr3 = Addr::unchecked("addr3");
+
+        alarms
+            .add(&mut storage, addr1.clone(), Timestamp::from_seconds(1))
+            .unwrap();
+        alarms
+            .add(&mut storage, addr2.clone(), Timestamp::from_seconds(3))
+            .unwrap();
+        alarms
+            .add(&mut storage, addr3.clone(), Timestamp::from_seconds(2))
+            .unwrap();
+
+        // single alarm per address(3) + index(3)
+        assert_eq!(6, storage.range(None, None, Order::Ascending).count());
+
+        alarms.start(&mut storage).unwrap();
+        assert!(alarms.in_progress(&storage).unwrap());
+        assert!(alarms.migrate(&mut storage, 10).unwrap());
+        assert!(!alarms.in_progress(&storage).unwrap());
+
+        // single keyed alarm per address(3) + index(3)
+        assert_eq!(6, storage.range(None, None, Order::Ascending).count());
+
+        assert_eq!(
+            [(addr1, 1), (addr3, 2), (addr2, 3)]
+                .into_iter()
+                .map(|(subscriber, _time)| migrated(subscriber))
+                .collect::<Vec<_>>(),
+            due_alarms(&mut storage, 10)
+        );
+    }
+
+    #[test]
+    fn test_migration_resumed() {
+        let mut storage = MockStorage::new();
+        let alarms = alarms_old();
+
+        let alarms_count: TimeSeconds = 7;
+        (0..alarms_count).for_each(|i| {
+            alarms
+                .add(
+                    &mut storage,
+                    Addr::unchecked(format!("addr{i}")),
+                    Timestamp::from_seconds(i),
+                )
+                .unwrap();
+        });
+
+        alarms.start(&mut storage).unwrap();
+        assert!(!alarms.migrate(&mut storage, 3).unwrap());
+
+        // the key of an alarm added meanwhile must sort before the cursor no matter
+        // how the subscriber address compares to the old ones
+        let late_subscriber = Addr::unchecked("zzz");
+        let replacing_subscriber = Addr::unchecked("addr6");
+        let late_time = Timestamp::from_seconds(100);
+        {
+            let (namespace_alarms, namespace_index, namespace_in_delivery) = MIGRATION_NAMESPACES;
+            let mut new_alarms = Alarms::new(
+                &mut storage as &mut dyn Storage,
+                namespace_alarms,
+                namespace_index,
+                namespace_in_delivery,
+            );
+            new_alarms
+                .add(late_subscriber.clone(), AlarmId::default(), late_time, None)
+                .unwrap();
+            new_alarms
+                .add(
+                    replacing_subscriber.clone(),
+                    AlarmId::default(),
+                    late_time,
+                    None,
+                )
+                .unwrap();
+        }
+
+        assert!(!alarms.migrate(&mut storage, 3).unwrap());
+        assert!(alarms.in_progress(&storage).unwrap());
+        assert!(alarms.migrate(&mut storage, 3).unwrap());
+        assert!(!alarms.in_progress(&storage).unwrap());
+        assert!(alarms.migrate(&mut storage, 3).unwrap());
+
+        // single keyed alarm per address + index
+        assert_eq!(
+            Ok(2 * (alarms_count + 1)),
+            storage
+                .range(None, None, Order::Ascending)
+                .count()
+                .try_into()
+        );
+        assert_eq!(
+            (0..alarms_count - 1)
+                .map(|i| migrated(Addr::unchecked(format!("addr{i}"))))
+                .collect::<Vec<_>>(),
+            due_alarms(&mut storage, alarms_count)
+        );
+        assert_eq!(
+            vec![migrated(late_subscriber), migrated(replacing_subscriber)],
+            due_alarms(&mut storage, late_time.seconds() + 1)
+                .split_off((alarms_count - 1).try_into().unwrap())
+        );
+    }
+
+    fn alarms_old() -> AlarmsOld {
+        let (namespace_alarms, namespace_index, namespace_in_delivery) = MIGRATION_NAMESPACES;
+        AlarmsOld::new(
+            namespace_alarms,
+            namespace_index,
+            namespace_in_delivery,
+            "alarms_migration",
+        )
+    }
+
+    fn due_alarms(storage: &mut dyn Storage, till: TimeSeconds) -> Vec<Alarm> {
+        let (namespace_alarms, namespace_index, namespace_in_delivery) = MIGRATION_NAMESPACES;
+        Alarms::new(
+            storage,
+            namespace_alarms,
+            namespace_index,
+            namespace_in_delivery,
+        )
+        .alarms_selection(Timestamp::from_seconds(till))
+        .map(Result::unwrap)
+        .collect()
+    }
+
+    fn migrated(subscriber: Addr) -> Alarm {
+        Alarm {
+            subscriber,
+            id: AlarmId::default(),
+            payload: None,
+            recurrence: None,
+        }
+    }
+}
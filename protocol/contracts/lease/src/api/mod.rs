@@ -1,13 +1,17 @@
 use serde::{Deserialize, Serialize};
 
 use currencies::{LeaseGroup, PaymentGroup};
-use finance::coin::CoinDTO;
+use currency::SymbolOwned;
+use finance::{coin::CoinDTO, percent::Percent, price::dto::PriceDTO};
 use sdk::{
-    cosmwasm_std::Addr,
+    cosmwasm_std::{Addr, Timestamp},
     schemars::{self, JsonSchema},
 };
 
-use self::position::PositionClose;
+use self::{
+    open::IbcParams,
+    position::{CloseTrigger, PositionClose},
+};
 
 pub mod open;
 pub mod position;
@@ -22,9 +26,17 @@ pub type LeaseCoin = CoinDTO<LeaseAssetCurrencies>;
 
 pub type LpnCoinDTO = crate::finance::LpnCoinDTO;
 
+pub type LeasePriceDTO = PriceDTO<LeaseAssetCurrencies, crate::finance::LpnCurrencies>;
+
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(deny_unknown_fields, rename_all = "snake_case")]
-pub struct MigrateMsg {}
+pub struct MigrateMsg {
+    /// The IBC parameters to use for the DEX transactions from now on
+    ///
+    /// Leases in the middle of a DEX operation keep their parameters until the next migration.
+    #[serde(default)]
+    pub dex_ibc: Option<IbcParams>,
+}
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
 #[cfg_attr(any(test, feature = "testing"), derive(Debug))]
@@ -35,8 +47,61 @@ pub enum ExecuteMsg {
     /// The funds should be sent attached to the message
     Repay(),
 
+    /// Add collateral to the lease position
+    ///
+    /// The funds, in any of the payment currencies, should be sent attached to the message.
+    /// They are swapped into the lease currency and the result is added to the position
+    /// without repaying any debt. Only the lease customer and its operators are allowed
+    /// to add collateral.
+    ///
+    /// Return `error::ContractError::InsufficientPayment` if the amount is less than
+    /// the minimum transaction amount specified on lease open.
+    AddCollateral(),
+
+    /// Borrow more against the lease position
+    ///
+    /// The amount is borrowed from the Lpp, swapped into the lease currency, and the result
+    /// is added to the position. The loan interest rate becomes the average of the current rate
+    /// and the one quoted by the Lpp for the amount, weighted by the principal.
+    /// Only the lease customer is allowed to borrow.
+    ///
+    /// Return `error::ContractError::InsufficientTransactionAmount` if the amount is less than
+    /// the minimum transaction amount specified on lease open.
+    ///
+    /// Return `error::ContractError::BorrowAmountTooBig` if the amount would bring
+    /// the lease liability above the initial one.
+    Borrow(LpnCoinDTO),
+
+    /// Re-open the loan at the current Lpp interest rate
+    ///
+    /// The loan and margin interests due, and the refinance fee set in the Lpp, are paid from
+    /// the Lpn amount sent along. The rest is sent back to the customer. The principal stays
+    /// intact. Only the lease customer is allowed to refinance.
+    ///
+    /// Return `error::ContractError::NoRefinanceGain` if the current Lpp rate is not below
+    /// the loan interest rate.
+    ///
+    /// Return `error::ContractError::InsufficientRefinancePayment` if the amount sent does not
+    /// cover the interests due and the fee.
+    Refinance(),
+
+    /// Swap the whole position into another lease currency
+    ///
+    /// The loan stays intact. The lease asset is swapped on the lease ICA, then
+    /// the liability is checked against the price of the new asset. Only the lease customer
+    /// and its operators are allowed to rotate the position.
+    ///
+    /// Return `error::ContractError::RotationIntoSameAsset` if the currency is the current
+    /// lease currency.
+    RotateAsset {
+        currency: SymbolOwned,
+    },
+
     /// Customer initiated position close
     ///
+    /// Only the lease customer and its operators are allowed to close the position.
+    /// The proceeds, if any, are sent to the customer.
+    ///
     /// Return `error::ContractError::PositionCloseAmountTooSmall` when a partial close is requested
     /// with amount less than the minimum sell asset position parameter sent on lease open. Refer to
     /// `NewLeaseForm::position_spec`.
@@ -49,6 +114,80 @@ pub enum ExecuteMsg {
     /// a `PositionClose::FullClose` is requested. It is executed irrespective of the amount.
     ClosePosition(PositionClose),
 
+    /// Set or, if `None`, remove the take-profit trigger
+    ///
+    /// Once the lease asset price goes up to or above the trigger price, the lease closes
+    /// the position as specified by the trigger. A `PositionClose::PartialClose`, that is
+    /// no longer valid at that time, is carried out as a `PositionClose::FullClose`.
+    ///
+    /// Return `error::ContractError::TakeProfitTooLow` if the trigger price is not above
+    /// the current market price.
+    ChangeTakeProfit(Option<CloseTrigger>),
+
+    /// Set or, if `None`, remove the stop-loss trigger
+    ///
+    /// Once the lease asset price goes down below the trigger price, the lease closes
+    /// the position as specified by the trigger. A `PositionClose::PartialClose`, that is
+    /// no longer valid at that time, is carried out as a `PositionClose::FullClose`.
+    ///
+    /// Return `error::ContractError::StopLossTooHigh` if the trigger price is above
+    /// the current market price.
+    ChangeStopLoss(Option<CloseTrigger>),
+
+    /// Permit an address to manage the lease on behalf of the customer
+    ///
+    /// An operator is allowed to add collateral, close the position, change the take-profit
+    /// and stop-loss triggers, and close a paid lease. It is not allowed to borrow or
+    /// to change the operators. Any proceeds are always sent to the customer.
+    ///
+    /// Only the lease customer is allowed to change the operators, and only while
+    /// there is no ongoing transaction.
+    AddOperator(Addr),
+
+    /// Revoke the permission of an address to manage the lease
+    ///
+    /// Only the lease customer is allowed to change the operators, and only while
+    /// there is no ongoing transaction.
+    RemoveOperator(Addr),
+
+    /// Hand the lease over to another address
+    ///
+    /// The new customer takes over all rights on the lease, including any proceeds
+    /// on closing it. The operators are revoked. The leaser is notified to update
+    /// its index of leases per customer.
+    ///
+    /// Only the lease customer is allowed to transfer the lease, and only while
+    /// there is no ongoing transaction.
+    TransferOwnership {
+        new_customer: Addr,
+    },
+
+    /// Merge this lease into another open lease of the same customer and currency
+    ///
+    /// The lease asset is transferred in from the lease ICA and, along with the loan,
+    /// handed over to the other lease through the leaser. The leaser merges the Lpp loan
+    /// into the loan of the other lease. This lease gets closed. Only the leaser is allowed
+    /// to merge leases.
+    ///
+    /// Return `error::ContractError::MergeTargetNotReady` if the other lease is not open or
+    /// is in the middle of a transaction.
+    ///
+    /// Return `error::ContractError::MergeIntoDifferentAsset` if the other lease is on
+    /// another currency.
+    ///
+    /// Return `error::ContractError::MergeLiabilityTooHigh` if the merged position
+    /// would not be below the maximum liability.
+    MergeInto {
+        lease: Addr,
+    },
+
+    /// Take over the asset and the loan of a lease merged into this one
+    ///
+    /// The asset should be sent attached to the message. It is transferred out to
+    /// the lease ICA and added to the position. Only the leaser is allowed to hand over
+    /// a merged lease.
+    AbsorbLease(MergedLoan),
+
     /// Close of a fully paid lease
     Close(),
 
@@ -73,24 +212,54 @@ pub enum ExecuteMsg {
     Heal(),
 }
 
+/// The loan of a lease merged into another one
+///
+/// The Lpp loan is already merged. The other lease takes over the margin.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Debug))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct MergedLoan {
+    pub principal_due: LpnCoinDTO,
+    pub margin_interest: Percent,
+    pub margin_paid_by: Timestamp,
+}
+
 /// The execute message any `Finalizer` should respond to.
 #[derive(Serialize, Deserialize)]
 #[cfg_attr(any(test, feature = "testing"), derive(Debug, PartialEq, Eq))]
 #[serde(deny_unknown_fields, rename_all = "snake_case")]
 pub enum FinalizerExecuteMsg {
-    FinalizeLease { customer: Addr },
+    FinalizeLease {
+        customer: Addr,
+    },
+    TransferLease {
+        customer: Addr,
+        new_customer: Addr,
+    },
+    /// The lease has been merged into `into`
+    ///
+    /// The lease asset is sent attached to the message. The finalizer is expected to merge
+    /// the Lpp loan of the lease into the loan of `into` before handing the asset over.
+    FinalizeMerge {
+        customer: Addr,
+        into: Addr,
+        loan: MergedLoan,
+    },
 }
 
 #[cfg(test)]
 mod test {
+    use currencies::test::LpnC;
+    use currency::Currency;
+    use finance::{coin::Coin, percent::Percent};
     use sdk::{
-        cosmwasm_std::{from_json, to_json_vec},
+        cosmwasm_std::{from_json, to_json_string, to_json_vec, Addr, Timestamp},
         schemars::_serde_json::to_string,
     };
 
     use crate::api::{
         position::{FullClose, PositionClose},
-        ExecuteMsg,
+        ExecuteMsg, MergedLoan,
     };
 
     #[test]
@@ -108,6 +277,110 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_borrow_representation() {
+        let msg = ExecuteMsg::Borrow(Coin::<LpnC>::new(150).into());
+        let borrow_bin = to_json_vec(&msg).expect("serialization failed");
+        assert_eq!(
+            from_json::<ExecuteMsg>(&borrow_bin).expect("deserialization failed"),
+            msg
+        );
+
+        assert_eq!(
+            to_json_string(&msg).expect("serialization failed"),
+            format!(
+                r#"{{"borrow":{{"amount":"150","ticker":"{lpn}"}}}}"#,
+                lpn = LpnC::TICKER
+            )
+        );
+    }
+
+    #[test]
+    fn test_add_collateral_representation() {
+        let msg = ExecuteMsg::AddCollateral();
+        let add_bin = to_json_vec(&msg).expect("serialization failed");
+        assert_eq!(
+            from_json::<ExecuteMsg>(&add_bin).expect("deserialization failed"),
+            msg
+        );
+
+        assert_eq!(
+            to_string(&msg).expect("deserialization failed"),
+            r#"{"add_collateral":[]}"#
+        );
+    }
+
+    #[test]
+    fn test_refinance_representation() {
+        let msg = ExecuteMsg::Refinance();
+        let refinance_bin = to_json_vec(&msg).expect("serialization failed");
+        assert_eq!(
+            from_json::<ExecuteMsg>(&refinance_bin).expect("deserialization failed"),
+            msg
+        );
+
+        assert_eq!(
+            to_string(&msg).expect("deserialization failed"),
+            r#"{"refinance":[]}"#
+        );
+    }
+
+    #[test]
+    fn test_rotate_asset_representation() {
+        let msg = ExecuteMsg::RotateAsset {
+            currency: "NLS".into(),
+        };
+        let rotate_bin = to_json_vec(&msg).expect("serialization failed");
+        assert_eq!(
+            from_json::<ExecuteMsg>(&rotate_bin).expect("deserialization failed"),
+            msg
+        );
+
+        assert_eq!(
+            to_string(&msg).expect("deserialization failed"),
+            r#"{"rotate_asset":{"currency":"NLS"}}"#
+        );
+    }
+
+    #[test]
+    fn test_merge_into_representation() {
+        let msg = ExecuteMsg::MergeInto {
+            lease: Addr::unchecked("lease2"),
+        };
+        let merge_bin = to_json_vec(&msg).expect("serialization failed");
+        assert_eq!(
+            from_json::<ExecuteMsg>(&merge_bin).expect("deserialization failed"),
+            msg
+        );
+
+        assert_eq!(
+            to_string(&msg).expect("deserialization failed"),
+            r#"{"merge_into":{"lease":"lease2"}}"#
+        );
+    }
+
+    #[test]
+    fn test_absorb_lease_representation() {
+        let msg = ExecuteMsg::AbsorbLease(MergedLoan {
+            principal_due: Coin::<LpnC>::new(150).into(),
+            margin_interest: Percent::from_permille(25),
+            margin_paid_by: Timestamp::from_seconds(10),
+        });
+        let absorb_bin = to_json_vec(&msg).expect("serialization failed");
+        assert_eq!(
+            from_json::<ExecuteMsg>(&absorb_bin).expect("deserialization failed"),
+            msg
+        );
+
+        assert_eq!(
+            to_string(&msg).expect("deserialization failed"),
+            format!(
+                r#"{{"absorb_lease":{{"principal_due":{{"amount":150,"ticker":"{lpn}"}},"margin_interest":25,"margin_paid_by":"10000000000"}}}}"#,
+                lpn = LpnC::TICKER
+            )
+        );
+    }
+
     #[test]
     fn test_close_position_representation() {
         let msg = ExecuteMsg::ClosePosition(PositionClose::FullClose(FullClose {}));
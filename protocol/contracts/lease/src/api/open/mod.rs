@@ -3,7 +3,7 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use currency::SymbolOwned;
-pub use dex::{ConnectionParams, Ics20Channel};
+pub use dex::{ConnectionParams, IbcParams, Ics20Channel};
 use finance::{duration::Duration, liability::Liability, percent::Percent};
 use sdk::{
     cosmwasm_std::Addr,
@@ -47,6 +47,16 @@ pub struct NewLeaseForm {
     pub position_spec: PositionSpecDTO,
     /// Loan parameters
     pub loan: LoanForm,
+    /// The maximum slippage of the lease swaps against the market price
+    ///
+    /// A swap that would deliver less than the amount estimated with the oracle prices
+    /// decreased by this percentage fails and is retried.
+    pub max_slippage: Percent,
+    /// The maximum duration of the lease, optional
+    ///
+    /// Once it elapses the position gets fully closed.
+    #[serde(default)]
+    pub max_duration: Option<Duration>,
     // TODO[all Addr contract parameters passed on opening] migrate to using their respective *Ref-s
     // Although being external for the contract, this API is internal for the system.
     /// The Reserve contract that would cover losses
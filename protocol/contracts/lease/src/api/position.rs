@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 
 use sdk::schemars::{self, JsonSchema};
 
-use super::LeaseCoin;
+use super::{LeaseCoin, LeasePriceDTO};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
 #[cfg_attr(any(test, feature = "testing"), derive(Debug))]
@@ -23,3 +23,23 @@ pub struct FullClose {}
 pub struct PartialClose {
     pub amount: LeaseCoin,
 }
+
+/// A customer defined position close, triggered when the lease asset price reaches a level
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Debug))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct CloseTrigger {
+    /// The price of the lease asset against the LPN
+    pub price: LeasePriceDTO,
+    pub close: PositionClose,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Debug))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct ClosePolicy {
+    /// Close when the price goes up to or above this level
+    pub take_profit: Option<CloseTrigger>,
+    /// Close when the price goes down below this level
+    pub stop_loss: Option<CloseTrigger>,
+}
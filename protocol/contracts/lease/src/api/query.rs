@@ -2,24 +2,137 @@ use serde::{Deserialize, Serialize};
 
 use finance::{duration::Duration, percent::Percent};
 use sdk::{
-    cosmwasm_std::Timestamp,
+    cosmwasm_std::{Addr, Timestamp},
     schemars::{self, JsonSchema},
 };
 
 use crate::finance::LpnCoinDTO;
 
-use super::{DownpaymentCoin, LeaseCoin};
+use super::{position::ClosePolicy, DownpaymentCoin, LeaseCoin, LeasePriceDTO};
 
-#[derive(Deserialize, JsonSchema)]
-#[cfg_attr(any(test, feature = "testing"), derive(Clone, Debug, Serialize))]
+/// The queries supported by the lease
+///
+/// [`StateQuery`] is kept as a standalone message for backward compatibility.
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Clone, Debug))]
+#[serde(untagged)]
+pub enum QueryMsg {
+    State(StateQuery),
+    Position(PositionQuery),
+    History(HistoryQuery),
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Clone, Debug))]
 #[serde(deny_unknown_fields, rename_all = "snake_case")]
 pub struct StateQuery {}
 
-#[derive(Serialize)]
-#[cfg_attr(
-    any(test, feature = "testing"),
-    derive(Clone, PartialEq, Eq, Debug, Deserialize)
-)]
+/// Queries on the position of an open lease
+///
+/// They are computed with the same liability logic the lease applies
+/// on its debt checks and are valid only while the lease is open.
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Clone, Debug))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub enum PositionQuery {
+    /// The lease asset prices at which the lease enters each warning zone
+    /// and becomes subject to full liquidation
+    ///
+    /// Return [`Option<LiquidationPrices>`], `None` if there is no debt.
+    LiquidationPrices {},
+    /// Simulate a repayment of `payment`
+    ///
+    /// Return [`Projection`].
+    WhatIfRepay { payment: LpnCoinDTO },
+    /// Simulate a partial close of `amount` at the current lease asset price
+    ///
+    /// Return [`Projection`].
+    WhatIfClose { amount: LeaseCoin },
+}
+
+/// Queries on the payment history of a lease
+///
+/// The history is available at any lease state, including after it has been closed or liquidated.
+/// The payments made in the middle of an operation are included once the lease gets at rest.
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Clone, Debug))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub enum HistoryQuery {
+    /// Return [`Vec<HistoryRecord>`] in the order the records have been made
+    ///
+    /// The page starts after the record with id `start_after`, or at the oldest kept one.
+    /// Only the latest records are kept, the oldest ones are dropped first.
+    History {
+        start_after: Option<RecordId>,
+        limit: Option<u32>,
+    },
+}
+
+pub type RecordId = u32;
+
+/// A payment toward the loan of a lease
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(any(test, feature = "testing"), derive(Clone, PartialEq, Eq, Debug))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct HistoryRecord {
+    pub id: RecordId,
+    pub at: Timestamp,
+    pub kind: PaymentKind,
+    /// The total amount paid, including the change
+    pub payment: LpnCoinDTO,
+    pub overdue_margin_interest: LpnCoinDTO,
+    pub overdue_loan_interest: LpnCoinDTO,
+    pub due_margin_interest: LpnCoinDTO,
+    pub due_loan_interest: LpnCoinDTO,
+    pub principal: LpnCoinDTO,
+    pub change: LpnCoinDTO,
+    /// Whether the payment has closed the loan
+    pub loan_close: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(any(test, feature = "testing"), derive(Clone, PartialEq, Eq, Debug))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub enum PaymentKind {
+    /// A repayment by the customer
+    Repayment,
+    /// A position close requested by the customer, or triggered by a close policy
+    PositionClose {
+        amount: LeaseCoin,
+    },
+    Liquidation {
+        amount: LeaseCoin,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(any(test, feature = "testing"), derive(Clone, PartialEq, Eq, Debug))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct LiquidationPrices {
+    pub first_liq_warn: LeasePriceDTO,
+    pub second_liq_warn: LeasePriceDTO,
+    pub third_liq_warn: LeasePriceDTO,
+    pub liquidation: LeasePriceDTO,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(any(test, feature = "testing"), derive(Clone, PartialEq, Eq, Debug))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct Projection {
+    /// The resulting loan-to-value ratio
+    pub ltv: Percent,
+    /// The resulting liability zone
+    ///
+    /// 0 if the lease is not in a warning zone, 1 to 3 for the warning zones,
+    /// and 4 if the lease would be subject to liquidation.
+    pub zone: u8,
+    /// The lease asset price at which the lease would be subject to full liquidation,
+    /// `None` if there would be no debt
+    pub liquidation_price: Option<LeasePriceDTO>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(any(test, feature = "testing"), derive(Clone, PartialEq, Eq, Debug))]
 #[serde(deny_unknown_fields, rename_all = "snake_case")]
 pub enum StateResponse {
     Opening {
@@ -39,10 +152,15 @@ pub enum StateResponse {
         due_margin: LpnCoinDTO,
         due_interest: LpnCoinDTO,
         validity: Timestamp,
+        /// The time the position gets fully closed at, if the lease has a maximum duration
+        expiry: Option<Timestamp>,
+        close_policy: ClosePolicy,
+        operators: Vec<Addr>,
         in_progress: Option<opened::OngoingTrx>,
     },
     Paid {
         amount: LeaseCoin,
+        operators: Vec<Addr>,
         in_progress: Option<paid::ClosingTrx>,
     },
     Closed(),
@@ -50,15 +168,10 @@ pub enum StateResponse {
 }
 
 pub(crate) mod opening {
-    #[cfg(any(test, feature = "testing"))]
-    use serde::Deserialize;
-    use serde::Serialize;
-
-    #[derive(Serialize)]
-    #[cfg_attr(
-        any(test, feature = "testing"),
-        derive(Clone, PartialEq, Eq, Deserialize, Debug)
-    )]
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize)]
+    #[cfg_attr(any(test, feature = "testing"), derive(Clone, PartialEq, Eq, Debug))]
     #[serde(deny_unknown_fields, rename_all = "snake_case")]
     pub enum OngoingTrx {
         OpenIcaAccount,
@@ -68,17 +181,15 @@ pub(crate) mod opening {
 }
 
 pub(crate) mod opened {
-    #[cfg(any(test, feature = "testing"))]
-    use serde::Deserialize;
-    use serde::Serialize;
+    use serde::{Deserialize, Serialize};
+
+    use currency::SymbolOwned;
+    use sdk::cosmwasm_std::Addr;
 
-    use crate::api::{LeaseCoin, PaymentCoin};
+    use crate::api::{LeaseCoin, LpnCoinDTO, PaymentCoin};
 
-    #[derive(Serialize)]
-    #[cfg_attr(
-        any(test, feature = "testing"),
-        derive(Clone, PartialEq, Eq, Debug, Deserialize)
-    )]
+    #[derive(Serialize, Deserialize)]
+    #[cfg_attr(any(test, feature = "testing"), derive(Clone, PartialEq, Eq, Debug))]
     #[serde(deny_unknown_fields, rename_all = "snake_case")]
     pub enum OngoingTrx {
         Repayment {
@@ -93,13 +204,34 @@ pub(crate) mod opened {
             close: LeaseCoin,
             in_progress: PositionCloseTrx,
         },
+        AddCollateral {
+            payment: PaymentCoin,
+            in_progress: BuyAssetTrx,
+        },
+        Borrow {
+            amount: LpnCoinDTO,
+            in_progress: BuyAssetTrx,
+        },
+        Rotation {
+            currency: SymbolOwned,
+            in_progress: BuyAssetTrx,
+        },
+        Merge {
+            into: Addr,
+            in_progress: MergeTrx,
+        },
+        MergeAbort {
+            amount: LeaseCoin,
+            in_progress: BuyAssetTrx,
+        },
+        Absorb {
+            amount: LeaseCoin,
+            in_progress: BuyAssetTrx,
+        },
     }
 
-    #[derive(Serialize)]
-    #[cfg_attr(
-        any(test, feature = "testing"),
-        derive(Clone, PartialEq, Eq, Debug, Deserialize)
-    )]
+    #[derive(Serialize, Deserialize)]
+    #[cfg_attr(any(test, feature = "testing"), derive(Clone, PartialEq, Eq, Debug))]
     #[serde(deny_unknown_fields, rename_all = "snake_case")]
     pub enum RepayTrx {
         TransferOut,
@@ -108,11 +240,24 @@ pub(crate) mod opened {
         TransferInFinish,
     }
 
-    #[derive(Serialize)]
-    #[cfg_attr(
-        any(test, feature = "testing"),
-        derive(Clone, PartialEq, Eq, Debug, Deserialize)
-    )]
+    #[derive(Serialize, Deserialize)]
+    #[cfg_attr(any(test, feature = "testing"), derive(Clone, PartialEq, Eq, Debug))]
+    #[serde(deny_unknown_fields, rename_all = "snake_case")]
+    pub enum BuyAssetTrx {
+        TransferOut,
+        Swap,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[cfg_attr(any(test, feature = "testing"), derive(Clone, PartialEq, Eq, Debug))]
+    #[serde(deny_unknown_fields, rename_all = "snake_case")]
+    pub enum MergeTrx {
+        TransferInInit,
+        TransferInFinish,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[cfg_attr(any(test, feature = "testing"), derive(Clone, PartialEq, Eq, Debug))]
     #[serde(deny_unknown_fields, rename_all = "snake_case")]
     pub enum PositionCloseTrx {
         Swap,
@@ -122,18 +267,49 @@ pub(crate) mod opened {
 }
 
 pub(crate) mod paid {
-    #[cfg(any(test, feature = "testing"))]
-    use serde::Deserialize;
-    use serde::Serialize;
-
-    #[derive(Serialize)]
-    #[cfg_attr(
-        any(test, feature = "testing"),
-        derive(Clone, PartialEq, Eq, Debug, Deserialize)
-    )]
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize)]
+    #[cfg_attr(any(test, feature = "testing"), derive(Clone, PartialEq, Eq, Debug))]
     #[serde(deny_unknown_fields, rename_all = "snake_case")]
     pub enum ClosingTrx {
         TransferInInit,
         TransferInFinish,
     }
 }
+
+#[cfg(test)]
+mod test {
+    use sdk::cosmwasm_std::from_json;
+
+    use super::{HistoryQuery, PositionQuery, QueryMsg};
+
+    #[test]
+    fn state_query_representation() {
+        assert!(matches!(
+            from_json(br#"{}"#).expect("deserialization failed"),
+            QueryMsg::State(_)
+        ));
+    }
+
+    #[test]
+    fn position_query_representation() {
+        assert!(matches!(
+            from_json(br#"{"liquidation_prices":{}}"#).expect("deserialization failed"),
+            QueryMsg::Position(PositionQuery::LiquidationPrices {})
+        ));
+        assert!(from_json::<QueryMsg>(br#"{"liquidation_price":{}}"#).is_err());
+    }
+
+    #[test]
+    fn history_query_representation() {
+        assert!(matches!(
+            from_json(br#"{"history":{"start_after":3,"limit":null}}"#)
+                .expect("deserialization failed"),
+            QueryMsg::History(HistoryQuery::History {
+                start_after: Some(3),
+                limit: None
+            })
+        ));
+    }
+}
@@ -0,0 +1,90 @@
+use currency::Currency;
+use lpp::stub::loan::LppLoan as LppLoanTrait;
+use oracle_platform::Oracle as OracleTrait;
+use platform::batch::Batch;
+use profit::stub::ProfitRef;
+use sdk::cosmwasm_std::Timestamp;
+use timealarms::stub::TimeAlarmsRef;
+
+use crate::{
+    api::{LeaseCoin, MergedLoan},
+    contract::SplitDTOOut,
+    error::ContractError,
+    finance::{LpnCurrencies, LpnCurrency, OracleRef, ReserveRef},
+    lease::{with_lease::WithLease, IntoDTOResult, Lease as LeaseDO, LeaseDTO},
+};
+
+use super::{check_debt, LiquidationStatus};
+
+/// Take over the asset and the loan margin of a lease merged into this one
+pub(crate) struct Cmd<'a> {
+    asset: LeaseCoin,
+    loan: MergedLoan,
+    now: &'a Timestamp,
+    profit: ProfitRef,
+    alarms: (TimeAlarmsRef, OracleRef),
+    reserve: ReserveRef,
+}
+
+impl<'a> Cmd<'a> {
+    pub fn new(
+        asset: LeaseCoin,
+        loan: MergedLoan,
+        now: &'a Timestamp,
+        profit: ProfitRef,
+        alarms: (TimeAlarmsRef, OracleRef),
+        reserve: ReserveRef,
+    ) -> Self {
+        Self {
+            asset,
+            loan,
+            now,
+            profit,
+            alarms,
+            reserve,
+        }
+    }
+}
+
+pub(crate) struct AbsorbResult {
+    lease: LeaseDTO,
+    result: (Batch, LiquidationStatus),
+}
+
+impl SplitDTOOut for AbsorbResult {
+    type Other = (Batch, LiquidationStatus);
+
+    fn split_into(self) -> (LeaseDTO, Self::Other) {
+        (self.lease, self.result)
+    }
+}
+
+impl<'a> WithLease for Cmd<'a> {
+    type Output = AbsorbResult;
+
+    type Error = ContractError;
+
+    fn exec<Asset, Lpp, Oracle>(
+        self,
+        mut lease: LeaseDO<Asset, Lpp, Oracle>,
+    ) -> Result<Self::Output, Self::Error>
+    where
+        Asset: Currency,
+        Lpp: LppLoanTrait<LpnCurrency, LpnCurrencies>,
+        Oracle: OracleTrait<LpnCurrency>,
+    {
+        self.asset
+            .try_into()
+            .map_err(Into::into)
+            .and_then(|asset| lease.absorb(asset, self.loan, self.now))?;
+
+        let liquidation = check_debt::check_debt(&lease, self.now, &self.alarms.0, &self.alarms.1)?;
+
+        lease
+            .try_into_dto(self.profit, self.alarms.0, self.reserve)
+            .map(|IntoDTOResult { lease, batch }| AbsorbResult {
+                lease,
+                result: (batch, liquidation),
+            })
+    }
+}
@@ -0,0 +1,84 @@
+use currency::Currency;
+use lpp::stub::loan::LppLoan as LppLoanTrait;
+use oracle_platform::Oracle as OracleTrait;
+use platform::batch::Batch;
+use profit::stub::ProfitRef;
+use sdk::cosmwasm_std::Timestamp;
+use timealarms::stub::TimeAlarmsRef;
+
+use crate::{
+    api::LeaseCoin,
+    contract::SplitDTOOut,
+    error::ContractError,
+    finance::{LpnCurrencies, LpnCurrency, OracleRef, ReserveRef},
+    lease::{with_lease::WithLease, IntoDTOResult, Lease as LeaseDO, LeaseDTO},
+};
+
+use super::{check_debt, LiquidationStatus};
+
+pub(crate) struct Cmd<'a> {
+    collateral: LeaseCoin,
+    now: &'a Timestamp,
+    profit: ProfitRef,
+    alarms: (TimeAlarmsRef, OracleRef),
+    reserve: ReserveRef,
+}
+
+impl<'a> Cmd<'a> {
+    pub fn new(
+        collateral: LeaseCoin,
+        now: &'a Timestamp,
+        profit: ProfitRef,
+        alarms: (TimeAlarmsRef, OracleRef),
+        reserve: ReserveRef,
+    ) -> Self {
+        Self {
+            collateral,
+            now,
+            profit,
+            alarms,
+            reserve,
+        }
+    }
+}
+
+pub(crate) struct AddCollateralResult {
+    lease: LeaseDTO,
+    result: (Batch, LiquidationStatus),
+}
+
+impl SplitDTOOut for AddCollateralResult {
+    type Other = (Batch, LiquidationStatus);
+
+    fn split_into(self) -> (LeaseDTO, Self::Other) {
+        (self.lease, self.result)
+    }
+}
+
+impl<'a> WithLease for Cmd<'a> {
+    type Output = AddCollateralResult;
+
+    type Error = ContractError;
+
+    fn exec<Asset, Lpp, Oracle>(
+        self,
+        mut lease: LeaseDO<Asset, Lpp, Oracle>,
+    ) -> Result<Self::Output, Self::Error>
+    where
+        Asset: Currency,
+        Lpp: LppLoanTrait<LpnCurrency, LpnCurrencies>,
+        Oracle: OracleTrait<LpnCurrency>,
+    {
+        let collateral = self.collateral.try_into()?;
+        lease.add_collateral(collateral);
+
+        let liquidation = check_debt::check_debt(&lease, self.now, &self.alarms.0, &self.alarms.1)?;
+
+        lease
+            .try_into_dto(self.profit, self.alarms.0, self.reserve)
+            .map(|IntoDTOResult { lease, batch }| AddCollateralResult {
+                lease,
+                result: (batch, liquidation),
+            })
+    }
+}
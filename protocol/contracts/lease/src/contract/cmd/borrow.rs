@@ -0,0 +1,115 @@
+use currency::Currency;
+use finance::percent::Percent;
+use lpp::{
+    error::ContractError as LppError,
+    msg::QueryQuoteResponse,
+    stub::{
+        lender::{LppLender as LppLenderTrait, WithLppLender},
+        loan::LppLoan as LppLoanTrait,
+    },
+};
+use oracle_platform::Oracle as OracleTrait;
+use platform::batch::Batch;
+use profit::stub::ProfitRef;
+use sdk::cosmwasm_std::Timestamp;
+use timealarms::stub::TimeAlarmsRef;
+
+use crate::{
+    contract::SplitDTOOut,
+    error::ContractError,
+    finance::{LpnCoin, LpnCurrencies, LpnCurrency, ReserveRef},
+    lease::{with_lease::WithLease, IntoDTOResult, Lease as LeaseDO, LeaseDTO},
+};
+
+/// Query the Lpp for the annual interest rate of a loan
+pub(crate) struct Quote {
+    amount: LpnCoin,
+}
+
+impl Quote {
+    pub fn new(amount: LpnCoin) -> Self {
+        Self { amount }
+    }
+}
+
+impl WithLppLender<LpnCurrency, LpnCurrencies> for Quote {
+    type Output = Percent;
+
+    type Error = ContractError;
+
+    fn exec<LppLender>(self, lpp: LppLender) -> Result<Self::Output, Self::Error>
+    where
+        LppLender: LppLenderTrait<LpnCurrency, LpnCurrencies>,
+    {
+        lpp.quote(self.amount)
+            .and_then(|quote| match quote {
+                QueryQuoteResponse::QuoteInterestRate(rate) => Ok(rate),
+                QueryQuoteResponse::NoLiquidity => Err(LppError::NoLiquidity {}),
+            })
+            .map_err(Into::into)
+    }
+}
+
+pub(crate) struct Cmd<'a> {
+    amount: LpnCoin,
+    annual_interest_rate: Percent,
+    now: &'a Timestamp,
+    profit: ProfitRef,
+    time_alarms: TimeAlarmsRef,
+    reserve: ReserveRef,
+}
+
+impl<'a> Cmd<'a> {
+    pub fn new(
+        amount: LpnCoin,
+        annual_interest_rate: Percent,
+        now: &'a Timestamp,
+        profit: ProfitRef,
+        time_alarms: TimeAlarmsRef,
+        reserve: ReserveRef,
+    ) -> Self {
+        Self {
+            amount,
+            annual_interest_rate,
+            now,
+            profit,
+            time_alarms,
+            reserve,
+        }
+    }
+}
+
+pub(crate) struct BorrowResult {
+    lease: LeaseDTO,
+    batch: Batch,
+}
+
+impl SplitDTOOut for BorrowResult {
+    type Other = Batch;
+
+    fn split_into(self) -> (LeaseDTO, Self::Other) {
+        (self.lease, self.batch)
+    }
+}
+
+impl<'a> WithLease for Cmd<'a> {
+    type Output = BorrowResult;
+
+    type Error = ContractError;
+
+    fn exec<Asset, Lpp, Oracle>(
+        self,
+        mut lease: LeaseDO<Asset, Lpp, Oracle>,
+    ) -> Result<Self::Output, Self::Error>
+    where
+        Asset: Currency,
+        Lpp: LppLoanTrait<LpnCurrency, LpnCurrencies>,
+        Oracle: OracleTrait<LpnCurrency>,
+    {
+        lease.borrow(self.amount, self.annual_interest_rate, self.now)?;
+
+        lease
+            .try_into_dto(self.profit, self.time_alarms, self.reserve)
+            .map(|IntoDTOResult { lease, batch }| BorrowResult { lease, batch })
+    }
+}
@@ -0,0 +1,87 @@
+use currency::Currency;
+use lpp::stub::loan::LppLoan as LppLoanTrait;
+use oracle_platform::Oracle as OracleTrait;
+use platform::batch::Batch;
+use profit::stub::ProfitRef;
+use sdk::cosmwasm_std::Timestamp;
+use timealarms::stub::TimeAlarmsRef;
+
+use crate::{
+    api::position::CloseTrigger,
+    contract::SplitDTOOut,
+    error::ContractError,
+    finance::{LpnCurrencies, LpnCurrency, OracleRef, ReserveRef},
+    lease::{with_lease::WithLease, IntoDTOResult, Lease as LeaseDO, LeaseDTO},
+    position::CloseStrategy,
+};
+
+use super::{check_debt, LiquidationStatus};
+
+pub(crate) struct Cmd<'a> {
+    strategy: CloseStrategy,
+    trigger: Option<CloseTrigger>,
+    now: &'a Timestamp,
+    profit: ProfitRef,
+    alarms: (TimeAlarmsRef, OracleRef),
+    reserve: ReserveRef,
+}
+
+impl<'a> Cmd<'a> {
+    pub fn new(
+        strategy: CloseStrategy,
+        trigger: Option<CloseTrigger>,
+        now: &'a Timestamp,
+        profit: ProfitRef,
+        alarms: (TimeAlarmsRef, OracleRef),
+        reserve: ReserveRef,
+    ) -> Self {
+        Self {
+            strategy,
+            trigger,
+            now,
+            profit,
+            alarms,
+            reserve,
+        }
+    }
+}
+
+pub(crate) struct ChangeClosePolicyResult {
+    lease: LeaseDTO,
+    result: (Batch, LiquidationStatus),
+}
+
+impl SplitDTOOut for ChangeClosePolicyResult {
+    type Other = (Batch, LiquidationStatus);
+
+    fn split_into(self) -> (LeaseDTO, Self::Other) {
+        (self.lease, self.result)
+    }
+}
+
+impl<'a> WithLease for Cmd<'a> {
+    type Output = ChangeClosePolicyResult;
+
+    type Error = ContractError;
+
+    fn exec<Asset, Lpp, Oracle>(
+        self,
+        mut lease: LeaseDO<Asset, Lpp, Oracle>,
+    ) -> Result<Self::Output, Self::Error>
+    where
+        Asset: Currency,
+        Lpp: LppLoanTrait<LpnCurrency, LpnCurrencies>,
+        Oracle: OracleTrait<LpnCurrency>,
+    {
+        lease.change_close_policy(self.strategy, self.trigger)?;
+
+        let liquidation = check_debt::check_debt(&lease, self.now, &self.alarms.0, &self.alarms.1)?;
+
+        lease
+            .try_into_dto(self.profit, self.alarms.0, self.reserve)
+            .map(|IntoDTOResult { lease, batch }| ChangeClosePolicyResult {
+                lease,
+                result: (batch, liquidation),
+            })
+    }
+}
@@ -9,11 +9,11 @@ use sdk::cosmwasm_std::Timestamp;
 use timealarms::stub::TimeAlarmsRef;
 
 use crate::{
-    api::LeaseCoin,
+    api::{position::PositionClose, LeaseCoin},
     error::{ContractError, ContractResult},
     finance::{LpnCurrencies, LpnCurrency, OracleRef},
     lease::{with_lease::WithLease, DebtStatus, Lease as LeaseDO},
-    position::{Cause, Liquidation},
+    position::{Cause, CloseStrategy, Liquidation},
 };
 
 pub(crate) fn check_debt<Asset, Lpp, Oracle>(
@@ -45,6 +45,11 @@ pub(crate) enum DebtStatusDTO {
         alarms: Batch,
     },
     NeedLiquidation(LiquidationDTO),
+    NeedClose {
+        strategy: CloseStrategy,
+        close: PositionClose,
+    },
+    Expired,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -78,6 +83,8 @@ where
                 alarms,
             },
             DebtStatus::NeedLiquidation(liquidation) => Self::NeedLiquidation(liquidation.into()),
+            DebtStatus::NeedClose { strategy, close } => Self::NeedClose { strategy, close },
+            DebtStatus::Expired => Self::Expired,
         }
     }
 }
@@ -8,6 +8,7 @@ use crate::{
     error::ContractError,
     finance::{LpnCoinDTO, LpnCurrencies, LpnCurrency, ReserveRef},
     lease::{with_lease::WithLease, Lease},
+    loan::RepayReceipt,
 };
 
 use super::repayable::Emitter;
@@ -47,7 +48,7 @@ where
     ChangeSender: FixedAddressSender,
     EmitterT: Emitter,
 {
-    type Output = MessageResponse;
+    type Output = (RepayReceipt, MessageResponse);
 
     type Error = ContractError;
 
@@ -76,9 +77,10 @@ where
             })
             .map(|result| {
                 let (receipt, messages) = result.decompose();
-                MessageResponse::messages_with_events(
-                    messages,
-                    self.emitter_fn.emit(&lease_addr, &receipt),
+                let events = self.emitter_fn.emit(&lease_addr, &receipt);
+                (
+                    receipt,
+                    MessageResponse::messages_with_events(messages, events),
                 )
             })
     }
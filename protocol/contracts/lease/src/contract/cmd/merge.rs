@@ -0,0 +1,104 @@
+use currency::Currency;
+use lpp::stub::loan::LppLoan as LppLoanTrait;
+use oracle_platform::Oracle as OracleTrait;
+use sdk::cosmwasm_std::Timestamp;
+
+use crate::{
+    api::{LeaseCoin, MergedLoan},
+    error::ContractError,
+    finance::{LpnCoin, LpnCurrencies, LpnCurrency},
+    lease::{with_lease::WithLease, Lease as LeaseDO},
+};
+
+/// Check if the lease can be merged with another one
+pub(crate) struct Validate<'a> {
+    other_asset: LeaseCoin,
+    other_due: LpnCoin,
+    now: &'a Timestamp,
+}
+
+impl<'a> Validate<'a> {
+    pub fn new(other_asset: LeaseCoin, other_due: LpnCoin, now: &'a Timestamp) -> Self {
+        Self {
+            other_asset,
+            other_due,
+            now,
+        }
+    }
+}
+
+impl<'a> WithLease for Validate<'a> {
+    type Output = ();
+
+    type Error = ContractError;
+
+    fn exec<Asset, Lpp, Oracle>(
+        self,
+        lease: LeaseDO<Asset, Lpp, Oracle>,
+    ) -> Result<Self::Output, Self::Error>
+    where
+        Asset: Currency,
+        Lpp: LppLoanTrait<LpnCurrency, LpnCurrencies>,
+        Oracle: OracleTrait<LpnCurrency>,
+    {
+        self.other_asset
+            .try_into()
+            .map_err(Into::into)
+            .and_then(|other_asset| lease.validate_merge(other_asset, self.other_due, self.now))
+    }
+}
+
+/// Check if the lease can take over the asset and the loan of a lease merged into it
+pub(crate) struct ValidateAbsorb<'a> {
+    asset: LeaseCoin,
+    loan: &'a MergedLoan,
+    now: &'a Timestamp,
+}
+
+impl<'a> ValidateAbsorb<'a> {
+    pub fn new(asset: LeaseCoin, loan: &'a MergedLoan, now: &'a Timestamp) -> Self {
+        Self { asset, loan, now }
+    }
+}
+
+impl<'a> WithLease for ValidateAbsorb<'a> {
+    type Output = ();
+
+    type Error = ContractError;
+
+    fn exec<Asset, Lpp, Oracle>(
+        self,
+        lease: LeaseDO<Asset, Lpp, Oracle>,
+    ) -> Result<Self::Output, Self::Error>
+    where
+        Asset: Currency,
+        Lpp: LppLoanTrait<LpnCurrency, LpnCurrencies>,
+        Oracle: OracleTrait<LpnCurrency>,
+    {
+        self.asset
+            .try_into()
+            .map_err(Into::into)
+            .and_then(|asset| lease.validate_absorb(asset, self.loan, self.now))
+    }
+}
+
+/// Obtain the loan terms of a lease being merged into another one
+pub(crate) struct Terms {}
+
+impl WithLease for Terms {
+    type Output = MergedLoan;
+
+    type Error = ContractError;
+
+    fn exec<Asset, Lpp, Oracle>(
+        self,
+        lease: LeaseDO<Asset, Lpp, Oracle>,
+    ) -> Result<Self::Output, Self::Error>
+    where
+        Asset: Currency,
+        Lpp: LppLoanTrait<LpnCurrency, LpnCurrencies>,
+        Oracle: OracleTrait<LpnCurrency>,
+    {
+        Ok(lease.merged_loan())
+    }
+}
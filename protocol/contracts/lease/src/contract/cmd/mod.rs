@@ -1,3 +1,7 @@
+pub(crate) use absorb::Cmd as Absorb;
+pub(crate) use add_collateral::Cmd as AddCollateral;
+pub(crate) use borrow::{Cmd as Borrow, Quote as BorrowQuote};
+pub(crate) use change_close_policy::Cmd as ChangeClosePolicy;
 pub(crate) use check_debt::{
     Cmd as LiquidationStatusCmd, DebtStatusDTO as LiquidationStatus, FullLiquidationDTO,
     LiquidationDTO, PartialLiquidationDTO,
@@ -5,21 +9,31 @@ pub(crate) use check_debt::{
 pub(crate) use close_full::Close as FullClose;
 pub(crate) use close_paid::Close;
 pub(crate) use close_partial::CloseFn as PartialCloseFn;
+pub(crate) use merge::{Terms as MergeTerms, Validate as ValidateMerge, ValidateAbsorb};
 pub(crate) use obtain_payment::ObtainPayment;
 pub(crate) use open::open_lease;
 pub(crate) use open_loan::{OpenLoanReq, OpenLoanReqResult, OpenLoanResp, OpenLoanRespResult};
+pub(crate) use position_query::Cmd as PositionQuery;
+pub(crate) use refinance::{Cmd as Refinance, Quote as RefinanceQuote};
 pub(crate) use repay::RepayLeaseFn;
 pub(crate) use repayable::{Emitter as RepayEmitter, Repay, RepayFn, RepayResult};
 pub(crate) use state::LeaseState;
 pub(super) use validate_close_position::Cmd as ValidateClosePosition;
 
+mod absorb;
+mod add_collateral;
+mod borrow;
+mod change_close_policy;
 mod check_debt;
 mod close_full;
 mod close_paid;
 mod close_partial;
+mod merge;
 mod obtain_payment;
 mod open;
 mod open_loan;
+mod position_query;
+mod refinance;
 mod repay;
 mod repayable;
 mod state;
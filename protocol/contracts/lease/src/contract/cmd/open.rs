@@ -92,6 +92,9 @@ impl<'a> WithLeaseDeps for LeaseFactory<'a> {
                     position,
                     loan,
                     oracle,
+                    self.form
+                        .max_duration
+                        .map(|max_duration| self.start_at + max_duration),
                 )
             })?;
 
@@ -107,6 +110,8 @@ impl<'a> WithLeaseDeps for LeaseFactory<'a> {
                 alarms,
             } => alarms,
             LiquidationStatus::NeedLiquidation(_) => unreachable!(),
+            LiquidationStatus::NeedClose { .. } => unreachable!(),
+            LiquidationStatus::Expired => return Err(ContractError::ExpiredOnOpen()),
         };
 
         lease
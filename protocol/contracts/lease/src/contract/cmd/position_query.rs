@@ -0,0 +1,61 @@
+use currency::Currency;
+use lpp::stub::loan::LppLoan as LppLoanTrait;
+use oracle_platform::Oracle as OracleTrait;
+use profit::stub::ProfitRef;
+use sdk::cosmwasm_std::{to_json_binary, Binary, Timestamp};
+
+use crate::{
+    api::query::{LiquidationPrices, PositionQuery, Projection},
+    error::ContractError,
+    finance::{LpnCurrencies, LpnCurrency},
+    lease::{with_lease::WithLease, Lease},
+};
+
+pub(crate) struct Cmd {
+    query: PositionQuery,
+    now: Timestamp,
+    profit: ProfitRef,
+}
+
+impl Cmd {
+    pub fn new(query: PositionQuery, now: Timestamp, profit: ProfitRef) -> Self {
+        Self { query, now, profit }
+    }
+}
+
+impl WithLease for Cmd {
+    type Output = Binary;
+
+    type Error = ContractError;
+
+    fn exec<Asset, LppLoan, Oracle>(
+        self,
+        lease: Lease<Asset, LppLoan, Oracle>,
+    ) -> Result<Self::Output, Self::Error>
+    where
+        Asset: Currency,
+        LppLoan: LppLoanTrait<LpnCurrency, LpnCurrencies>,
+        Oracle: OracleTrait<LpnCurrency>,
+    {
+        // the messages of the simulated operations are discarded
+        let mut profit = self.profit.into_stub();
+
+        match self.query {
+            PositionQuery::LiquidationPrices {} => {
+                lease.liquidation_prices(&self.now).and_then(|may_prices| {
+                    to_json_binary(&may_prices.map(LiquidationPrices::from)).map_err(Into::into)
+                })
+            }
+            PositionQuery::WhatIfRepay { payment } => payment
+                .try_into()
+                .map_err(Into::into)
+                .and_then(|payment| lease.what_if_repay(payment, &self.now, &mut profit))
+                .and_then(|outlook| to_json_binary(&Projection::from(outlook)).map_err(Into::into)),
+            PositionQuery::WhatIfClose { amount } => (&amount)
+                .try_into()
+                .map_err(Into::into)
+                .and_then(|amount| lease.what_if_close(amount, &self.now, &mut profit))
+                .and_then(|outlook| to_json_binary(&Projection::from(outlook)).map_err(Into::into)),
+        }
+    }
+}
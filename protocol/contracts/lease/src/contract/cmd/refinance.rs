@@ -0,0 +1,133 @@
+use currency::Currency;
+use lpp::{
+    msg::QueryRefinanceQuoteResponse,
+    stub::{
+        lender::{LppLender as LppLenderTrait, WithLppLender},
+        loan::LppLoan as LppLoanTrait,
+    },
+};
+use oracle_platform::Oracle as OracleTrait;
+use platform::{
+    bank::{FixedAddressSender, LazySenderStub},
+    batch::Batch,
+};
+use profit::stub::ProfitRef;
+use sdk::cosmwasm_std::{Addr, Timestamp};
+use timealarms::stub::TimeAlarmsRef;
+
+use crate::{
+    contract::SplitDTOOut,
+    error::ContractError,
+    finance::{LpnCoin, LpnCurrencies, LpnCurrency, OracleRef, ReserveRef},
+    lease::{with_lease::WithLease, IntoDTOResult, Lease as LeaseDO, LeaseDTO},
+    loan::RefinanceReceipt,
+};
+
+use super::{check_debt, LiquidationStatus};
+
+/// Query the Lpp for the terms a loan would be refinanced at
+pub(crate) struct Quote {}
+
+impl WithLppLender<LpnCurrency, LpnCurrencies> for Quote {
+    type Output = QueryRefinanceQuoteResponse;
+
+    type Error = ContractError;
+
+    fn exec<LppLender>(self, lpp: LppLender) -> Result<Self::Output, Self::Error>
+    where
+        LppLender: LppLenderTrait<LpnCurrency, LpnCurrencies>,
+    {
+        lpp.refinance_quote().map_err(Into::into)
+    }
+}
+
+pub(crate) struct Cmd<'a> {
+    payment: LpnCoin,
+    quote: QueryRefinanceQuoteResponse,
+    customer: Addr,
+    now: &'a Timestamp,
+    profit: ProfitRef,
+    alarms: (TimeAlarmsRef, OracleRef),
+    reserve: ReserveRef,
+}
+
+impl<'a> Cmd<'a> {
+    pub fn new(
+        payment: LpnCoin,
+        quote: QueryRefinanceQuoteResponse,
+        customer: Addr,
+        now: &'a Timestamp,
+        profit: ProfitRef,
+        alarms: (TimeAlarmsRef, OracleRef),
+        reserve: ReserveRef,
+    ) -> Self {
+        Self {
+            payment,
+            quote,
+            customer,
+            now,
+            profit,
+            alarms,
+            reserve,
+        }
+    }
+}
+
+pub(crate) struct RefinanceResult {
+    lease: LeaseDTO,
+    result: (Batch, RefinanceReceipt, LiquidationStatus),
+}
+
+impl SplitDTOOut for RefinanceResult {
+    type Other = (Batch, RefinanceReceipt, LiquidationStatus);
+
+    fn split_into(self) -> (LeaseDTO, Self::Other) {
+        (self.lease, self.result)
+    }
+}
+
+impl<'a> WithLease for Cmd<'a> {
+    type Output = RefinanceResult;
+
+    type Error = ContractError;
+
+    fn exec<Asset, Lpp, Oracle>(
+        self,
+        mut lease: LeaseDO<Asset, Lpp, Oracle>,
+    ) -> Result<Self::Output, Self::Error>
+    where
+        Asset: Currency,
+        Lpp: LppLoanTrait<LpnCurrency, LpnCurrencies>,
+        Oracle: OracleTrait<LpnCurrency>,
+    {
+        let mut profit_sender = self.profit.clone().into_stub();
+
+        let receipt = lease.refinance(
+            self.payment,
+            self.quote.annual_interest_rate,
+            self.quote.fee,
+            self.now,
+            &mut profit_sender,
+        )?;
+
+        let mut change_sender = LazySenderStub::new(self.customer);
+        if !receipt.change().is_zero() {
+            change_sender.send(receipt.change());
+        }
+
+        let liquidation = check_debt::check_debt(&lease, self.now, &self.alarms.0, &self.alarms.1)?;
+
+        lease
+            .try_into_dto(self.profit, self.alarms.0, self.reserve)
+            .map(|IntoDTOResult { lease, batch }| RefinanceResult {
+                lease,
+                result: (
+                    batch
+                        .merge(profit_sender.into())
+                        .merge(change_sender.into()),
+                    receipt,
+                    liquidation,
+                ),
+            })
+    }
+}
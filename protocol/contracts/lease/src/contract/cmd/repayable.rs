@@ -94,6 +94,7 @@ impl SplitDTOOut for RepayLeaseResult {
 pub(crate) struct RepayResult {
     pub response: MessageResponse,
     pub loan_paid: bool,
+    pub receipt: RepayReceipt,
     pub liquidation: LiquidationStatus,
 }
 
@@ -140,6 +141,7 @@ where
                                 events,
                             ),
                             loan_paid: receipt.close(),
+                            receipt,
                             liquidation,
                         },
                     }
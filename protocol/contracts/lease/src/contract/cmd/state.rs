@@ -1,7 +1,7 @@
 use currency::Currency;
 use lpp::stub::loan::LppLoan as LppLoanTrait;
 use oracle_platform::Oracle as OracleTrait;
-use sdk::cosmwasm_std::Timestamp;
+use sdk::cosmwasm_std::{Addr, Timestamp};
 
 use crate::{
     api::query::{opened::OngoingTrx, StateResponse},
@@ -12,12 +12,17 @@ use crate::{
 
 pub struct LeaseState {
     now: Timestamp,
+    operators: Vec<Addr>,
     in_progress: Option<OngoingTrx>,
 }
 
 impl LeaseState {
-    pub fn new(now: Timestamp, in_progress: Option<OngoingTrx>) -> Self {
-        Self { now, in_progress }
+    pub fn new(now: Timestamp, operators: Vec<Addr>, in_progress: Option<OngoingTrx>) -> Self {
+        Self {
+            now,
+            operators,
+            in_progress,
+        }
     }
 }
 
@@ -37,6 +42,8 @@ impl WithLease for LeaseState {
     {
         Ok(StateResponse::opened_from(
             lease.state(self.now),
+            lease.close_policy(),
+            self.operators,
             self.in_progress,
         ))
     }
@@ -2,7 +2,7 @@ use platform::{error as platform_error, message::Response as MessageResponse, re
 use sdk::{
     cosmwasm_ext::Response as CwResponse,
     cosmwasm_std::{
-        entry_point, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, QuerierWrapper,
+        entry_point, to_json_binary, Api, Binary, Deps, DepsMut, Env, MessageInfo, QuerierWrapper,
         Reply, Storage,
     },
     neutron_sdk::sudo::msg::SudoMsg,
@@ -11,10 +11,13 @@ use versioning::{package_version, version, SemVer, Version, VersionSegment};
 
 use crate::{
     api::{
-        open::NewLeaseContract, query::StateQuery, ExecuteMsg, LeaseAssetCurrencies, MigrateMsg,
+        open::NewLeaseContract,
+        query::{HistoryQuery, QueryMsg},
+        ExecuteMsg, LeaseAssetCurrencies, MigrateMsg,
     },
-    contract::api::Contract,
+    contract::{api::Contract, History},
     error::ContractResult,
+    position::CloseStrategy,
 };
 
 use super::state::{self, Response, State};
@@ -53,9 +56,18 @@ pub fn instantiate(
 pub fn migrate(
     deps: DepsMut<'_>,
     _env: Env,
-    MigrateMsg {}: MigrateMsg,
+    MigrateMsg { dex_ibc }: MigrateMsg,
 ) -> ContractResult<CwResponse> {
     versioning::update_software(deps.storage, CONTRACT_VERSION, Into::into)
+        .and_then(|label| {
+            dex_ibc
+                .map_or(Ok(()), |ibc| {
+                    state::load(deps.storage)
+                        .map(|lease| state::with_dex_ibc(lease, ibc))
+                        .and_then(|lease| state::save(deps.storage, &lease))
+                })
+                .map(|()| label)
+        })
         .and_then(response::response)
         .inspect_err(platform_error::log(deps.api))
 }
@@ -75,7 +87,7 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> ContractResult<CwResponse> {
     process_lease(deps.storage, |lease| {
-        process_execute(msg, lease, deps.querier, env, info)
+        process_execute(msg, lease, deps.api, deps.querier, env, info)
     })
     .map(response::response_only_messages)
     .inspect_err(platform_error::log(deps.api))
@@ -91,11 +103,19 @@ pub fn sudo(deps: DepsMut<'_>, env: Env, msg: SudoMsg) -> ContractResult<CwRespo
 }
 
 #[entry_point]
-pub fn query(deps: Deps<'_>, env: Env, _msg: StateQuery) -> ContractResult<Binary> {
-    state::load(deps.storage)
-        .and_then(|state| state.state(env.block.time, deps.querier))
-        .and_then(|resp| to_json_binary(&resp).map_err(Into::into))
-        .inspect_err(platform_error::log(deps.api))
+pub fn query(deps: Deps<'_>, env: Env, msg: QueryMsg) -> ContractResult<Binary> {
+    match msg {
+        QueryMsg::State(_) => state::load(deps.storage)
+            .and_then(|state| state.state(env.block.time, deps.querier))
+            .and_then(|resp| to_json_binary(&resp).map_err(Into::into)),
+        QueryMsg::Position(query) => state::load(deps.storage)
+            .and_then(|state| state.position_query(query, env.block.time, deps.querier)),
+        QueryMsg::History(HistoryQuery::History { start_after, limit }) => {
+            History::page(deps.storage, start_after, limit)
+                .and_then(|resp| to_json_binary(&resp).map_err(Into::into))
+        }
+    }
+    .inspect_err(platform_error::log(deps.api))
 }
 
 fn process_lease<ProcFn>(
@@ -109,20 +129,49 @@ where
         |Response {
              response,
              next_state,
-         }| state::save(storage, &next_state).map(|()| response),
+         }| {
+            state::store_history(storage, next_state)
+                .and_then(|next_state| state::save(storage, &next_state))
+                .map(|()| response)
+        },
     )
 }
 
 fn process_execute(
     msg: ExecuteMsg,
     state: State,
+    api: &dyn Api,
     querier: QuerierWrapper<'_>,
     env: Env,
     info: MessageInfo,
 ) -> ContractResult<Response> {
     match msg {
         ExecuteMsg::Repay() => state.repay(querier, env, info),
+        ExecuteMsg::AddCollateral() => state.add_collateral(querier, env, info),
+        ExecuteMsg::Borrow(amount) => state.borrow(amount, querier, env, info),
+        ExecuteMsg::Refinance() => state.refinance(querier, env, info),
+        ExecuteMsg::RotateAsset { currency } => state.rotate_asset(currency, querier, env, info),
         ExecuteMsg::ClosePosition(spec) => state.close_position(spec, querier, env, info),
+        ExecuteMsg::ChangeTakeProfit(trigger) => {
+            state.change_close_policy(CloseStrategy::TakeProfit, trigger, querier, env, info)
+        }
+        ExecuteMsg::ChangeStopLoss(trigger) => {
+            state.change_close_policy(CloseStrategy::StopLoss, trigger, querier, env, info)
+        }
+        ExecuteMsg::AddOperator(operator) => api
+            .addr_validate(operator.as_str())
+            .map_err(Into::into)
+            .and_then(|operator| state.add_operator(operator, info)),
+        ExecuteMsg::RemoveOperator(operator) => state.remove_operator(operator, info),
+        ExecuteMsg::TransferOwnership { new_customer } => api
+            .addr_validate(new_customer.as_str())
+            .map_err(Into::into)
+            .and_then(|new_customer| state.transfer_ownership(new_customer, env, info)),
+        ExecuteMsg::MergeInto { lease } => api
+            .addr_validate(lease.as_str())
+            .map_err(Into::into)
+            .and_then(|lease| state.merge_into(lease, querier, env, info)),
+        ExecuteMsg::AbsorbLease(loan) => state.absorb_lease(loan, querier, env, info),
         ExecuteMsg::Close() => state.close(querier, env, info),
         ExecuteMsg::TimeAlarm {} => state.on_time_alarm(querier, env, info),
         ExecuteMsg::PriceAlarm() => state.on_price_alarm(querier, env, info),
@@ -1,9 +1,13 @@
 use serde::{Deserialize, Serialize};
 
-use platform::batch::Batch;
-use sdk::cosmwasm_std::{Addr, QuerierWrapper};
+use currency::BankSymbols;
+use platform::{batch::Batch, coin_legacy};
+use sdk::cosmwasm_std::{to_json_binary, Addr, QuerierWrapper, WasmMsg};
 
-use crate::{api::FinalizerExecuteMsg, error::ContractResult};
+use crate::{
+    api::{FinalizerExecuteMsg, LeaseAssetCurrencies, LeaseCoin, MergedLoan},
+    error::ContractResult,
+};
 
 #[derive(Serialize, Deserialize)]
 #[serde(deny_unknown_fields, rename_all = "snake_case")]
@@ -20,6 +24,10 @@ impl FinalizerRef {
             .map_err(Into::into)
     }
 
+    pub(super) fn owned_by(&self, addr: &Addr) -> bool {
+        self.addr == addr
+    }
+
     pub(super) fn notify(&self, customer: Addr) -> ContractResult<Batch> {
         let mut msgs = Batch::default();
         msgs.schedule_execute_wasm_no_reply_no_funds(
@@ -29,4 +37,51 @@ impl FinalizerRef {
         .map(|()| msgs)
         .map_err(Into::into)
     }
+
+    pub(super) fn notify_transfer(
+        &self,
+        customer: Addr,
+        new_customer: Addr,
+    ) -> ContractResult<Batch> {
+        let mut msgs = Batch::default();
+        msgs.schedule_execute_wasm_no_reply_no_funds(
+            self.addr.clone(),
+            &FinalizerExecuteMsg::TransferLease {
+                customer,
+                new_customer,
+            },
+        )
+        .map(|()| msgs)
+        .map_err(Into::into)
+    }
+
+    /// Hand the asset and the loan of a lease over to the lease it gets merged into
+    pub(super) fn notify_merge(
+        &self,
+        customer: Addr,
+        into: Addr,
+        loan: MergedLoan,
+        asset: &LeaseCoin,
+    ) -> ContractResult<Batch> {
+        let mut msgs = Batch::default();
+        coin_legacy::to_cosmwasm_on_network::<LeaseAssetCurrencies, BankSymbols>(asset)
+            .and_then(|funds| {
+                to_json_binary(&FinalizerExecuteMsg::FinalizeMerge {
+                    customer,
+                    into,
+                    loan,
+                })
+                .map(|msg| WasmMsg::Execute {
+                    contract_addr: self.addr.clone().into(),
+                    msg,
+                    funds: vec![funds],
+                })
+                .map_err(Into::into)
+            })
+            .map(|msg| {
+                msgs.schedule_execute_no_reply(msg);
+                msgs
+            })
+            .map_err(Into::into)
+    }
 }
@@ -0,0 +1,194 @@
+use serde::{Deserialize, Serialize};
+
+use sdk::{
+    cosmwasm_std::{Order, Storage, Timestamp},
+    cw_storage_plus::{Bound, Map},
+};
+
+use crate::{
+    api::query::{HistoryRecord, PaymentKind, RecordId},
+    error::ContractResult,
+    loan::RepayReceipt,
+};
+
+/// A bounded log of the payments toward the loan of a lease
+///
+/// The records are kept in a storage of their own, apart from the lease state.
+/// The payments are recorded as pending ones while a lease message is being processed,
+/// and are moved into the storage once the lease is at rest. Once full, the oldest records
+/// are dropped to make room for the new ones.
+#[derive(Serialize, Deserialize, Default)]
+#[cfg_attr(test, derive(Debug, PartialEq, Eq))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub(crate) struct History {
+    next_id: RecordId,
+    pending: Vec<HistoryRecord>,
+}
+
+impl History {
+    const CAPACITY: RecordId = 50;
+    const DEFAULT_LIMIT: u32 = 10;
+    const RECORDS: Map<'static, RecordId, HistoryRecord> = Map::new("history");
+
+    pub fn record(mut self, at: Timestamp, kind: PaymentKind, receipt: &RepayReceipt) -> Self {
+        self.pending.push(HistoryRecord {
+            id: self.next_id,
+            at,
+            kind,
+            payment: receipt.total().into(),
+            overdue_margin_interest: receipt.overdue_margin_paid().into(),
+            overdue_loan_interest: receipt.overdue_interest_paid().into(),
+            due_margin_interest: receipt.due_margin_paid().into(),
+            due_loan_interest: receipt.due_interest_paid().into(),
+            principal: receipt.principal_paid().into(),
+            change: receipt.change().into(),
+            loan_close: receipt.close(),
+        });
+        self.next_id += 1;
+        self
+    }
+
+    /// Move the pending records into the storage dropping the ones beyond the capacity
+    pub fn store(self, storage: &mut dyn Storage) -> ContractResult<Self> {
+        self.pending
+            .into_iter()
+            .try_for_each(|record| {
+                if let Some(dropped) = record.id.checked_sub(Self::CAPACITY) {
+                    Self::RECORDS.remove(storage, dropped);
+                }
+                Self::RECORDS.save(storage, record.id, &record)
+            })
+            .map(|()| Self {
+                next_id: self.next_id,
+                pending: vec![],
+            })
+            .map_err(Into::into)
+    }
+
+    /// Return up to `limit` stored records made after `start_after`, the oldest first
+    pub fn page(
+        storage: &dyn Storage,
+        start_after: Option<RecordId>,
+        limit: Option<u32>,
+    ) -> ContractResult<Vec<HistoryRecord>> {
+        let limit = limit.unwrap_or(Self::DEFAULT_LIMIT).min(Self::CAPACITY) as usize;
+        Self::RECORDS
+            .range(
+                storage,
+                start_after.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+            .map(|record| record.map(|(_id, record)| record).map_err(Into::into))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use sdk::cosmwasm_std::{testing::MockStorage, Storage, Timestamp};
+
+    use crate::{
+        api::query::{HistoryRecord, PaymentKind, RecordId},
+        finance::LpnCoin,
+        loan::RepayReceipt,
+    };
+
+    use super::History;
+
+    #[test]
+    fn record() {
+        let mut storage = MockStorage::default();
+        let at = Timestamp::from_seconds(100);
+        let history = History::default().record(at, PaymentKind::Repayment, &receipt());
+        assert!(History::page(&storage, None, None).unwrap().is_empty());
+
+        let history = history.store(&mut storage).unwrap();
+        assert_eq!(
+            History {
+                next_id: 1,
+                pending: vec![]
+            },
+            history
+        );
+        assert_eq!(
+            vec![HistoryRecord {
+                id: 0,
+                at,
+                kind: PaymentKind::Repayment,
+                payment: LpnCoin::new(35).into(),
+                overdue_margin_interest: LpnCoin::new(1).into(),
+                overdue_loan_interest: LpnCoin::new(2).into(),
+                due_margin_interest: LpnCoin::new(3).into(),
+                due_loan_interest: LpnCoin::new(4).into(),
+                principal: LpnCoin::new(20).into(),
+                change: LpnCoin::new(5).into(),
+                loan_close: false,
+            }],
+            History::page(&storage, None, None).unwrap()
+        );
+    }
+
+    #[test]
+    fn page() {
+        let mut storage = MockStorage::default();
+        (0..15)
+            .fold(History::default(), record_next)
+            .store(&mut storage)
+            .unwrap();
+
+        assert_eq!((0..10).collect::<Vec<_>>(), ids(&storage, None, None));
+        assert_eq!(vec![8, 9, 10], ids(&storage, Some(7), Some(3)));
+        assert_eq!(Vec::<RecordId>::new(), ids(&storage, Some(14), None));
+    }
+
+    #[test]
+    fn bounded() {
+        let mut storage = MockStorage::default();
+        let records = 2 * History::CAPACITY + 3;
+        let history = (0..records / 2).fold(History::default(), record_next);
+        let history = history.store(&mut storage).unwrap();
+        (records / 2..records)
+            .fold(history, record_next)
+            .store(&mut storage)
+            .unwrap();
+
+        let ids = ids(&storage, None, Some(u32::MAX));
+        assert_eq!(Ok(History::CAPACITY), ids.len().try_into());
+        assert_eq!(Some(&(records - History::CAPACITY)), ids.first());
+        assert_eq!(Some(&(records - 1)), ids.last());
+    }
+
+    fn record_next(history: History, at: RecordId) -> History {
+        history.record(
+            Timestamp::from_seconds(at.into()),
+            PaymentKind::Repayment,
+            &receipt(),
+        )
+    }
+
+    fn receipt() -> RepayReceipt {
+        RepayReceipt::new(
+            LpnCoin::new(2),
+            LpnCoin::new(1),
+            LpnCoin::new(4),
+            LpnCoin::new(3),
+            LpnCoin::new(30),
+            LpnCoin::new(20),
+            LpnCoin::new(5),
+        )
+    }
+
+    fn ids(
+        storage: &dyn Storage,
+        start_after: Option<RecordId>,
+        limit: Option<u32>,
+    ) -> Vec<RecordId> {
+        History::page(storage, start_after, limit)
+            .unwrap()
+            .into_iter()
+            .map(|record| record.id)
+            .collect()
+    }
+}
@@ -1,17 +1,27 @@
+use std::collections::BTreeSet;
+
 use serde::{Deserialize, Serialize};
 
-use dex::{Account, ConnectionParams, DexConnectable};
-use sdk::cosmwasm_std::QuerierWrapper;
+use dex::{Account, ConnectionParams, DexConnectable, IbcParams};
+use finance::percent::Percent;
+use platform::batch::Batch;
+use sdk::cosmwasm_std::{Addr, QuerierWrapper, Storage, Timestamp};
 
-use crate::lease::{with_lease::WithLease, LeaseDTO};
+use crate::{
+    api::query::PaymentKind,
+    error::ContractResult,
+    lease::{with_lease::WithLease, LeaseDTO},
+    loan::RepayReceipt,
+};
 
 pub use self::endpoins::{execute, instantiate, migrate, query, reply, sudo};
-use self::finalize::FinalizerRef;
+use self::{finalize::FinalizerRef, history::History};
 
 mod api;
 mod cmd;
 mod endpoins;
 mod finalize;
+mod history;
 pub mod msg;
 mod state;
 
@@ -21,6 +31,16 @@ pub(crate) struct Lease {
     lease: LeaseDTO,
     dex: Account,
     finalizer: FinalizerRef,
+    // TODO remove the default once all leases have been opened with a maximum slippage
+    #[serde(default = "Lease::no_max_slippage")]
+    max_slippage: Percent,
+    /// Addresses permitted to manage the lease on behalf of the customer
+    ///
+    /// They cannot receive any proceeds, the latter are always sent to the customer.
+    #[serde(default)]
+    operators: BTreeSet<Addr>,
+    #[serde(default)]
+    history: History,
 }
 
 pub(crate) trait SplitDTOOut {
@@ -30,14 +50,100 @@ pub(crate) trait SplitDTOOut {
 }
 
 impl Lease {
-    fn new(lease: LeaseDTO, dex: Account, finalizer: FinalizerRef) -> Self {
+    fn new(lease: LeaseDTO, dex: Account, finalizer: FinalizerRef, max_slippage: Percent) -> Self {
         Self {
             lease,
             dex,
             finalizer,
+            max_slippage,
+            operators: BTreeSet::new(),
+            history: History::default(),
+        }
+    }
+
+    /// The leases opened before introducing the slippage protection accept any swap output
+    const fn no_max_slippage() -> Percent {
+        Percent::HUNDRED
+    }
+
+    fn with_dex_ibc(self, ibc: IbcParams) -> Self {
+        Self {
+            dex: self.dex.with_ibc(ibc),
+            ..self
+        }
+    }
+
+    fn operators(&self) -> Vec<Addr> {
+        self.operators.iter().cloned().collect()
+    }
+
+    /// Check if `sender` is permitted to manage the lease, i.e. is the customer or an operator
+    fn check_manager(&self, sender: &Addr) -> ContractResult<()> {
+        if self.operators.contains(sender) {
+            Ok(())
+        } else {
+            access_control::check(&self.lease.customer, sender).map_err(Into::into)
         }
     }
 
+    /// Permit `operator` to manage the lease
+    ///
+    /// Only the customer is permitted to change the operators.
+    fn add_operator(mut self, operator: Addr, sender: &Addr) -> ContractResult<Self> {
+        access_control::check(&self.lease.customer, sender)
+            .map(|()| {
+                self.operators.insert(operator);
+                self
+            })
+            .map_err(Into::into)
+    }
+
+    /// Revoke the permission of `operator` to manage the lease
+    ///
+    /// Only the customer is permitted to change the operators.
+    fn remove_operator(mut self, operator: &Addr, sender: &Addr) -> ContractResult<Self> {
+        access_control::check(&self.lease.customer, sender)
+            .map(|()| {
+                self.operators.remove(operator);
+                self
+            })
+            .map_err(Into::into)
+    }
+
+    /// Hand the lease over to `new_customer`
+    ///
+    /// Only the customer is permitted to transfer the lease. The operators are revoked.
+    fn transfer_ownership(
+        mut self,
+        new_customer: Addr,
+        sender: &Addr,
+    ) -> ContractResult<(Self, Batch)> {
+        access_control::check(&self.lease.customer, sender)
+            .map_err(Into::into)
+            .and_then(|()| {
+                self.finalizer
+                    .notify_transfer(self.lease.customer.clone(), new_customer.clone())
+            })
+            .map(|batch| {
+                self.lease.customer = new_customer;
+                self.operators.clear();
+                (self, batch)
+            })
+    }
+
+    fn record_payment(self, at: Timestamp, kind: PaymentKind, receipt: &RepayReceipt) -> Self {
+        Self {
+            history: self.history.record(at, kind, receipt),
+            ..self
+        }
+    }
+
+    fn store_history(self, storage: &mut dyn Storage) -> ContractResult<Self> {
+        self.history
+            .store(storage)
+            .map(|history| Self { history, ..self })
+    }
+
     fn update<Cmd>(
         self,
         cmd: Cmd,
@@ -53,7 +159,7 @@ impl Lease {
     {
         self.lease.execute(cmd, querier).map(|result| {
             let (lease, other) = result.split_into();
-            (Self::new(lease, self.dex, self.finalizer), other)
+            (Self { lease, ..self }, other)
         })
     }
 }
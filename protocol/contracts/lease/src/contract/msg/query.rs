@@ -1,13 +1,24 @@
 use currency::Currency;
+use finance::liability::Level;
+use sdk::cosmwasm_std::Addr;
 
 use crate::{
-    api::query::{opened, paid, StateResponse},
-    lease::{LeaseDTO, State},
+    api::{
+        position::ClosePolicy,
+        query::{opened, paid, LiquidationPrices, Projection, StateResponse},
+    },
+    lease::{LeaseDTO, LiquidationPrices as LeaseLiquidationPrices, Outlook, State},
+    position::Debt,
 };
 
+/// The zone reported for a lease subject to liquidation, the ordinal of [`Level::Max`]
+const LIQUIDATION_ZONE: u8 = 4;
+
 impl StateResponse {
     pub fn opened_from<Asset>(
         open_lease: State<Asset>,
+        close_policy: ClosePolicy,
+        operators: Vec<Addr>,
         in_progress: Option<opened::OngoingTrx>,
     ) -> Self
     where
@@ -24,14 +35,54 @@ impl StateResponse {
             due_margin: open_lease.due_margin.into(),
             due_interest: open_lease.due_interest.into(),
             validity: open_lease.validity,
+            expiry: open_lease.expiry,
+            close_policy,
+            operators,
             in_progress,
         }
     }
 
-    pub fn paid_from(lease: LeaseDTO, in_progress: Option<paid::ClosingTrx>) -> Self {
+    pub fn paid_from(
+        lease: LeaseDTO,
+        operators: Vec<Addr>,
+        in_progress: Option<paid::ClosingTrx>,
+    ) -> Self {
         Self::Paid {
             amount: lease.position.into(),
+            operators,
             in_progress,
         }
     }
 }
+
+impl<Asset> From<LeaseLiquidationPrices<Asset>> for LiquidationPrices
+where
+    Asset: Currency,
+{
+    fn from(prices: LeaseLiquidationPrices<Asset>) -> Self {
+        Self {
+            first_liq_warn: prices.first_liq_warn.into(),
+            second_liq_warn: prices.second_liq_warn.into(),
+            third_liq_warn: prices.third_liq_warn.into(),
+            liquidation: prices.liquidation.into(),
+        }
+    }
+}
+
+impl<Asset> From<Outlook<Asset>> for Projection
+where
+    Asset: Currency,
+{
+    fn from(outlook: Outlook<Asset>) -> Self {
+        let zone = match outlook.debt {
+            Debt::No => 0,
+            Debt::Ok { zone, .. } => zone.low().map_or(0, Level::ordinal),
+            Debt::Bad(_) => LIQUIDATION_ZONE,
+        };
+        Self {
+            ltv: outlook.ltv,
+            zone,
+            liquidation_price: outlook.liquidation_price.map(Into::into),
+        }
+    }
+}
@@ -1,13 +1,28 @@
 use serde::{Deserialize, Serialize};
 
-use sdk::cosmwasm_std::{Env, MessageInfo, QuerierWrapper, Timestamp};
+use sdk::cosmwasm_std::{Env, MessageInfo, QuerierWrapper, Storage, Timestamp};
 
-use crate::{api::query::StateResponse, error::ContractResult};
+use crate::{api::query::StateResponse, contract::History, error::ContractResult};
 
 use super::{Handler, Response};
 
-#[derive(Serialize, Deserialize, Default)]
-pub struct Closed {}
+#[derive(Serialize, Deserialize)]
+pub struct Closed {
+    #[serde(default)]
+    history: History,
+}
+
+impl Closed {
+    pub(super) fn store_history(self, storage: &mut dyn Storage) -> ContractResult<Self> {
+        self.history.store(storage).map(Self::from)
+    }
+}
+
+impl From<History> for Closed {
+    fn from(history: History) -> Self {
+        Self { history }
+    }
+}
 
 impl Handler for Closed {
     fn state(self, _now: Timestamp, _querier: QuerierWrapper<'_>) -> ContractResult<StateResponse> {
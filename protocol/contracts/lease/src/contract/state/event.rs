@@ -2,7 +2,8 @@ use platform::batch::{Emit, Emitter};
 use sdk::cosmwasm_std::{Addr, Env};
 
 use crate::{
-    api::LeaseCoin, contract::cmd::RepayEmitter, event::Type, loan::RepayReceipt, position::Cause,
+    api::LeaseCoin, contract::cmd::RepayEmitter, event::Type, lease::LeaseDTO, loan::RepayReceipt,
+    position::Cause,
 };
 
 pub(crate) struct LiquidationEmitter<'liq, 'env> {
@@ -68,3 +69,15 @@ fn emit_liquidation_cause(emitter: Emitter, cause: &Cause) -> Emitter {
         Cause::Overdue() => emitter.emit("cause", "overdue interest"),
     }
 }
+
+pub(super) fn emit_ownership_transferred(
+    env: &Env,
+    lease: &LeaseDTO,
+    prev_customer: Addr,
+) -> Emitter {
+    Emitter::of_type(Type::TransferOwnership)
+        .emit_tx_info(env)
+        .emit("id", &lease.addr)
+        .emit("from", prev_customer)
+        .emit("to", lease.customer.clone())
+}
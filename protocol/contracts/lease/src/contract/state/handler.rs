@@ -1,11 +1,17 @@
 use enum_dispatch::enum_dispatch;
 
+use currency::SymbolOwned;
 use platform::state_machine::Response as StateMachineResponse;
-use sdk::cosmwasm_std::{Env, MessageInfo, QuerierWrapper, Reply, Timestamp};
+use sdk::cosmwasm_std::{Addr, Binary, Env, MessageInfo, QuerierWrapper, Reply, Timestamp};
 
 use crate::{
-    api::{position::PositionClose, query::StateResponse},
+    api::{
+        position::{CloseTrigger, PositionClose},
+        query::{PositionQuery, StateResponse},
+        LpnCoinDTO, MergedLoan,
+    },
     error::{ContractError, ContractResult},
+    position::CloseStrategy,
 };
 
 use super::State;
@@ -18,6 +24,15 @@ where
 {
     fn state(self, now: Timestamp, querier: QuerierWrapper<'_>) -> ContractResult<StateResponse>;
 
+    fn position_query(
+        self,
+        _query: PositionQuery,
+        _now: Timestamp,
+        _querier: QuerierWrapper<'_>,
+    ) -> ContractResult<Binary> {
+        err("position query")
+    }
+
     fn reply(
         self,
         _querier: QuerierWrapper<'_>,
@@ -36,6 +51,44 @@ where
         err("repay")
     }
 
+    fn add_collateral(
+        self,
+        _querier: QuerierWrapper<'_>,
+        _env: Env,
+        _info: MessageInfo,
+    ) -> ContractResult<Response> {
+        err("add collateral")
+    }
+
+    fn borrow(
+        self,
+        _amount: LpnCoinDTO,
+        _querier: QuerierWrapper<'_>,
+        _env: Env,
+        _info: MessageInfo,
+    ) -> ContractResult<Response> {
+        err("borrow")
+    }
+
+    fn refinance(
+        self,
+        _querier: QuerierWrapper<'_>,
+        _env: Env,
+        _info: MessageInfo,
+    ) -> ContractResult<Response> {
+        err("refinance")
+    }
+
+    fn rotate_asset(
+        self,
+        _currency: SymbolOwned,
+        _querier: QuerierWrapper<'_>,
+        _env: Env,
+        _info: MessageInfo,
+    ) -> ContractResult<Response> {
+        err("rotate asset")
+    }
+
     fn close_position(
         self,
         _spec: PositionClose,
@@ -46,6 +99,54 @@ where
         err("close position")
     }
 
+    fn change_close_policy(
+        self,
+        _strategy: CloseStrategy,
+        _trigger: Option<CloseTrigger>,
+        _querier: QuerierWrapper<'_>,
+        _env: Env,
+        _info: MessageInfo,
+    ) -> ContractResult<Response> {
+        err("change close policy")
+    }
+
+    fn add_operator(self, _operator: Addr, _info: MessageInfo) -> ContractResult<Response> {
+        err("add operator")
+    }
+
+    fn remove_operator(self, _operator: Addr, _info: MessageInfo) -> ContractResult<Response> {
+        err("remove operator")
+    }
+
+    fn transfer_ownership(
+        self,
+        _new_customer: Addr,
+        _env: Env,
+        _info: MessageInfo,
+    ) -> ContractResult<Response> {
+        err("transfer ownership")
+    }
+
+    fn merge_into(
+        self,
+        _lease: Addr,
+        _querier: QuerierWrapper<'_>,
+        _env: Env,
+        _info: MessageInfo,
+    ) -> ContractResult<Response> {
+        err("merge into")
+    }
+
+    fn absorb_lease(
+        self,
+        _loan: MergedLoan,
+        _querier: QuerierWrapper<'_>,
+        _env: Env,
+        _info: MessageInfo,
+    ) -> ContractResult<Response> {
+        err("absorb lease")
+    }
+
     fn close(
         self,
         _querier: QuerierWrapper<'_>,
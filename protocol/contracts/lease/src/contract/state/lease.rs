@@ -1,10 +1,16 @@
 use serde::{Deserialize, Serialize};
 
-use sdk::cosmwasm_std::{Env, MessageInfo, QuerierWrapper, Reply, Timestamp};
+use currency::SymbolOwned;
+use sdk::cosmwasm_std::{Addr, Binary, Env, MessageInfo, QuerierWrapper, Reply, Timestamp};
 
 use crate::{
-    api::{position::PositionClose, query::StateResponse},
+    api::{
+        position::{CloseTrigger, PositionClose},
+        query::{PositionQuery, StateResponse},
+        LpnCoinDTO, MergedLoan,
+    },
     error::ContractResult,
+    position::CloseStrategy,
 };
 
 use super::{handler::Handler as LeaseHandler, Contract, Response};
@@ -19,6 +25,20 @@ impl<H> State<H> {
     pub fn new(handler: H) -> Self {
         Self { handler }
     }
+
+    pub fn map<F>(self, f: F) -> Self
+    where
+        F: FnOnce(H) -> H,
+    {
+        Self::new(f(self.handler))
+    }
+
+    pub fn try_map<F, E>(self, f: F) -> Result<Self, E>
+    where
+        F: FnOnce(H) -> Result<H, E>,
+    {
+        f(self.handler).map(Self::new)
+    }
 }
 
 impl<H> Contract for State<H>
@@ -29,6 +49,15 @@ where
         self.handler.state(now, querier)
     }
 
+    fn position_query(
+        self,
+        query: PositionQuery,
+        now: Timestamp,
+        querier: QuerierWrapper<'_>,
+    ) -> ContractResult<Binary> {
+        self.handler.position_query(query, now, querier)
+    }
+
     fn reply(self, querier: QuerierWrapper<'_>, env: Env, msg: Reply) -> ContractResult<Response> {
         self.handler.reply(querier, env, msg)
     }
@@ -42,6 +71,44 @@ where
         self.handler.repay(querier, env, info)
     }
 
+    fn add_collateral(
+        self,
+        querier: QuerierWrapper<'_>,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        self.handler.add_collateral(querier, env, info)
+    }
+
+    fn borrow(
+        self,
+        amount: LpnCoinDTO,
+        querier: QuerierWrapper<'_>,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        self.handler.borrow(amount, querier, env, info)
+    }
+
+    fn refinance(
+        self,
+        querier: QuerierWrapper<'_>,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        self.handler.refinance(querier, env, info)
+    }
+
+    fn rotate_asset(
+        self,
+        currency: SymbolOwned,
+        querier: QuerierWrapper<'_>,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        self.handler.rotate_asset(currency, querier, env, info)
+    }
+
     fn close_position(
         self,
         spec: PositionClose,
@@ -52,6 +119,55 @@ where
         self.handler.close_position(spec, querier, env, info)
     }
 
+    fn change_close_policy(
+        self,
+        strategy: CloseStrategy,
+        trigger: Option<CloseTrigger>,
+        querier: QuerierWrapper<'_>,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        self.handler
+            .change_close_policy(strategy, trigger, querier, env, info)
+    }
+
+    fn add_operator(self, operator: Addr, info: MessageInfo) -> ContractResult<Response> {
+        self.handler.add_operator(operator, info)
+    }
+
+    fn remove_operator(self, operator: Addr, info: MessageInfo) -> ContractResult<Response> {
+        self.handler.remove_operator(operator, info)
+    }
+
+    fn transfer_ownership(
+        self,
+        new_customer: Addr,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        self.handler.transfer_ownership(new_customer, env, info)
+    }
+
+    fn merge_into(
+        self,
+        lease: Addr,
+        querier: QuerierWrapper<'_>,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        self.handler.merge_into(lease, querier, env, info)
+    }
+
+    fn absorb_lease(
+        self,
+        loan: MergedLoan,
+        querier: QuerierWrapper<'_>,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        self.handler.absorb_lease(loan, querier, env, info)
+    }
+
     fn close(
         self,
         querier: QuerierWrapper<'_>,
@@ -3,17 +3,24 @@ use std::str;
 use enum_dispatch::enum_dispatch;
 use serde::{Deserialize, Serialize};
 
+use currency::SymbolOwned;
 use platform::{batch::Batch, message::Response as MessageResponse};
 use sdk::{
-    cosmwasm_std::{Binary, Env, MessageInfo, QuerierWrapper, Reply, Storage, Timestamp},
+    cosmwasm_std::{Addr, Binary, Env, MessageInfo, QuerierWrapper, Reply, Storage, Timestamp},
     cw_storage_plus::Item,
 };
 use swap::Impl;
 
 use crate::{
-    api::{open::NewLeaseContract, position::PositionClose, query::StateResponse},
-    contract::api::Contract,
+    api::{
+        open::{IbcParams, NewLeaseContract},
+        position::{CloseTrigger, PositionClose},
+        query::{PositionQuery, StateResponse},
+        LpnCoinDTO, MergedLoan,
+    },
+    contract::{api::Contract, Lease},
     error::ContractResult,
+    position::CloseStrategy,
 };
 
 pub(crate) use self::handler::{Handler, Response};
@@ -38,6 +45,12 @@ type OpenedActive = LeaseState<opened::active::Active>;
 
 type BuyLpn = DexState<opened::repay::buy_lpn::DexState>;
 
+type AddCollateral = DexState<opened::collateral::DexState>;
+
+type RotateAsset = DexState<opened::rotate::DexState>;
+
+type MergeTransferIn = DexState<opened::merge::DexState>;
+
 type PartialLiquidation = DexState<opened::close::liquidation::partial::DexState>;
 
 type FullLiquidation = DexState<opened::close::liquidation::full::DexState>;
@@ -65,6 +78,9 @@ pub(crate) enum State {
     BuyAsset,
     OpenedActive,
     BuyLpn,
+    AddCollateral,
+    RotateAsset,
+    MergeTransferIn,
     PartialLiquidation,
     FullLiquidation,
     PartialClose,
@@ -94,6 +110,56 @@ pub(super) fn new_lease(
         .map(|(batch, start_state)| (batch, start_state.into()))
 }
 
+/// Replace the IBC parameters of the DEX transactions
+///
+/// Only the leases at rest are updated. The ones in the middle of a DEX operation,
+/// or not opened yet, keep their current parameters.
+pub(super) fn with_dex_ibc(state: State, ibc: IbcParams) -> State {
+    match state {
+        State::OpenedActive(active) => active.map(|active| active.with_dex_ibc(ibc)).into(),
+        State::PaidActive(active) => active.map(|active| active.with_dex_ibc(ibc)).into(),
+        state => state,
+    }
+}
+
+/// Move the payments recorded while processing a message into the history storage
+///
+/// Only the leases at rest are processed. The ones in the middle of an operation
+/// keep their payments pending until they get at rest.
+pub(super) fn store_history(storage: &mut dyn Storage, state: State) -> ContractResult<State> {
+    match state {
+        State::OpenedActive(active) => active
+            .try_map(|active| active.store_history(storage))
+            .map(Into::into),
+        State::PaidActive(active) => active
+            .try_map(|active| active.store_history(storage))
+            .map(Into::into),
+        State::Closed(closed) => closed
+            .try_map(|closed| closed.store_history(storage))
+            .map(Into::into),
+        State::Liquidated(liquidated) => liquidated
+            .try_map(|liquidated| liquidated.store_history(storage))
+            .map(Into::into),
+        state => Ok(state),
+    }
+}
+
+/// Hand the lease over to `new_customer` notifying the finalizer
+fn transfer_ownership(
+    lease: Lease,
+    new_customer: Addr,
+    env: &Env,
+    sender: &Addr,
+) -> ContractResult<(Lease, MessageResponse)> {
+    let prev_customer = lease.lease.customer.clone();
+    lease
+        .transfer_ownership(new_customer, sender)
+        .map(|(lease, batch)| {
+            let emitter = event::emit_ownership_transferred(env, &lease.lease, prev_customer);
+            (lease, MessageResponse::messages_with_events(batch, emitter))
+        })
+}
+
 fn ignore_msg<S>(state: S) -> ContractResult<Response>
 where
     S: Into<State>,
@@ -103,8 +169,9 @@ where
 
 mod impl_from {
     use super::{
-        BuyAsset, BuyLpn, Closed, ClosingTransferIn, FullClose, FullLiquidation, Liquidated,
-        OpenedActive, PaidActive, PartialClose, PartialLiquidation, RequestLoan, State,
+        AddCollateral, BuyAsset, BuyLpn, Closed, ClosingTransferIn, FullClose, FullLiquidation,
+        Liquidated, MergeTransferIn, OpenedActive, PaidActive, PartialClose, PartialLiquidation,
+        RequestLoan, RotateAsset, State,
     };
 
     impl From<super::opening::request_loan::RequestLoan> for State {
@@ -131,6 +198,24 @@ mod impl_from {
         }
     }
 
+    impl From<super::opened::collateral::DexState> for State {
+        fn from(value: super::opened::collateral::DexState) -> Self {
+            AddCollateral::new(value).into()
+        }
+    }
+
+    impl From<super::opened::rotate::DexState> for State {
+        fn from(value: super::opened::rotate::DexState) -> Self {
+            RotateAsset::new(value).into()
+        }
+    }
+
+    impl From<super::opened::merge::DexState> for State {
+        fn from(value: super::opened::merge::DexState) -> Self {
+            MergeTransferIn::new(value).into()
+        }
+    }
+
     impl From<super::opened::close::liquidation::partial::DexState> for State {
         fn from(value: super::opened::close::liquidation::partial::DexState) -> Self {
             PartialLiquidation::new(value).into()
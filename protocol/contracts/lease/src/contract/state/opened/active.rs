@@ -1,30 +1,46 @@
-use currency::never;
+use currency::{never, SymbolOwned};
 use serde::{Deserialize, Serialize};
 
 use dex::Enterable;
 use finance::coin::IntoDTO;
 use platform::{bank, batch::Emitter, message::Response as MessageResponse};
-use sdk::cosmwasm_std::{Coin as CwCoin, Env, MessageInfo, QuerierWrapper, Timestamp};
+use sdk::cosmwasm_std::{
+    Addr, Binary, Coin as CwCoin, Env, MessageInfo, QuerierWrapper, Storage, Timestamp,
+};
 
 use crate::{
-    api::{position::PositionClose, query::StateResponse, DownpaymentCoin},
+    api::{
+        open::IbcParams,
+        position::{CloseTrigger, PositionClose},
+        query::{PositionQuery, StateResponse},
+        DownpaymentCoin, LeaseAssetCurrencies, LeaseCoin, LpnCoinDTO, MergedLoan,
+    },
     contract::{
-        cmd::{LiquidationStatus, LiquidationStatusCmd, ObtainPayment, OpenLoanRespResult},
+        cmd::{
+            Borrow as BorrowCmd, BorrowQuote, ChangeClosePolicy, LiquidationStatus,
+            LiquidationStatusCmd, ObtainPayment, OpenLoanRespResult,
+            PositionQuery as PositionQueryCmd, Refinance as RefinanceCmd, RefinanceQuote,
+            ValidateAbsorb,
+        },
         state::{Handler, Response},
         Lease,
     },
     error::{ContractError, ContractResult},
-    finance::LpnCurrencies,
+    finance::{LpnCoin, LpnCurrencies},
+    position::CloseStrategy,
 };
 
 use super::{
     alarm, balance,
     close::{customer_close, liquidation},
+    collateral::{self, DexState as AddCollateralState},
     event,
+    merge::{self, DexState as MergeState},
     repay::{
         self,
         buy_lpn::{self, DexState as BuyLpnState},
     },
+    rotate::{self, DexState as RotateAssetState},
 };
 
 #[derive(Serialize, Deserialize)]
@@ -37,6 +53,14 @@ impl Active {
         Self { lease }
     }
 
+    pub(in super::super) fn with_dex_ibc(self, ibc: IbcParams) -> Self {
+        Self::new(self.lease.with_dex_ibc(ibc))
+    }
+
+    pub(in super::super) fn store_history(self, storage: &mut dyn Storage) -> ContractResult<Self> {
+        self.lease.store_history(storage).map(Self::new)
+    }
+
     pub(in super::super) fn emit_opened(
         &self,
         env: &Env,
@@ -105,22 +129,251 @@ impl Active {
             querier,
         )?;
 
+        self.on_liquidation_status(liquidation_status, MessageResponse::default(), env, querier)
+    }
+
+    fn try_change_close_policy(
+        self,
+        strategy: CloseStrategy,
+        trigger: Option<CloseTrigger>,
+        querier: QuerierWrapper<'_>,
+        env: &Env,
+    ) -> ContractResult<Response> {
+        let profit = self.lease.lease.loan.profit().clone();
+        let time_alarms = self.lease.lease.time_alarms.clone();
+        let price_alarms = self.lease.lease.oracle.clone();
+        let reserve = self.lease.lease.reserve.clone();
+        let (lease, (messages, liquidation_status)) = self.lease.update(
+            ChangeClosePolicy::new(
+                strategy,
+                trigger,
+                &env.block.time,
+                profit,
+                (time_alarms, price_alarms),
+                reserve,
+            ),
+            querier,
+        )?;
+
+        Self::new(lease).on_liquidation_status(
+            liquidation_status,
+            MessageResponse::messages_only(messages),
+            env,
+            querier,
+        )
+    }
+
+    pub(super) fn on_liquidation_status(
+        self,
+        liquidation_status: LiquidationStatus,
+        curr_request_response: MessageResponse,
+        env: &Env,
+        querier: QuerierWrapper<'_>,
+    ) -> ContractResult<Response> {
         match liquidation_status {
-            LiquidationStatus::NoDebt => Ok(Response::no_msgs(self)),
+            LiquidationStatus::NoDebt => Ok(Response::from(curr_request_response, self)),
             LiquidationStatus::NewAlarms {
                 current_liability,
                 alarms,
             } => Ok(Response::from(
-                alarm::build_resp(&self.lease, current_liability, alarms),
+                alarm::build_resp(&self.lease, current_liability, alarms)
+                    .merge_with(curr_request_response),
                 self,
             )),
-            LiquidationStatus::NeedLiquidation(liquidation) => liquidation::start(
+            LiquidationStatus::NeedLiquidation(liquidation) => {
+                liquidation::start(self.lease, liquidation, curr_request_response, env, querier)
+            }
+            LiquidationStatus::NeedClose { strategy, close } => customer_close::start_on_trigger(
+                strategy,
+                close,
                 self.lease,
-                liquidation,
-                MessageResponse::default(),
+                curr_request_response,
                 env,
                 querier,
             ),
+            LiquidationStatus::Expired => {
+                customer_close::start_on_expiry(self.lease, curr_request_response, env, querier)
+            }
+        }
+    }
+
+    fn try_add_collateral(
+        self,
+        cw_amount: Vec<CwCoin>,
+        now: Timestamp,
+        querier: QuerierWrapper<'_>,
+    ) -> ContractResult<Response> {
+        self.lease
+            .lease
+            .clone()
+            .execute(ObtainPayment::new(cw_amount), querier)
+            .and_then(|payment| {
+                let add_collateral = collateral::start_add_collateral(self.lease, payment);
+                add_collateral
+                    .enter(now, querier)
+                    .map(|batch| Response::from(batch, AddCollateralState::from(add_collateral)))
+                    .map_err(Into::into)
+            })
+    }
+
+    fn try_refinance(
+        self,
+        funds: Vec<CwCoin>,
+        env: &Env,
+        querier: QuerierWrapper<'_>,
+    ) -> ContractResult<Response> {
+        let payment: LpnCoin =
+            bank::may_received::<LpnCurrencies, _>(&funds, IntoDTO::<LpnCurrencies>::new())
+                .ok_or_else(ContractError::NoPaymentError)
+                .map(never::safe_unwrap)?
+                .try_into()?;
+        let quote = self
+            .lease
+            .lease
+            .loan
+            .lpp()
+            .clone()
+            .execute_lender(RefinanceQuote {}, querier)?;
+
+        let customer = self.lease.lease.customer.clone();
+        let profit = self.lease.lease.loan.profit().clone();
+        let time_alarms = self.lease.lease.time_alarms.clone();
+        let price_alarms = self.lease.lease.oracle.clone();
+        let reserve = self.lease.lease.reserve.clone();
+        let (lease, (messages, receipt, liquidation_status)) = self.lease.update(
+            RefinanceCmd::new(
+                payment,
+                quote,
+                customer,
+                &env.block.time,
+                profit,
+                (time_alarms, price_alarms),
+                reserve,
+            ),
+            querier,
+        )?;
+
+        let events = event::emit_refinanced(env, &lease.lease, &receipt);
+        Self::new(lease).on_liquidation_status(
+            liquidation_status,
+            MessageResponse::messages_with_events(messages, events),
+            env,
+            querier,
+        )
+    }
+
+    fn try_rotate_asset(
+        self,
+        currency: SymbolOwned,
+        now: Timestamp,
+        querier: QuerierWrapper<'_>,
+    ) -> ContractResult<Response> {
+        currency::validate::<LeaseAssetCurrencies>(&currency)?;
+        if &currency == self.lease.lease.position.amount().ticker() {
+            return Err(ContractError::RotationIntoSameAsset(currency));
+        }
+
+        let rotate_asset = rotate::start(self.lease, currency);
+        rotate_asset
+            .enter(now, querier)
+            .map(|batch| Response::from(batch, RotateAssetState::from(rotate_asset)))
+            .map_err(Into::into)
+    }
+
+    fn try_borrow(
+        self,
+        amount: LpnCoinDTO,
+        now: Timestamp,
+        querier: QuerierWrapper<'_>,
+    ) -> ContractResult<Response> {
+        let amount: LpnCoin = amount.try_into()?;
+        let annual_interest_rate = self
+            .lease
+            .lease
+            .loan
+            .lpp()
+            .clone()
+            .execute_lender(BorrowQuote::new(amount), querier)?;
+
+        let profit = self.lease.lease.loan.profit().clone();
+        let time_alarms = self.lease.lease.time_alarms.clone();
+        let reserve = self.lease.lease.reserve.clone();
+        let (lease, borrow_messages) = self.lease.update(
+            BorrowCmd::new(
+                amount,
+                annual_interest_rate,
+                &now,
+                profit,
+                time_alarms,
+                reserve,
+            ),
+            querier,
+        )?;
+
+        let borrow = collateral::start_borrow(lease, amount.into());
+        borrow
+            .enter(now, querier)
+            .map(|batch| {
+                Response::from(
+                    borrow_messages.merge(batch),
+                    AddCollateralState::from(borrow),
+                )
+            })
+            .map_err(Into::into)
+    }
+
+    fn try_merge_into(
+        self,
+        into: Addr,
+        now: Timestamp,
+        querier: QuerierWrapper<'_>,
+    ) -> ContractResult<Response> {
+        merge::validate_into(&self.lease, into.clone(), &now, querier)?;
+
+        let merge = merge::start(self.lease, into);
+        merge
+            .enter(now, querier)
+            .map(|batch| Response::from(batch, MergeState::from(merge)))
+            .map_err(Into::into)
+    }
+
+    fn try_absorb_lease(
+        self,
+        funds: Vec<CwCoin>,
+        loan: MergedLoan,
+        now: Timestamp,
+        querier: QuerierWrapper<'_>,
+    ) -> ContractResult<Response> {
+        let asset: LeaseCoin = bank::may_received::<LeaseAssetCurrencies, _>(
+            &funds,
+            IntoDTO::<LeaseAssetCurrencies>::new(),
+        )
+        .ok_or_else(ContractError::NoPaymentError)
+        .map(never::safe_unwrap)?;
+        if asset.ticker() != self.lease.lease.position.amount().ticker() {
+            return Err(ContractError::MergeIntoDifferentAsset(
+                self.lease.lease.position.amount().ticker().into(),
+            ));
+        }
+        self.lease
+            .lease
+            .clone()
+            .execute(ValidateAbsorb::new(asset.clone(), &loan, &now), querier)?;
+
+        let absorb = collateral::start_absorb(self.lease, asset, loan);
+        absorb
+            .enter(now, querier)
+            .map(|batch| Response::from(batch, AddCollateralState::from(absorb)))
+            .map_err(Into::into)
+    }
+
+    fn check_finalizer(&self, sender: &Addr) -> ContractResult<()> {
+        if self.lease.finalizer.owned_by(sender) {
+            Ok(())
+        } else {
+            Err(ContractError::Unauthorized(
+                access_control::error::Error::Unauthorized {},
+            ))
         }
     }
 
@@ -149,6 +402,18 @@ impl Handler for Active {
         super::lease_state(self.lease, None, now, querier)
     }
 
+    fn position_query(
+        self,
+        query: PositionQuery,
+        now: Timestamp,
+        querier: QuerierWrapper<'_>,
+    ) -> ContractResult<Binary> {
+        let profit = self.lease.lease.loan.profit().clone();
+        self.lease
+            .lease
+            .execute(PositionQueryCmd::new(query, now, profit), querier)
+    }
+
     fn repay(
         self,
         querier: QuerierWrapper<'_>,
@@ -158,18 +423,121 @@ impl Handler for Active {
         self.try_repay(querier, &env, info)
     }
 
-    fn close_position(
+    fn add_collateral(
         self,
-        spec: PositionClose,
+        querier: QuerierWrapper<'_>,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        self.lease
+            .check_manager(&info.sender)
+            .and_then(|()| self.try_add_collateral(info.funds, env.block.time, querier))
+    }
+
+    fn borrow(
+        self,
+        amount: LpnCoinDTO,
         querier: QuerierWrapper<'_>,
         env: Env,
         info: MessageInfo,
     ) -> ContractResult<Response> {
         access_control::check(&self.lease.lease.customer, &info.sender)
             .map_err(Into::into)
+            .and_then(|()| self.try_borrow(amount, env.block.time, querier))
+    }
+
+    fn refinance(
+        self,
+        querier: QuerierWrapper<'_>,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        access_control::check(&self.lease.lease.customer, &info.sender)
+            .map_err(Into::into)
+            .and_then(|()| self.try_refinance(info.funds, &env, querier))
+    }
+
+    fn rotate_asset(
+        self,
+        currency: SymbolOwned,
+        querier: QuerierWrapper<'_>,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        self.lease
+            .check_manager(&info.sender)
+            .and_then(|()| self.try_rotate_asset(currency, env.block.time, querier))
+    }
+
+    fn close_position(
+        self,
+        spec: PositionClose,
+        querier: QuerierWrapper<'_>,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        self.lease
+            .check_manager(&info.sender)
             .and_then(|()| customer_close::start(spec, self.lease, &env, querier))
     }
 
+    fn change_close_policy(
+        self,
+        strategy: CloseStrategy,
+        trigger: Option<CloseTrigger>,
+        querier: QuerierWrapper<'_>,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        self.lease
+            .check_manager(&info.sender)
+            .and_then(|()| self.try_change_close_policy(strategy, trigger, querier, &env))
+    }
+
+    fn add_operator(self, operator: Addr, info: MessageInfo) -> ContractResult<Response> {
+        self.lease
+            .add_operator(operator, &info.sender)
+            .map(|lease| Response::no_msgs(Self::new(lease)))
+    }
+
+    fn remove_operator(self, operator: Addr, info: MessageInfo) -> ContractResult<Response> {
+        self.lease
+            .remove_operator(&operator, &info.sender)
+            .map(|lease| Response::no_msgs(Self::new(lease)))
+    }
+
+    fn transfer_ownership(
+        self,
+        new_customer: Addr,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        super::super::transfer_ownership(self.lease, new_customer, &env, &info.sender)
+            .map(|(lease, response)| Response::from(response, Self::new(lease)))
+    }
+
+    fn merge_into(
+        self,
+        lease: Addr,
+        querier: QuerierWrapper<'_>,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        self.check_finalizer(&info.sender)
+            .and_then(|()| self.try_merge_into(lease, env.block.time, querier))
+    }
+
+    fn absorb_lease(
+        self,
+        loan: MergedLoan,
+        querier: QuerierWrapper<'_>,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        self.check_finalizer(&info.sender)
+            .and_then(|()| self.try_absorb_lease(info.funds, loan, env.block.time, querier))
+    }
+
     fn on_time_alarm(
         self,
         querier: QuerierWrapper<'_>,
@@ -4,7 +4,10 @@ use sdk::cosmwasm_std::Env;
 use crate::{
     api::{
         position::FullClose,
-        query::opened::{OngoingTrx, PositionCloseTrx},
+        query::{
+            opened::{OngoingTrx, PositionCloseTrx},
+            PaymentKind,
+        },
         LeaseCoin,
     },
     contract::{
@@ -57,7 +60,8 @@ impl CloseAlgo for Spec {
 
     type ChangeSender = Self::ProfitSender;
 
-    type PaymentEmitter<'this, 'env> = PositionCloseEmitter<'env>
+    type PaymentEmitter<'this, 'env>
+        = PositionCloseEmitter<'env>
     where
         Self: 'this,
         'env: 'this;
@@ -70,6 +74,12 @@ impl CloseAlgo for Spec {
         Self::ChangeSender::new(lease.lease.customer.clone())
     }
 
+    fn payment_kind(&self, lease: &Lease) -> PaymentKind {
+        PaymentKind::PositionClose {
+            amount: self.amount(lease).clone(),
+        }
+    }
+
     fn emitter_fn<'this, 'lease, 'env>(
         &'this self,
         lease: &'lease Lease,
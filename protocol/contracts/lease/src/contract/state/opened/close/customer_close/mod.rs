@@ -2,9 +2,14 @@ use platform::message::Response as MessageResponse;
 use sdk::cosmwasm_std::{Env, QuerierWrapper};
 
 use crate::{
-    api::position::PositionClose,
-    contract::{cmd::ValidateClosePosition, state::Response, Lease},
-    error::ContractResult,
+    api::position::{FullClose, PositionClose},
+    contract::{
+        cmd::ValidateClosePosition,
+        state::{opened::event, Response},
+        Lease,
+    },
+    error::{ContractError, ContractResult},
+    position::CloseStrategy,
 };
 
 use super::ClosePositionTask;
@@ -29,3 +34,51 @@ pub(in super::super) fn start(
         }
     }
 }
+
+/// Start a position close on a take-profit or a stop-loss trigger
+///
+/// The trigger is removed since it has been fulfilled. A partial close, that is no longer valid,
+/// for example, due to a decrease of the position, is carried out as a full close.
+pub(in super::super) fn start_on_trigger(
+    strategy: CloseStrategy,
+    close: PositionClose,
+    mut lease: Lease,
+    curr_request_response: MessageResponse,
+    env: &Env,
+    querier: QuerierWrapper<'_>,
+) -> ContractResult<Response> {
+    lease.lease.position.reset_close_policy(strategy);
+
+    match close {
+        PositionClose::PartialClose(spec) => match lease
+            .lease
+            .clone()
+            .execute(ValidateClosePosition::new(&spec), querier)
+        {
+            Ok(()) => spec.start(lease, curr_request_response, env, querier),
+            Err(
+                ContractError::PositionCloseAmountTooSmall(_)
+                | ContractError::PositionCloseAmountTooBig(_),
+            ) => FullClose {}.start(lease, curr_request_response, env, querier),
+            Err(err) => Err(err),
+        },
+        PositionClose::FullClose(spec) => spec.start(lease, curr_request_response, env, querier),
+    }
+}
+
+/// Start a full position close on the lease reaching its maximum duration
+pub(in super::super) fn start_on_expiry(
+    lease: Lease,
+    curr_request_response: MessageResponse,
+    env: &Env,
+    querier: QuerierWrapper<'_>,
+) -> ContractResult<Response> {
+    let expired = event::emit_expired(env, &lease.lease);
+
+    FullClose {}.start(
+        lease,
+        curr_request_response.merge_with(expired),
+        env,
+        querier,
+    )
+}
@@ -3,7 +3,10 @@ use sdk::cosmwasm_std::Env;
 use crate::{
     api::{
         position::PartialClose,
-        query::opened::{OngoingTrx, PositionCloseTrx},
+        query::{
+            opened::{OngoingTrx, PositionCloseTrx},
+            PaymentKind,
+        },
         LeaseCoin,
     },
     contract::{
@@ -58,6 +61,12 @@ impl RepayAlgo for Spec {
         Self::RepayFn::new(self.amount.clone())
     }
 
+    fn payment_kind(&self) -> PaymentKind {
+        PaymentKind::PositionClose {
+            amount: self.amount.clone(),
+        }
+    }
+
     fn emitter_fn<'this, 'env>(&'this self, env: &'env Env) -> Self::PaymentEmitter<'this, 'env> {
         Self::PaymentEmitter::new(self.amount.clone(), env)
     }
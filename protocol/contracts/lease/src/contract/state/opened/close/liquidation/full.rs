@@ -3,7 +3,10 @@ use sdk::cosmwasm_std::Env;
 
 use crate::{
     api::{
-        query::opened::{OngoingTrx, PositionCloseTrx},
+        query::{
+            opened::{OngoingTrx, PositionCloseTrx},
+            PaymentKind,
+        },
         LeaseCoin,
     },
     contract::{
@@ -57,10 +60,11 @@ impl CloseAlgo for Spec {
 
     type ChangeSender = Self::ProfitSender;
 
-    type PaymentEmitter<'this, 'env> = LiquidationEmitter<'this, 'env>
+    type PaymentEmitter<'this, 'env>
+        = LiquidationEmitter<'this, 'env>
     where
-    Self: 'this,
-    'env: 'this;
+        Self: 'this,
+        'env: 'this;
 
     fn profit_sender(&self, lease: &Lease) -> Self::ProfitSender {
         lease.lease.loan.profit().clone().into_stub()
@@ -70,6 +74,12 @@ impl CloseAlgo for Spec {
         self.profit_sender(lease)
     }
 
+    fn payment_kind(&self, lease: &Lease) -> PaymentKind {
+        PaymentKind::Liquidation {
+            amount: self.amount(lease).clone(),
+        }
+    }
+
     fn emitter_fn<'this, 'lease, 'env>(
         &'this self,
         lease: &'lease Lease,
@@ -2,7 +2,10 @@ use sdk::cosmwasm_std::Env;
 
 use crate::{
     api::{
-        query::opened::{OngoingTrx, PositionCloseTrx},
+        query::{
+            opened::{OngoingTrx, PositionCloseTrx},
+            PaymentKind,
+        },
         LeaseCoin,
     },
     contract::{
@@ -57,6 +60,12 @@ impl RepayAlgo for Spec {
         Self::RepayFn::new(self.amount.clone())
     }
 
+    fn payment_kind(&self) -> PaymentKind {
+        PaymentKind::Liquidation {
+            amount: self.amount.clone(),
+        }
+    }
+
     fn emitter_fn<'liq, 'env>(&'liq self, env: &'env Env) -> Self::PaymentEmitter<'liq, 'env> {
         Self::PaymentEmitter::new(&self.cause, self.amount.clone(), env)
     }
@@ -0,0 +1,311 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use serde::{Deserialize, Serialize};
+
+use currency::SymbolSlice;
+use dex::{
+    Account, CoinVisitor, ConnectionParams, Contract as DexContract, ContractInSwap,
+    DexConnectable, DexResult, IcaConnectee, IterNext, IterState, StartLocalRemoteTransferOutState,
+    SwapState, SwapTask, TimeAlarm, TransferOut, TransferOutState,
+};
+use finance::{coin::CoinDTO, percent::Percent};
+use platform::{batch::Batch, message::Response as MessageResponse};
+use sdk::cosmwasm_std::{Env, QuerierWrapper, Timestamp};
+use timealarms::stub::TimeAlarmsRef;
+
+use crate::{
+    api::{
+        query::{
+            opened::{BuyAssetTrx, OngoingTrx},
+            StateResponse as QueryStateResponse,
+        },
+        LeaseAssetCurrencies, LeaseCoin, LeasePaymentCurrencies, LpnCoinDTO, MergedLoan,
+        PaymentCoin,
+    },
+    contract::{
+        cmd::{Absorb as AbsorbCmd, AddCollateral as AddCollateralCmd, LiquidationStatusCmd},
+        state::{
+            resp_delivery::{ForwardToDexEntry, ForwardToDexEntryContinue},
+            StateResponse as ContractStateResponse, SwapClient, SwapResult,
+        },
+        Lease,
+    },
+    error::ContractResult,
+    event::Type,
+    finance::OracleRef,
+};
+
+use super::{active::Active, event};
+
+pub(super) type StartState = StartLocalRemoteTransferOutState<
+    IcaOpen,
+    BuyAsset,
+    LeasePaymentCurrencies,
+    SwapClient,
+    ForwardToDexEntry,
+    ForwardToDexEntryContinue,
+>;
+pub(crate) type DexState = dex::StateRemoteOut<
+    IcaOpen,
+    BuyAsset,
+    LeasePaymentCurrencies,
+    SwapClient,
+    ForwardToDexEntry,
+    ForwardToDexEntryContinue,
+>;
+
+pub(super) fn start_add_collateral(lease: Lease, payment: PaymentCoin) -> StartState {
+    dex::start_local_remote_transfer_out(BuyAsset::new(lease, Funds::Payment(payment)))
+}
+
+pub(super) fn start_borrow(lease: Lease, loan: LpnCoinDTO) -> StartState {
+    dex::start_local_remote_transfer_out(BuyAsset::new(lease, Funds::Loan(loan)))
+}
+
+pub(super) fn start_absorb(lease: Lease, asset: LeaseCoin, loan: MergedLoan) -> StartState {
+    dex::start_local_remote_transfer_out(BuyAsset::new(lease, Funds::Merge { asset, loan }))
+}
+
+pub(super) fn start_merge_abort(lease: Lease, asset: LeaseCoin) -> StartState {
+    dex::start_local_remote_transfer_out(BuyAsset::new(lease, Funds::MergeAbort(asset)))
+}
+
+/// Swap funds into the lease currency and add the result to the position
+///
+/// The lease asset stays on the lease ICA. The swap reuses the flow of the lease opening
+/// once the ICA has been opened. The asset of a merged lease is already in the lease
+/// currency so it is only transferred out. So is the asset of an aborted merge, that is
+/// already part of the position.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct BuyAsset {
+    lease: Lease,
+    funds: Funds,
+}
+
+/// The origin of the funds to buy the lease asset with
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Funds {
+    /// A payment provided by the customer
+    Payment(PaymentCoin),
+    /// An amount borrowed from the Lpp
+    Loan(LpnCoinDTO),
+    /// The asset of a lease merged into this one along with its loan
+    Merge { asset: LeaseCoin, loan: MergedLoan },
+    /// The asset of this lease transferred in for a merge that has been aborted
+    MergeAbort(LeaseCoin),
+}
+
+impl BuyAsset {
+    fn new(lease: Lease, funds: Funds) -> Self {
+        Self { lease, funds }
+    }
+
+    fn query(
+        self,
+        in_progress: BuyAssetTrx,
+        now: Timestamp,
+        querier: QuerierWrapper<'_>,
+    ) -> ContractResult<ContractStateResponse> {
+        let in_progress = match self.funds {
+            Funds::Payment(payment) => OngoingTrx::AddCollateral {
+                payment,
+                in_progress,
+            },
+            Funds::Loan(amount) => OngoingTrx::Borrow {
+                amount,
+                in_progress,
+            },
+            Funds::Merge { asset, .. } => OngoingTrx::Absorb {
+                amount: asset,
+                in_progress,
+            },
+            Funds::MergeAbort(amount) => OngoingTrx::MergeAbort {
+                amount,
+                in_progress,
+            },
+        };
+
+        super::lease_state(self.lease, Some(in_progress), now, querier)
+    }
+}
+
+impl SwapTask for BuyAsset {
+    type OutG = LeaseAssetCurrencies;
+    type Label = Type;
+    type StateResponse = ContractResult<QueryStateResponse>;
+    type Result = SwapResult;
+
+    fn label(&self) -> Self::Label {
+        match self.funds {
+            Funds::Payment(_) => Type::AddCollateralSwap,
+            Funds::Loan(_) => Type::BorrowSwap,
+            Funds::Merge { .. } => Type::AbsorbTransferOut,
+            Funds::MergeAbort(_) => Type::MergeAbortTransferOut,
+        }
+    }
+
+    fn dex_account(&self) -> &Account {
+        &self.lease.dex
+    }
+
+    fn oracle(&self) -> &OracleRef {
+        &self.lease.lease.oracle
+    }
+
+    fn time_alarm(&self) -> &TimeAlarmsRef {
+        &self.lease.lease.time_alarms
+    }
+
+    fn out_currency(&self) -> &SymbolSlice {
+        self.lease.lease.position.amount().ticker()
+    }
+
+    fn max_slippage(&self) -> Percent {
+        self.lease.max_slippage
+    }
+
+    fn on_coins<Visitor>(&self, visitor: &mut Visitor) -> Result<IterState, Visitor::Error>
+    where
+        Visitor: CoinVisitor<Result = IterNext>,
+    {
+        match &self.funds {
+            Funds::Payment(payment) => dex::on_coin(payment, visitor),
+            Funds::Loan(loan) => dex::on_coin(loan, visitor),
+            Funds::Merge { asset, .. } => dex::on_coin(asset, visitor),
+            Funds::MergeAbort(asset) => dex::on_coin(asset, visitor),
+        }
+    }
+
+    fn finish(
+        self,
+        amount_out: CoinDTO<Self::OutG>,
+        env: &Env,
+        querier: QuerierWrapper<'_>,
+    ) -> Self::Result {
+        let profit = self.lease.lease.loan.profit().clone();
+        let time_alarms = self.lease.lease.time_alarms.clone();
+        let price_alarms = self.lease.lease.oracle.clone();
+        let reserve = self.lease.lease.reserve.clone();
+        let now = env.block.time;
+        let (lease, (messages, liquidation_status)) = match &self.funds {
+            Funds::MergeAbort(_) => self
+                .lease
+                .lease
+                .clone()
+                .execute(
+                    LiquidationStatusCmd::new(&now, &time_alarms, &price_alarms),
+                    querier,
+                )
+                .map(|liquidation_status| (self.lease, (Batch::default(), liquidation_status))),
+            Funds::Merge { loan, .. } => self.lease.update(
+                AbsorbCmd::new(
+                    amount_out.clone(),
+                    loan.clone(),
+                    &now,
+                    profit,
+                    (time_alarms, price_alarms),
+                    reserve,
+                ),
+                querier,
+            ),
+            Funds::Payment(_) | Funds::Loan(_) => self.lease.update(
+                AddCollateralCmd::new(
+                    amount_out.clone(),
+                    &now,
+                    profit,
+                    (time_alarms, price_alarms),
+                    reserve,
+                ),
+                querier,
+            ),
+        }?;
+
+        let response = match &self.funds {
+            Funds::Payment(payment) => MessageResponse::messages_with_events(
+                messages,
+                event::emit_collateral_added(env, &lease.lease, payment, &amount_out),
+            ),
+            Funds::Loan(loan) => MessageResponse::messages_with_events(
+                messages,
+                event::emit_borrowed(env, &lease.lease, loan, &amount_out),
+            ),
+            Funds::Merge { loan, .. } => MessageResponse::messages_with_events(
+                messages,
+                event::emit_absorbed(env, &lease.lease, &amount_out, loan),
+            ),
+            // the asset is already part of the position
+            Funds::MergeAbort(_) => MessageResponse::messages_only(messages),
+        };
+        Active::new(lease).on_liquidation_status(liquidation_status, response, env, querier)
+    }
+}
+
+impl<DexState> ContractInSwap<DexState> for BuyAsset
+where
+    DexState: InProgressTrx,
+{
+    type StateResponse = <Self as SwapTask>::StateResponse;
+
+    fn state(self, now: Timestamp, querier: QuerierWrapper<'_>) -> Self::StateResponse {
+        self.query(DexState::trx_in_progress(), now, querier)
+    }
+}
+
+trait InProgressTrx {
+    fn trx_in_progress() -> BuyAssetTrx;
+}
+
+impl InProgressTrx for TransferOutState {
+    fn trx_in_progress() -> BuyAssetTrx {
+        BuyAssetTrx::TransferOut
+    }
+}
+
+impl InProgressTrx for SwapState {
+    fn trx_in_progress() -> BuyAssetTrx {
+        BuyAssetTrx::Swap
+    }
+}
+
+/// The lease ICA is open before the swap starts
+///
+/// The type cannot be instantiated. It only fills in the ICA opening states of the remote out
+/// swap flow that are never entered here.
+#[derive(Serialize, Deserialize)]
+pub(crate) enum IcaOpen {}
+
+impl IcaConnectee for IcaOpen {
+    type State = DexState;
+    type NextState = TransferOut<BuyAsset, Self::State, LeasePaymentCurrencies, SwapClient>;
+
+    fn connected(self, _dex_account: Account) -> Self::NextState {
+        match self {}
+    }
+}
+
+impl DexConnectable for IcaOpen {
+    fn dex(&self) -> &ConnectionParams {
+        match *self {}
+    }
+}
+
+impl DexContract for IcaOpen {
+    type StateResponse = ContractResult<QueryStateResponse>;
+
+    fn state(self, _now: Timestamp, _querier: QuerierWrapper<'_>) -> Self::StateResponse {
+        match self {}
+    }
+}
+
+impl Display for IcaOpen {
+    fn fmt(&self, _f: &mut Formatter<'_>) -> FmtResult {
+        match *self {}
+    }
+}
+
+impl TimeAlarm for IcaOpen {
+    fn setup_alarm(&self, _forr: Timestamp) -> DexResult<Batch> {
+        match *self {}
+    }
+}
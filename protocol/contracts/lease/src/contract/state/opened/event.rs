@@ -3,14 +3,15 @@ use platform::batch::{Emit, Emitter};
 use sdk::cosmwasm_std::{Addr, Env};
 
 use crate::{
-    api::DownpaymentCoin,
+    api::{DownpaymentCoin, LeaseCoin, LpnCoinDTO, MergedLoan, PaymentCoin},
     contract::{
         cmd::{OpenLoanRespResult, RepayEmitter},
         state::event as state_event,
     },
+    error::ContractError,
     event::Type,
     lease::LeaseDTO,
-    loan::RepayReceipt,
+    loan::{RefinanceReceipt, RepayReceipt},
 };
 
 pub(super) fn emit_lease_opened(
@@ -33,6 +34,107 @@ pub(super) fn emit_lease_opened(
         .emit_coin_dto("downpayment", &downpayment)
 }
 
+pub(super) fn emit_collateral_added(
+    env: &Env,
+    lease: &LeaseDTO,
+    payment: &PaymentCoin,
+    collateral: &LeaseCoin,
+) -> Emitter {
+    Emitter::of_type(Type::AddCollateral)
+        .emit_tx_info(env)
+        .emit("to", &lease.addr)
+        .emit_coin_dto("payment", payment)
+        .emit_coin_dto("collateral", collateral)
+}
+
+pub(super) fn emit_borrowed(
+    env: &Env,
+    lease: &LeaseDTO,
+    loan: &LpnCoinDTO,
+    collateral: &LeaseCoin,
+) -> Emitter {
+    Emitter::of_type(Type::Borrow)
+        .emit_tx_info(env)
+        .emit("to", &lease.addr)
+        .emit_coin_dto("loan", loan)
+        .emit_coin_dto("collateral", collateral)
+}
+
+pub(super) fn emit_refinanced(env: &Env, lease: &LeaseDTO, receipt: &RefinanceReceipt) -> Emitter {
+    Emitter::of_type(Type::Refinance)
+        .emit_tx_info(env)
+        .emit("to", &lease.addr)
+        .emit_coin("interest", receipt.interest_paid())
+        .emit_coin("margin-interest", receipt.margin_paid())
+        .emit_coin("fee", receipt.fee_paid())
+        .emit_coin("change", receipt.change())
+}
+
+pub(super) fn emit_asset_rotated(
+    env: &Env,
+    lease: &LeaseDTO,
+    amount_in: &LeaseCoin,
+    amount_out: &LeaseCoin,
+) -> Emitter {
+    Emitter::of_type(Type::RotateAsset)
+        .emit_tx_info(env)
+        .emit("to", &lease.addr)
+        .emit_coin_dto("from-amount", amount_in)
+        .emit_coin_dto("to-amount", amount_out)
+}
+
+pub(super) fn emit_merged(
+    env: &Env,
+    lease: &LeaseDTO,
+    into: &Addr,
+    amount: &LeaseCoin,
+    loan: &MergedLoan,
+) -> Emitter {
+    Emitter::of_type(Type::Merge)
+        .emit_tx_info(env)
+        .emit("id", &lease.addr)
+        .emit("into", into)
+        .emit_coin_dto("amount", amount)
+        .emit_coin_dto("principal", &loan.principal_due)
+}
+
+pub(super) fn emit_merge_aborted(
+    env: &Env,
+    lease: &LeaseDTO,
+    into: &Addr,
+    cause: &ContractError,
+) -> Emitter {
+    Emitter::of_type(Type::MergeAbort)
+        .emit_tx_info(env)
+        .emit("id", &lease.addr)
+        .emit("into", into)
+        .emit_to_string_value("cause", cause)
+}
+
+pub(super) fn emit_absorbed(
+    env: &Env,
+    lease: &LeaseDTO,
+    amount: &LeaseCoin,
+    loan: &MergedLoan,
+) -> Emitter {
+    Emitter::of_type(Type::Absorb)
+        .emit_tx_info(env)
+        .emit("to", &lease.addr)
+        .emit_coin_dto("amount", amount)
+        .emit_coin_dto("principal", &loan.principal_due)
+}
+
+pub(super) fn emit_expired(env: &Env, lease: &LeaseDTO) -> Emitter {
+    let emitter = Emitter::of_type(Type::Expired)
+        .emit_tx_info(env)
+        .emit("id", &lease.addr);
+    if let Some(expiry) = &lease.expiry {
+        emitter.emit_timestamp("expiry", expiry)
+    } else {
+        emitter
+    }
+}
+
 pub(super) struct PaymentEmitter<'env>(&'env Env);
 impl<'env> PaymentEmitter<'env> {
     pub fn new(env: &'env Env) -> Self {
@@ -0,0 +1,269 @@
+use serde::{Deserialize, Serialize};
+
+use currency::SymbolSlice;
+use dex::{
+    Account, CoinVisitor, ContractInSwap, Enterable, IterNext, IterState, StartTransferInState,
+    SwapState, SwapTask, TransferInFinishState, TransferInInitState, TransferOutState,
+};
+use finance::{coin::CoinDTO, percent::Percent};
+use platform::{
+    message::Response as MessageResponse, state_machine::Response as StateMachineResponse,
+};
+use sdk::cosmwasm_std::{Addr, Env, QuerierWrapper, Timestamp};
+use timealarms::stub::TimeAlarmsRef;
+
+use crate::{
+    api::{
+        query::{
+            opened::{MergeTrx, OngoingTrx},
+            StateQuery, StateResponse as QueryStateResponse,
+        },
+        LeaseAssetCurrencies, LeaseCoin, LeasePaymentCurrencies,
+    },
+    contract::{
+        cmd::{MergeTerms, ValidateMerge},
+        state::{
+            closed::Closed,
+            resp_delivery::{ForwardToDexEntry, ForwardToDexEntryContinue},
+            SwapClient, SwapResult,
+        },
+        Lease,
+    },
+    error::{ContractError, ContractResult},
+    event::Type,
+    finance::{LpnCoin, OracleRef},
+};
+
+use super::{
+    collateral::{self, DexState as AddCollateralState},
+    event,
+};
+
+type AssetGroup = LeaseAssetCurrencies;
+pub(super) type StartState = StartTransferInState<
+    MergeTransferIn,
+    LeasePaymentCurrencies,
+    SwapClient,
+    ForwardToDexEntry,
+    ForwardToDexEntryContinue,
+>;
+pub(crate) type DexState = dex::StateLocalOut<
+    MergeTransferIn,
+    LeasePaymentCurrencies,
+    SwapClient,
+    ForwardToDexEntry,
+    ForwardToDexEntryContinue,
+>;
+
+pub(super) fn start(lease: Lease, into: Addr) -> StartState {
+    let transfer = MergeTransferIn::new(lease, into);
+    let amount_in = transfer.amount().clone();
+    StartState::new(transfer, amount_in)
+}
+
+/// Check if the lease can be merged into `into`
+///
+/// The other lease should be open on the same currency with no ongoing transaction,
+/// and the merged position should be below the maximum liability.
+pub(super) fn validate_into(
+    lease: &Lease,
+    into: Addr,
+    now: &Timestamp,
+    querier: QuerierWrapper<'_>,
+) -> ContractResult<()> {
+    let (asset, total_due) = query_into(into, querier)?;
+    if asset.ticker() != lease.lease.position.amount().ticker() {
+        return Err(ContractError::MergeIntoDifferentAsset(
+            asset.ticker().into(),
+        ));
+    }
+    lease
+        .lease
+        .clone()
+        .execute(ValidateMerge::new(asset, total_due, now), querier)
+}
+
+/// Query the asset and the total due of an open lease with no ongoing transaction
+fn query_into(lease: Addr, querier: QuerierWrapper<'_>) -> ContractResult<(LeaseCoin, LpnCoin)> {
+    querier
+        .query_wasm_smart(lease, &StateQuery {})
+        .map_err(Into::into)
+        .and_then(|state| match state {
+            QueryStateResponse::Opened {
+                amount,
+                principal_due,
+                overdue_margin,
+                overdue_interest,
+                due_margin,
+                due_interest,
+                in_progress: None,
+                ..
+            } => [
+                principal_due,
+                overdue_margin,
+                overdue_interest,
+                due_margin,
+                due_interest,
+            ]
+            .into_iter()
+            .map(TryInto::<LpnCoin>::try_into)
+            .sum::<Result<LpnCoin, _>>()
+            .map(|total_due| (amount, total_due))
+            .map_err(Into::into),
+            _ => Err(ContractError::MergeTargetNotReady()),
+        })
+}
+
+/// Transfer the lease asset in and hand it over, along with the loan, to another lease
+///
+/// The Lpp loan is merged into the loan of the other lease. The asset and the margin terms
+/// are sent to the leaser that forwards them to the other lease. This lease gets closed.
+///
+/// The other lease may have changed while the asset has been transferred in. It is checked
+/// again before the hand-over. If the merge is not possible anymore, the asset is transferred
+/// back out to the lease ICA and the lease gets active again.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct MergeTransferIn {
+    lease: Lease,
+    into: Addr,
+}
+
+impl MergeTransferIn {
+    fn new(lease: Lease, into: Addr) -> Self {
+        Self { lease, into }
+    }
+
+    fn query(
+        self,
+        in_progress: MergeTrx,
+        now: Timestamp,
+        querier: QuerierWrapper<'_>,
+    ) -> <Self as SwapTask>::StateResponse {
+        let in_progress = OngoingTrx::Merge {
+            into: self.into,
+            in_progress,
+        };
+
+        super::lease_state(self.lease, Some(in_progress), now, querier)
+    }
+
+    fn amount(&self) -> &LeaseCoin {
+        self.lease.lease.position.amount()
+    }
+
+    /// Transfer the asset back out to the lease ICA
+    fn abort(self, err: ContractError, env: &Env, querier: QuerierWrapper<'_>) -> SwapResult {
+        let emitter = event::emit_merge_aborted(env, &self.lease.lease, &self.into, &err);
+        let amount = self.amount().clone();
+        let abort = collateral::start_merge_abort(self.lease, amount);
+        abort
+            .enter(env.block.time, querier)
+            .map(|batch| MessageResponse::messages_with_events(batch, emitter))
+            .map(|response| StateMachineResponse::from(response, AddCollateralState::from(abort)))
+            .map_err(Into::into)
+    }
+}
+
+impl SwapTask for MergeTransferIn {
+    type OutG = AssetGroup;
+    type Label = Type;
+    type StateResponse = ContractResult<QueryStateResponse>;
+    type Result = SwapResult;
+
+    fn label(&self) -> Self::Label {
+        Type::MergeTransferIn
+    }
+
+    fn dex_account(&self) -> &Account {
+        &self.lease.dex
+    }
+
+    fn oracle(&self) -> &OracleRef {
+        &self.lease.lease.oracle
+    }
+
+    fn time_alarm(&self) -> &TimeAlarmsRef {
+        &self.lease.lease.time_alarms
+    }
+
+    fn out_currency(&self) -> &SymbolSlice {
+        self.amount().ticker()
+    }
+
+    fn max_slippage(&self) -> Percent {
+        self.lease.max_slippage
+    }
+
+    fn on_coins<Visitor>(&self, visitor: &mut Visitor) -> Result<IterState, Visitor::Error>
+    where
+        Visitor: CoinVisitor<Result = IterNext>,
+    {
+        dex::on_coin(self.amount(), visitor)
+    }
+
+    fn finish(
+        self,
+        amount_out: CoinDTO<Self::OutG>,
+        env: &Env,
+        querier: QuerierWrapper<'_>,
+    ) -> Self::Result {
+        debug_assert!(&amount_out == self.amount());
+        if let Err(err) = validate_into(&self.lease, self.into.clone(), &env.block.time, querier) {
+            return self.abort(err, env, querier);
+        }
+
+        let loan = self.lease.lease.clone().execute(MergeTerms {}, querier)?;
+        let emitter = event::emit_merged(env, &self.lease.lease, &self.into, &amount_out, &loan);
+        let customer = self.lease.lease.customer.clone();
+
+        // the finalizer merges the loans before handing the asset over
+        self.lease
+            .finalizer
+            .notify_merge(customer, self.into, loan, &amount_out)
+            .map(|finalizer_msgs| MessageResponse::messages_with_events(finalizer_msgs, emitter))
+            .map(|response| StateMachineResponse::from(response, Closed::from(self.lease.history)))
+    }
+}
+
+impl<DexState> ContractInSwap<DexState> for MergeTransferIn
+where
+    DexState: InProgressTrx,
+{
+    type StateResponse = <Self as SwapTask>::StateResponse;
+
+    fn state(self, now: Timestamp, querier: QuerierWrapper<'_>) -> Self::StateResponse {
+        self.query(DexState::trx_in_progress(), now, querier)
+    }
+}
+
+trait InProgressTrx {
+    fn trx_in_progress() -> MergeTrx;
+}
+
+impl InProgressTrx for TransferOutState {
+    fn trx_in_progress() -> MergeTrx {
+        // it's due to reusing the same enum dex::State
+        // have to define a tailored enum dex::State that starts from TransferIn
+        unreachable!("The lease asset transfer-in task never goes through a 'TransferOut' state!")
+    }
+}
+
+impl InProgressTrx for SwapState {
+    fn trx_in_progress() -> MergeTrx {
+        // it's due to reusing the same enum dex::State
+        // have to define a tailored enum dex::State that starts from TransferIn
+        unreachable!("The lease asset transfer-in task never goes through a 'Swap'!")
+    }
+}
+
+impl InProgressTrx for TransferInInitState {
+    fn trx_in_progress() -> MergeTrx {
+        MergeTrx::TransferInInit
+    }
+}
+
+impl InProgressTrx for TransferInFinishState {
+    fn trx_in_progress() -> MergeTrx {
+        MergeTrx::TransferInFinish
+    }
+}
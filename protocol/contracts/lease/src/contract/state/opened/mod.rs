@@ -10,9 +10,12 @@ pub mod active;
 mod alarm;
 mod balance;
 pub mod close;
+pub mod collateral;
 mod event;
+pub mod merge;
 mod payment;
 pub mod repay;
+pub mod rotate;
 
 fn lease_state(
     lease: Lease,
@@ -20,7 +23,8 @@ fn lease_state(
     now: Timestamp,
     querier: QuerierWrapper<'_>,
 ) -> ContractResult<StateResponse> {
+    let operators = lease.operators();
     lease
         .lease
-        .execute(LeaseState::new(now, in_progress), querier)
+        .execute(LeaseState::new(now, operators, in_progress), querier)
 }
@@ -5,13 +5,16 @@ use sdk::cosmwasm_std::{Env, QuerierWrapper};
 
 use crate::{
     api::{
-        query::opened::{OngoingTrx, PositionCloseTrx},
+        query::{
+            opened::{OngoingTrx, PositionCloseTrx},
+            PaymentKind,
+        },
         LeaseCoin,
     },
     contract::{
         cmd::{FullClose as FullCloseCmd, RepayEmitter},
         state::{opened::close::Closable, Response, State},
-        Lease,
+        History, Lease,
     },
     error::ContractResult,
     event::Type,
@@ -21,7 +24,7 @@ use crate::{
 use super::Repayable;
 
 pub(crate) trait CloseAlgo {
-    type OutState: Default + Into<State>;
+    type OutState: From<History> + Into<State>;
 
     type ProfitSender: FixedAddressSender;
 
@@ -34,6 +37,7 @@ pub(crate) trait CloseAlgo {
 
     fn profit_sender(&self, lease: &Lease) -> Self::ProfitSender;
     fn change_sender(&self, lease: &Lease) -> Self::ChangeSender;
+    fn payment_kind(&self, lease: &Lease) -> PaymentKind;
     fn emitter_fn<'this, 'lease, 'env>(
         &'this self,
         lease: &'lease Lease,
@@ -94,15 +98,20 @@ where
             let reserve = lease.lease.reserve.clone();
             let change = self.0.change_sender(&lease);
             let emitter_fn = self.0.emitter_fn(&lease, env);
+            let payment_kind = self.0.payment_kind(&lease);
+            let history = lease.history;
             lease
                 .lease
                 .execute(
                     FullCloseCmd::new(amount, env.block.time, profit, reserve, change, emitter_fn),
                     querier,
                 )
-                .map(|liquidation_response| liquidation_response.merge_with(finalizer_msgs))
-                //make sure the finalizer messages go out last
-                .map(|response| Response::from(response, CloseAlgoT::OutState::default()))
+                .map(|(receipt, liquidation_response)| {
+                    let history = history.record(env.block.time, payment_kind, &receipt);
+                    //make sure the finalizer messages go out last
+                    let response = liquidation_response.merge_with(finalizer_msgs);
+                    Response::from(response, CloseAlgoT::OutState::from(history))
+                })
         })
     }
 }
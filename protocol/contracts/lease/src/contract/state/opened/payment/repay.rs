@@ -5,7 +5,10 @@ use sdk::cosmwasm_std::{Env, QuerierWrapper};
 
 use crate::{
     api::{
-        query::opened::{OngoingTrx, PositionCloseTrx},
+        query::{
+            opened::{OngoingTrx, PositionCloseTrx},
+            PaymentKind,
+        },
         LeaseCoin,
     },
     contract::{
@@ -13,7 +16,7 @@ use crate::{
         state::{
             opened::{
                 active, alarm,
-                close::{liquidation, Closable},
+                close::{customer_close, liquidation, Closable},
             },
             paid, Response,
         },
@@ -33,6 +36,7 @@ pub(crate) trait RepayAlgo {
         Self: 'this;
 
     fn repay_fn(&self) -> Self::RepayFn;
+    fn payment_kind(&self) -> PaymentKind;
     fn emitter_fn<'this, 'env>(&'this self, env: &'env Env) -> Self::PaymentEmitter<'this, 'env>;
 }
 
@@ -86,6 +90,7 @@ where
             RepayResult {
                 response,
                 loan_paid,
+                receipt,
                 liquidation,
             },
         ) = lease.update(
@@ -100,6 +105,7 @@ where
             ),
             querier,
         )?;
+        let lease = lease.record_payment(env.block.time, self.0.payment_kind(), &receipt);
 
         match liquidation {
             LiquidationStatus::NoDebt => Ok(finish_repay(loan_paid, response, lease)),
@@ -114,6 +120,12 @@ where
             LiquidationStatus::NeedLiquidation(liquidation) => {
                 liquidation::start(lease, liquidation, response, env, querier)
             }
+            LiquidationStatus::NeedClose { strategy, close } => {
+                customer_close::start_on_trigger(strategy, close, lease, response, env, querier)
+            }
+            LiquidationStatus::Expired => {
+                customer_close::start_on_expiry(lease, response, env, querier)
+            }
         }
     }
 }
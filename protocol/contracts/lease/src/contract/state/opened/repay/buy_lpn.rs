@@ -5,7 +5,7 @@ use dex::{
     Account, CoinVisitor, ContractInSwap, IterNext, IterState, StartLocalLocalState, SwapState,
     SwapTask, TransferInFinishState, TransferInInitState, TransferOutState,
 };
-use finance::coin::CoinDTO;
+use finance::{coin::CoinDTO, percent::Percent};
 use sdk::cosmwasm_std::{Env, QuerierWrapper, Timestamp};
 use timealarms::stub::TimeAlarmsRef;
 
@@ -101,6 +101,10 @@ impl SwapTask for BuyLpn {
         self.lease.lease.loan.lpp().lpn()
     }
 
+    fn max_slippage(&self) -> Percent {
+        self.lease.max_slippage
+    }
+
     fn on_coins<Visitor>(&self, visitor: &mut Visitor) -> Result<IterState, Visitor::Error>
     where
         Visitor: CoinVisitor<Result = IterNext>,
@@ -1,6 +1,7 @@
 use sdk::cosmwasm_std::{Env, QuerierWrapper};
 
 use crate::{
+    api::query::PaymentKind,
     contract::{cmd::RepayLeaseFn, state::Response, Lease},
     error::ContractResult,
     finance::LpnCoinDTO,
@@ -33,6 +34,10 @@ impl RepayAlgo for CustomerRepay {
         Self::RepayFn {}
     }
 
+    fn payment_kind(&self) -> PaymentKind {
+        PaymentKind::Repayment
+    }
+
     fn emitter_fn<'liq, 'env>(&'liq self, env: &'env Env) -> Self::PaymentEmitter<'liq, 'env> {
         Self::PaymentEmitter::new(env)
     }
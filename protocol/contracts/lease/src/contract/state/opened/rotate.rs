@@ -0,0 +1,216 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use serde::{Deserialize, Serialize};
+
+use currency::{SymbolOwned, SymbolSlice};
+use dex::{
+    Account, CoinVisitor, ConnectionParams, Contract as DexContract, ContractInSwap,
+    DexConnectable, DexResult, IcaConnectee, IterNext, IterState, StartLocalRemoteSwapState,
+    SwapState, SwapTask, TimeAlarm, TransferOut, TransferOutState,
+};
+use finance::{coin::CoinDTO, percent::Percent};
+use platform::batch::Batch;
+use sdk::cosmwasm_std::{Env, QuerierWrapper, Timestamp};
+use timealarms::stub::TimeAlarmsRef;
+
+use crate::{
+    api::{
+        query::{
+            opened::{BuyAssetTrx, OngoingTrx},
+            StateResponse as QueryStateResponse,
+        },
+        LeaseAssetCurrencies, LeasePaymentCurrencies,
+    },
+    contract::{
+        cmd::LiquidationStatusCmd,
+        state::{
+            resp_delivery::{ForwardToDexEntry, ForwardToDexEntryContinue},
+            StateResponse as ContractStateResponse, SwapClient, SwapResult,
+        },
+        Lease,
+    },
+    error::ContractResult,
+    event::Type,
+    finance::OracleRef,
+};
+
+use super::{active::Active, event};
+
+pub(super) type StartState = StartLocalRemoteSwapState<
+    IcaOpen,
+    RotateAsset,
+    LeasePaymentCurrencies,
+    SwapClient,
+    ForwardToDexEntry,
+    ForwardToDexEntryContinue,
+>;
+pub(crate) type DexState = dex::StateRemoteOut<
+    IcaOpen,
+    RotateAsset,
+    LeasePaymentCurrencies,
+    SwapClient,
+    ForwardToDexEntry,
+    ForwardToDexEntryContinue,
+>;
+
+pub(super) fn start(lease: Lease, currency: SymbolOwned) -> StartState {
+    dex::start_local_remote_swap(RotateAsset::new(lease, currency))
+}
+
+/// Swap the whole position into another lease currency
+///
+/// The lease asset is on the lease ICA and the result stays there. The loan is not affected.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct RotateAsset {
+    lease: Lease,
+    currency: SymbolOwned,
+}
+
+impl RotateAsset {
+    fn new(lease: Lease, currency: SymbolOwned) -> Self {
+        Self { lease, currency }
+    }
+
+    fn query(
+        self,
+        in_progress: BuyAssetTrx,
+        now: Timestamp,
+        querier: QuerierWrapper<'_>,
+    ) -> ContractResult<ContractStateResponse> {
+        let in_progress = OngoingTrx::Rotation {
+            currency: self.currency,
+            in_progress,
+        };
+
+        super::lease_state(self.lease, Some(in_progress), now, querier)
+    }
+}
+
+impl SwapTask for RotateAsset {
+    type OutG = LeaseAssetCurrencies;
+    type Label = Type;
+    type StateResponse = ContractResult<QueryStateResponse>;
+    type Result = SwapResult;
+
+    fn label(&self) -> Self::Label {
+        Type::RotateAssetSwap
+    }
+
+    fn dex_account(&self) -> &Account {
+        &self.lease.dex
+    }
+
+    fn oracle(&self) -> &OracleRef {
+        &self.lease.lease.oracle
+    }
+
+    fn time_alarm(&self) -> &TimeAlarmsRef {
+        &self.lease.lease.time_alarms
+    }
+
+    fn out_currency(&self) -> &SymbolSlice {
+        &self.currency
+    }
+
+    fn max_slippage(&self) -> Percent {
+        self.lease.max_slippage
+    }
+
+    fn on_coins<Visitor>(&self, visitor: &mut Visitor) -> Result<IterState, Visitor::Error>
+    where
+        Visitor: CoinVisitor<Result = IterNext>,
+    {
+        dex::on_coin(self.lease.lease.position.amount(), visitor)
+    }
+
+    fn finish(
+        self,
+        amount_out: CoinDTO<Self::OutG>,
+        env: &Env,
+        querier: QuerierWrapper<'_>,
+    ) -> Self::Result {
+        let mut lease = self.lease;
+        let amount_in = lease.lease.position.amount().clone();
+        lease.lease.position.rotate(amount_out.clone());
+
+        let time_alarms = lease.lease.time_alarms.clone();
+        let price_alarms = lease.lease.oracle.clone();
+        let liquidation_status = lease.lease.clone().execute(
+            LiquidationStatusCmd::new(&env.block.time, &time_alarms, &price_alarms),
+            querier,
+        )?;
+
+        let emitter = event::emit_asset_rotated(env, &lease.lease, &amount_in, &amount_out);
+        Active::new(lease).on_liquidation_status(liquidation_status, emitter.into(), env, querier)
+    }
+}
+
+impl<DexState> ContractInSwap<DexState> for RotateAsset
+where
+    DexState: InProgressTrx,
+{
+    type StateResponse = <Self as SwapTask>::StateResponse;
+
+    fn state(self, now: Timestamp, querier: QuerierWrapper<'_>) -> Self::StateResponse {
+        self.query(DexState::trx_in_progress(), now, querier)
+    }
+}
+
+trait InProgressTrx {
+    fn trx_in_progress() -> BuyAssetTrx;
+}
+
+impl InProgressTrx for TransferOutState {
+    fn trx_in_progress() -> BuyAssetTrx {
+        // it's due to reusing the same enum dex::State
+        unreachable!("The rotate asset task never goes through a 'TransferOut' state!")
+    }
+}
+
+impl InProgressTrx for SwapState {
+    fn trx_in_progress() -> BuyAssetTrx {
+        BuyAssetTrx::Swap
+    }
+}
+
+/// The lease ICA is open before the swap starts
+///
+/// The type cannot be instantiated. It only fills in the ICA opening states of the remote out
+/// swap flow that are never entered here.
+#[derive(Serialize, Deserialize)]
+pub(crate) enum IcaOpen {}
+
+impl IcaConnectee for IcaOpen {
+    type State = DexState;
+    type NextState = TransferOut<RotateAsset, Self::State, LeasePaymentCurrencies, SwapClient>;
+
+    fn connected(self, _dex_account: Account) -> Self::NextState {
+        match self {}
+    }
+}
+
+impl DexConnectable for IcaOpen {
+    fn dex(&self) -> &ConnectionParams {
+        match *self {}
+    }
+}
+
+impl DexContract for IcaOpen {
+    type StateResponse = ContractResult<QueryStateResponse>;
+
+    fn state(self, _now: Timestamp, _querier: QuerierWrapper<'_>) -> Self::StateResponse {
+        match self {}
+    }
+}
+
+impl Display for IcaOpen {
+    fn fmt(&self, _f: &mut Formatter<'_>) -> FmtResult {
+        match *self {}
+    }
+}
+
+impl TimeAlarm for IcaOpen {
+    fn setup_alarm(&self, _forr: Timestamp) -> DexResult<Batch> {
+        match *self {}
+    }
+}
@@ -5,7 +5,7 @@ use dex::{
     Account, CoinVisitor, ContractInSwap, IterNext, IterState, StartLocalRemoteState, SwapState,
     SwapTask, TransferOutState,
 };
-use finance::coin::CoinDTO;
+use finance::{coin::CoinDTO, percent::Percent};
 use platform::{
     ica::HostAccount, message::Response as MessageResponse,
     state_machine::Response as StateMachineResponse,
@@ -138,6 +138,10 @@ impl SwapTask for BuyAsset {
         &self.form.currency
     }
 
+    fn max_slippage(&self) -> Percent {
+        self.form.max_slippage
+    }
+
     fn on_coins<Visitor>(&self, visitor: &mut Visitor) -> Result<IterState, Visitor::Error>
     where
         Visitor: CoinVisitor<Result = IterNext>,
@@ -151,6 +155,7 @@ impl SwapTask for BuyAsset {
         env: &Env,
         querier: QuerierWrapper<'_>,
     ) -> Self::Result {
+        let max_slippage = self.form.max_slippage;
         let IntoDTOResult { lease, batch } = cmd::open_lease(
             self.form,
             self.dex_account.owner().clone(),
@@ -161,7 +166,12 @@ impl SwapTask for BuyAsset {
             (self.deps.0, self.deps.1, self.deps.2),
         )?;
 
-        let active = Active::new(Lease::new(lease, self.dex_account, self.deps.3));
+        let active = Active::new(Lease::new(
+            lease,
+            self.dex_account,
+            self.deps.3,
+            max_slippage,
+        ));
         let emitter = active.emit_opened(env, self.downpayment, self.loan);
         Ok(StateMachineResponse::from(
             MessageResponse::messages_with_events(batch, emitter),
@@ -1,9 +1,13 @@
 use serde::{Deserialize, Serialize};
 
 use dex::Enterable;
-use sdk::cosmwasm_std::{Env, MessageInfo, QuerierWrapper, Timestamp};
+use sdk::cosmwasm_std::{Addr, Env, MessageInfo, QuerierWrapper, Storage, Timestamp};
 
-use crate::{api::query::StateResponse, contract::Lease, error::ContractResult};
+use crate::{
+    api::{open::IbcParams, query::StateResponse},
+    contract::Lease,
+    error::ContractResult,
+};
 
 use super::{Handler, Response};
 
@@ -20,11 +24,20 @@ impl Active {
     pub(in super::super) fn new(lease: Lease) -> Self {
         Self { lease }
     }
+
+    pub(in super::super) fn with_dex_ibc(self, ibc: IbcParams) -> Self {
+        Self::new(self.lease.with_dex_ibc(ibc))
+    }
+
+    pub(in super::super) fn store_history(self, storage: &mut dyn Storage) -> ContractResult<Self> {
+        self.lease.store_history(storage).map(Self::new)
+    }
 }
 
 impl Handler for Active {
     fn state(self, _now: Timestamp, _querier: QuerierWrapper<'_>) -> ContractResult<StateResponse> {
-        Ok(StateResponse::paid_from(self.lease.lease, None))
+        let operators = self.lease.operators();
+        Ok(StateResponse::paid_from(self.lease.lease, operators, None))
     }
 
     fn close(
@@ -33,7 +46,7 @@ impl Handler for Active {
         env: Env,
         info: MessageInfo,
     ) -> ContractResult<Response> {
-        access_control::check(&self.lease.lease.customer, &info.sender)?;
+        self.lease.check_manager(&info.sender)?;
 
         let start_transfer_in = transfer_in::start(self.lease);
         start_transfer_in
@@ -41,6 +54,29 @@ impl Handler for Active {
             .map(|batch| Response::from(batch, DexState::from(start_transfer_in)))
             .map_err(Into::into)
     }
+
+    fn add_operator(self, operator: Addr, info: MessageInfo) -> ContractResult<Response> {
+        self.lease
+            .add_operator(operator, &info.sender)
+            .map(|lease| Response::no_msgs(Self::new(lease)))
+    }
+
+    fn remove_operator(self, operator: Addr, info: MessageInfo) -> ContractResult<Response> {
+        self.lease
+            .remove_operator(&operator, &info.sender)
+            .map(|lease| Response::no_msgs(Self::new(lease)))
+    }
+
+    fn transfer_ownership(
+        self,
+        new_customer: Addr,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        super::transfer_ownership(self.lease, new_customer, &env, &info.sender)
+            .map(|(lease, response)| Response::from(response, Self::new(lease)))
+    }
+
     fn on_time_alarm(
         self,
         _querier: QuerierWrapper<'_>,
@@ -5,7 +5,7 @@ use dex::{
     Account, CoinVisitor, ContractInSwap, IterNext, IterState, StartTransferInState, SwapState,
     SwapTask, TransferInFinishState, TransferInInitState, TransferOutState,
 };
-use finance::coin::CoinDTO;
+use finance::{coin::CoinDTO, percent::Percent};
 use platform::{
     bank,
     batch::{Emit, Emitter},
@@ -68,8 +68,10 @@ impl TransferIn {
     }
 
     fn state(self, in_progress: ClosingTrx) -> <Self as SwapTask>::StateResponse {
+        let operators = self.lease.operators();
         Ok(QueryStateResponse::paid_from(
             self.lease.lease,
+            operators,
             Some(in_progress),
         ))
     }
@@ -111,6 +113,10 @@ impl SwapTask for TransferIn {
         self.amount().ticker()
     }
 
+    fn max_slippage(&self) -> Percent {
+        self.lease.max_slippage
+    }
+
     fn on_coins<Visitor>(&self, visitor: &mut Visitor) -> Result<IterState, Visitor::Error>
     where
         Visitor: CoinVisitor<Result = IterNext>,
@@ -138,7 +144,7 @@ impl SwapTask for TransferIn {
                     .map(|finalizer_msgs| close_msgs.merge(finalizer_msgs)) //make sure the finalizer messages go out last
             })
             .map(|all_messages| MessageResponse::messages_with_events(all_messages, emitter))
-            .map(|response| StateMachineResponse::from(response, Closed::default()))
+            .map(|response| StateMachineResponse::from(response, Closed::from(self.lease.history)))
     }
 }
 
@@ -2,9 +2,9 @@ use std::any::type_name;
 
 use thiserror::Error;
 
-use currency::error::Error as CurrencyError;
+use currency::{error::Error as CurrencyError, SymbolOwned};
 use dex::Error as DexError;
-use finance::error::Error as FinanceError;
+use finance::{error::Error as FinanceError, percent::Percent};
 use lpp::error::ContractError as LppError;
 use oracle::api::alarms::Error as OracleAlarmError;
 use oracle_platform::error::Error as OracleError;
@@ -63,6 +63,12 @@ pub enum ContractError {
     #[error("[Lease] The asset amount should worth at least {0}")]
     InsufficientAssetAmount(LpnCoinDTO),
 
+    #[error("[Lease] The loan interest rate is not above the refinance one, {0}")]
+    NoRefinanceGain(Percent),
+
+    #[error("[Lease] The refinance payment should be at least {0}")]
+    InsufficientRefinancePayment(LpnCoinDTO),
+
     #[error("[Lease] The transaction amount should worth at least {0}")]
     InsufficientTransactionAmount(LpnCoinDTO),
 
@@ -72,6 +78,30 @@ pub enum ContractError {
     #[error("[Lease] The position past this close should worth at least {0}")]
     PositionCloseAmountTooBig(LpnCoinDTO),
 
+    #[error("[Lease] The borrow amount should worth at most {0}")]
+    BorrowAmountTooBig(LpnCoinDTO),
+
+    #[error("[Lease] The take-profit price should be above the current market price")]
+    TakeProfitTooLow(),
+
+    #[error("[Lease] The stop-loss price should not be above the current market price")]
+    StopLossTooHigh(),
+
+    #[error("[Lease] The lease currency is already {0}")]
+    RotationIntoSameAsset(SymbolOwned),
+
+    #[error("[Lease] The lease maximum duration elapsed before the lease got opened")]
+    ExpiredOnOpen(),
+
+    #[error("[Lease] The lease to merge into should be open with no ongoing transaction")]
+    MergeTargetNotReady(),
+
+    #[error("[Lease] The lease to merge into is on another currency, {0}")]
+    MergeIntoDifferentAsset(SymbolOwned),
+
+    #[error("[Lease] The merged position liability should be below the maximum one, {0}")]
+    MergeLiabilityTooHigh(Percent),
+
     #[error("[Lease] The operation '{0}' is not supported in the current state")]
     UnsupportedOperation(String),
 
@@ -4,6 +4,19 @@ pub enum Type {
     OpeningSwap,
     OpenedActive,
     RepaymentSwap,
+    AddCollateralSwap,
+    AddCollateral,
+    BorrowSwap,
+    Borrow,
+    Refinance,
+    RotateAssetSwap,
+    RotateAsset,
+    MergeTransferIn,
+    Merge,
+    MergeAbort,
+    MergeAbortTransferOut,
+    AbsorbTransferOut,
+    Absorb,
     PaidActive,
     ClosingTransferIn,
     Closed,
@@ -11,6 +24,8 @@ pub enum Type {
     LiquidationSwap,
     Liquidation,
     ClosePosition,
+    TransferOwnership,
+    Expired,
 }
 
 impl Type {
@@ -22,6 +37,19 @@ impl Type {
             Self::OpeningSwap => "ls-open-swap",
             Self::OpenedActive => "ls-open",
             Self::RepaymentSwap => "ls-repay-swap",
+            Self::AddCollateralSwap => "ls-add-collateral-swap",
+            Self::AddCollateral => "ls-add-collateral",
+            Self::BorrowSwap => "ls-borrow-swap",
+            Self::Borrow => "ls-borrow",
+            Self::Refinance => "ls-refinance",
+            Self::RotateAssetSwap => "ls-rotate-asset-swap",
+            Self::RotateAsset => "ls-rotate-asset",
+            Self::MergeTransferIn => "ls-merge-transfer-in",
+            Self::Merge => "ls-merge",
+            Self::MergeAbort => "ls-merge-abort",
+            Self::MergeAbortTransferOut => "ls-merge-abort-transfer-out",
+            Self::AbsorbTransferOut => "ls-absorb-transfer-out",
+            Self::Absorb => "ls-absorb",
             Self::PaidActive => "ls-repay",
             Self::ClosingTransferIn => "ls-close-transfer-in",
             Self::Closed => "ls-close",
@@ -29,6 +57,8 @@ impl Type {
             Self::LiquidationSwap => "ls-liquidation-swap",
             Self::Liquidation => "ls-liquidation",
             Self::ClosePosition => "ls-close-position",
+            Self::TransferOwnership => "ls-transfer-ownership",
+            Self::Expired => "ls-expired",
         }
     }
 }
@@ -31,7 +31,9 @@ where
         time_alarms: &TimeAlarmsRef,
         price_alarms: &OracleRef<LpnCurrency>,
     ) -> ContractResult<Batch> {
-        let next_recheck = now + recheck_in;
+        let next_recheck = self
+            .expiry
+            .map_or(now + recheck_in, |expiry| expiry.min(now + recheck_in));
 
         time_alarms
             .setup_alarm(next_recheck)
@@ -43,6 +45,8 @@ where
             })
     }
 
+    /// Set an alarm on the nearest, to the current price, of the liability zone boundaries
+    /// and the close policy triggers
     fn reschedule_price_alarm<PriceAlarms>(
         &self,
         liquidation_zone: &Zone,
@@ -55,12 +59,29 @@ where
         debug_assert!(!currency::equal::<LpnCurrency, Asset>());
         debug_assert!(!total_due.is_zero());
 
-        let below = self.position.price_at(liquidation_zone.high(), total_due)?;
+        let close_policy = self.position.close_policy();
+
+        let below = self
+            .position
+            .price_at(liquidation_zone.high(), total_due)
+            .map(|below_liquidation| {
+                close_policy
+                    .may_below()
+                    .filter(|stop_loss| stop_loss > &below_liquidation)
+                    .unwrap_or(below_liquidation)
+            })?;
 
         let above_or_equal = liquidation_zone
             .low()
             .map(|low| self.position.price_at(low, total_due))
-            .transpose()?;
+            .transpose()
+            .map(|above_or_equal_zone| {
+                match (above_or_equal_zone, close_policy.may_above_or_equal()) {
+                    (Some(zone), Some(take_profit)) if take_profit < zone => Some(take_profit),
+                    (Some(zone), _) => Some(zone),
+                    (None, may_take_profit) => may_take_profit,
+                }
+            })?;
 
         price_alarms
             .add_alarm(Alarm::new(below, above_or_equal))
@@ -83,17 +104,21 @@ mod tests {
     use oracle_platform::OracleRef;
     use platform::batch::Batch;
     use sdk::cosmwasm_std::{to_json_binary, Addr, WasmMsg};
-    use timealarms::{msg::ExecuteMsg::AddAlarm, stub::TimeAlarmsRef};
+    use timealarms::{
+        msg::{AlarmId, ExecuteMsg::AddAlarm},
+        stub::TimeAlarmsRef,
+    };
 
     use crate::{
+        api::position::{CloseTrigger, FullClose, PositionClose},
         lease::{
             self,
             tests::{
-                loan, open_lease, TestLpn, FIRST_LIQ_WARN, LEASE_START, RECHECK_TIME,
+                loan, open_lease, TestCurrency, TestLpn, FIRST_LIQ_WARN, LEASE_START, RECHECK_TIME,
                 SECOND_LIQ_WARN, THIRD_LIQ_WARN,
             },
         },
-        position::DueTrait,
+        position::{CloseStrategy, DueTrait},
     };
 
     const TIME_ALARMS_ADDR: &str = "timealarms";
@@ -126,7 +151,61 @@ mod tests {
 
             batch.schedule_execute_no_reply(WasmMsg::Execute {
                 contract_addr: TIME_ALARMS_ADDR.into(),
-                msg: to_json_binary(&AddAlarm { time: recheck_time }).unwrap(),
+                msg: to_json_binary(&AddAlarm {
+                    time: recheck_time,
+                    id: AlarmId::default(),
+                    payload: None,
+                })
+                .unwrap(),
+                funds: vec![],
+            });
+
+            let below_alarm = total_of(liability_alarm_on.of(asset)).is(due.total_due());
+            batch.schedule_execute_no_reply(WasmMsg::Execute {
+                contract_addr: ORACLE_ADDR.into(),
+                msg: to_json_binary(&AddPriceAlarm {
+                    alarm: Alarm::new(below_alarm, None),
+                })
+                .unwrap(),
+                funds: vec![],
+            });
+
+            batch
+        });
+    }
+
+    #[test]
+    fn expiry_alarm() {
+        let asset = Coin::from(10);
+        let mut lease = lease::tests::open_lease(asset, loan());
+        let now = LEASE_START;
+        let expiry = now + RECHECK_TIME - Duration::from_hours(1);
+        lease.expiry = Some(expiry);
+
+        let liability_alarm_on = FIRST_LIQ_WARN;
+        let due = lease.loan.state(&now);
+        let alarm_msgs = lease
+            .reschedule(
+                &now,
+                RECHECK_TIME,
+                &Zone::no_warnings(liability_alarm_on),
+                due.total_due(),
+                &timealarms(),
+                &pricealarms(),
+            )
+            .unwrap();
+
+        assert_eq!(alarm_msgs, {
+            let mut batch = Batch::default();
+
+            batch.schedule_execute_no_reply(WasmMsg::Execute {
+                contract_addr: TIME_ALARMS_ADDR.into(),
+                msg: to_json_binary(&AddAlarm {
+                    time: expiry,
+                    id: AlarmId::default(),
+                    payload: None,
+                })
+                .unwrap(),
                 funds: vec![],
             });
 
@@ -185,7 +264,12 @@ mod tests {
 
             batch.schedule_execute_no_reply(WasmMsg::Execute {
                 contract_addr: TIME_ALARMS_ADDR.into(),
-                msg: to_json_binary(&AddAlarm { time: recalc_at }).unwrap(),
+                msg: to_json_binary(&AddAlarm {
+                    time: recalc_at,
+                    id: AlarmId::default(),
+                    payload: None,
+                })
+                .unwrap(),
                 funds: vec![],
             });
 
@@ -202,6 +286,140 @@ mod tests {
         });
     }
 
+    #[test]
+    fn close_policy_alarms() {
+        let asset = Coin::from(10);
+        let mut lease = lease::tests::open_lease(asset, loan());
+        let now = LEASE_START;
+        let recheck_time = now + RECHECK_TIME;
+
+        let current_price = price_of(30);
+        let stop_loss = price_of(20);
+        let take_profit = price_of(50);
+        lease
+            .position
+            .change_close_policy(
+                CloseStrategy::StopLoss,
+                Some(full_close_at(stop_loss)),
+                current_price,
+            )
+            .unwrap();
+        lease
+            .position
+            .change_close_policy(
+                CloseStrategy::TakeProfit,
+                Some(full_close_at(take_profit)),
+                current_price,
+            )
+            .unwrap();
+
+        let liability_alarm_on = FIRST_LIQ_WARN;
+        let due = lease.loan.state(&now);
+        let below_liquidation = total_of(liability_alarm_on.of(asset)).is(due.total_due());
+        assert!(below_liquidation < stop_loss);
+
+        let alarm_msgs = lease
+            .reschedule(
+                &now,
+                RECHECK_TIME,
+                &Zone::no_warnings(liability_alarm_on),
+                due.total_due(),
+                &timealarms(),
+                &pricealarms(),
+            )
+            .unwrap();
+
+        assert_eq!(alarm_msgs, {
+            let mut batch = Batch::default();
+
+            batch.schedule_execute_no_reply(WasmMsg::Execute {
+                contract_addr: TIME_ALARMS_ADDR.into(),
+                msg: to_json_binary(&AddAlarm {
+                    time: recheck_time,
+                    id: AlarmId::default(),
+                    payload: None,
+                })
+                .unwrap(),
+                funds: vec![],
+            });
+
+            batch.schedule_execute_no_reply(WasmMsg::Execute {
+                contract_addr: ORACLE_ADDR.into(),
+                msg: to_json_binary(&AddPriceAlarm {
+                    alarm: Alarm::new(stop_loss, Some(take_profit)),
+                })
+                .unwrap(),
+                funds: vec![],
+            });
+
+            batch
+        });
+    }
+
+    #[test]
+    fn add_collateral_alarms() {
+        let asset = Coin::from(10);
+        let collateral = Coin::from(5);
+        let mut lease = lease::tests::open_lease(asset, loan());
+        let now = LEASE_START;
+        let recheck_time = now + RECHECK_TIME;
+
+        lease.add_collateral(collateral);
+        assert_eq!(asset + collateral, lease.position.amount());
+
+        let liability_alarm_on = FIRST_LIQ_WARN;
+        let due = lease.loan.state(&now);
+        let alarm_msgs = lease
+            .reschedule(
+                &now,
+                RECHECK_TIME,
+                &Zone::no_warnings(liability_alarm_on),
+                due.total_due(),
+                &timealarms(),
+                &pricealarms(),
+            )
+            .unwrap();
+
+        let below_alarm = total_of(liability_alarm_on.of(asset + collateral)).is(due.total_due());
+        assert!(below_alarm < total_of(liability_alarm_on.of(asset)).is(due.total_due()));
+        assert_eq!(alarm_msgs, {
+            let mut batch = Batch::default();
+
+            batch.schedule_execute_no_reply(WasmMsg::Execute {
+                contract_addr: TIME_ALARMS_ADDR.into(),
+                msg: to_json_binary(&AddAlarm {
+                    time: recheck_time,
+                    id: AlarmId::default(),
+                    payload: None,
+                })
+                .unwrap(),
+                funds: vec![],
+            });
+
+            batch.schedule_execute_no_reply(WasmMsg::Execute {
+                contract_addr: ORACLE_ADDR.into(),
+                msg: to_json_binary(&AddPriceAlarm {
+                    alarm: Alarm::new(below_alarm, None),
+                })
+                .unwrap(),
+                funds: vec![],
+            });
+
+            batch
+        });
+    }
+
+    fn price_of(lpns: u128) -> Price<TestCurrency, TestLpn> {
+        total_of(Coin::new(1)).is(Coin::new(lpns))
+    }
+
+    fn full_close_at(price: Price<TestCurrency, TestLpn>) -> CloseTrigger {
+        CloseTrigger {
+            price: price.into(),
+            close: PositionClose::FullClose(FullClose {}),
+        }
+    }
+
     fn timealarms() -> TimeAlarmsRef {
         TimeAlarmsRef::unchecked(TIME_ALARMS_ADDR)
     }
@@ -0,0 +1,104 @@
+use currency::Currency;
+use finance::percent::Percent;
+use lpp::stub::loan::LppLoan as LppLoanTrait;
+use oracle_platform::Oracle as OracleTrait;
+use sdk::cosmwasm_std::Timestamp;
+
+use crate::{
+    error::ContractResult,
+    finance::{LpnCoin, LpnCurrencies, LpnCurrency},
+    lease::Lease,
+    position::DueTrait,
+};
+
+impl<Asset, Lpp, Oracle> Lease<Asset, Lpp, Oracle>
+where
+    Lpp: LppLoanTrait<LpnCurrency, LpnCurrencies>,
+    Oracle: OracleTrait<LpnCurrency>,
+    Asset: Currency,
+{
+    /// Increase the loan with the given amount at the given annual interest rate
+    ///
+    /// The position is left intact until the borrowed amount gets swapped into
+    /// the lease asset. Refer to `Self::add_collateral`.
+    pub(crate) fn borrow(
+        &mut self,
+        amount: LpnCoin,
+        annual_interest_rate: Percent,
+        now: &Timestamp,
+    ) -> ContractResult<()> {
+        let total_due = self.loan.state(now).total_due();
+
+        self.price_of_lease_currency()
+            .and_then(|asset_in_lpns| {
+                self.position
+                    .validate_borrow_amount(total_due, amount, asset_in_lpns)
+            })
+            .map(|()| self.loan.borrow(amount, annual_interest_rate, now))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use finance::{duration::Duration, percent::Percent};
+
+    use crate::{
+        error::ContractError,
+        lease::{
+            tests::{self, LEASE_START},
+            State,
+        },
+    };
+
+    #[test]
+    fn borrow() {
+        let lease_amount = tests::coin(1_000_000);
+        let mut lease = tests::open_lease(lease_amount, tests::loan_of(tests::lpn_coin(100_000)));
+        let now = LEASE_START + Duration::from_days(1);
+        let principal_due_before = lease.state(now).principal_due;
+
+        let amount = tests::lpn_coin(100_000);
+        lease
+            .borrow(amount, Percent::from_percent(10), &now)
+            .expect("the amount should be borrowed");
+
+        let State {
+            amount: amount_after,
+            principal_due,
+            ..
+        } = lease.state(now);
+        assert_eq!(lease_amount, amount_after);
+        assert_eq!(principal_due_before + amount, principal_due);
+    }
+
+    #[test]
+    fn borrow_too_small() {
+        let mut lease = tests::open_lease(tests::coin(1_000_000), tests::loan());
+
+        let err = lease
+            .borrow(
+                tests::lpn_coin(9_999),
+                Percent::from_percent(10),
+                &LEASE_START,
+            )
+            .expect_err("the amount should be below the minimum transaction");
+        assert!(matches!(
+            err,
+            ContractError::InsufficientTransactionAmount(_)
+        ));
+    }
+
+    #[test]
+    fn borrow_too_much() {
+        let mut lease = tests::open_lease(tests::coin(1_000_000), tests::loan());
+
+        let err = lease
+            .borrow(
+                tests::lpn_coin(2_000_000),
+                Percent::from_percent(10),
+                &LEASE_START,
+            )
+            .expect_err("the amount should exceed the initial liability");
+        assert!(matches!(err, ContractError::BorrowAmountTooBig(_)));
+    }
+}
@@ -8,10 +8,12 @@ use reserve::stub::Reserve as ReserveTrait;
 use sdk::cosmwasm_std::Timestamp;
 
 use crate::{
+    api::position::{ClosePolicy as ClosePolicyDTO, CloseTrigger},
     error::{ContractError, ContractResult},
     finance::{LpnCoin, LpnCurrencies, LpnCurrency},
     lease::Lease,
     loan::RepayReceipt,
+    position::CloseStrategy,
 };
 
 pub(crate) struct FullRepayReceipt {
@@ -41,6 +43,21 @@ where
             .and_then(|asset_in_lpns| self.position.validate_close_amount(amount, asset_in_lpns))
     }
 
+    pub(crate) fn close_policy(&self) -> ClosePolicyDTO {
+        self.position.close_policy().clone().into()
+    }
+
+    pub(crate) fn change_close_policy(
+        &mut self,
+        strategy: CloseStrategy,
+        trigger: Option<CloseTrigger>,
+    ) -> ContractResult<()> {
+        self.price_of_lease_currency().and_then(|asset_in_lpns| {
+            self.position
+                .change_close_policy(strategy, trigger, asset_in_lpns)
+        })
+    }
+
     pub(crate) fn close_partial<Profit>(
         &mut self,
         asset: Coin<Asset>,
@@ -7,10 +7,10 @@ use sdk::cosmwasm_std::Timestamp;
 use timealarms::stub::TimeAlarmsRef;
 
 use crate::{
-    api::LeaseAssetCurrencies,
+    api::{position::PositionClose, LeaseAssetCurrencies},
     error::ContractResult,
     finance::{LpnCurrencies, LpnCurrency, Price},
-    position::{Debt, DueTrait, Liquidation},
+    position::{CloseStrategy, Debt, DueTrait, Liquidation},
 };
 
 use super::Lease;
@@ -29,26 +29,35 @@ where
     ) -> ContractResult<DebtStatus<Asset>> {
         let due = self.loan.state(now);
 
-        let debt = self
-            .price_of_lease_currency()
-            .map(|asset_in_lpns| self.position.debt(&due, asset_in_lpns))?;
-        Ok(match debt {
+        let asset_in_lpns = self.price_of_lease_currency()?;
+        Ok(match self.position.debt(&due, asset_in_lpns) {
             Debt::No => DebtStatus::NoDebt,
-            Debt::Ok { zone, recheck_in } => DebtStatus::NewAlarms {
-                alarms: self.reschedule(
-                    now,
-                    recheck_in,
-                    &zone,
-                    due.total_due(),
-                    time_alarms,
-                    price_alarms,
-                )?,
-                current_liability: zone,
-            },
+            Debt::Ok { .. } if self.expired(now) => DebtStatus::Expired,
+            Debt::Ok { zone, recheck_in } => {
+                match self.position.close_policy().may_fire(asset_in_lpns) {
+                    Some((strategy, close)) => DebtStatus::NeedClose { strategy, close },
+                    None => DebtStatus::NewAlarms {
+                        alarms: self.reschedule(
+                            now,
+                            recheck_in,
+                            &zone,
+                            due.total_due(),
+                            time_alarms,
+                            price_alarms,
+                        )?,
+                        current_liability: zone,
+                    },
+                }
+            }
             Debt::Bad(liquidation) => DebtStatus::NeedLiquidation(liquidation),
         })
     }
 
+    /// Determine whether the maximum duration of the lease, if any, has elapsed
+    pub(super) fn expired(&self, now: &Timestamp) -> bool {
+        self.expiry.is_some_and(|expiry| &expiry <= now)
+    }
+
     pub(super) fn price_of_lease_currency(&self) -> ContractResult<Price<Asset>> {
         self.oracle
             .price_of::<Asset, LeaseAssetCurrencies>()
@@ -66,4 +75,65 @@ where
         alarms: Batch,
     },
     NeedLiquidation(Liquidation<Asset>),
+    /// A take-profit or a stop-loss trigger has fired
+    NeedClose {
+        strategy: CloseStrategy,
+        close: PositionClose,
+    },
+    /// The maximum duration of the lease has elapsed
+    Expired,
+}
+
+#[cfg(test)]
+mod tests {
+    use finance::{coin::Coin, duration::Duration};
+    use oracle_platform::OracleRef;
+    use sdk::cosmwasm_std::Addr;
+    use timealarms::stub::TimeAlarmsRef;
+
+    use crate::lease::tests::{self, LEASE_START, RECHECK_TIME};
+
+    use super::DebtStatus;
+
+    #[test]
+    fn not_expired() {
+        let mut lease = tests::open_lease(
+            Coin::from(1_000_000),
+            tests::loan_of(tests::lpn_coin(100_000)),
+        );
+        let expiry = LEASE_START + RECHECK_TIME;
+        lease.expiry = Some(expiry);
+
+        let status = lease
+            .check_debt(
+                &(expiry - Duration::from_nanos(1)),
+                &timealarms(),
+                &pricealarms(),
+            )
+            .unwrap();
+        assert!(matches!(status, DebtStatus::NewAlarms { .. }));
+    }
+
+    #[test]
+    fn expired() {
+        let mut lease = tests::open_lease(
+            Coin::from(1_000_000),
+            tests::loan_of(tests::lpn_coin(100_000)),
+        );
+        let expiry = LEASE_START + RECHECK_TIME;
+        lease.expiry = Some(expiry);
+
+        let status = lease
+            .check_debt(&expiry, &timealarms(), &pricealarms())
+            .unwrap();
+        assert!(matches!(status, DebtStatus::Expired));
+    }
+
+    fn timealarms() -> TimeAlarmsRef {
+        TimeAlarmsRef::unchecked("timealarms")
+    }
+
+    fn pricealarms() -> OracleRef<tests::TestLpn> {
+        OracleRef::unchecked(Addr::unchecked("oracle"))
+    }
 }
@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use currency::Currency;
 use lpp::stub::loan::LppLoan as LppLoanTrait;
 use oracle_platform::Oracle as OracleTrait;
-use sdk::cosmwasm_std::{Addr, QuerierWrapper};
+use sdk::cosmwasm_std::{Addr, QuerierWrapper, Timestamp};
 use timealarms::stub::TimeAlarmsRef;
 
 use crate::{
@@ -29,6 +29,9 @@ pub struct LeaseDTO {
     pub(crate) time_alarms: TimeAlarmsRef,
     pub(crate) oracle: OracleRef,
     pub(crate) reserve: ReserveRef,
+    /// The time the lease position gets fully closed at, if any
+    #[serde(default)]
+    pub(crate) expiry: Option<Timestamp>,
 }
 
 impl LeaseDTO {
@@ -49,9 +52,14 @@ impl LeaseDTO {
             time_alarms,
             oracle,
             reserve,
+            expiry: None,
         }
     }
 
+    pub(crate) fn with_expiry(self, expiry: Option<Timestamp>) -> Self {
+        Self { expiry, ..self }
+    }
+
     pub(crate) fn execute<Cmd>(
         self,
         cmd: Cmd,
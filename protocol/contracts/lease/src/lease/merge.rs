@@ -0,0 +1,75 @@
+use currency::Currency;
+use finance::coin::Coin;
+use lpp::stub::loan::LppLoan as LppLoanTrait;
+use oracle_platform::Oracle as OracleTrait;
+use sdk::cosmwasm_std::Timestamp;
+
+use crate::{
+    api::MergedLoan,
+    error::ContractResult,
+    finance::{LpnCoin, LpnCurrencies, LpnCurrency},
+    lease::Lease,
+    position::DueTrait,
+};
+
+impl<Asset, Lpp, Oracle> Lease<Asset, Lpp, Oracle>
+where
+    Lpp: LppLoanTrait<LpnCurrency, LpnCurrencies>,
+    Oracle: OracleTrait<LpnCurrency>,
+    Asset: Currency,
+{
+    /// Check if this lease can be merged with another one of the same currency
+    ///
+    /// The merged position is priced at the current market price.
+    pub(crate) fn validate_merge(
+        &self,
+        other_asset: Coin<Asset>,
+        other_due: LpnCoin,
+        now: &Timestamp,
+    ) -> ContractResult<()> {
+        let total_due = self.loan.state(now).total_due() + other_due;
+
+        self.price_of_lease_currency().and_then(|asset_in_lpns| {
+            self.position
+                .validate_merge(other_asset, total_due, asset_in_lpns)
+        })
+    }
+
+    /// Check if this lease can take over the asset and the loan of a lease merged into it
+    ///
+    /// The Lpp loan should already include the merged principal. The merged position is
+    /// priced at the current market price.
+    pub(crate) fn validate_absorb(
+        mut self,
+        asset: Coin<Asset>,
+        loan: &MergedLoan,
+        now: &Timestamp,
+    ) -> ContractResult<()> {
+        self.loan.absorb(loan.clone(), now)?;
+        let total_due = self.loan.state(now).total_due();
+
+        self.price_of_lease_currency().and_then(|asset_in_lpns| {
+            self.position
+                .validate_merge(asset, total_due, asset_in_lpns)
+        })
+    }
+
+    /// The loan terms the lease this one gets merged into should take over
+    pub(crate) fn merged_loan(&self) -> MergedLoan {
+        self.loan.merged()
+    }
+
+    /// Take over the asset and the loan margin of a lease merged into this one
+    ///
+    /// The Lpp loan should already include the merged principal.
+    pub(crate) fn absorb(
+        &mut self,
+        asset: Coin<Asset>,
+        loan: MergedLoan,
+        now: &Timestamp,
+    ) -> ContractResult<()> {
+        self.loan
+            .absorb(loan, now)
+            .map(|()| self.position.add(asset))
+    }
+}
@@ -1,4 +1,5 @@
 use currency::Currency;
+use finance::coin::Coin;
 use lpp::stub::loan::LppLoan as LppLoanTrait;
 use oracle_platform::Oracle as OracleTrait;
 use platform::batch::Batch;
@@ -13,16 +14,26 @@ use crate::{
     position::Position,
 };
 
-pub(super) use self::{debt::DebtStatus, dto::LeaseDTO, paid::Lease as LeasePaid, state::State};
+pub(super) use self::{
+    debt::DebtStatus,
+    dto::LeaseDTO,
+    paid::Lease as LeasePaid,
+    state::State,
+    what_if::{LiquidationPrices, Outlook},
+};
 
 mod alarm;
+mod borrow;
 mod close;
 mod debt;
 mod dto;
 mod due;
+mod merge;
 mod paid;
+mod refinance;
 mod repay;
 mod state;
+mod what_if;
 pub(crate) mod with_lease;
 pub(crate) mod with_lease_deps;
 pub(crate) mod with_lease_paid;
@@ -37,6 +48,7 @@ pub struct Lease<Asset, Lpp, Oracle> {
     position: Position<Asset>,
     loan: Loan<Lpp>,
     oracle: Oracle,
+    expiry: Option<Timestamp>,
 }
 
 #[cfg_attr(test, derive(Debug))]
@@ -63,6 +75,7 @@ where
         position: Position<Asset>,
         loan: Loan<LppLoan>,
         oracle: Oracle,
+        expiry: Option<Timestamp>,
     ) -> Self {
         debug_assert!(!currency::equal::<LpnCurrency, Asset>());
         // TODO specify that Lpn is of Lpns and Asset is of LeaseGroup
@@ -73,6 +86,7 @@ where
             position,
             loan,
             oracle,
+            expiry,
         }
     }
 
@@ -84,9 +98,17 @@ where
             position,
             Loan::from_dto(dto.loan, lpp_loan),
             oracle,
+            dto.expiry,
         )
     }
 
+    /// Grow the position with an amount of asset provided by the customer
+    ///
+    /// The debt is left intact so the liability decreases.
+    pub(crate) fn add_collateral(&mut self, asset: Coin<Asset>) {
+        self.position.add(asset)
+    }
+
     pub(crate) fn state(&self, now: Timestamp) -> State<Asset> {
         let loan = self.loan.state(&now);
         let overdue_collect_in = self.position.overdue_collection_in(&loan);
@@ -102,6 +124,7 @@ where
             due_margin: loan.due_margin_interest,
             due_interest: loan.due_interest,
             validity: now,
+            expiry: self.expiry,
         }
     }
 }
@@ -130,7 +153,8 @@ where
                 time_alarms,
                 self.oracle.into(),
                 reserve,
-            ),
+            )
+            .with_expiry(self.expiry),
             batch: loan_batch,
         })
     }
@@ -195,6 +219,14 @@ mod tests {
         }
     }
 
+    /// A loan large enough for the interest to reach the minimum transaction amount in time
+    pub fn loan_of(principal_due: Coin<TestLpn>) -> LoanResponse<TestLpn> {
+        LoanResponse {
+            principal_due,
+            ..loan()
+        }
+    }
+
     // TODO migrate to using lpp::stub::unchecked_lpp_loan
     #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
     pub struct LppLoanLocal<Lpn>
@@ -215,7 +247,7 @@ mod tests {
 
     impl<Lpn> LppLoan<Lpn, LpnCurrencies> for LppLoanLocal<Lpn>
     where
-        Lpn: ?Sized,
+        Lpn: ?Sized + PartialEq,
     {
         fn principal_due(&self) -> Coin<Lpn> {
             self.loan.principal_due
@@ -229,6 +261,19 @@ mod tests {
             self.loan.repay(by, repayment)
         }
 
+        fn borrow(&mut self, by: &Timestamp, amount: Coin<Lpn>, annual_interest_rate: Percent) {
+            self.loan.borrow(by, amount, annual_interest_rate)
+        }
+
+        fn refinance(
+            &mut self,
+            by: &Timestamp,
+            annual_interest_rate: Percent,
+            _fee: Coin<Lpn>,
+        ) -> Coin<Lpn> {
+            self.loan.refinance(by, annual_interest_rate)
+        }
+
         fn annual_interest_rate(&self) -> Percent {
             self.loan.annual_interest_rate
         }
@@ -329,6 +374,7 @@ mod tests {
             Position::<TestCurrency>::new(amount, position_spec),
             loan,
             oracle,
+            None,
         )
     }
 
@@ -367,6 +413,7 @@ mod tests {
             due_margin: lpn_coin(0),
             due_interest: lpn_coin(0),
             validity: state_at,
+            expiry: None,
         };
 
         assert_eq!(exp, res);
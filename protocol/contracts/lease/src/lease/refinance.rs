@@ -0,0 +1,143 @@
+use currency::Currency;
+use finance::percent::Percent;
+use lpp::stub::loan::LppLoan as LppLoanTrait;
+use oracle_platform::Oracle as OracleTrait;
+use platform::bank::FixedAddressSender;
+use sdk::cosmwasm_std::Timestamp;
+
+use crate::{
+    error::ContractResult,
+    finance::{LpnCoin, LpnCurrencies, LpnCurrency},
+    lease::Lease,
+    loan::RefinanceReceipt,
+};
+
+impl<Asset, Lpp, Oracle> Lease<Asset, Lpp, Oracle>
+where
+    Lpp: LppLoanTrait<LpnCurrency, LpnCurrencies>,
+    Oracle: OracleTrait<LpnCurrency>,
+    Asset: Currency,
+{
+    /// Re-open the loan at the given annual interest rate
+    ///
+    /// The interests due and the refinance fee are paid from the payment. The position
+    /// and the loan principal are left intact.
+    pub(crate) fn refinance<Profit>(
+        &mut self,
+        payment: LpnCoin,
+        annual_interest_rate: Percent,
+        fee: Percent,
+        now: &Timestamp,
+        profit: &mut Profit,
+    ) -> ContractResult<RefinanceReceipt>
+    where
+        Profit: FixedAddressSender,
+    {
+        self.loan
+            .refinance(payment, annual_interest_rate, fee, now, profit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use finance::{coin::Coin, duration::Duration, percent::Percent, zero::Zero};
+    use platform::bank::FixedAddressSender;
+    use profit::stub::ProfitRef;
+
+    use crate::{
+        error::ContractError,
+        lease::{
+            tests::{self, LEASE_START},
+            State,
+        },
+    };
+
+    #[test]
+    fn refinance() {
+        let lease_amount = tests::coin(1_000_000);
+        let mut lease = tests::open_lease(lease_amount, tests::loan_of(tests::lpn_coin(100_000)));
+        let now = LEASE_START + Duration::YEAR;
+        let state_before = lease.state(now);
+
+        let payment = tests::lpn_coin(15_000);
+        let receipt = lease
+            .refinance(
+                payment,
+                Percent::from_percent(5),
+                Percent::from_percent(1),
+                &now,
+                &mut profit_stub(),
+            )
+            .expect("the loan should be refinanced");
+        assert_eq!(
+            state_before.overdue_interest + state_before.due_interest,
+            receipt.interest_paid()
+        );
+        assert_eq!(
+            state_before.overdue_margin + state_before.due_margin,
+            receipt.margin_paid()
+        );
+        assert_eq!(tests::lpn_coin(1_000), receipt.fee_paid());
+        assert_eq!(
+            payment,
+            receipt.interest_paid() + receipt.margin_paid() + receipt.fee_paid() + receipt.change()
+        );
+
+        let State {
+            amount,
+            interest_rate,
+            principal_due,
+            overdue_margin,
+            overdue_interest,
+            due_margin,
+            due_interest,
+            ..
+        } = lease.state(now);
+        assert_eq!(lease_amount, amount);
+        assert_eq!(Percent::from_percent(5), interest_rate);
+        assert_eq!(state_before.principal_due, principal_due);
+        assert_eq!(
+            Coin::ZERO,
+            overdue_margin + overdue_interest + due_margin + due_interest
+        );
+    }
+
+    #[test]
+    fn refinance_no_gain() {
+        let mut lease = tests::open_lease(tests::coin(1_000_000), tests::loan());
+
+        let err = lease
+            .refinance(
+                tests::lpn_coin(100),
+                Percent::from_percent(10),
+                Percent::ZERO,
+                &(LEASE_START + Duration::YEAR),
+                &mut profit_stub(),
+            )
+            .expect_err("the loan rate should be above the refinance one");
+        assert!(matches!(err, ContractError::NoRefinanceGain(_)));
+    }
+
+    #[test]
+    fn refinance_insufficient_payment() {
+        let mut lease = tests::open_lease(tests::coin(1_000_000), tests::loan());
+
+        let err = lease
+            .refinance(
+                tests::lpn_coin(10),
+                Percent::from_percent(5),
+                Percent::ZERO,
+                &(LEASE_START + Duration::YEAR),
+                &mut profit_stub(),
+            )
+            .expect_err("the payment should not cover the interests due");
+        assert!(matches!(
+            err,
+            ContractError::InsufficientRefinancePayment(_)
+        ));
+    }
+
+    fn profit_stub() -> impl FixedAddressSender {
+        ProfitRef::unchecked("profit").into_stub()
+    }
+}
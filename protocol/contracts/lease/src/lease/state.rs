@@ -18,6 +18,7 @@ where
     pub due_margin: LpnCoin,
     pub due_interest: LpnCoin,
     pub validity: Timestamp,
+    pub expiry: Option<Timestamp>,
 }
 
 impl<Asset> State<Asset> {
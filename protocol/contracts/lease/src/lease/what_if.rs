@@ -0,0 +1,226 @@
+use currency::Currency;
+use finance::{coin::Coin, percent::Percent, price};
+use lpp::stub::loan::LppLoan as LppLoanTrait;
+use oracle_platform::Oracle as OracleTrait;
+use platform::bank::FixedAddressSender;
+use sdk::cosmwasm_std::Timestamp;
+
+use crate::{
+    error::ContractResult,
+    finance::{LpnCoin, LpnCurrencies, LpnCurrency, Price},
+    position::{Debt, DueTrait},
+};
+
+use super::Lease;
+
+/// The lease asset prices at which a lease reaches its liability levels
+#[cfg_attr(test, derive(Debug, PartialEq, Eq))]
+pub(crate) struct LiquidationPrices<Asset> {
+    pub first_liq_warn: Price<Asset>,
+    pub second_liq_warn: Price<Asset>,
+    pub third_liq_warn: Price<Asset>,
+    pub liquidation: Price<Asset>,
+}
+
+/// The liability of a lease at the current lease asset price
+#[cfg_attr(test, derive(Debug, PartialEq, Eq))]
+pub(crate) struct Outlook<Asset> {
+    pub ltv: Percent,
+    pub debt: Debt<Asset>,
+    /// The price at which the lease would be subject to liquidation, if there is a debt
+    pub liquidation_price: Option<Price<Asset>>,
+}
+
+impl<Asset, Lpp, Oracle> Lease<Asset, Lpp, Oracle>
+where
+    Lpp: LppLoanTrait<LpnCurrency, LpnCurrencies>,
+    Oracle: OracleTrait<LpnCurrency>,
+    Asset: Currency,
+{
+    /// Calculate the lease asset prices at which the lease reaches the liability levels
+    ///
+    /// Return `None` if there is no debt.
+    pub(crate) fn liquidation_prices(
+        &self,
+        now: &Timestamp,
+    ) -> ContractResult<Option<LiquidationPrices<Asset>>> {
+        let total_due = self.loan.state(now).total_due();
+        if total_due.is_zero() {
+            Ok(None)
+        } else {
+            let [first, second, third, max] = self.position.levels();
+            Ok(Some(LiquidationPrices {
+                first_liq_warn: self.position.price_at(first, total_due)?,
+                second_liq_warn: self.position.price_at(second, total_due)?,
+                third_liq_warn: self.position.price_at(third, total_due)?,
+                liquidation: self.position.price_at(max, total_due)?,
+            }))
+        }
+    }
+
+    /// Simulate a repayment and calculate the resulting outlook
+    ///
+    /// The lease is consumed since the changes are not meant to be persisted.
+    pub(crate) fn what_if_repay<Profit>(
+        mut self,
+        payment: LpnCoin,
+        now: &Timestamp,
+        profit: &mut Profit,
+    ) -> ContractResult<Outlook<Asset>>
+    where
+        Profit: FixedAddressSender,
+    {
+        self.repay(payment, now, profit)
+            .and_then(|_receipt| self.outlook(now))
+    }
+
+    /// Simulate a partial close of the position and calculate the resulting outlook
+    ///
+    /// The amount is assumed to be swapped at the current oracle price.
+    /// The lease is consumed since the changes are not meant to be persisted.
+    pub(crate) fn what_if_close<Profit>(
+        mut self,
+        amount: Coin<Asset>,
+        now: &Timestamp,
+        profit: &mut Profit,
+    ) -> ContractResult<Outlook<Asset>>
+    where
+        Profit: FixedAddressSender,
+    {
+        self.validate_close(amount)
+            .and_then(|()| self.price_of_lease_currency())
+            .and_then(|asset_in_lpns| {
+                self.close_partial(amount, price::total(amount, asset_in_lpns), now, profit)
+            })
+            .and_then(|_receipt| self.outlook(now))
+    }
+
+    fn outlook(&self, now: &Timestamp) -> ContractResult<Outlook<Asset>> {
+        let due = self.loan.state(now);
+        let total_due = due.total_due();
+
+        self.price_of_lease_currency().and_then(|asset_in_lpns| {
+            let [.., max] = self.position.levels();
+            let liquidation_price = if total_due.is_zero() {
+                None
+            } else {
+                Some(self.position.price_at(max, total_due)?)
+            };
+
+            Ok(Outlook {
+                ltv: Percent::from_ratio(
+                    price::total(total_due, asset_in_lpns.inv()),
+                    self.position.amount(),
+                ),
+                debt: self.position.debt(&due, asset_in_lpns),
+                liquidation_price,
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use finance::{coin::Coin, fraction::Fraction, percent::Percent, price::total_of};
+    use lpp::msg::LoanResponse;
+    use platform::bank::FixedAddressSender;
+    use profit::stub::ProfitRef;
+    use sdk::cosmwasm_std::Addr;
+
+    use crate::{
+        lease::tests::{
+            self, TestLpn, FIRST_LIQ_WARN, LEASE_START, SECOND_LIQ_WARN, THIRD_LIQ_WARN,
+        },
+        position::Debt,
+    };
+
+    use super::{LiquidationPrices, Outlook};
+
+    #[test]
+    fn liquidation_prices() {
+        let lease_amount = tests::coin(1000);
+        let principal_due = tests::lpn_coin(600);
+        let lease = tests::open_lease(lease_amount, loan(principal_due));
+
+        let price_at = |ltv: Percent| total_of(ltv.of(lease_amount)).is(principal_due);
+        assert_eq!(
+            Some(LiquidationPrices {
+                first_liq_warn: price_at(FIRST_LIQ_WARN),
+                second_liq_warn: price_at(SECOND_LIQ_WARN),
+                third_liq_warn: price_at(THIRD_LIQ_WARN),
+                liquidation: price_at(Percent::from_percent(80)),
+            }),
+            lease.liquidation_prices(&LEASE_START).unwrap()
+        );
+    }
+
+    #[test]
+    fn liquidation_prices_no_debt() {
+        let lease = tests::open_lease(tests::coin(1000), loan(Coin::new(0)));
+
+        assert_eq!(None, lease.liquidation_prices(&LEASE_START).unwrap());
+    }
+
+    #[test]
+    fn what_if_repay() {
+        let lease_amount = tests::coin(1000);
+        let lease = tests::open_lease(lease_amount, loan(tests::lpn_coin(740)));
+
+        let outlook = lease
+            .what_if_repay(tests::lpn_coin(100), &LEASE_START, &mut profit())
+            .unwrap();
+        assert_eq!(Percent::from_percent(64), outlook.ltv);
+        assert!(matches!(outlook.debt, Debt::Ok { .. }));
+        assert_eq!(
+            Some(total_of(Percent::from_percent(80).of(lease_amount)).is(tests::lpn_coin(640))),
+            outlook.liquidation_price
+        );
+    }
+
+    #[test]
+    fn what_if_repay_all() {
+        let lease = tests::open_lease(tests::coin(1000), loan(tests::lpn_coin(740)));
+
+        assert_eq!(
+            Outlook {
+                ltv: Percent::ZERO,
+                debt: Debt::No,
+                liquidation_price: None,
+            },
+            lease
+                .what_if_repay(tests::lpn_coin(1000), &LEASE_START, &mut profit())
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn what_if_close() {
+        let lease_amount = tests::coin(100_000_000);
+        let lease = tests::open_lease(lease_amount, loan(tests::lpn_coin(70_000_000)));
+
+        let outlook = lease
+            .what_if_close(tests::coin(20_000_000), &LEASE_START, &mut profit())
+            .unwrap();
+        assert_eq!(Percent::from_permille(625), outlook.ltv);
+        assert!(matches!(outlook.debt, Debt::Ok { .. }));
+        assert_eq!(
+            Some(
+                total_of(Percent::from_percent(80).of(tests::coin(80_000_000)))
+                    .is(tests::lpn_coin(50_000_000))
+            ),
+            outlook.liquidation_price
+        );
+    }
+
+    fn loan(principal_due: Coin<TestLpn>) -> LoanResponse<TestLpn> {
+        LoanResponse {
+            principal_due,
+            annual_interest_rate: Percent::from_percent(10),
+            interest_paid: LEASE_START,
+        }
+    }
+
+    fn profit() -> impl FixedAddressSender {
+        ProfitRef::unchecked(Addr::unchecked("profit")).into_stub()
+    }
+}
@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
 
 use finance::{
-    coin::Coin, duration::Duration, interest, percent::Percent, period::Period, zero::Zero,
+    coin::Coin, duration::Duration, fraction::Fraction, interest, percent::Percent, period::Period,
+    zero::Zero,
 };
 use lpp::{
     loan::RepayShares,
@@ -12,13 +13,15 @@ use profit::stub::ProfitRef;
 use sdk::cosmwasm_std::Timestamp;
 
 use crate::{
+    api::MergedLoan,
     error::{ContractError, ContractResult},
     finance::{LpnCoin, LpnCurrencies, LpnCurrency},
 };
 
-pub(crate) use self::repay::Receipt as RepayReceipt;
 pub use self::state::{Overdue, State};
+pub(crate) use self::{refinance::Receipt as RefinanceReceipt, repay::Receipt as RepayReceipt};
 
+mod refinance;
 mod repay;
 mod state;
 
@@ -169,6 +172,126 @@ where
         Ok(receipt)
     }
 
+    /// Borrow an additional amount at the given annual interest rate by the given timestamp.
+    ///
+    /// The due interests are preserved. The periods they cover shrink accordingly
+    /// since the principal increases.
+    pub(crate) fn borrow(
+        &mut self,
+        amount: LpnCoin,
+        annual_interest_rate: Percent,
+        by: &Timestamp,
+    ) {
+        self.debug_check_start_due_before(by, "before the 'borrow-by' time");
+
+        let due_period_margin = Duration::between(&self.margin_paid_by, by);
+        let principal_due = self.lpp_loan.principal_due();
+        let due_margin_interest =
+            interest::interest(self.margin_interest, principal_due, due_period_margin);
+        let (due_margin_for, _change) = interest::pay(
+            self.margin_interest,
+            principal_due + amount,
+            due_margin_interest,
+            due_period_margin,
+        );
+        self.margin_paid_by = by - due_margin_for;
+
+        self.lpp_loan.borrow(by, amount, annual_interest_rate);
+    }
+
+    /// Take over the margin of a loan merged into this one by the given timestamp.
+    ///
+    /// The Lpp loan should already include the merged principal. The due margin interests
+    /// of both loans are preserved. The margin interest rate becomes the average of both,
+    /// weighted by the principal.
+    pub(crate) fn absorb(&mut self, merged: MergedLoan, by: &Timestamp) -> ContractResult<()> {
+        self.debug_check_start_due_before(by, "before the 'absorb-by' time");
+
+        let merged_principal: LpnCoin = merged.principal_due.try_into()?;
+        let principal_due = self.lpp_loan.principal_due();
+        debug_assert!(merged_principal <= principal_due);
+        let own_principal = principal_due - merged_principal;
+
+        let due_margin_interest = interest::interest(
+            self.margin_interest,
+            own_principal,
+            Duration::between(&self.margin_paid_by, by),
+        ) + interest::interest(
+            merged.margin_interest,
+            merged_principal,
+            Duration::between(&merged.margin_paid_by, by),
+        );
+        let annual_margin =
+            self.margin_interest.of(own_principal) + merged.margin_interest.of(merged_principal);
+        self.margin_interest = Percent::from_ratio(annual_margin, principal_due);
+
+        let due_period_margin =
+            Duration::between(&self.margin_paid_by.min(merged.margin_paid_by), by);
+        let (due_margin_for, _change) = interest::pay(
+            self.margin_interest,
+            principal_due,
+            due_margin_interest,
+            due_period_margin,
+        );
+        self.margin_paid_by = by - due_margin_for;
+        Ok(())
+    }
+
+    /// The terms another loan should take over when this one gets merged into it
+    pub(crate) fn merged(&self) -> MergedLoan {
+        MergedLoan {
+            principal_due: self.lpp_loan.principal_due().into(),
+            margin_interest: self.margin_interest,
+            margin_paid_by: self.margin_paid_by,
+        }
+    }
+
+    /// Re-open the loan at the given annual interest rate by the given timestamp.
+    ///
+    /// The loan and margin interests due are paid along with a fee charged as a percentage
+    /// of the principal. Both due periods restart at the given timestamp. The rest of
+    /// the payment is returned as a change.
+    pub(crate) fn refinance<Profit>(
+        &mut self,
+        payment: LpnCoin,
+        annual_interest_rate: Percent,
+        fee: Percent,
+        by: &Timestamp,
+        profit: &mut Profit,
+    ) -> ContractResult<RefinanceReceipt>
+    where
+        Profit: FixedAddressSender,
+    {
+        self.debug_check_start_due_before(by, "before the 'refinance-by' time");
+
+        let state = self.state(by);
+        if annual_interest_rate >= state.annual_interest {
+            return Err(ContractError::NoRefinanceGain(annual_interest_rate));
+        }
+
+        let interest = state.overdue.interest() + state.due_interest;
+        let margin = state.overdue.margin() + state.due_margin_interest;
+        let fee = fee.of(state.principal_due);
+        let payment_due = interest + margin + fee;
+        if payment < payment_due {
+            return Err(ContractError::InsufficientRefinancePayment(
+                payment_due.into(),
+            ));
+        }
+
+        self.margin_paid_by = *by;
+        profit.send(margin);
+        let interest_paid = self.lpp_loan.refinance(by, annual_interest_rate, fee);
+        debug_assert_eq!(interest, interest_paid);
+
+        Ok(RefinanceReceipt::new(
+            interest,
+            margin,
+            fee,
+            payment - payment_due,
+        ))
+    }
+
     pub(crate) fn state(&self, now: &Timestamp) -> State {
         self.debug_check_start_due_before(now, "in the past. Now is ");
 
@@ -1074,6 +1197,115 @@ mod tests {
     }
 
     #[cfg(test)]
+    mod test_borrow {
+        use finance::{duration::Duration, percent::Percent};
+        use lpp::msg::LoanResponse;
+
+        use crate::loan::tests::create_loan_custom;
+
+        use super::{LEASE_START, MARGIN_INTEREST_RATE};
+
+        #[test]
+        fn borrow_no_due() {
+            let principal_due = 10000.into();
+            let annual_interest = Percent::from_permille(100);
+            let mut loan = create_loan_custom(
+                MARGIN_INTEREST_RATE,
+                LoanResponse {
+                    principal_due,
+                    annual_interest_rate: annual_interest,
+                    interest_paid: LEASE_START,
+                },
+                LEASE_START,
+                Duration::YEAR,
+            );
+
+            loan.borrow(principal_due, Percent::from_permille(200), &LEASE_START);
+
+            let state = loan.state(&LEASE_START);
+            assert_eq!(principal_due + principal_due, state.principal_due);
+            assert_eq!(Percent::from_permille(150), state.annual_interest);
+            assert_eq!(MARGIN_INTEREST_RATE, state.annual_interest_margin);
+            assert!(state.due_interest.is_zero());
+            assert!(state.due_margin_interest.is_zero());
+        }
+
+        #[test]
+        fn borrow_due_preserved() {
+            let principal_due = 10000.into();
+            let annual_interest = Percent::from_permille(100);
+            let mut loan = create_loan_custom(
+                MARGIN_INTEREST_RATE,
+                LoanResponse {
+                    principal_due,
+                    annual_interest_rate: annual_interest,
+                    interest_paid: LEASE_START,
+                },
+                LEASE_START,
+                Duration::YEAR,
+            );
+
+            let now = LEASE_START + Duration::from_nanos(Duration::YEAR.nanos() / 2);
+            let state_before = loan.state(&now);
+            loan.borrow(principal_due, annual_interest, &now);
+
+            let state_after = loan.state(&now);
+            assert_eq!(principal_due + principal_due, state_after.principal_due);
+            assert_eq!(annual_interest, state_after.annual_interest);
+            assert_eq!(state_before.due_interest, state_after.due_interest);
+            assert_eq!(
+                state_before.due_margin_interest,
+                state_after.due_margin_interest
+            );
+        }
+    }
+
+    mod test_absorb {
+        use finance::{duration::Duration, percent::Percent};
+        use lpp::msg::LoanResponse;
+
+        use crate::{api::MergedLoan, finance::LpnCoin, loan::tests::create_loan_custom};
+
+        use super::LEASE_START;
+
+        #[test]
+        fn absorb_due_preserved() {
+            let own_principal: LpnCoin = 10000.into();
+            let merged_principal: LpnCoin = 30000.into();
+            let annual_interest = Percent::from_permille(100);
+            let mut loan = create_loan_custom(
+                Percent::from_permille(20),
+                LoanResponse {
+                    principal_due: own_principal + merged_principal,
+                    annual_interest_rate: annual_interest,
+                    interest_paid: LEASE_START,
+                },
+                LEASE_START,
+                Duration::YEAR,
+            );
+
+            let now = LEASE_START + Duration::YEAR;
+            loan.absorb(
+                MergedLoan {
+                    principal_due: merged_principal.into(),
+                    margin_interest: Percent::from_permille(60),
+                    margin_paid_by: LEASE_START,
+                },
+                &now,
+            )
+            .unwrap();
+
+            let state = loan.state(&now);
+            assert_eq!(own_principal + merged_principal, state.principal_due);
+            assert_eq!(Percent::from_permille(50), state.annual_interest_margin);
+            // 2% of 10000 plus 6% of 30000 for a year
+            assert_eq!(
+                LpnCoin::from(2000),
+                state.due_margin_interest + state.overdue.margin()
+            );
+        }
+    }
+
     mod test_state {
         use finance::{duration::Duration, interest, percent::Percent, period::Period};
         use lpp::{msg::LoanResponse, stub::loan::LppLoan};
@@ -1201,6 +1433,19 @@ mod tests {
             self.loan.repay(by, repayment)
         }
 
+        fn borrow(&mut self, by: &Timestamp, amount: LpnCoin, annual_interest_rate: Percent) {
+            self.loan.borrow(by, amount, annual_interest_rate)
+        }
+
+        fn refinance(
+            &mut self,
+            by: &Timestamp,
+            annual_interest_rate: Percent,
+            _fee: LpnCoin,
+        ) -> LpnCoin {
+            self.loan.refinance(by, annual_interest_rate)
+        }
+
         fn annual_interest_rate(&self) -> Percent {
             self.loan.annual_interest_rate
         }
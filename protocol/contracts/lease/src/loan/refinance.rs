@@ -0,0 +1,36 @@
+use crate::finance::LpnCoin;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct Receipt {
+    interest_paid: LpnCoin,
+    margin_paid: LpnCoin,
+    fee_paid: LpnCoin,
+    change: LpnCoin,
+}
+
+impl Receipt {
+    pub fn new(interest: LpnCoin, margin: LpnCoin, fee: LpnCoin, change: LpnCoin) -> Self {
+        Self {
+            interest_paid: interest,
+            margin_paid: margin,
+            fee_paid: fee,
+            change,
+        }
+    }
+
+    pub fn interest_paid(&self) -> LpnCoin {
+        self.interest_paid
+    }
+
+    pub fn margin_paid(&self) -> LpnCoin {
+        self.margin_paid
+    }
+
+    pub fn fee_paid(&self) -> LpnCoin {
+        self.fee_paid
+    }
+
+    pub fn change(&self) -> LpnCoin {
+        self.change
+    }
+}
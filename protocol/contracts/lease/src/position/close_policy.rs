@@ -0,0 +1,287 @@
+use currency::Currency;
+
+use crate::{
+    api::position::{ClosePolicy as ClosePolicyDTO, CloseTrigger, PositionClose},
+    error::{ContractError, ContractResult},
+    finance::Price,
+};
+
+/// The kind of a customer defined position close trigger
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(test, derive(Debug))]
+pub enum Strategy {
+    TakeProfit,
+    StopLoss,
+}
+
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug))]
+struct Trigger<Asset> {
+    price: Price<Asset>,
+    close: PositionClose,
+}
+
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug))]
+pub struct ClosePolicy<Asset> {
+    take_profit: Option<Trigger<Asset>>,
+    stop_loss: Option<Trigger<Asset>>,
+}
+
+impl<Asset> Default for ClosePolicy<Asset> {
+    fn default() -> Self {
+        Self {
+            take_profit: None,
+            stop_loss: None,
+        }
+    }
+}
+
+impl<Asset> ClosePolicy<Asset>
+where
+    Asset: Currency,
+{
+    /// Set, or reset if `None`, the trigger of the given strategy
+    ///
+    /// The new trigger should not fire at the current market price.
+    pub fn change(
+        &mut self,
+        strategy: Strategy,
+        trigger: Option<CloseTrigger>,
+        asset_in_lpns: Price<Asset>,
+    ) -> ContractResult<()> {
+        trigger
+            .map(Trigger::try_from)
+            .transpose()
+            .and_then(|may_trigger| {
+                may_trigger
+                    .as_ref()
+                    .map_or(Ok(()), |trigger| {
+                        Self::check_not_firing(strategy, &trigger.price, &asset_in_lpns)
+                    })
+                    .map(|()| *self.trigger_mut(strategy) = may_trigger)
+            })
+    }
+
+    /// Determine the trigger, if any, that fires at the given price
+    ///
+    /// The stop-loss takes precedence.
+    pub fn may_fire(&self, asset_in_lpns: Price<Asset>) -> Option<(Strategy, PositionClose)> {
+        [Strategy::StopLoss, Strategy::TakeProfit]
+            .into_iter()
+            .find_map(|strategy| {
+                self.trigger(strategy)
+                    .filter(|trigger| Self::fires(strategy, &trigger.price, &asset_in_lpns))
+                    .map(|trigger| (strategy, trigger.close.clone()))
+            })
+    }
+
+    /// The price below which the stop-loss fires
+    pub fn may_below(&self) -> Option<Price<Asset>> {
+        self.stop_loss.as_ref().map(|trigger| trigger.price)
+    }
+
+    /// The price at or above which the take-profit fires
+    pub fn may_above_or_equal(&self) -> Option<Price<Asset>> {
+        self.take_profit.as_ref().map(|trigger| trigger.price)
+    }
+
+    fn check_not_firing(
+        strategy: Strategy,
+        trigger_price: &Price<Asset>,
+        asset_in_lpns: &Price<Asset>,
+    ) -> ContractResult<()> {
+        if Self::fires(strategy, trigger_price, asset_in_lpns) {
+            Err(match strategy {
+                Strategy::TakeProfit => ContractError::TakeProfitTooLow(),
+                Strategy::StopLoss => ContractError::StopLossTooHigh(),
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    fn fires(
+        strategy: Strategy,
+        trigger_price: &Price<Asset>,
+        asset_in_lpns: &Price<Asset>,
+    ) -> bool {
+        match strategy {
+            Strategy::TakeProfit => asset_in_lpns >= trigger_price,
+            Strategy::StopLoss => asset_in_lpns < trigger_price,
+        }
+    }
+
+    fn trigger(&self, strategy: Strategy) -> Option<&Trigger<Asset>> {
+        match strategy {
+            Strategy::TakeProfit => self.take_profit.as_ref(),
+            Strategy::StopLoss => self.stop_loss.as_ref(),
+        }
+    }
+
+    fn trigger_mut(&mut self, strategy: Strategy) -> &mut Option<Trigger<Asset>> {
+        match strategy {
+            Strategy::TakeProfit => &mut self.take_profit,
+            Strategy::StopLoss => &mut self.stop_loss,
+        }
+    }
+}
+
+impl<Asset> TryFrom<CloseTrigger> for Trigger<Asset>
+where
+    Asset: Currency,
+{
+    type Error = ContractError;
+
+    fn try_from(dto: CloseTrigger) -> ContractResult<Self> {
+        dto.price.try_into().map_err(Into::into).map(|price| Self {
+            price,
+            close: dto.close,
+        })
+    }
+}
+
+impl<Asset> From<Trigger<Asset>> for CloseTrigger
+where
+    Asset: Currency,
+{
+    fn from(value: Trigger<Asset>) -> Self {
+        Self {
+            price: value.price.into(),
+            close: value.close,
+        }
+    }
+}
+
+impl<Asset> TryFrom<ClosePolicyDTO> for ClosePolicy<Asset>
+where
+    Asset: Currency,
+{
+    type Error = ContractError;
+
+    fn try_from(dto: ClosePolicyDTO) -> ContractResult<Self> {
+        dto.take_profit
+            .map(TryInto::try_into)
+            .transpose()
+            .and_then(|take_profit| {
+                dto.stop_loss
+                    .map(TryInto::try_into)
+                    .transpose()
+                    .map(|stop_loss| Self {
+                        take_profit,
+                        stop_loss,
+                    })
+            })
+    }
+}
+
+impl<Asset> From<ClosePolicy<Asset>> for ClosePolicyDTO
+where
+    Asset: Currency,
+{
+    fn from(value: ClosePolicy<Asset>) -> Self {
+        Self {
+            take_profit: value.take_profit.map(Into::into),
+            stop_loss: value.stop_loss.map(Into::into),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use currencies::test::PaymentC7;
+    use finance::{
+        coin::Coin,
+        price::{self, Price},
+    };
+
+    use crate::{
+        api::position::{CloseTrigger, FullClose, PartialClose, PositionClose},
+        error::ContractError,
+        finance::LpnCurrency,
+    };
+
+    use super::{ClosePolicy, Strategy};
+
+    type TestAsset = PaymentC7;
+
+    #[test]
+    fn no_triggers() {
+        let policy = ClosePolicy::<TestAsset>::default();
+        assert_eq!(None, policy.may_fire(price(1, 2)));
+        assert_eq!(None, policy.may_below());
+        assert_eq!(None, policy.may_above_or_equal());
+    }
+
+    #[test]
+    fn take_profit() {
+        let mut policy = ClosePolicy::<TestAsset>::default();
+        assert_eq!(
+            Err(ContractError::TakeProfitTooLow()),
+            policy.change(Strategy::TakeProfit, Some(full_close(2, 3)), price(2, 3))
+        );
+        assert_eq!(
+            Ok(()),
+            policy.change(Strategy::TakeProfit, Some(partial_close(2, 3)), price(1, 1))
+        );
+        assert_eq!(Some(price(2, 3)), policy.may_above_or_equal());
+        assert_eq!(None, policy.may_below());
+
+        assert_eq!(None, policy.may_fire(price(5, 7)));
+        assert_eq!(
+            Some((Strategy::TakeProfit, partial_close(2, 3).close)),
+            policy.may_fire(price(2, 3))
+        );
+
+        assert_eq!(
+            Ok(()),
+            policy.change(Strategy::TakeProfit, None, price(2, 3))
+        );
+        assert_eq!(None, policy.may_above_or_equal());
+        assert_eq!(None, policy.may_fire(price(2, 3)));
+    }
+
+    #[test]
+    fn stop_loss() {
+        let mut policy = ClosePolicy::<TestAsset>::default();
+        assert_eq!(
+            Err(ContractError::StopLossTooHigh()),
+            policy.change(Strategy::StopLoss, Some(full_close(1, 2)), price(2, 3))
+        );
+        assert_eq!(
+            Ok(()),
+            policy.change(Strategy::StopLoss, Some(full_close(1, 2)), price(1, 2))
+        );
+        assert_eq!(Some(price(1, 2)), policy.may_below());
+        assert_eq!(None, policy.may_above_or_equal());
+
+        assert_eq!(None, policy.may_fire(price(1, 2)));
+        assert_eq!(
+            Some((Strategy::StopLoss, full_close(1, 2).close)),
+            policy.may_fire(price(2, 3))
+        );
+
+        assert_eq!(Ok(()), policy.change(Strategy::StopLoss, None, price(1, 2)));
+        assert_eq!(None, policy.may_below());
+    }
+
+    fn price(amount: u128, amount_quote: u128) -> Price<TestAsset, LpnCurrency> {
+        price::total_of(Coin::new(amount)).is(Coin::new(amount_quote))
+    }
+
+    fn full_close(amount: u128, amount_quote: u128) -> CloseTrigger {
+        CloseTrigger {
+            price: price(amount, amount_quote).into(),
+            close: PositionClose::FullClose(FullClose {}),
+        }
+    }
+
+    fn partial_close(amount: u128, amount_quote: u128) -> CloseTrigger {
+        CloseTrigger {
+            price: price(amount, amount_quote).into(),
+            close: PositionClose::PartialClose(PartialClose {
+                amount: Coin::<TestAsset>::new(100).into(),
+            }),
+        }
+    }
+}
@@ -2,11 +2,11 @@ use currency::Currency;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    api::{open::PositionSpecDTO, LeaseCoin},
+    api::{open::PositionSpecDTO, position::ClosePolicy as ClosePolicyDTO, LeaseCoin},
     error::{ContractError, ContractResult},
 };
 
-use super::{Position, Spec};
+use super::{ClosePolicy, CloseStrategy, Position, Spec};
 
 #[derive(Serialize, Deserialize, Clone)]
 #[cfg_attr(test, derive(Debug))]
@@ -14,12 +14,28 @@ use super::{Position, Spec};
 pub struct PositionDTO {
     amount: LeaseCoin,
     spec: PositionSpecDTO,
+    // TODO remove the default once all leases have been migrated
+    #[serde(default)]
+    close_policy: ClosePolicyDTO,
 }
 
 impl PositionDTO {
     pub fn amount(&self) -> &LeaseCoin {
         &self.amount
     }
+
+    /// Replace the position with `amount` of another lease currency
+    pub fn rotate(&mut self, amount: LeaseCoin) {
+        debug_assert_ne!(self.amount.ticker(), amount.ticker());
+        self.amount = amount;
+    }
+
+    pub fn reset_close_policy(&mut self, strategy: CloseStrategy) {
+        match strategy {
+            CloseStrategy::TakeProfit => self.close_policy.take_profit = None,
+            CloseStrategy::StopLoss => self.close_policy.stop_loss = None,
+        }
+    }
 }
 
 impl<Asset> TryFrom<PositionDTO> for Position<Asset>
@@ -29,7 +45,11 @@ where
     type Error = ContractError;
 
     fn try_from(dto: PositionDTO) -> ContractResult<Self> {
-        Spec::try_from(dto.spec).and_then(|spec| Self::try_from(dto.amount, spec))
+        Spec::try_from(dto.spec).and_then(|spec| {
+            ClosePolicy::try_from(dto.close_policy).and_then(|close_policy| {
+                Self::try_with_close_policy(dto.amount, spec, close_policy)
+            })
+        })
     }
 }
 
@@ -41,6 +61,7 @@ where
         Self {
             amount: value.amount.into(),
             spec: value.spec.into(),
+            close_policy: value.close_policy.into(),
         }
     }
 }
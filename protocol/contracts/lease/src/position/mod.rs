@@ -4,16 +4,21 @@ use finance::{
 };
 
 use crate::{
-    api::LeaseCoin,
+    api::{
+        position::{CloseTrigger, PositionClose},
+        LeaseCoin,
+    },
     error::{ContractError, ContractResult},
     finance::{LpnCoin, Price},
 };
 
+pub use close_policy::{ClosePolicy, Strategy as CloseStrategy};
 pub use dto::PositionDTO;
 pub use interest::{Due as DueTrait, OverdueCollection};
 pub use spec::Spec;
 pub use status::{Cause, Debt, Liquidation};
 
+mod close_policy;
 mod dto;
 mod interest;
 mod spec;
@@ -23,34 +28,84 @@ mod status;
 pub struct Position<Asset> {
     amount: Coin<Asset>,
     spec: Spec,
+    close_policy: ClosePolicy<Asset>,
 }
 
 impl<Asset> Position<Asset>
 where
     Asset: Currency,
 {
-    fn new_internal(amount: Coin<Asset>, spec: Spec) -> Self {
-        let obj = Self { amount, spec };
+    fn new_internal(amount: Coin<Asset>, spec: Spec, close_policy: ClosePolicy<Asset>) -> Self {
+        let obj = Self {
+            amount,
+            spec,
+            close_policy,
+        };
         debug_assert_eq!(Ok(()), obj.invariant_held());
         obj
     }
 
     pub fn try_from(amount: LeaseCoin, spec: Spec) -> ContractResult<Self> {
+        Self::try_with_close_policy(amount, spec, ClosePolicy::default())
+    }
+
+    fn try_with_close_policy(
+        amount: LeaseCoin,
+        spec: Spec,
+        close_policy: ClosePolicy<Asset>,
+    ) -> ContractResult<Self> {
         amount
             .try_into()
             .map_err(Into::into)
-            .map(|amount| Self::new_internal(amount, spec))
+            .map(|amount| Self::new_internal(amount, spec, close_policy))
     }
 
     #[cfg(test)]
     pub fn new(amount: Coin<Asset>, spec: Spec) -> Self {
-        Self::new_internal(amount, spec)
+        Self::new_internal(amount, spec, ClosePolicy::default())
     }
 
     pub(crate) fn amount(&self) -> Coin<Asset> {
         self.amount
     }
 
+    pub(crate) fn close_policy(&self) -> &ClosePolicy<Asset> {
+        &self.close_policy
+    }
+
+    /// Set, or reset if `None`, a take-profit or a stop-loss trigger
+    ///
+    /// The trigger should not fire at the current market price and a partial close
+    /// amount should be valid at that price. Refer to `Self::validate_close_amount`.
+    pub fn change_close_policy(
+        &mut self,
+        strategy: CloseStrategy,
+        trigger: Option<CloseTrigger>,
+        asset_in_lpns: Price<Asset>,
+    ) -> ContractResult<()> {
+        trigger
+            .as_ref()
+            .and_then(|trigger| match &trigger.close {
+                PositionClose::PartialClose(spec) => Some(&spec.amount),
+                PositionClose::FullClose(_) => None,
+            })
+            .map_or(Ok(()), |close_amount| {
+                close_amount
+                    .try_into()
+                    .map_err(Into::into)
+                    .and_then(|close_amount| {
+                        self.validate_close_amount(close_amount, asset_in_lpns)
+                    })
+            })
+            .and_then(|()| self.close_policy.change(strategy, trigger, asset_in_lpns))
+    }
+
+    pub fn add(&mut self, asset: Coin<Asset>) {
+        debug_assert!(!asset.is_zero(), "The added asset should be positive");
+
+        self.amount += asset
+    }
+
     pub fn close(&mut self, asset: Coin<Asset>) {
         debug_assert!(
             asset < self.amount,
@@ -110,6 +165,39 @@ where
             .validate_close_amount(self.amount, close_amount, asset_in_lpns)
     }
 
+    /// Check if the amount can be borrowed against the position.
+    /// Return `error::ContractError::InsufficientTransactionAmount` when the borrow amount
+    /// is less than the minimum transaction amount.
+    ///
+    /// Return `error::ContractError::BorrowAmountTooBig` when the borrow amount would bring
+    /// the position liability above the initial one.
+    pub fn validate_borrow_amount(
+        &self,
+        total_due: LpnCoin,
+        borrow: LpnCoin,
+        asset_in_lpns: Price<Asset>,
+    ) -> ContractResult<()> {
+        self.spec
+            .validate_borrow_amount(self.amount, total_due, borrow, asset_in_lpns)
+    }
+
+    /// Check if this position, merged with `other`, is healthy enough
+    /// to bear the total due of both.
+    pub fn validate_merge(
+        &self,
+        other: Coin<Asset>,
+        total_due: LpnCoin,
+        asset_in_lpns: Price<Asset>,
+    ) -> ContractResult<()> {
+        self.spec
+            .validate_merge(self.amount + other, total_due, asset_in_lpns)
+    }
+
+    /// The liability levels, from the first warning up to the liquidation one
+    pub(crate) fn levels(&self) -> [Level; 4] {
+        self.spec.levels()
+    }
+
     /// Calculate the price at which the lease reaches given ltv.
     pub(crate) fn price_at(
         &self,
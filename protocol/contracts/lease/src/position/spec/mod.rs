@@ -4,7 +4,7 @@ use currency::Currency;
 use finance::{
     coin::Coin,
     duration::Duration,
-    liability::Liability,
+    liability::{Level, Liability},
     percent::Percent,
     price::{self},
 };
@@ -76,6 +76,16 @@ impl Spec {
         self.overdue_collection(due).start_in()
     }
 
+    /// The liability levels, from the first warning up to the liquidation one
+    pub fn levels(&self) -> [Level; 4] {
+        [
+            Level::First(self.liability.first_liq_warn()),
+            Level::Second(self.liability.second_liq_warn()),
+            Level::Third(self.liability.third_liq_warn()),
+            Level::Max(self.liability.max()),
+        ]
+    }
+
     pub fn debt<Asset, Due>(
         &self,
         asset: Coin<Asset>,
@@ -150,6 +160,60 @@ impl Spec {
         }
     }
 
+    /// Check if the amount can be borrowed against the position.
+    /// Return `error::ContractError::InsufficientTransactionAmount` when the borrow amount
+    /// is less than the minimum transaction amount.
+    ///
+    /// Return `error::ContractError::BorrowAmountTooBig` when the borrow amount would bring
+    /// the position liability above the initial one.
+    pub fn validate_borrow_amount<Asset>(
+        &self,
+        asset: Coin<Asset>,
+        total_due: LpnCoin,
+        borrow: LpnCoin,
+        asset_in_lpns: Price<Asset>,
+    ) -> ContractResult<()>
+    where
+        Asset: Currency,
+    {
+        let one = Price::identity();
+
+        if !self.valid_transaction(borrow, one) {
+            Err(ContractError::InsufficientTransactionAmount(
+                self.min_transaction.into(),
+            ))
+        } else {
+            let max_borrow = self
+                .liability
+                .max_borrow_amount(price::total(asset, asset_in_lpns), total_due);
+            if borrow > max_borrow {
+                Err(ContractError::BorrowAmountTooBig(max_borrow.into()))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    /// Check if a position merged from two leases is healthy enough.
+    /// Return `error::ContractError::MergeLiabilityTooHigh` when the liability of the merged
+    /// position is not below the maximum one.
+    pub fn validate_merge<Asset>(
+        &self,
+        asset: Coin<Asset>,
+        total_due: LpnCoin,
+        asset_in_lpns: Price<Asset>,
+    ) -> ContractResult<()>
+    where
+        Asset: Currency,
+    {
+        let ltv = Percent::from_ratio(total_due, price::total(asset, asset_in_lpns));
+        if ltv < self.liability.max() {
+            Ok(())
+        } else {
+            Err(ContractError::MergeLiabilityTooHigh(ltv))
+        }
+    }
+
     fn invariant_held(&self) -> ContractResult<()> {
         Self::check(
             !self.min_asset.is_zero(),
@@ -1287,3 +1351,160 @@ mod test_validate_close {
         price::total_of(price_asset.into()).is(price_lpn.into())
     }
 }
+
+#[cfg(test)]
+mod test_validate_borrow {
+    use currencies::test::{LpnC, PaymentC3};
+    use finance::{
+        coin::Coin,
+        duration::Duration,
+        liability::Liability,
+        percent::Percent,
+        price::{self, Price},
+    };
+
+    use crate::{error::ContractError, position::Spec};
+
+    type TestCurrency = PaymentC3;
+    type TestLpn = LpnC;
+
+    #[test]
+    fn too_small_amount() {
+        let spec = spec();
+        let asset = 100.into();
+
+        let result = spec.validate_borrow_amount(asset, 30.into(), 14.into(), price(1, 1));
+        assert!(matches!(
+            result,
+            Err(ContractError::InsufficientTransactionAmount(_))
+        ));
+    }
+
+    #[test]
+    fn too_big_amount() {
+        let spec = spec();
+        let asset = 100.into();
+
+        let result_1 = spec.validate_borrow_amount(asset, 30.into(), 101.into(), price(1, 1));
+        assert!(matches!(
+            result_1,
+            Err(ContractError::BorrowAmountTooBig(max)) if max == Coin::<TestLpn>::new(100).into()
+        ));
+
+        let result_2 = spec.validate_borrow_amount(asset, 65.into(), 15.into(), price(1, 1));
+        assert!(matches!(
+            result_2,
+            Err(ContractError::BorrowAmountTooBig(_))
+        ));
+
+        let result_3 = spec.validate_borrow_amount(asset, 30.into(), 101.into(), price(2, 1));
+        assert!(matches!(
+            result_3,
+            Err(ContractError::BorrowAmountTooBig(_))
+        ));
+    }
+
+    #[test]
+    fn valid_amount() {
+        let spec = spec();
+        let asset = 100.into();
+
+        let result_1 = spec.validate_borrow_amount(asset, 30.into(), 100.into(), price(1, 1));
+        assert!(result_1.is_ok());
+
+        let result_2 = spec.validate_borrow_amount(asset, 30.into(), 15.into(), price(1, 1));
+        assert!(result_2.is_ok());
+
+        let result_3 = spec.validate_borrow_amount(asset, 30.into(), 285.into(), price(1, 2));
+        assert!(result_3.is_ok());
+    }
+
+    fn spec() -> Spec {
+        let liability = Liability::new(
+            Percent::from_percent(65),
+            Percent::from_percent(70),
+            Percent::from_percent(73),
+            Percent::from_percent(75),
+            Percent::from_percent(78),
+            Percent::from_percent(80),
+            Duration::from_hours(1),
+        );
+        Spec::new(liability, 25.into(), 15.into())
+    }
+
+    fn price<Asset, Lpn>(price_asset: Asset, price_lpn: Lpn) -> Price<TestCurrency, TestLpn>
+    where
+        Asset: Into<Coin<TestCurrency>>,
+        Lpn: Into<Coin<TestLpn>>,
+    {
+        price::total_of(price_asset.into()).is(price_lpn.into())
+    }
+}
+
+#[cfg(test)]
+mod test_validate_merge {
+    use currencies::test::{LpnC, PaymentC3};
+    use finance::{
+        coin::Coin,
+        duration::Duration,
+        liability::Liability,
+        percent::Percent,
+        price::{self, Price},
+    };
+
+    use crate::{error::ContractError, position::Spec};
+
+    type TestCurrency = PaymentC3;
+    type TestLpn = LpnC;
+
+    #[test]
+    fn below_max() {
+        let spec = spec();
+
+        assert!(spec
+            .validate_merge(1000.into(), 799.into(), price(1, 1))
+            .is_ok());
+        assert!(spec
+            .validate_merge(1000.into(), 1500.into(), price(1, 2))
+            .is_ok());
+    }
+
+    #[test]
+    fn at_or_above_max() {
+        let spec = spec();
+
+        assert_eq!(
+            Err(ContractError::MergeLiabilityTooHigh(Percent::from_percent(
+                80
+            ))),
+            spec.validate_merge(1000.into(), 800.into(), price(1, 1))
+        );
+        assert_eq!(
+            Err(ContractError::MergeLiabilityTooHigh(Percent::from_percent(
+                90
+            ))),
+            spec.validate_merge(2000.into(), 900.into(), price(2, 1))
+        );
+    }
+
+    fn spec() -> Spec {
+        let liability = Liability::new(
+            Percent::from_percent(65),
+            Percent::from_percent(70),
+            Percent::from_percent(73),
+            Percent::from_percent(75),
+            Percent::from_percent(78),
+            Percent::from_percent(80),
+            Duration::from_hours(1),
+        );
+        Spec::new(liability, 25.into(), 15.into())
+    }
+
+    fn price<Asset, Lpn>(price_asset: Asset, price_lpn: Lpn) -> Price<TestCurrency, TestLpn>
+    where
+        Asset: Into<Coin<TestCurrency>>,
+        Lpn: Into<Coin<TestLpn>>,
+    {
+        price::total_of(price_asset.into()).is(price_lpn.into())
+    }
+}
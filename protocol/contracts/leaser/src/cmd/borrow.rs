@@ -47,18 +47,23 @@ impl Borrow {
         max_ltd: Option<Percent>,
         finalizer: Addr,
     ) -> NewLeaseContract {
+        let position_spec = config.position_spec_of(&currency).clone();
+        let annual_margin_interest = config.interest_rate_margin_of(&currency);
+
         NewLeaseContract {
             form: NewLeaseForm {
                 customer,
                 currency,
                 max_ltd,
-                position_spec: config.lease_position_spec,
+                position_spec,
                 loan: LoanForm {
                     lpp: config.lpp,
                     profit: config.profit,
-                    annual_margin_interest: config.lease_interest_rate_margin,
+                    annual_margin_interest,
                     due_period: config.lease_due_period,
                 },
+                max_slippage: config.lease_max_slippage,
+                max_duration: config.lease_max_duration,
                 reserve: config.reserve,
                 time_alarms: config.time_alarms,
                 market_price_oracle: config.market_price_oracle,
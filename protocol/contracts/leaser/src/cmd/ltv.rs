@@ -0,0 +1,64 @@
+use currencies::LeaseGroup;
+use currency::Currency;
+use finance::{
+    coin::{Coin, WithCoin, WithCoinResult},
+    percent::Percent,
+    price::total,
+};
+use lease::api::LeaseCoin;
+use oracle_platform::{Oracle as OracleTrait, WithOracle};
+
+use crate::{finance::LpnCurrency, ContractError};
+
+/// Calculate the Loan-To-Value of a lease at the current market price
+pub struct Ltv {
+    amount: LeaseCoin,
+    total_due: Coin<LpnCurrency>,
+}
+
+impl Ltv {
+    pub fn new(amount: LeaseCoin, total_due: Coin<LpnCurrency>) -> Self {
+        Self { amount, total_due }
+    }
+}
+
+impl WithOracle<LpnCurrency> for Ltv {
+    type Output = Percent;
+    type Error = ContractError;
+
+    fn exec<O>(self, oracle: O) -> Result<Self::Output, Self::Error>
+    where
+        O: OracleTrait<LpnCurrency>,
+    {
+        self.amount.with_coin(LtvStage2 {
+            total_due: self.total_due,
+            oracle,
+        })
+    }
+}
+
+struct LtvStage2<Oracle> {
+    total_due: Coin<LpnCurrency>,
+    oracle: Oracle,
+}
+
+impl<Oracle> WithCoin for LtvStage2<Oracle>
+where
+    Oracle: OracleTrait<LpnCurrency>,
+{
+    type Output = Percent;
+    type Error = ContractError;
+
+    fn on<C>(self, amount: Coin<C>) -> WithCoinResult<Self>
+    where
+        C: Currency,
+    {
+        let amount_lpn = total(amount, self.oracle.price_of::<C, LeaseGroup>()?);
+
+        Ok(if amount_lpn.is_zero() {
+            Percent::HUNDRED
+        } else {
+            Percent::from_ratio(self.total_due, amount_lpn)
+        })
+    }
+}
@@ -1,5 +1,7 @@
 pub use borrow::Borrow;
+pub use ltv::Ltv;
 pub use quote::Quote;
 
 mod borrow;
+mod ltv;
 mod quote;
@@ -26,6 +26,7 @@ use crate::{
     result::ContractResult,
     state::{
         config::{migrate as cfg_migrate, Config},
+        exposure::Exposure,
         leases::Leases,
     },
 };
@@ -66,12 +67,15 @@ pub fn instantiate(
 pub fn migrate(
     deps: DepsMut<'_>,
     _env: Env,
-    MigrateMsg { protocols_registry }: MigrateMsg,
+    MigrateMsg {
+        protocols_registry,
+        lease_max_slippage,
+    }: MigrateMsg,
 ) -> ContractResult<Response> {
     versioning::update_software_and_storage::<CONTRACT_STORAGE_VERSION_FROM, _, _, _, _>(
         deps.storage,
         CONTRACT_VERSION,
-        |storage| cfg_migrate::migrate(storage, protocols_registry),
+        |storage| cfg_migrate::migrate(storage, protocols_registry, lease_max_slippage),
         Into::into,
     )
     .and_then(
@@ -85,34 +89,67 @@ pub fn migrate(
 
 #[entry_point]
 pub fn execute(
-    deps: DepsMut<'_>,
+    mut deps: DepsMut<'_>,
     env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> ContractResult<Response> {
     match msg {
-        ExecuteMsg::OpenLease { currency, max_ltd } => Borrow::with(
-            deps.storage,
-            info.funds,
-            info.sender,
-            env.contract.address.clone(),
-            finalizer(env),
-            currency,
-            max_ltd,
-        ),
+        ExecuteMsg::OpenLease { currency, max_ltd } => {
+            leaser::try_reserve_exposure(deps.branch(), &info, &currency, max_ltd).and_then(|()| {
+                Borrow::with(
+                    deps.storage,
+                    info.funds,
+                    info.sender,
+                    env.contract.address.clone(),
+                    finalizer(env),
+                    currency,
+                    max_ltd,
+                )
+            })
+        }
         ExecuteMsg::FinalizeLease { customer } => {
             validate_customer(customer, deps.api, deps.querier)
                 .and_then(|customer| {
                     validate_lease(info.sender, deps.as_ref()).map(|lease| (customer, lease))
                 })
                 .and_then(|(customer, lease)| {
-                    Leases::remove(deps.storage, customer, &lease).map_err(Into::into)
+                    Leases::remove(deps.storage, customer, &lease).and_then(|removed| {
+                        Exposure::release(deps.storage, lease).map(|()| removed)
+                    })
                 })
                 .map(|removed| {
                     debug_assert!(removed);
                     MessageResponse::default()
                 })
         }
+        ExecuteMsg::TransferLease {
+            customer,
+            new_customer,
+        } => validate_customer(customer, deps.api, deps.querier)
+            .and_then(|customer| {
+                validate_customer(new_customer, deps.api, deps.querier)
+                    .map(|new_customer| (customer, new_customer))
+            })
+            .and_then(|(customer, new_customer)| {
+                validate_lease(info.sender, deps.as_ref()).and_then(|lease| {
+                    leaser::try_transfer_lease(deps.storage, lease, customer, new_customer)
+                })
+            }),
+        ExecuteMsg::MergeLeases { into, from } => {
+            leaser::try_merge_leases(deps.storage, info.sender, into, from)
+        }
+        ExecuteMsg::FinalizeMerge {
+            customer,
+            into,
+            loan,
+        } => validate_customer(customer, deps.api, deps.querier)
+            .and_then(|customer| {
+                validate_lease(info.sender, deps.as_ref()).map(|lease| (customer, lease))
+            })
+            .and_then(|(customer, lease)| {
+                leaser::try_finalize_merge(deps.storage, customer, lease, into, loan, info.funds)
+            }),
         ExecuteMsg::MigrateLeases {
             new_code_id,
             max_leases,
@@ -121,7 +158,14 @@ pub fn execute(
             .map_err(Into::into)
             .and_then(|()| new_code(new_code_id, deps.querier))
             .and_then(|new_lease_code| {
-                leaser::try_migrate_leases(deps.storage, new_lease_code, max_leases, migrate_msg)
+                migrate_msg(deps.storage).and_then(|migrate_msg| {
+                    leaser::try_migrate_leases(
+                        deps.storage,
+                        new_lease_code,
+                        max_leases,
+                        migrate_msg,
+                    )
+                })
             }),
         ExecuteMsg::MigrateLeasesCont {
             key: next_customer,
@@ -131,12 +175,14 @@ pub fn execute(
             .map_err(Into::into)
             .and_then(|()| validate_customer(next_customer, deps.api, deps.querier))
             .and_then(|next_customer_validated| {
-                leaser::try_migrate_leases_cont(
-                    deps.storage,
-                    next_customer_validated,
-                    max_leases,
-                    migrate_msg,
-                )
+                migrate_msg(deps.storage).and_then(|migrate_msg| {
+                    leaser::try_migrate_leases_cont(
+                        deps.storage,
+                        next_customer_validated,
+                        max_leases,
+                        migrate_msg,
+                    )
+                })
             }),
     }
     .map(response::response_only_messages)
@@ -149,28 +195,41 @@ pub fn sudo(deps: DepsMut<'_>, _env: Env, msg: SudoMsg) -> ContractResult<Respon
         SudoMsg::Config {
             lease_interest_rate_margin,
             lease_position_spec,
+            lease_asset_specs,
             lease_due_period,
+            lease_max_slippage,
         } => leaser::try_configure(
             deps.storage,
             lease_interest_rate_margin,
             lease_position_spec,
+            lease_asset_specs,
             lease_due_period,
+            lease_max_slippage,
         ),
+        SudoMsg::DexIbc { ibc } => leaser::try_configure_dex_ibc(deps.storage, ibc),
+        SudoMsg::Limits { limits } => leaser::try_configure_limits(deps.storage, limits),
+        SudoMsg::LeaseMaxDuration { max_duration } => {
+            leaser::try_configure_lease_max_duration(deps.storage, max_duration)
+        }
         SudoMsg::CloseProtocol {
             new_lease_code_id,
             migration_spec,
             force,
-        } => new_code(new_lease_code_id, deps.querier).and_then(|new_lease_code| {
-            leaser::try_close_protocol(
-                deps.storage,
-                new_lease_code,
-                MaxLeases::MAX,
-                migrate_msg,
-                protocols_registry_load,
-                migration_spec,
-                force,
-            )
-        }),
+        } => new_code(new_lease_code_id, deps.querier)
+            .and_then(|new_lease_code| {
+                migrate_msg(deps.storage).map(|migrate_msg| (new_lease_code, migrate_msg))
+            })
+            .and_then(|(new_lease_code, migrate_msg)| {
+                leaser::try_close_protocol(
+                    deps.storage,
+                    new_lease_code,
+                    MaxLeases::MAX,
+                    migrate_msg,
+                    protocols_registry_load,
+                    migration_spec,
+                    force,
+                )
+            }),
     }
     .map(response::response_only_messages)
     .inspect_err(platform_error::log(deps.api))
@@ -186,6 +245,13 @@ pub fn query(deps: Deps<'_>, _env: Env, msg: QueryMsg) -> ContractResult<Binary>
             max_ltd,
         } => to_json_binary(&Leaser::new(deps).quote(downpayment, lease_asset, max_ltd)?),
         QueryMsg::Leases { owner } => to_json_binary(&Leaser::new(deps).customer_leases(owner)?),
+        QueryMsg::AllLeases { start_after, limit } => {
+            to_json_binary(&Leaser::new(deps).leases(start_after, limit)?)
+        }
+        QueryMsg::AssetExposure { lease_asset } => {
+            to_json_binary(&Leaser::new(deps).asset_exposure(&lease_asset)?)
+        }
+        QueryMsg::IsLease { address } => to_json_binary(&is_lease(&address, deps)?),
     }
     .map_err(Into::into)
     .inspect_err(platform_error::log(deps.api))
@@ -203,6 +269,7 @@ pub fn reply(deps: DepsMut<'_>, _env: Env, msg: Reply) -> ContractResult<Respons
                 lease
             })
         })
+        .and_then(|lease| Exposure::save(deps.storage, lease.clone()).map(|()| lease))
         .map(|lease| Response::new().add_attribute("lease_address", lease))
         .inspect_err(platform_error::log(deps.api))
 }
@@ -227,15 +294,24 @@ fn validate_customer(
 }
 
 fn validate_lease(lease: Addr, deps: Deps<'_>) -> ContractResult<Addr> {
-    Leaser::new(deps)
-        .config()
-        .map(|config| config.config.lease_code)
+    lease_code(deps)
         .and_then(|lease_code| {
             contract::validate_code_id(deps.querier, &lease, lease_code).map_err(Into::into)
         })
         .map(|()| lease)
 }
 
+fn is_lease(address: &Addr, deps: Deps<'_>) -> ContractResult<bool> {
+    lease_code(deps)
+        .map(|lease_code| contract::validate_code_id(deps.querier, address, lease_code).is_ok())
+}
+
+fn lease_code(deps: Deps<'_>) -> ContractResult<Code> {
+    Leaser::new(deps)
+        .config()
+        .map(|config| config.config.lease_code)
+}
+
 fn protocols_registry_load(storage: &dyn Storage) -> ContractResult<Addr> {
     Config::load(storage).map(|cfg| cfg.protocols_registry)
 }
@@ -247,8 +323,13 @@ where
     Code::try_new(new_code_id.into(), &querier).map_err(Into::into)
 }
 
-fn migrate_msg(_customer: Addr) -> LeaseMigrateMsg {
-    LeaseMigrateMsg {}
+fn migrate_msg(storage: &dyn Storage) -> ContractResult<impl Fn(Addr) -> LeaseMigrateMsg> {
+    Config::load(storage).map(|config| {
+        let dex_ibc = config.dex.ibc;
+        move |_customer| LeaseMigrateMsg {
+            dex_ibc: Some(dex_ibc.clone()),
+        }
+    })
 }
 
 fn finalizer(env: Env) -> Addr {
@@ -2,6 +2,7 @@ use std::num::TryFromIntError;
 
 use thiserror::Error;
 
+use currency::SymbolOwned;
 use sdk::cosmwasm_std::StdError;
 
 #[derive(Error, Debug, PartialEq)]
@@ -12,6 +13,9 @@ pub enum ContractError {
     #[error("[Leaser] integer conversion {0}")]
     Conversion(#[from] TryFromIntError),
 
+    #[error("[Leaser] {0}")]
+    Currency(#[from] currency::error::Error),
+
     #[error("[Leaser] {0}")]
     Finance(#[from] finance::error::Error),
 
@@ -42,6 +46,24 @@ pub enum ContractError {
     #[error("[Leaser] No Liquidity")]
     NoLiquidity {},
 
+    #[error("[Leaser] The borrow cap of the '{asset}' leases would be exceeded")]
+    AssetBorrowCap { asset: SymbolOwned },
+
+    #[error("[Leaser] The maximum number of open '{asset}' leases has been reached")]
+    AssetLeasesCap { asset: SymbolOwned },
+
+    #[error("[Leaser] The maximum number of open leases of a customer has been reached")]
+    CustomerLeasesCap {},
+
+    #[error("[Leaser] A lease cannot be merged into itself")]
+    SelfLeaseMerge {},
+
+    #[error("[Leaser] Only leases of the same customer can be merged")]
+    ForeignLeaseMerge {},
+
+    #[error("[Leaser] The lease maximum duration should be positive")]
+    ZeroLeaseMaxDuration(),
+
     #[error("[Leaser] Invalid continuation key, cause: {err} ")]
     InvalidContinuationKey { err: String },
 
@@ -1,25 +1,40 @@
 use std::collections::HashSet;
 
 use admin_contract::msg::{ExecuteMsg, MigrationSpec, ProtocolContracts};
-use currency::SymbolOwned;
-use finance::{duration::Duration, percent::Percent};
-use lease::api::{open::PositionSpecDTO, DownpaymentCoin, MigrateMsg};
+use currencies::PaymentGroup;
+use currency::{never, SymbolOwned, SymbolSlice};
+use finance::{
+    coin::{Coin, IntoDTO},
+    duration::Duration,
+    percent::Percent,
+};
+use lease::api::{
+    open::{IbcParams, PositionSpecDTO},
+    query::{StateQuery, StateResponse},
+    DownpaymentCoin, ExecuteMsg as LeaseExecuteMsg, MergedLoan, MigrateMsg,
+};
 use lpp::{msg::ExecuteMsg as LppExecuteMsg, stub::LppRef};
 use platform::{
+    bank,
     batch::{Batch, Emit, Emitter},
     contract::Code,
     message::Response as MessageResponse,
 };
 use reserve::api::ExecuteMsg as ReserveExecuteMsg;
-use sdk::cosmwasm_std::{Addr, Deps, Storage};
+use sdk::cosmwasm_std::{
+    to_json_binary, Addr, Coin as CwCoin, Deps, DepsMut, MessageInfo, Storage, WasmMsg,
+};
 
 use crate::{
-    cmd::Quote,
+    cmd::{Ltv, Quote},
     finance::LpnCurrencies,
     migrate,
-    msg::{ConfigResponse, MaxLeases, QuoteResponse},
+    msg::{
+        AssetExposure, ConfigResponse, LeaseAssetSpecs, LeaseInfo, LeaseKey, LeaseLimits,
+        LeaseStatus, MaxLeases, QuoteResponse,
+    },
     result::ContractResult,
-    state::{config::Config, leases::Leases},
+    state::{config::Config, exposure::Exposure, leases::Leases},
 };
 use crate::{
     finance::{LpnCurrency, OracleRef},
@@ -32,6 +47,9 @@ pub struct Leaser<'a> {
 }
 
 impl<'a> Leaser<'a> {
+    const DEFAULT_LEASES_LIMIT: MaxLeases = 10;
+    const MAX_LEASES_LIMIT: MaxLeases = 30;
+
     pub fn new(deps: Deps<'a>) -> Self {
         Self { deps }
     }
@@ -43,6 +61,83 @@ impl<'a> Leaser<'a> {
         Leases::load_by_customer(self.deps.storage, customer)
     }
 
+    pub fn leases(
+        &self,
+        start_after: Option<LeaseKey>,
+        limit: Option<MaxLeases>,
+    ) -> ContractResult<Vec<LeaseInfo>> {
+        let limit = limit
+            .unwrap_or(Self::DEFAULT_LEASES_LIMIT)
+            .min(Self::MAX_LEASES_LIMIT)
+            .try_into()?;
+
+        let oracle = OracleRef::try_from_base(
+            Config::load(self.deps.storage)?.market_price_oracle,
+            self.deps.querier,
+        )?;
+
+        Leases::iter_after(self.deps.storage, start_after)
+            .take(limit)
+            .map(|maybe_key| {
+                maybe_key.and_then(|key| {
+                    self.lease_status(&key.lease, &oracle)
+                        .map(|status| LeaseInfo { key, status })
+                })
+            })
+            .collect()
+    }
+
+    fn lease_status(&self, lease: &Addr, oracle: &OracleRef) -> ContractResult<LeaseStatus> {
+        self.deps
+            .querier
+            .query_wasm_smart(lease, &StateQuery {})
+            .map_err(Into::into)
+            .and_then(|state| match state {
+                StateResponse::Opening { .. } => Ok(LeaseStatus::Opening),
+                StateResponse::Opened {
+                    amount,
+                    principal_due,
+                    overdue_margin,
+                    overdue_interest,
+                    due_margin,
+                    due_interest,
+                    in_progress,
+                    ..
+                } => [
+                    principal_due,
+                    overdue_margin,
+                    overdue_interest,
+                    due_margin,
+                    due_interest,
+                ]
+                .into_iter()
+                .map(TryInto::<Coin<LpnCurrency>>::try_into)
+                .sum::<Result<Coin<LpnCurrency>, _>>()
+                .map_err(Into::into)
+                .and_then(|total_due| {
+                    oracle.clone().execute_as_oracle::<LpnCurrencies, _>(
+                        Ltv::new(amount.clone(), total_due),
+                        self.deps.querier,
+                    )
+                })
+                .map(|ltv| LeaseStatus::Opened {
+                    amount,
+                    ltv,
+                    in_progress: in_progress.is_some(),
+                }),
+                StateResponse::Paid {
+                    amount,
+                    in_progress,
+                    ..
+                } => Ok(LeaseStatus::Paid {
+                    amount,
+                    in_progress: in_progress.is_some(),
+                }),
+                StateResponse::Closed() => Ok(LeaseStatus::Closed),
+                StateResponse::Liquidated() => Ok(LeaseStatus::Liquidated),
+            })
+    }
+
     pub fn quote(
         &self,
         downpayment: DownpaymentCoin,
@@ -51,7 +146,12 @@ impl<'a> Leaser<'a> {
     ) -> ContractResult<QuoteResponse> {
         let config = Config::load(self.deps.storage)?;
 
-        let lpp = LppRef::<LpnCurrency, LpnCurrencies>::try_new(config.lpp, self.deps.querier)?;
+        let lpp =
+            LppRef::<LpnCurrency, LpnCurrencies>::try_new(config.lpp.clone(), self.deps.querier)?;
+
+        let liability = config.position_spec_of(&lease_asset).liability;
+        let lease_interest_rate_margin = config.interest_rate_margin_of(&lease_asset);
+        let asset_limits = config.lease_limits.assets.get(&lease_asset).cloned();
 
         let oracle = OracleRef::try_from_base(config.market_price_oracle, self.deps.querier)?;
 
@@ -59,14 +159,30 @@ impl<'a> Leaser<'a> {
             Quote::new(
                 self.deps.querier,
                 downpayment,
-                lease_asset,
+                lease_asset.clone(),
                 oracle,
-                config.lease_position_spec.liability,
-                config.lease_interest_rate_margin,
+                liability,
+                lease_interest_rate_margin,
                 max_ltd,
             ),
             self.deps.querier,
         )
+        .and_then(|quote| {
+            asset_limits
+                .map_or(Ok(()), |ref limits| {
+                    Exposure::check(
+                        self.deps.storage,
+                        &lease_asset,
+                        limits,
+                        quote.borrow.amount(),
+                    )
+                })
+                .map(|()| quote)
+        })
+    }
+
+    pub fn asset_exposure(&self, lease_asset: &SymbolSlice) -> ContractResult<AssetExposure> {
+        Exposure::load(self.deps.storage, lease_asset)
     }
 }
 
@@ -74,17 +190,178 @@ pub(super) fn try_configure(
     storage: &mut dyn Storage,
     lease_interest_rate_margin: Percent,
     lease_position_spec: PositionSpecDTO,
+    lease_asset_specs: LeaseAssetSpecs,
     lease_due_period: Duration,
+    lease_max_slippage: Percent,
 ) -> ContractResult<MessageResponse> {
     Config::update(
         storage,
         lease_interest_rate_margin,
         lease_position_spec,
+        lease_asset_specs,
         lease_due_period,
+        lease_max_slippage,
     )
     .map(|()| MessageResponse::default())
 }
 
+/// Account for a lease about to be opened against the exposure limits
+pub(super) fn try_reserve_exposure(
+    deps: DepsMut<'_>,
+    info: &MessageInfo,
+    lease_asset: &SymbolSlice,
+    max_ltd: Option<Percent>,
+) -> ContractResult<()> {
+    let limits = Config::load(deps.storage)?.lease_limits;
+
+    check_customer_leases(deps.storage, &limits, info.sender.clone()).and_then(|()| {
+        limits
+            .assets
+            .get(lease_asset)
+            .map_or(Ok(()), |asset_limits| {
+                bank::may_received::<PaymentGroup, _>(&info.funds, IntoDTO::<PaymentGroup>::new())
+                    .map(never::safe_unwrap)
+                    .ok_or(ContractError::ZeroDownpayment {})
+                    .and_then(|downpayment| {
+                        Leaser::new(deps.as_ref()).quote(downpayment, lease_asset.into(), max_ltd)
+                    })
+                    .and_then(|quote| {
+                        Exposure::open(
+                            deps.storage,
+                            lease_asset,
+                            asset_limits,
+                            quote.borrow.amount(),
+                        )
+                    })
+            })
+    })
+}
+
+fn check_customer_leases(
+    storage: &dyn Storage,
+    limits: &LeaseLimits,
+    customer: Addr,
+) -> ContractResult<()> {
+    limits.max_customer_leases.map_or(Ok(()), |max_leases| {
+        Leases::load_by_customer(storage, customer).and_then(|leases| {
+            if leases.len() < usize::try_from(max_leases)? {
+                Ok(())
+            } else {
+                Err(ContractError::CustomerLeasesCap {})
+            }
+        })
+    })
+}
+
+/// Move a lease to the index of its new customer
+pub(super) fn try_transfer_lease(
+    storage: &mut dyn Storage,
+    lease: Addr,
+    customer: Addr,
+    new_customer: Addr,
+) -> ContractResult<MessageResponse> {
+    Config::load(storage)
+        .and_then(|config| {
+            check_customer_leases(storage, &config.lease_limits, new_customer.clone())
+        })
+        .and_then(|()| Leases::transfer(storage, lease, customer, new_customer))
+        .map(|transferred| {
+            debug_assert!(transferred);
+            MessageResponse::default()
+        })
+}
+
+/// Request a lease of a customer to merge into another one of theirs
+pub(super) fn try_merge_leases(
+    storage: &dyn Storage,
+    customer: Addr,
+    into: Addr,
+    from: Addr,
+) -> ContractResult<MessageResponse> {
+    if into == from {
+        return Err(ContractError::SelfLeaseMerge {});
+    }
+
+    Leases::load_by_customer(storage, customer).and_then(|leases| {
+        if leases.contains(&into) && leases.contains(&from) {
+            let mut batch = Batch::default();
+            batch
+                .schedule_execute_wasm_no_reply_no_funds(
+                    from,
+                    &LeaseExecuteMsg::MergeInto { lease: into },
+                )
+                .map(|()| MessageResponse::messages_only(batch))
+                .map_err(Into::into)
+        } else {
+            Err(ContractError::ForeignLeaseMerge {})
+        }
+    })
+}
+
+/// Drop a lease merged into another one and hand its asset and loan over
+pub(super) fn try_finalize_merge(
+    storage: &mut dyn Storage,
+    customer: Addr,
+    lease: Addr,
+    into: Addr,
+    loan: MergedLoan,
+    asset: Vec<CwCoin>,
+) -> ContractResult<MessageResponse> {
+    if !Leases::load_by_customer(storage, customer.clone())?.contains(&into) {
+        return Err(ContractError::ForeignLeaseMerge {});
+    }
+
+    let lpp = Config::load(storage)?.lpp;
+    Leases::remove(storage, customer, &lease)
+        .and_then(|removed| {
+            debug_assert!(removed);
+            Exposure::merge(storage, lease.clone(), into.clone())
+        })
+        .and_then(|()| to_json_binary(&LeaseExecuteMsg::AbsorbLease(loan)).map_err(Into::into))
+        .and_then(|absorb_msg| {
+            let mut batch = Batch::default();
+            // make sure the loan gets merged before the hand-over
+            batch
+                .schedule_execute_wasm_no_reply_no_funds(
+                    lpp,
+                    &LppExecuteMsg::<LpnCurrencies>::MergeLoan {
+                        from: lease,
+                        into: into.clone(),
+                    },
+                )
+                .map(|()| {
+                    batch.schedule_execute_no_reply(WasmMsg::Execute {
+                        contract_addr: into.into(),
+                        msg: absorb_msg,
+                        funds: asset,
+                    });
+                    MessageResponse::messages_only(batch)
+                })
+                .map_err(Into::into)
+        })
+}
+
+pub(super) fn try_configure_limits(
+    storage: &mut dyn Storage,
+    limits: LeaseLimits,
+) -> ContractResult<MessageResponse> {
+    Config::update_limits(storage, limits).map(|()| MessageResponse::default())
+}
+
+pub(super) fn try_configure_dex_ibc(
+    storage: &mut dyn Storage,
+    ibc: IbcParams,
+) -> ContractResult<MessageResponse> {
+    Config::update_dex_ibc(storage, ibc).map(|()| MessageResponse::default())
+}
+
+pub(super) fn try_configure_lease_max_duration(
+    storage: &mut dyn Storage,
+    max_duration: Option<Duration>,
+) -> ContractResult<MessageResponse> {
+    Config::update_lease_max_duration(storage, max_duration).map(|()| MessageResponse::default())
+}
+
 pub(super) fn try_migrate_leases<MsgFactory>(
     storage: &mut dyn Storage,
     new_lease: Code,
@@ -197,7 +474,7 @@ mod test {
     use currencies::test::LpnC;
     use finance::{coin::Coin, duration::Duration, liability::Liability, percent::Percent};
     use lease::api::{
-        open::{ConnectionParams, Ics20Channel, PositionSpecDTO},
+        open::{ConnectionParams, IbcParams, Ics20Channel, PositionSpecDTO},
         MigrateMsg,
     };
     use platform::{contract::Code, response};
@@ -297,13 +574,17 @@ mod test {
                 min_transaction: Coin::<LpnC>::from(12_000).into(),
             },
             lease_interest_rate_margin: Percent::from_percent(3),
+            lease_asset_specs: Default::default(),
             lease_due_period: Duration::from_days(14),
+            lease_max_slippage: Percent::from_percent(5),
+            lease_max_duration: None,
             dex: ConnectionParams {
                 connection_id: "conn-12".into(),
                 transfer_channel: Ics20Channel {
                     local_endpoint: "chan-1".into(),
                     remote_endpoint: "chan-13".into(),
                 },
+                ibc: IbcParams::default(),
             },
         }
     }
@@ -324,6 +605,6 @@ mod test {
     }
 
     fn migrate_msg(_customer: Addr) -> MigrateMsg {
-        MigrateMsg {}
+        MigrateMsg { dex_ibc: None }
     }
 }
@@ -52,6 +52,10 @@ where
     pub fn from(customer: Addr, leases: LeaseIter) -> Self {
         Self { customer, leases }
     }
+
+    pub fn into_parts(self) -> (Addr, LeaseIter) {
+        (self.customer, self.leases)
+    }
 }
 
 impl MigrationResult {
@@ -357,6 +361,6 @@ mod test {
     }
 
     fn migrate_msg() -> impl Fn(Addr) -> MigrateMsg {
-        |_customer| MigrateMsg {}
+        |_customer| MigrateMsg { dex_ibc: None }
     }
 }
@@ -1,18 +1,20 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 use admin_contract::msg::{MigrationSpec, ProtocolContracts};
 use currency::SymbolOwned;
 use finance::{duration::Duration, percent::Percent};
 use lease::api::{
-    open::{ConnectionParams, PositionSpecDTO},
-    DownpaymentCoin, LeaseCoin, LpnCoinDTO,
+    open::{ConnectionParams, IbcParams, PositionSpecDTO},
+    DownpaymentCoin, LeaseCoin, LpnCoinDTO, MergedLoan,
 };
 use sdk::{
     cosmwasm_std::{Addr, Uint64},
     schemars::{self, JsonSchema},
 };
 
-pub use crate::state::config::Config;
+pub use crate::state::{config::Config, exposure::AssetExposure};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
 #[cfg_attr(any(test, feature = "testing"), derive(Debug))]
@@ -27,14 +29,69 @@ pub struct InstantiateMsg {
     pub protocols_registry: Addr,
     pub lease_position_spec: PositionSpecDTO,
     pub lease_interest_rate_margin: Percent,
+    #[serde(default)]
+    pub lease_asset_specs: LeaseAssetSpecs,
     pub lease_due_period: Duration,
+    pub lease_max_slippage: Percent,
+    /// The maximum duration of the leases, if any
+    #[serde(default)]
+    pub lease_max_duration: Option<Duration>,
     pub dex: ConnectionParams,
 }
 
+/// Lease parameters of the assets that differ from the protocol-wide ones
+///
+/// The assets without an entry get `lease_position_spec` and `lease_interest_rate_margin`.
+pub type LeaseAssetSpecs = BTreeMap<SymbolOwned, LeaseAssetSpec>;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Debug))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct LeaseAssetSpec {
+    pub position_spec: PositionSpecDTO,
+    pub interest_rate_margin: Percent,
+}
+
+/// Caps on the exposure of the protocol to the lease assets and on the leases of a customer
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Debug))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct LeaseLimits {
+    #[serde(default)]
+    pub assets: BTreeMap<SymbolOwned, AssetLimits>,
+    /// The maximum number of open leases of a customer
+    #[serde(default)]
+    pub max_customer_leases: Option<MaxLeases>,
+}
+
+/// Caps on the exposure of the protocol to a lease asset
+///
+/// They are enforced against the leases opened while the asset has limits.
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Debug))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct AssetLimits {
+    /// The maximum total amount borrowed by the open leases, as estimated on their opening
+    #[serde(default)]
+    pub max_borrow: Option<LpnCoinDTO>,
+    /// The maximum number of open leases
+    #[serde(default)]
+    pub max_leases: Option<MaxLeases>,
+}
+
 #[derive(Serialize, Deserialize)]
 #[serde(deny_unknown_fields, rename_all = "snake_case")]
 pub struct MigrateMsg {
     pub protocols_registry: Addr,
+    /// Defaults to no limit, the behavior before the maximum slippage was introduced
+    #[serde(default = "MigrateMsg::no_max_slippage")]
+    pub lease_max_slippage: Percent,
+}
+
+impl MigrateMsg {
+    const fn no_max_slippage() -> Percent {
+        Percent::HUNDRED
+    }
 }
 
 pub type MaxLeases = u32;
@@ -52,6 +109,26 @@ pub enum ExecuteMsg {
     ///
     /// It matches the `lease::api::FinalizerExecuteMsg::FinalizeLease`.
     FinalizeLease { customer: Addr },
+    /// A callback from a lease that it has just been handed over to a new customer
+    ///
+    /// It matches the `lease::api::FinalizerExecuteMsg::TransferLease`.
+    TransferLease { customer: Addr, new_customer: Addr },
+    /// Merge the lease `from` into the lease `into`
+    ///
+    /// Both leases should be owned by the sender. The asset and the loan of `from`
+    /// are handed over to `into`, and `from` gets closed. Refer to
+    /// `lease::api::ExecuteMsg::MergeInto` for the conditions the leases should meet.
+    MergeLeases { into: Addr, from: Addr },
+    /// A callback from a lease that it has just been merged into another one
+    ///
+    /// The lease asset is sent attached to the message. The Lpp loan of the lease is
+    /// merged into the loan of `into`, and then the asset and the loan are handed over.
+    /// It matches the `lease::api::FinalizerExecuteMsg::FinalizeMerge`.
+    FinalizeMerge {
+        customer: Addr,
+        into: Addr,
+        loan: MergedLoan,
+    },
     /// Start a Lease migration
     ///
     /// The consumed gas is a limitaton factor for the maximum lease instances that
@@ -81,8 +158,22 @@ pub enum SudoMsg {
     Config {
         lease_interest_rate_margin: Percent,
         lease_position_spec: PositionSpecDTO,
+        /// Replace the asset specific lease parameters
+        #[serde(default)]
+        lease_asset_specs: LeaseAssetSpecs,
         lease_due_period: Duration,
+        lease_max_slippage: Percent,
     },
+    /// Update the relayer tips and timeout of the IBC transactions to the DEX
+    ///
+    /// New leases use them straight away. Existing leases get them on their next migration.
+    DexIbc { ibc: IbcParams },
+    /// Replace the exposure limits
+    Limits { limits: LeaseLimits },
+    /// Set or remove the maximum duration of the leases
+    ///
+    /// Only the leases opened afterwards are affected.
+    LeaseMaxDuration { max_duration: Option<Duration> },
     CloseProtocol {
         // Since this is an external system API we should not use [Code].
         new_lease_code_id: Uint64,
@@ -121,6 +212,68 @@ pub enum QueryMsg {
     Leases {
         owner: Addr,
     },
+    /// List the leases of all customers
+    ///
+    /// The leases are ordered by customer, and then by lease address. Up to `limit` leases,
+    /// past `start_after` if provided, are returned. The key of the last lease in a response
+    /// should be provided as `start_after` to get the next page. A response with less than
+    /// `limit` leases is the last page.
+    ///
+    /// Return a [Vec] of [LeaseInfo].
+    AllLeases {
+        #[serde(default)]
+        start_after: Option<LeaseKey>,
+        #[serde(default)]
+        limit: Option<MaxLeases>,
+    },
+    /// Report the exposure of the protocol to a lease asset
+    ///
+    /// Return an [AssetExposure].
+    AssetExposure {
+        lease_asset: SymbolOwned,
+    },
+    /// Check whether an address is a lease of this protocol
+    ///
+    /// Return a [bool].
+    IsLease {
+        address: Addr,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, PartialOrd, Ord, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Debug))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct LeaseKey {
+    pub customer: Addr,
+    pub lease: Addr,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Debug))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct LeaseInfo {
+    pub key: LeaseKey,
+    pub status: LeaseStatus,
+}
+
+/// A summary of the lease state
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Debug))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub enum LeaseStatus {
+    Opening,
+    Opened {
+        amount: LeaseCoin,
+        /// The total amount due versus the lease amount at the current market price
+        ltv: Percent,
+        in_progress: bool,
+    },
+    Paid {
+        amount: LeaseCoin,
+        in_progress: bool,
+    },
+    Closed,
+    Liquidated,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
@@ -158,4 +311,56 @@ mod test {
         let msg_out: FinalizerExecuteMsg = from_json(finalize_bin).expect("deserialization passed");
         assert_eq!(FinalizerExecuteMsg::FinalizeLease { customer }, msg_out);
     }
+
+    #[test]
+    fn transfer_api_match() {
+        use sdk::cosmwasm_std::{from_json, to_json_vec};
+
+        let customer = Addr::unchecked("c");
+        let new_customer = Addr::unchecked("n");
+        let transfer_bin = to_json_vec(&ExecuteMsg::TransferLease {
+            customer: customer.clone(),
+            new_customer: new_customer.clone(),
+        })
+        .expect("serialization passed");
+        let msg_out: FinalizerExecuteMsg = from_json(transfer_bin).expect("deserialization passed");
+        assert_eq!(
+            FinalizerExecuteMsg::TransferLease {
+                customer,
+                new_customer
+            },
+            msg_out
+        );
+    }
+
+    #[test]
+    fn merge_api_match() {
+        use currencies::test::LpnC;
+        use finance::{coin::Coin, percent::Percent};
+        use lease::api::MergedLoan;
+        use sdk::cosmwasm_std::{from_json, to_json_vec, Timestamp};
+
+        let customer = Addr::unchecked("c");
+        let into = Addr::unchecked("l");
+        let loan = MergedLoan {
+            principal_due: Coin::<LpnC>::new(1000).into(),
+            margin_interest: Percent::from_permille(30),
+            margin_paid_by: Timestamp::from_seconds(100),
+        };
+        let merge_bin = to_json_vec(&ExecuteMsg::FinalizeMerge {
+            customer: customer.clone(),
+            into: into.clone(),
+            loan: loan.clone(),
+        })
+        .expect("serialization passed");
+        let msg_out: FinalizerExecuteMsg = from_json(merge_bin).expect("deserialization passed");
+        assert_eq!(
+            FinalizerExecuteMsg::FinalizeMerge {
+                customer,
+                into,
+                loan
+            },
+            msg_out
+        );
+    }
 }
@@ -2,8 +2,13 @@ use std::mem;
 
 use serde::{Deserialize, Serialize};
 
-use finance::{duration::Duration, percent::Percent};
-use lease::api::open::{ConnectionParams, PositionSpecDTO};
+use currencies::LeaseGroup;
+use currency::{SymbolOwned, SymbolSlice};
+use finance::{
+    duration::Duration,
+    percent::{bound::BoundToHundredPercent, Percent},
+};
+use lease::api::open::{ConnectionParams, IbcParams, PositionSpecDTO};
 use platform::contract::Code;
 use sdk::{
     cosmwasm_std::{Addr, Storage},
@@ -11,7 +16,11 @@ use sdk::{
     schemars::{self, JsonSchema},
 };
 
-use crate::{msg::InstantiateMsg, result::ContractResult};
+use crate::{
+    error::ContractError,
+    msg::{InstantiateMsg, LeaseAssetSpecs, LeaseLimits},
+    result::ContractResult,
+};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
 #[cfg_attr(any(test, feature = "testing"), derive(Debug))]
@@ -25,7 +34,14 @@ pub struct Config {
     pub protocols_registry: Addr,
     pub lease_position_spec: PositionSpecDTO,
     pub lease_interest_rate_margin: Percent,
+    #[serde(default)]
+    pub lease_asset_specs: LeaseAssetSpecs,
+    #[serde(default)]
+    pub lease_limits: LeaseLimits,
     pub lease_due_period: Duration,
+    pub lease_max_slippage: Percent,
+    #[serde(default)]
+    pub lease_max_duration: Option<Duration>,
     pub dex: ConnectionParams,
 }
 
@@ -43,13 +59,22 @@ impl Config {
             protocols_registry: msg.protocols_registry,
             lease_position_spec: msg.lease_position_spec,
             lease_interest_rate_margin: msg.lease_interest_rate_margin,
+            lease_asset_specs: msg.lease_asset_specs,
+            lease_limits: LeaseLimits::default(),
             lease_due_period: msg.lease_due_period,
+            lease_max_slippage: msg.lease_max_slippage,
+            lease_max_duration: msg.lease_max_duration,
             dex: msg.dex,
         }
     }
 
     pub fn store(&self, storage: &mut dyn Storage) -> ContractResult<()> {
-        Self::STORAGE.save(storage, self).map_err(Into::into)
+        BoundToHundredPercent::try_from(self.lease_max_slippage)
+            .map_err(ContractError::from)
+            .and_then(|_| validate_max_duration(self.lease_max_duration))
+            .and_then(|()| validate_lease_assets(self.lease_asset_specs.keys()))
+            .and_then(|()| validate_lease_assets(self.lease_limits.assets.keys()))
+            .and_then(|()| Self::STORAGE.save(storage, self).map_err(Into::into))
     }
 
     pub fn load(storage: &dyn Storage) -> ContractResult<Self> {
@@ -60,14 +85,65 @@ impl Config {
         storage: &mut dyn Storage,
         lease_interest_rate_margin: Percent,
         lease_position_spec: PositionSpecDTO,
+        lease_asset_specs: LeaseAssetSpecs,
         lease_due_period: Duration,
+        lease_max_slippage: Percent,
     ) -> ContractResult<()> {
+        BoundToHundredPercent::try_from(lease_max_slippage)
+            .map_err(ContractError::from)
+            .and_then(|_| validate_lease_assets(lease_asset_specs.keys()))
+            .and_then(|()| {
+                Self::STORAGE
+                    .update(storage, |c| {
+                        ContractResult::Ok(Self {
+                            lease_interest_rate_margin,
+                            lease_position_spec,
+                            lease_asset_specs,
+                            lease_due_period,
+                            lease_max_slippage,
+                            ..c
+                        })
+                    })
+                    .map(mem::drop)
+                    .map_err(Into::into)
+            })
+    }
+
+    /// The position specification of the leases of `asset`
+    pub fn position_spec_of(&self, asset: &SymbolSlice) -> &PositionSpecDTO {
+        self.lease_asset_specs
+            .get(asset)
+            .map_or(&self.lease_position_spec, |spec| &spec.position_spec)
+    }
+
+    /// The interest rate margin of the leases of `asset`
+    pub fn interest_rate_margin_of(&self, asset: &SymbolSlice) -> Percent {
+        self.lease_asset_specs
+            .get(asset)
+            .map_or(self.lease_interest_rate_margin, |spec| {
+                spec.interest_rate_margin
+            })
+    }
+
+    pub fn update_limits(
+        storage: &mut dyn Storage,
+        lease_limits: LeaseLimits,
+    ) -> ContractResult<()> {
+        validate_lease_assets(lease_limits.assets.keys()).and_then(|()| {
+            Self::STORAGE
+                .update(storage, |c| -> ContractResult<Config> {
+                    Ok(Self { lease_limits, ..c })
+                })
+                .map(mem::drop)
+                .map_err(Into::into)
+        })
+    }
+
+    pub fn update_dex_ibc(storage: &mut dyn Storage, ibc: IbcParams) -> ContractResult<()> {
         Self::STORAGE
-            .update(storage, |c| {
-                ContractResult::Ok(Self {
-                    lease_interest_rate_margin,
-                    lease_position_spec,
-                    lease_due_period,
+            .update(storage, |c| -> ContractResult<Config> {
+                Ok(Self {
+                    dex: ConnectionParams { ibc, ..c.dex },
                     ..c
                 })
             })
@@ -75,6 +151,23 @@ impl Config {
             .map_err(Into::into)
     }
 
+    pub fn update_lease_max_duration(
+        storage: &mut dyn Storage,
+        lease_max_duration: Option<Duration>,
+    ) -> ContractResult<()> {
+        validate_max_duration(lease_max_duration).and_then(|()| {
+            Self::STORAGE
+                .update(storage, |c| -> ContractResult<Config> {
+                    Ok(Self {
+                        lease_max_duration,
+                        ..c
+                    })
+                })
+                .map(mem::drop)
+                .map_err(Into::into)
+        })
+    }
+
     pub fn update_lease_code(storage: &mut dyn Storage, new_code: Code) -> ContractResult<()> {
         Self::STORAGE
             .update(storage, |c| -> ContractResult<Config> {
@@ -88,6 +181,23 @@ impl Config {
     }
 }
 
+fn validate_max_duration(max_duration: Option<Duration>) -> ContractResult<()> {
+    if max_duration.is_some_and(|max_duration| max_duration == Duration::default()) {
+        Err(ContractError::ZeroLeaseMaxDuration())
+    } else {
+        Ok(())
+    }
+}
+
+fn validate_lease_assets<'assets, Assets>(mut assets: Assets) -> ContractResult<()>
+where
+    Assets: Iterator<Item = &'assets SymbolOwned>,
+{
+    assets
+        .try_for_each(|asset| currency::validate::<LeaseGroup>(asset))
+        .map_err(Into::into)
+}
+
 pub mod migrate {
     use cosmwasm_std::{Addr, Storage};
     use finance::{duration::Duration, percent::Percent};
@@ -124,7 +234,11 @@ pub mod migrate {
         }
     }
 
-    pub fn migrate(storage: &mut dyn Storage, protocols_registry: Addr) -> ContractResult<()> {
+    pub fn migrate(
+        storage: &mut dyn Storage,
+        protocols_registry: Addr,
+        lease_max_slippage: Percent,
+    ) -> ContractResult<()> {
         STORAGE_OLD
             .load(storage)
             .map_err(Into::into)
@@ -138,7 +252,11 @@ pub mod migrate {
                 protocols_registry,
                 lease_position_spec: old_cfg.lease_position_spec,
                 lease_interest_rate_margin: old_cfg.lease_interest_rate_margin,
+                lease_asset_specs: Default::default(),
+                lease_limits: Default::default(),
                 lease_due_period: old_cfg.lease_due_period,
+                lease_max_slippage,
+                lease_max_duration: None,
                 dex: old_cfg.dex,
             })
             .and_then(|config: Config| config.store(storage))
@@ -0,0 +1,320 @@
+use serde::{Deserialize, Serialize};
+
+use currency::{SymbolOwned, SymbolSlice};
+use finance::coin::Amount;
+use sdk::{
+    cosmwasm_std::{Addr, StdResult, Storage},
+    cw_storage_plus::{Item, Map},
+    schemars::{self, JsonSchema},
+};
+
+use crate::{
+    msg::{AssetLimits, MaxLeases},
+    result::ContractResult,
+    ContractError,
+};
+
+/// The exposure of the protocol to a lease asset
+///
+/// Only the leases opened while the asset has limits are accounted for,
+/// at the amount borrowed on their opening.
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Debug))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct AssetExposure {
+    /// The total amount of LPN borrowed
+    pub borrow: Amount,
+    /// The number of open leases
+    pub leases: MaxLeases,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LeaseExposure {
+    asset: SymbolOwned,
+    borrow: Amount,
+}
+
+pub(crate) struct Exposure {}
+
+impl Exposure {
+    const PENDING_LEASE: Item<'static, LeaseExposure> = Item::new("pending_lease_exposure");
+    const ASSETS: Map<'static, SymbolOwned, AssetExposure> = Map::new("asset_exposure");
+    const LEASES: Map<'static, Addr, LeaseExposure> = Map::new("lease_exposure");
+
+    pub fn load(storage: &dyn Storage, asset: &SymbolSlice) -> ContractResult<AssetExposure> {
+        Self::ASSETS
+            .may_load(storage, asset.into())
+            .map(Option::unwrap_or_default)
+            .map_err(Into::into)
+    }
+
+    /// Check if a new lease of `asset` borrowing `borrow` fits in the asset limits
+    pub fn check(
+        storage: &dyn Storage,
+        asset: &SymbolSlice,
+        limits: &AssetLimits,
+        borrow: Amount,
+    ) -> ContractResult<()> {
+        Self::load(storage, asset).and_then(|exposure| {
+            if limits
+                .max_leases
+                .is_some_and(|max_leases| max_leases <= exposure.leases)
+            {
+                Err(ContractError::AssetLeasesCap {
+                    asset: asset.into(),
+                })
+            } else if limits.max_borrow.as_ref().is_some_and(|max_borrow| {
+                max_borrow.amount() < exposure.borrow.saturating_add(borrow)
+            }) {
+                Err(ContractError::AssetBorrowCap {
+                    asset: asset.into(),
+                })
+            } else {
+                Ok(())
+            }
+        })
+    }
+
+    /// Account for a lease about to be open
+    ///
+    /// The lease gets bound to the exposure on [`Exposure::save`].
+    pub fn open(
+        storage: &mut dyn Storage,
+        asset: &SymbolSlice,
+        limits: &AssetLimits,
+        borrow: Amount,
+    ) -> ContractResult<()> {
+        Self::check(storage, asset, limits, borrow)
+            .and_then(|()| {
+                Self::ASSETS
+                    .update(storage, asset.into(), |may_exposure| {
+                        let exposure = may_exposure.unwrap_or_default();
+                        StdResult::Ok(AssetExposure {
+                            borrow: exposure.borrow.saturating_add(borrow),
+                            leases: exposure.leases.saturating_add(1),
+                        })
+                    })
+                    .map_err(Into::into)
+            })
+            .and_then(|_| {
+                Self::PENDING_LEASE
+                    .save(
+                        storage,
+                        &LeaseExposure {
+                            asset: asset.into(),
+                            borrow,
+                        },
+                    )
+                    .map_err(Into::into)
+            })
+    }
+
+    /// Bind the exposure of the last open lease, if accounted for, to its address
+    pub fn save(storage: &mut dyn Storage, lease: Addr) -> ContractResult<()> {
+        Self::PENDING_LEASE
+            .may_load(storage)
+            .map_err(Into::into)
+            .and_then(|may_pending| {
+                may_pending.map_or(Ok(()), |pending| {
+                    Self::PENDING_LEASE.remove(storage);
+                    Self::LEASES
+                        .save(storage, lease, &pending)
+                        .map_err(Into::into)
+                })
+            })
+    }
+
+    /// Move the exposure of a lease to the lease it has just been merged into
+    ///
+    /// The borrowed amount stays intact. The asset accounts for one lease less
+    /// unless the other lease has not been accounted for so far.
+    pub fn merge(storage: &mut dyn Storage, lease: Addr, into: Addr) -> ContractResult<()> {
+        let Some(LeaseExposure { asset, borrow }) =
+            Self::LEASES.may_load(storage, lease.clone())?
+        else {
+            return Ok(());
+        };
+        Self::LEASES.remove(storage, lease);
+
+        let may_into_exposure = Self::LEASES.may_load(storage, into.clone())?;
+        let merged_leases = MaxLeases::from(may_into_exposure.is_some());
+        let into_borrow = may_into_exposure.map_or(Amount::default(), |exposure| exposure.borrow);
+        Self::LEASES.save(
+            storage,
+            into,
+            &LeaseExposure {
+                asset: asset.clone(),
+                borrow: into_borrow.saturating_add(borrow),
+            },
+        )?;
+
+        Self::ASSETS
+            .update(storage, asset, |may_exposure| {
+                let exposure = may_exposure.unwrap_or_default();
+                StdResult::Ok(AssetExposure {
+                    borrow: exposure.borrow,
+                    leases: exposure.leases.saturating_sub(merged_leases),
+                })
+            })
+            .map(|_| ())
+            .map_err(Into::into)
+    }
+
+    /// Release the exposure of a lease that has just been finalized
+    pub fn release(storage: &mut dyn Storage, lease: Addr) -> ContractResult<()> {
+        Self::LEASES
+            .may_load(storage, lease.clone())
+            .map_err(Into::into)
+            .and_then(|may_lease_exposure| {
+                may_lease_exposure.map_or(Ok(()), |LeaseExposure { asset, borrow }| {
+                    Self::LEASES.remove(storage, lease);
+                    Self::ASSETS
+                        .update(storage, asset, |may_exposure| {
+                            let exposure = may_exposure.unwrap_or_default();
+                            StdResult::Ok(AssetExposure {
+                                borrow: exposure.borrow.saturating_sub(borrow),
+                                leases: exposure.leases.saturating_sub(1),
+                            })
+                        })
+                        .map(|_| ())
+                        .map_err(Into::into)
+                })
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use currencies::test::{LeaseC1, LeaseC2, LpnC};
+    use currency::Currency;
+    use finance::coin::Coin;
+    use sdk::cosmwasm_std::{testing::MockStorage, Addr};
+
+    use crate::{msg::AssetLimits, ContractError};
+
+    use super::{AssetExposure, Exposure};
+
+    #[test]
+    fn open_release() {
+        let mut storage = MockStorage::default();
+        let limits = AssetLimits {
+            max_borrow: Some(Coin::<LpnC>::from(1000).into()),
+            max_leases: Some(2),
+        };
+
+        Exposure::open(&mut storage, LeaseC1::TICKER, &limits, 600).unwrap();
+        Exposure::save(&mut storage, lease(1)).unwrap();
+        assert_eq!(
+            AssetExposure {
+                borrow: 600,
+                leases: 1
+            },
+            Exposure::load(&storage, LeaseC1::TICKER).unwrap()
+        );
+        assert_eq!(
+            AssetExposure::default(),
+            Exposure::load(&storage, LeaseC2::TICKER).unwrap()
+        );
+
+        assert_eq!(
+            Err(ContractError::AssetBorrowCap {
+                asset: LeaseC1::TICKER.into()
+            }),
+            Exposure::open(&mut storage, LeaseC1::TICKER, &limits, 401)
+        );
+        Exposure::open(&mut storage, LeaseC1::TICKER, &limits, 400).unwrap();
+        Exposure::save(&mut storage, lease(2)).unwrap();
+        assert_eq!(
+            Err(ContractError::AssetLeasesCap {
+                asset: LeaseC1::TICKER.into()
+            }),
+            Exposure::check(&storage, LeaseC1::TICKER, &limits, 0)
+        );
+
+        Exposure::release(&mut storage, lease(1)).unwrap();
+        assert_eq!(
+            AssetExposure {
+                borrow: 400,
+                leases: 1
+            },
+            Exposure::load(&storage, LeaseC1::TICKER).unwrap()
+        );
+        Exposure::check(&storage, LeaseC1::TICKER, &limits, 600).unwrap();
+
+        // not accounted leases
+        Exposure::release(&mut storage, lease(1)).unwrap();
+        Exposure::release(&mut storage, lease(3)).unwrap();
+        assert_eq!(
+            AssetExposure {
+                borrow: 400,
+                leases: 1
+            },
+            Exposure::load(&storage, LeaseC1::TICKER).unwrap()
+        );
+    }
+
+    #[test]
+    fn merge() {
+        let mut storage = MockStorage::default();
+        let limits = AssetLimits {
+            max_borrow: Some(Coin::<LpnC>::from(1000).into()),
+            max_leases: Some(3),
+        };
+
+        Exposure::open(&mut storage, LeaseC1::TICKER, &limits, 600).unwrap();
+        Exposure::save(&mut storage, lease(1)).unwrap();
+        Exposure::open(&mut storage, LeaseC1::TICKER, &limits, 300).unwrap();
+        Exposure::save(&mut storage, lease(2)).unwrap();
+
+        Exposure::merge(&mut storage, lease(1), lease(2)).unwrap();
+        assert_eq!(
+            AssetExposure {
+                borrow: 900,
+                leases: 1
+            },
+            Exposure::load(&storage, LeaseC1::TICKER).unwrap()
+        );
+
+        // the merged lease is not accounted anymore
+        Exposure::release(&mut storage, lease(1)).unwrap();
+        assert_eq!(
+            AssetExposure {
+                borrow: 900,
+                leases: 1
+            },
+            Exposure::load(&storage, LeaseC1::TICKER).unwrap()
+        );
+
+        // into a not accounted lease
+        Exposure::merge(&mut storage, lease(2), lease(3)).unwrap();
+        assert_eq!(
+            AssetExposure {
+                borrow: 900,
+                leases: 1
+            },
+            Exposure::load(&storage, LeaseC1::TICKER).unwrap()
+        );
+
+        Exposure::release(&mut storage, lease(3)).unwrap();
+        assert_eq!(
+            AssetExposure::default(),
+            Exposure::load(&storage, LeaseC1::TICKER).unwrap()
+        );
+    }
+
+    #[test]
+    fn save_not_accounted() {
+        let mut storage = MockStorage::default();
+
+        Exposure::save(&mut storage, lease(1)).unwrap();
+        Exposure::release(&mut storage, lease(1)).unwrap();
+        assert_eq!(
+            AssetExposure::default(),
+            Exposure::load(&storage, LeaseC1::TICKER).unwrap()
+        );
+    }
+
+    fn lease(id: u8) -> Addr {
+        Addr::unchecked(format!("lease{id}"))
+    }
+}
@@ -7,6 +7,7 @@ use sdk::{
 
 use crate::{
     migrate::{Customer, MaybeCustomer},
+    msg::LeaseKey,
     result::ContractResult,
 };
 
@@ -67,6 +68,27 @@ impl Leases {
             .map_err(Into::into)
     }
 
+    /// Move a lease from a customer to another
+    ///
+    /// Return whether the lease was present before the transfer.
+    pub fn transfer(
+        storage: &mut dyn Storage,
+        lease: Addr,
+        customer: Addr,
+        new_customer: Addr,
+    ) -> ContractResult<bool> {
+        Self::remove(storage, customer, &lease).and_then(|removed| {
+            Self::CUSTOMER_LEASES
+                .update(storage, new_customer, |may_leases| -> StdResult<_> {
+                    let mut leases = may_leases.unwrap_or_default();
+                    leases.insert(lease);
+                    Ok(leases)
+                })
+                .map(|_| removed)
+                .map_err(Into::into)
+        })
+    }
+
     pub fn iter(
         storage: &dyn Storage,
         next_customer: Option<Addr>,
@@ -81,13 +103,46 @@ impl Leases {
                     .map_err(Into::into)
             })
     }
+
+    /// Iterate over all leases past the given one
+    ///
+    /// The leases are ordered by customer, and then by lease address.
+    pub fn iter_after(
+        storage: &dyn Storage,
+        start_after: Option<LeaseKey>,
+    ) -> impl Iterator<Item = ContractResult<LeaseKey>> + '_ {
+        let next_customer = start_after.as_ref().map(|key| key.customer.clone());
+        Self::iter(storage, next_customer)
+            .flat_map(|maybe_customer| match maybe_customer {
+                Ok(customer) => {
+                    let (customer, leases) = customer.into_parts();
+                    let mut leases: Vec<Addr> = leases.collect();
+                    leases.sort();
+                    leases
+                        .into_iter()
+                        .map(|lease| {
+                            Ok(LeaseKey {
+                                customer: customer.clone(),
+                                lease,
+                            })
+                        })
+                        .collect()
+                }
+                Err(err) => vec![Err(err)],
+            })
+            .skip_while(move |maybe_key| {
+                start_after.as_ref().is_some_and(|start_after| {
+                    maybe_key.as_ref().is_ok_and(|key| key <= start_after)
+                })
+            })
+    }
 }
 
 #[cfg(test)]
 mod test {
     use sdk::cosmwasm_std::{testing::MockStorage, Addr, Storage};
 
-    use crate::{state::leases::Leases, ContractError};
+    use crate::{msg::LeaseKey, state::leases::Leases, ContractError};
 
     #[test]
     fn test_save_customer_not_cached() {
@@ -163,6 +218,95 @@ mod test {
         assert_lease_not_exist(&storage);
     }
 
+    #[test]
+    fn test_transfer() {
+        let mut storage = MockStorage::default();
+        let new_customer = Addr::unchecked("customerY");
+
+        Leases::cache_open_req(&mut storage, &test_customer()).unwrap();
+        Leases::save(&mut storage, test_lease()).unwrap();
+
+        assert_eq!(
+            Ok(true),
+            Leases::transfer(
+                &mut storage,
+                test_lease(),
+                test_customer(),
+                new_customer.clone()
+            )
+        );
+        assert_lease_not_exist(&storage);
+        assert!(Leases::load_by_customer(&storage, new_customer)
+            .unwrap()
+            .contains(&test_lease()));
+    }
+
+    #[test]
+    fn test_iter_after() {
+        let mut storage = MockStorage::default();
+        let customer1 = Addr::unchecked("customer1");
+        let customer2 = Addr::unchecked("customer2");
+        save_lease(&mut storage, &customer2, "lease3");
+        save_lease(&mut storage, &customer1, "lease2");
+        save_lease(&mut storage, &customer1, "lease1");
+        save_lease(&mut storage, &customer2, "lease4");
+
+        let all = vec![
+            key(&customer1, "lease1"),
+            key(&customer1, "lease2"),
+            key(&customer2, "lease3"),
+            key(&customer2, "lease4"),
+        ];
+        assert_eq!(all, collect_after(&storage, None, 10));
+        assert_eq!(all[..3], collect_after(&storage, None, 3));
+        assert_eq!(all[2..], collect_after(&storage, Some(all[1].clone()), 10));
+        assert_eq!(all[2..3], collect_after(&storage, Some(all[1].clone()), 1));
+        assert_eq!(all[3..], collect_after(&storage, Some(all[2].clone()), 10));
+        assert!(collect_after(&storage, Some(all[3].clone()), 10).is_empty());
+    }
+
+    #[test]
+    fn test_iter_after_removed() {
+        let mut storage = MockStorage::default();
+        let customer1 = Addr::unchecked("customer1");
+        let customer2 = Addr::unchecked("customer2");
+        save_lease(&mut storage, &customer1, "lease1");
+        save_lease(&mut storage, &customer1, "lease2");
+        save_lease(&mut storage, &customer2, "lease3");
+
+        assert_eq!(
+            Ok(true),
+            Leases::remove(&mut storage, customer1.clone(), &Addr::unchecked("lease2"))
+        );
+        assert_eq!(
+            vec![key(&customer2, "lease3")],
+            collect_after(&storage, Some(key(&customer1, "lease2")), 10)
+        );
+    }
+
+    fn save_lease(storage: &mut dyn Storage, customer: &Addr, lease: &str) {
+        Leases::cache_open_req(storage, customer).unwrap();
+        assert_eq!(Ok(true), Leases::save(storage, Addr::unchecked(lease)));
+    }
+
+    fn key(customer: &Addr, lease: &str) -> LeaseKey {
+        LeaseKey {
+            customer: customer.clone(),
+            lease: Addr::unchecked(lease),
+        }
+    }
+
+    fn collect_after(
+        storage: &dyn Storage,
+        start_after: Option<LeaseKey>,
+        limit: usize,
+    ) -> Vec<LeaseKey> {
+        Leases::iter_after(storage, start_after)
+            .take(limit)
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
     fn test_customer() -> Addr {
         const CUSTOMER: &str = "customerX";
         Addr::unchecked(CUSTOMER)
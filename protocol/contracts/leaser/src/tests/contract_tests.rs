@@ -1,15 +1,16 @@
 use serde::{Deserialize, Serialize};
 
-use currencies::test::LpnC;
+use currencies::test::{LeaseC1, LeaseC2, LpnC};
 use currency::{Currency, SymbolStatic};
 use finance::{
     coin::{Amount, Coin},
     duration::Duration,
+    error::Error as FinanceError,
     liability::Liability,
     percent::Percent,
 };
 use lease::api::{
-    open::{ConnectionParams, Ics20Channel, InterestPaymentSpec, PositionSpecDTO},
+    open::{ConnectionParams, IbcParams, Ics20Channel, InterestPaymentSpec, PositionSpecDTO},
     LpnCoinDTO,
 };
 use platform::contract::{Code, CodeId};
@@ -27,8 +28,11 @@ use sdk::{
 use crate::{
     cmd::Borrow,
     contract::{execute, instantiate, query, sudo},
-    msg::{ConfigResponse, ExecuteMsg, QueryMsg, SudoMsg},
-    state::config::Config,
+    msg::{
+        AssetLimits, ConfigResponse, ExecuteMsg, LeaseAssetSpec, LeaseLimits, QueryMsg, SudoMsg,
+    },
+    state::{config::Config, leases::Leases},
+    ContractError,
 };
 
 const CREATOR: &str = "creator";
@@ -67,7 +71,10 @@ fn leaser_instantiate_msg(lease_code: Code, lpp: Addr) -> crate::msg::Instantiat
             lpn_coin(10),
         ),
         lease_interest_rate_margin: MARGIN_INTEREST_RATE,
+        lease_asset_specs: Default::default(),
         lease_due_period: Duration::from_days(90),
+        lease_max_slippage: Percent::from_percent(5),
+        lease_max_duration: None,
         dex: dex_params(),
     }
 }
@@ -101,6 +108,7 @@ fn dex_params() -> ConnectionParams {
             local_endpoint: "channel-0".into(),
             remote_endpoint: "channel-2048".into(),
         },
+        ibc: IbcParams::default(),
     }
 }
 
@@ -142,13 +150,16 @@ fn test_update_config() {
         lpn_coin(100_000),
     );
     let expected_due_period = Duration::from_secs(100);
+    let expected_max_slippage = Percent::from_permille(25);
 
     setup_test_case(deps.as_mut());
 
     let msg = SudoMsg::Config {
         lease_interest_rate_margin: Percent::from_percent(5),
         lease_position_spec: expected_position_spec.clone(),
+        lease_asset_specs: Default::default(),
         lease_due_period: expected_due_period,
+        lease_max_slippage: expected_max_slippage,
     };
 
     sudo(deps.as_mut(), mock_env(), msg).unwrap();
@@ -156,6 +167,96 @@ fn test_update_config() {
     let config = query_config(deps.as_ref());
     assert_eq!(expected_position_spec, config.lease_position_spec);
     assert_eq!(expected_due_period, config.lease_due_period);
+    assert_eq!(expected_max_slippage, config.lease_max_slippage);
+}
+
+#[test]
+fn test_update_lease_max_duration() {
+    let mut deps = mock_deps_with_contracts([LPP_ADDR, TIMEALARMS_ADDR, PROFIT_ADDR, ORACLE_ADDR]);
+
+    setup_test_case(deps.as_mut());
+    assert_eq!(None, query_config(deps.as_ref()).lease_max_duration);
+
+    let max_duration = Duration::from_days(365);
+    sudo(
+        deps.as_mut(),
+        mock_env(),
+        SudoMsg::LeaseMaxDuration {
+            max_duration: Some(max_duration),
+        },
+    )
+    .unwrap();
+
+    let config = query_config(deps.as_ref());
+    assert_eq!(Some(max_duration), config.lease_max_duration);
+
+    let form = Borrow::open_lease_msg(
+        customer().sender,
+        config,
+        DENOM.to_string(),
+        None,
+        mock_env().contract.address,
+    )
+    .form;
+    assert_eq!(Some(max_duration), form.max_duration);
+}
+
+#[test]
+fn test_zero_lease_max_duration() {
+    let mut deps = mock_deps_with_contracts([LPP_ADDR, TIMEALARMS_ADDR, PROFIT_ADDR, ORACLE_ADDR]);
+
+    let mut msg = leaser_instantiate_msg(Code::unchecked(1), Addr::unchecked(LPP_ADDR));
+    msg.lease_max_duration = Some(Duration::default());
+    assert_eq!(
+        Err(ContractError::ZeroLeaseMaxDuration()),
+        instantiate(deps.as_mut(), mock_env(), owner(), msg)
+    );
+
+    setup_test_case(deps.as_mut());
+
+    assert_eq!(
+        Err(ContractError::ZeroLeaseMaxDuration()),
+        sudo(
+            deps.as_mut(),
+            mock_env(),
+            SudoMsg::LeaseMaxDuration {
+                max_duration: Some(Duration::default()),
+            },
+        )
+    );
+    assert_eq!(None, query_config(deps.as_ref()).lease_max_duration);
+}
+
+#[test]
+fn test_update_dex_ibc() {
+    let mut deps = mock_deps_with_contracts([LPP_ADDR, TIMEALARMS_ADDR, PROFIT_ADDR, ORACLE_ADDR]);
+
+    setup_test_case(deps.as_mut());
+    assert_eq!(dex_params(), query_config(deps.as_ref()).dex);
+
+    let expected_ibc = IbcParams {
+        timeout: Duration::from_minutes(5),
+        transfer_ack_tip: Coin::new(1_000),
+        transfer_timeout_tip: Coin::new(2_000),
+        ica_ack_tip: Coin::new(3_000),
+        ica_timeout_tip: Coin::new(4_000),
+    };
+    sudo(
+        deps.as_mut(),
+        mock_env(),
+        SudoMsg::DexIbc {
+            ibc: expected_ibc.clone(),
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        ConnectionParams {
+            ibc: expected_ibc,
+            ..dex_params()
+        },
+        query_config(deps.as_ref()).dex
+    );
 }
 
 #[test]
@@ -232,6 +333,163 @@ fn open_lease_with(max_ltd: Option<Percent>) {
     );
 }
 
+#[test]
+fn test_open_lease_asset_spec() {
+    let mut deps = mock_deps_with_contracts([LPP_ADDR, TIMEALARMS_ADDR, PROFIT_ADDR, ORACLE_ADDR]);
+
+    let asset_spec = LeaseAssetSpec {
+        position_spec: PositionSpecDTO::new(
+            Liability::new(
+                Percent::from_percent(50),
+                Percent::from_percent(55),
+                Percent::from_percent(58),
+                Percent::from_percent(60),
+                Percent::from_percent(62),
+                Percent::from_percent(65),
+                Duration::from_hours(1),
+            ),
+            lpn_coin(2000),
+            lpn_coin(20),
+        ),
+        interest_rate_margin: Percent::from_permille(45),
+    };
+    let mut msg = leaser_instantiate_msg(Code::unchecked(1), Addr::unchecked(LPP_ADDR));
+    msg.lease_asset_specs
+        .insert(LeaseC1::TICKER.into(), asset_spec.clone());
+    instantiate(deps.as_mut(), mock_env(), owner(), msg).unwrap();
+
+    let config = query_config(deps.as_ref());
+    let finalizer = mock_env().contract.address;
+
+    let form = Borrow::open_lease_msg(
+        customer().sender,
+        config.clone(),
+        LeaseC1::TICKER.into(),
+        None,
+        finalizer.clone(),
+    )
+    .form;
+    assert_eq!(asset_spec.position_spec, form.position_spec);
+    assert_eq!(
+        asset_spec.interest_rate_margin,
+        form.loan.annual_margin_interest
+    );
+
+    let form = Borrow::open_lease_msg(
+        customer().sender,
+        config.clone(),
+        LeaseC2::TICKER.into(),
+        None,
+        finalizer,
+    )
+    .form;
+    assert_eq!(config.lease_position_spec, form.position_spec);
+    assert_eq!(MARGIN_INTEREST_RATE, form.loan.annual_margin_interest);
+}
+
+#[test]
+fn test_invalid_lease_asset_spec() {
+    let mut deps = mock_deps_with_contracts([LPP_ADDR, TIMEALARMS_ADDR, PROFIT_ADDR, ORACLE_ADDR]);
+
+    let mut msg = leaser_instantiate_msg(Code::unchecked(1), Addr::unchecked(LPP_ADDR));
+    msg.lease_asset_specs.insert(
+        "UNKNOWN".into(),
+        LeaseAssetSpec {
+            position_spec: msg.lease_position_spec.clone(),
+            interest_rate_margin: MARGIN_INTEREST_RATE,
+        },
+    );
+    assert!(instantiate(deps.as_mut(), mock_env(), owner(), msg).is_err());
+}
+
+#[test]
+fn test_customer_leases_cap() {
+    let mut deps = mock_deps_with_contracts([LPP_ADDR, TIMEALARMS_ADDR, PROFIT_ADDR, ORACLE_ADDR]);
+
+    setup_test_case(deps.as_mut());
+
+    let limits = LeaseLimits {
+        assets: Default::default(),
+        max_customer_leases: Some(1),
+    };
+    sudo(
+        deps.as_mut(),
+        mock_env(),
+        SudoMsg::Limits {
+            limits: limits.clone(),
+        },
+    )
+    .unwrap();
+    assert_eq!(limits, query_config(deps.as_ref()).lease_limits);
+
+    Leases::cache_open_req(deps.as_mut().storage, &customer().sender).unwrap();
+    Leases::save(deps.as_mut().storage, Addr::unchecked("lease1")).unwrap();
+
+    assert_eq!(
+        Err(ContractError::CustomerLeasesCap {}),
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            customer(),
+            ExecuteMsg::OpenLease {
+                currency: DENOM.to_string(),
+                max_ltd: None,
+            },
+        )
+    );
+}
+
+#[test]
+fn test_invalid_asset_limits() {
+    let mut deps = mock_deps_with_contracts([LPP_ADDR, TIMEALARMS_ADDR, PROFIT_ADDR, ORACLE_ADDR]);
+
+    setup_test_case(deps.as_mut());
+
+    let limits = LeaseLimits {
+        assets: [("UNKNOWN".into(), AssetLimits::default())].into(),
+        max_customer_leases: None,
+    };
+    assert!(sudo(deps.as_mut(), mock_env(), SudoMsg::Limits { limits }).is_err());
+}
+
+#[test]
+fn test_invalid_max_slippage() {
+    let mut deps = mock_deps_with_contracts([LPP_ADDR, TIMEALARMS_ADDR, PROFIT_ADDR, ORACLE_ADDR]);
+
+    let invalid_max_slippage = Percent::from_percent(101);
+    let max_slippage_err = || {
+        Err(ContractError::Finance(FinanceError::UpperBoundCrossed {
+            bound: Percent::HUNDRED.units(),
+            value: invalid_max_slippage.units(),
+        }))
+    };
+
+    let mut msg = leaser_instantiate_msg(Code::unchecked(1), Addr::unchecked(LPP_ADDR));
+    msg.lease_max_slippage = invalid_max_slippage;
+    assert_eq!(
+        max_slippage_err(),
+        instantiate(deps.as_mut(), mock_env(), owner(), msg)
+    );
+
+    setup_test_case(deps.as_mut());
+
+    let config = query_config(deps.as_ref());
+    assert_eq!(
+        max_slippage_err(),
+        sudo(
+            deps.as_mut(),
+            mock_env(),
+            SudoMsg::Config {
+                lease_interest_rate_margin: config.lease_interest_rate_margin,
+                lease_position_spec: config.lease_position_spec,
+                lease_asset_specs: config.lease_asset_specs,
+                lease_due_period: config.lease_due_period,
+                lease_max_slippage: invalid_max_slippage,
+            },
+        )
+    );
+}
+
 #[test]
 fn test_open_lease() {
     open_lease_with(None);
@@ -243,6 +501,36 @@ fn test_open_lease_with_max_ltd() {
     open_lease_with(Some(Percent::from_percent(5)));
 }
 
+#[test]
+fn test_is_lease() {
+    const LEASE_ADDR: &str = "lease";
+
+    let mut deps = mock_deps_with_contracts([
+        LPP_ADDR,
+        TIMEALARMS_ADDR,
+        PROFIT_ADDR,
+        ORACLE_ADDR,
+        LEASE_ADDR,
+    ]);
+    // the mock querier reports the default code id of every known contract
+    let msg = leaser_instantiate_msg(Code::unchecked(0), Addr::unchecked(LPP_ADDR));
+    instantiate(deps.as_mut(), mock_env(), owner(), msg).unwrap();
+
+    let is_lease = |address: &str| -> bool {
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::IsLease {
+                address: Addr::unchecked(address),
+            },
+        )
+        .unwrap();
+        from_json(res).unwrap()
+    };
+    assert!(is_lease(LEASE_ADDR));
+    assert!(!is_lease(customer().sender.as_str()));
+}
+
 fn lpn_coin(amount: Amount) -> LpnCoinDTO {
     Coin::<TheCurrency>::from(amount).into()
 }
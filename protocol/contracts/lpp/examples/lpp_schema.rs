@@ -1,7 +1,7 @@
 use currencies::Lpns;
 use lpp::msg::{
     BalanceResponse, ExecuteMsg, InstantiateMsg, LoanResponse, PriceResponse, QueryLoanResponse,
-    QueryMsg, QueryQuoteResponse, RewardsResponse,
+    QueryMsg, QueryQuoteResponse, RewardsResponse, WithdrawRequestResponse,
 };
 use lpp_platform::Stable;
 use sdk::cosmwasm_schema::{export_schema, schema_for};
@@ -20,4 +20,5 @@ fn main() {
     export_schema(&schema_for!(BalanceResponse), &out_dir);
     export_schema(&schema_for!(PriceResponse<Stable>), &out_dir);
     export_schema(&schema_for!(RewardsResponse), &out_dir);
+    export_schema(&schema_for!(WithdrawRequestResponse<Lpns>), &out_dir);
 }
@@ -8,12 +8,54 @@ use finance::{
 };
 use sdk::schemars::{self, JsonSchema};
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+/// The borrow interest rate curve
+///
+/// Up to the optimal utilization the rate is
+/// `base_interest_rate + addon_optimal_interest_rate * utilization / utilization_optimal`,
+/// where `utilization` is the ratio of the total liability to the balance.
+/// Above the optimal utilization the rate goes linearly through the `kinks`, if any,
+/// and stays flat past the last one.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(try_from = "UncheckedInterestRate")]
 pub struct InterestRate {
     base_interest_rate: Percent,
     utilization_optimal: Percent,
     addon_optimal_interest_rate: Percent,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    kinks: Vec<Kink>,
+}
+
+/// A point of the curve above the optimal utilization
+///
+/// Here, `utilization` is the ratio of the total liability to the sum of the total
+/// liability and the balance.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct Kink {
+    pub utilization: Percent,
+    pub interest_rate: Percent,
+}
+
+/// The segment of the curve a rate is taken from
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub enum CurveSegment {
+    /// Up to the optimal utilization
+    BelowOptimal,
+    /// Above the optimal utilization, or the previous kink, up to the kink with this index
+    ToKink(u32),
+    /// Past the last kink, or past the optimal utilization if there are no kinks
+    Capped,
+}
+
+/// A point on the curve
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct RatePosition {
+    pub annual_interest_rate: Percent,
+    /// The ratio of the total liability to the sum of the total liability and the balance
+    pub utilization: Percent,
+    pub segment: CurveSegment,
 }
 
 impl InterestRate {
@@ -30,6 +72,11 @@ impl InterestRate {
         )
     }
 
+    #[cfg(any(test, feature = "testing"))]
+    pub fn with_kinks(self, kinks: Vec<Kink>) -> Option<Self> {
+        self.private_with_kinks(kinks)
+    }
+
     fn private_new(
         base_interest_rate: Percent,
         utilization_optimal: Percent,
@@ -39,11 +86,18 @@ impl InterestRate {
             base_interest_rate,
             utilization_optimal,
             addon_optimal_interest_rate,
+            kinks: vec![],
         };
 
         value.validate().then_some(value)
     }
 
+    fn private_with_kinks(self, kinks: Vec<Kink>) -> Option<Self> {
+        let value = Self { kinks, ..self };
+
+        value.validate_kinks().then_some(value)
+    }
+
     pub fn base_interest_rate(&self) -> Percent {
         self.base_interest_rate
     }
@@ -56,26 +110,92 @@ impl InterestRate {
         self.addon_optimal_interest_rate
     }
 
+    pub fn kinks(&self) -> &[Kink] {
+        &self.kinks
+    }
+
     pub fn calculate<Lpn>(&self, total_liability: Coin<Lpn>, balance: Coin<Lpn>) -> Percent
     where
         Lpn: ?Sized + PartialEq,
     {
-        let utilization_max = Percent::from_ratio(
-            self.utilization_optimal.units(),
-            (Percent::HUNDRED - self.utilization_optimal).units(),
-        );
+        self.position(total_liability, balance).annual_interest_rate
+    }
+
+    pub fn position<Lpn>(&self, total_liability: Coin<Lpn>, balance: Coin<Lpn>) -> RatePosition
+    where
+        Lpn: ?Sized + PartialEq,
+    {
         let utilization = if balance.is_zero() {
-            utilization_max
+            Percent::HUNDRED
         } else {
-            Percent::from_ratio(total_liability, balance).min(utilization_max)
+            Percent::from_ratio(total_liability, total_liability + balance)
         };
 
+        let utilization_max = self.liability_to_balance_max();
+        let below_optimal = (!balance.is_zero())
+            .then(|| Percent::from_ratio(total_liability, balance))
+            .filter(|&liability_to_balance| liability_to_balance < utilization_max);
+
+        if let Some(liability_to_balance) = below_optimal {
+            RatePosition {
+                annual_interest_rate: self.rate_below_optimal(liability_to_balance),
+                utilization,
+                segment: CurveSegment::BelowOptimal,
+            }
+        } else {
+            self.position_above_optimal(utilization.max(self.utilization_optimal))
+        }
+    }
+
+    /// The ratio of the total liability to the balance at the optimal utilization
+    fn liability_to_balance_max(&self) -> Percent {
+        Percent::from_ratio(
+            self.utilization_optimal.units(),
+            (Percent::HUNDRED - self.utilization_optimal).units(),
+        )
+    }
+
+    fn rate_below_optimal(&self, liability_to_balance: Percent) -> Percent {
         let config = Rational::new(
             self.addon_optimal_interest_rate.units(),
             self.utilization_optimal.units(),
         );
 
-        self.base_interest_rate + Fraction::<Units>::of(&config, utilization)
+        self.base_interest_rate + Fraction::<Units>::of(&config, liability_to_balance)
+    }
+
+    fn rate_at_optimal(&self) -> Percent {
+        self.rate_below_optimal(self.liability_to_balance_max())
+    }
+
+    fn position_above_optimal(&self, utilization: Percent) -> RatePosition {
+        let mut from = Kink {
+            utilization: self.utilization_optimal,
+            interest_rate: self.rate_at_optimal(),
+        };
+
+        for (index, kink) in (0..).zip(self.kinks.iter()) {
+            if utilization <= kink.utilization {
+                let slope = Rational::new(
+                    (utilization - from.utilization).units(),
+                    (kink.utilization - from.utilization).units(),
+                );
+
+                return RatePosition {
+                    annual_interest_rate: from.interest_rate
+                        + Fraction::<Units>::of(&slope, kink.interest_rate - from.interest_rate),
+                    utilization,
+                    segment: CurveSegment::ToKink(index),
+                };
+            }
+            from = *kink;
+        }
+
+        RatePosition {
+            annual_interest_rate: from.interest_rate,
+            utilization,
+            segment: CurveSegment::Capped,
+        }
     }
 
     fn validate(&self) -> bool {
@@ -84,6 +204,24 @@ impl InterestRate {
             && self.utilization_optimal < Percent::HUNDRED
             && self.addon_optimal_interest_rate <= Percent::HUNDRED
     }
+
+    fn validate_kinks(&self) -> bool {
+        self.kinks
+            .iter()
+            .try_fold(
+                Kink {
+                    utilization: self.utilization_optimal,
+                    interest_rate: self.rate_at_optimal(),
+                },
+                |prev, &kink| {
+                    (prev.utilization < kink.utilization
+                        && kink.utilization <= Percent::HUNDRED
+                        && prev.interest_rate <= kink.interest_rate)
+                        .then_some(kink)
+                },
+            )
+            .is_some()
+    }
 }
 
 impl TryFrom<UncheckedInterestRate> for InterestRate {
@@ -96,6 +234,11 @@ impl TryFrom<UncheckedInterestRate> for InterestRate {
             value.addon_optimal_interest_rate,
         )
         .ok_or("Rates should not be greater than a hundred percent!")
+        .and_then(|rate| {
+            rate.private_with_kinks(value.kinks).ok_or(
+                "Kinks should be above the optimal utilization, ordered by utilization, and with non-decreasing rates!",
+            )
+        })
     }
 }
 
@@ -104,6 +247,8 @@ struct UncheckedInterestRate {
     base_interest_rate: Percent,
     utilization_optimal: Percent,
     addon_optimal_interest_rate: Percent,
+    #[serde(default)]
+    kinks: Vec<Kink>,
 }
 
 #[cfg(test)]
@@ -167,6 +312,110 @@ mod tests {
         .is_none());
     }
 
+    mod kinks {
+        use finance::{coin::Coin, percent::Percent};
+        use lpp_platform::NLpn;
+        use sdk::cosmwasm_std::{from_json, to_json_vec, StdError};
+
+        use crate::borrow::{CurveSegment, InterestRate, Kink, RatePosition};
+
+        fn kink(utilization: u32, interest_rate: u32) -> Kink {
+            Kink {
+                utilization: Percent::from_permille(utilization),
+                interest_rate: Percent::from_permille(interest_rate),
+            }
+        }
+
+        fn rate(kinks: Vec<Kink>) -> Option<InterestRate> {
+            InterestRate::new(
+                Percent::from_permille(100),
+                Percent::from_permille(700),
+                Percent::from_permille(20),
+            )
+            .unwrap()
+            .with_kinks(kinks)
+        }
+
+        fn assert_position(
+            rate: &InterestRate,
+            liability: u128,
+            balance: u128,
+            annual_interest_rate: u32,
+            utilization: u32,
+            segment: CurveSegment,
+        ) {
+            assert_eq!(
+                RatePosition {
+                    annual_interest_rate: Percent::from_permille(annual_interest_rate),
+                    utilization: Percent::from_permille(utilization),
+                    segment,
+                },
+                rate.position::<NLpn>(Coin::new(liability), Coin::new(balance))
+            );
+        }
+
+        #[test]
+        fn validate() {
+            assert!(rate(vec![]).is_some());
+            assert!(rate(vec![kink(800, 300), kink(900, 1000)]).is_some());
+            assert!(rate(vec![kink(800, 300), kink(1000, 300)]).is_some());
+
+            assert!(rate(vec![kink(700, 300)]).is_none());
+            assert!(rate(vec![kink(800, 100)]).is_none());
+            assert!(rate(vec![kink(800, 300), kink(800, 1000)]).is_none());
+            assert!(rate(vec![kink(800, 300), kink(900, 200)]).is_none());
+            assert!(rate(vec![kink(800, 300), kink(1001, 1000)]).is_none());
+        }
+
+        #[test]
+        fn no_kinks() {
+            let rate = rate(vec![]).unwrap();
+
+            assert_position(&rate, 5, 5, 128, 500, CurveSegment::BelowOptimal);
+            assert_position(&rate, 7, 3, 166, 700, CurveSegment::Capped);
+            assert_position(&rate, 9, 1, 166, 900, CurveSegment::Capped);
+            assert_position(&rate, 0, 0, 166, 1000, CurveSegment::Capped);
+        }
+
+        #[test]
+        fn multiple_kinks() {
+            let rate = rate(vec![kink(800, 300), kink(900, 1000)]).unwrap();
+
+            assert_position(&rate, 5, 5, 128, 500, CurveSegment::BelowOptimal);
+            assert_position(&rate, 7, 3, 166, 700, CurveSegment::ToKink(0));
+            assert_position(&rate, 75, 25, 233, 750, CurveSegment::ToKink(0));
+            assert_position(&rate, 8, 2, 300, 800, CurveSegment::ToKink(0));
+            assert_position(&rate, 85, 15, 650, 850, CurveSegment::ToKink(1));
+            assert_position(&rate, 95, 5, 1000, 950, CurveSegment::Capped);
+            assert_position(&rate, 10, 0, 1000, 1000, CurveSegment::Capped);
+        }
+
+        #[test]
+        fn backward_compatible_json() {
+            const JSON: &str = r#"{"base_interest_rate":100,"utilization_optimal":700,"addon_optimal_interest_rate":20}"#;
+
+            let rate_no_kinks = rate(vec![]).unwrap();
+            assert_eq!(Ok(rate_no_kinks.clone()), from_json(JSON));
+            assert_eq!(JSON.as_bytes(), to_json_vec(&rate_no_kinks).unwrap());
+        }
+
+        #[test]
+        fn kinks_json() {
+            const JSON: &str = r#"{"base_interest_rate":100,"utilization_optimal":700,"addon_optimal_interest_rate":20,"kinks":[{"utilization":800,"interest_rate":300}]}"#;
+
+            let rate_kinks = rate(vec![kink(800, 300)]).unwrap();
+            assert_eq!(Ok(rate_kinks.clone()), from_json(JSON));
+            assert_eq!(JSON.as_bytes(), to_json_vec(&rate_kinks).unwrap());
+
+            assert!(matches!(
+                from_json::<InterestRate>(
+                    r#"{"base_interest_rate":100,"utilization_optimal":700,"addon_optimal_interest_rate":20,"kinks":[{"utilization":600,"interest_rate":300}]}"#
+                ),
+                Err(StdError::ParseErr { .. })
+            ));
+        }
+    }
+
     /// Test suit specifically for verifying correctness of [`InterestRate::calculate`](InterestRate::calculate).cargo fmt
     mod calculate {
         use crate::borrow::InterestRate;
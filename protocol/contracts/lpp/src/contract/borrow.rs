@@ -11,9 +11,14 @@ use crate::{
     error::Result,
     loan::Loan,
     lpp::LiquidityPool,
-    msg::{LoanResponse, QueryLoanResponse, QueryQuoteResponse},
+    msg::{
+        LoanResponse, QueryLoanResponse, QueryQuoteDetailsResponse, QueryQuoteResponse,
+        QueryRefinanceQuoteResponse,
+    },
 };
 
+use super::lender;
+
 pub(super) fn try_open_loan<Lpn>(
     mut deps: DepsMut<'_>,
     env: Env,
@@ -37,6 +42,75 @@ where
     Ok((loan, messages.into()))
 }
 
+pub(super) fn try_increase_loan<Lpn>(
+    mut deps: DepsMut<'_>,
+    env: Env,
+    info: MessageInfo,
+    amount: Coin<Lpn>,
+) -> Result<(LoanResponse<Lpn>, MessageResponse)>
+where
+    Lpn: 'static + Currency,
+{
+    let lease_addr = info.sender;
+    let mut lpp = LiquidityPool::<Lpn>::load(deps.storage)?;
+    lpp.validate_lease_addr(&deps.as_ref(), &lease_addr)?;
+
+    let loan = lpp.try_increase_loan(&mut deps, &env, lease_addr.clone(), amount)?;
+
+    let mut bank = bank::account(&env.contract.address, deps.querier);
+    bank.send(amount, lease_addr);
+
+    let messages: Batch = bank.into();
+
+    Ok((loan, messages.into()))
+}
+
+pub(super) fn try_refinance_loan<Lpn>(
+    mut deps: DepsMut<'_>,
+    env: Env,
+    info: MessageInfo,
+) -> Result<(LoanResponse<Lpn>, MessageResponse)>
+where
+    Lpn: 'static + Currency,
+{
+    let lease_addr = info.sender;
+    let payment = bank::received_one(info.funds)?;
+
+    let mut lpp = LiquidityPool::<Lpn>::load(deps.storage)?;
+    lpp.validate_lease_addr(&deps.as_ref(), &lease_addr)?;
+    let (loan, excess_received) =
+        lpp.try_refinance_loan(&mut deps, &env, lease_addr.clone(), payment)?;
+
+    let fill_response =
+        lender::fill_withdraw_queue(deps.storage, deps.querier, &env, excess_received)?;
+
+    let batch = if excess_received.is_zero() {
+        Batch::default()
+    } else {
+        let mut bank = bank::account(&env.contract.address, deps.querier);
+        bank.send(excess_received, lease_addr);
+        bank.into()
+    };
+    Ok((loan, MessageResponse::from(batch).merge_with(fill_response)))
+}
+
+pub(super) fn try_merge_loan<Lpn>(
+    mut deps: DepsMut<'_>,
+    env: Env,
+    from: Addr,
+    into: Addr,
+) -> Result<MessageResponse>
+where
+    Lpn: 'static + Currency,
+{
+    let mut lpp = LiquidityPool::<Lpn>::load(deps.storage)?;
+    lpp.validate_lease_addr(&deps.as_ref(), &from)?;
+    lpp.validate_lease_addr(&deps.as_ref(), &into)?;
+
+    lpp.try_merge_loan(&mut deps, &env, from, into)
+        .map(|_| MessageResponse::default())
+}
+
 pub(super) fn try_repay_loan<Lpn>(
     mut deps: DepsMut<'_>,
     env: Env,
@@ -52,6 +126,9 @@ where
     lpp.validate_lease_addr(&deps.as_ref(), &lease_addr)?;
     let excess_received = lpp.try_repay_loan(&mut deps, &env, lease_addr.clone(), repay_amount)?;
 
+    let fill_response =
+        lender::fill_withdraw_queue(deps.storage, deps.querier, &env, excess_received)?;
+
     let batch = if excess_received.is_zero() {
         Batch::default()
     } else {
@@ -59,7 +136,10 @@ where
         bank.send(excess_received, lease_addr);
         bank.into()
     };
-    Ok((excess_received, batch.into()))
+    Ok((
+        excess_received,
+        MessageResponse::from(batch).merge_with(fill_response),
+    ))
 }
 
 pub(super) fn query_quote<Lpn>(
@@ -78,6 +158,34 @@ where
     }
 }
 
+pub(super) fn query_quote_details<Lpn>(
+    deps: &Deps<'_>,
+    env: &Env,
+    quote: Coin<Lpn>,
+) -> Result<QueryQuoteDetailsResponse>
+where
+    Lpn: 'static + Currency,
+{
+    let lpp = LiquidityPool::<Lpn>::load(deps.storage)?;
+
+    match lpp.query_quote_position(quote, &env.contract.address, deps.querier, &env.block.time)? {
+        Some(position) => Ok(QueryQuoteDetailsResponse::Quote(position)),
+        None => Ok(QueryQuoteDetailsResponse::NoLiquidity),
+    }
+}
+
+pub(super) fn query_refinance_quote<Lpn>(
+    deps: &Deps<'_>,
+    env: &Env,
+) -> Result<QueryRefinanceQuoteResponse>
+where
+    Lpn: 'static + Currency,
+{
+    let lpp = LiquidityPool::<Lpn>::load(deps.storage)?;
+
+    lpp.query_refinance_quote(&env.contract.address, deps.querier, &env.block.time)
+}
+
 pub fn query_loan<Lpn>(storage: &dyn Storage, lease_addr: Addr) -> Result<QueryLoanResponse<Lpn>>
 where
     Lpn: 'static + Currency,
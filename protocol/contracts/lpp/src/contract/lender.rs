@@ -1,23 +1,29 @@
 use serde::Serialize;
 
+use currencies::Lpns;
 use currency::Currency;
 use finance::{coin::Coin, zero::Zero};
 use lpp_platform::NLpn;
 use platform::{
-    bank::{self, BankAccount},
+    bank::{self, BankAccount, BankAccountView},
     batch::Batch,
     message::Response as MessageResponse,
 };
-use sdk::cosmwasm_std::{Addr, Deps, DepsMut, Env, MessageInfo, Storage, Uint128};
+use sdk::cosmwasm_std::{Addr, Deps, DepsMut, Env, MessageInfo, QuerierWrapper, Storage, Uint128};
 
 use crate::{
     error::{ContractError, Result},
     event,
-    lpp::LiquidityPool,
-    msg::{BalanceResponse, PriceResponse},
-    state::Deposit,
+    lpp::{LiquidityPool, Withdrawal},
+    msg::{BalanceResponse, PriceResponse, WithdrawRequestResponse},
+    state::{Deposit, RequestId, WithdrawQueue},
 };
 
+/// The maximum number of queued withdraws to pay per deposit or repayment
+const MAX_FILLS: usize = 10;
+const DEFAULT_REQUESTS_LIMIT: u32 = 10;
+const MAX_REQUESTS_LIMIT: u32 = 30;
+
 pub(super) fn try_deposit<Lpn>(
     deps: DepsMut<'_>,
     env: Env,
@@ -47,7 +53,15 @@ where
         price,
     )?;
 
-    Ok(event::emit_deposit(env, lender_addr, pending_deposit, receipts).into())
+    let fill_response = fill_withdraw_queue::<Lpn>(deps.storage, deps.querier, &env, Coin::ZERO)?;
+
+    Ok(MessageResponse::from(event::emit_deposit(
+        env,
+        lender_addr,
+        pending_deposit,
+        receipts,
+    ))
+    .merge_with(fill_response))
 }
 
 pub(super) fn deposit_capacity<Lpn>(deps: Deps<'_>, env: Env) -> Result<Option<Coin<Lpn>>>
@@ -75,14 +89,13 @@ where
     let amount_nlpn: Coin<NLpn> = amount_nlpn.u128().into();
 
     let lpp = LiquidityPool::<Lpn>::load(deps.storage)?;
-    let payment_lpn = lpp.withdraw_lpn(&deps.as_ref(), &env, amount_nlpn)?;
+    let withdrawal = lpp.withdraw_lpn(&deps.as_ref(), &env, amount_nlpn)?;
 
     let maybe_reward = Deposit::may_load(deps.storage, lender_addr.clone())?
         .ok_or(ContractError::NoDeposit {})?
         .withdraw(deps.storage, amount_nlpn)?;
 
     let mut bank = bank::account(&env.contract.address, deps.querier);
-    bank.send(payment_lpn, lender_addr.clone());
 
     if let Some(reward) = maybe_reward {
         if !reward.is_zero() {
@@ -90,17 +103,71 @@ where
         }
     }
 
+    let events = match withdrawal {
+        Withdrawal::Pay(payment_lpn) => {
+            bank.send(payment_lpn, lender_addr.clone());
+
+            event::emit_withdraw(
+                &env,
+                lender_addr,
+                payment_lpn,
+                amount_nlpn,
+                maybe_reward.is_some(),
+            )
+        }
+        Withdrawal::Queue(amount_lpn) => WithdrawQueue::<Lpn>::load(deps.storage)?
+            .push(deps.storage, lender_addr.clone(), amount_nlpn, amount_lpn)
+            .map(|request_id| {
+                event::emit_withdraw_queued(
+                    &env,
+                    lender_addr,
+                    amount_lpn,
+                    amount_nlpn,
+                    request_id,
+                    maybe_reward.is_some(),
+                )
+            })?,
+    };
+
     let batch: Batch = bank.into();
-    Ok(MessageResponse::messages_with_events(
-        batch,
-        event::emit_withdraw(
-            env,
-            lender_addr,
-            payment_lpn,
-            amount_nlpn,
-            maybe_reward.is_some(),
-        ),
-    ))
+    Ok(MessageResponse::messages_with_events(batch, events))
+}
+
+/// Pay up to [`MAX_FILLS`] queued withdraws, in FIFO order, out of the balance
+/// not `reserved` for other payments
+///
+/// The number of paid requests is limited to keep the gas of the deposits and repayments bounded.
+/// The rest are paid on the next ones.
+pub(super) fn fill_withdraw_queue<Lpn>(
+    storage: &mut dyn Storage,
+    querier: QuerierWrapper<'_>,
+    env: &Env,
+    reserved: Coin<Lpn>,
+) -> Result<MessageResponse>
+where
+    Lpn: 'static + Currency,
+{
+    let mut queue = WithdrawQueue::<Lpn>::load(storage)?;
+
+    if queue.is_empty() {
+        return Ok(MessageResponse::default());
+    }
+
+    let mut bank = bank::account(&env.contract.address, querier);
+
+    let available = bank.balance::<Lpn>()?.saturating_sub(reserved);
+
+    let events = queue
+        .fill(storage, available, MAX_FILLS)?
+        .into_iter()
+        .map(|fill| {
+            bank.send(fill.amount, fill.lender.clone());
+            event::emit_withdraw_fill(env, fill)
+        })
+        .fold(MessageResponse::default(), MessageResponse::merge_with);
+
+    let batch: Batch = bank.into();
+    Ok(MessageResponse::messages_only(batch).merge_with(events))
 }
 
 pub fn query_ntoken_price<Lpn>(deps: Deps<'_>, env: Env) -> Result<PriceResponse<Lpn>>
@@ -113,6 +180,25 @@ where
     })
 }
 
+pub fn query_withdraw_requests<Lpn>(
+    storage: &dyn Storage,
+    addr: Addr,
+    start_after: Option<RequestId>,
+    limit: Option<u32>,
+) -> Result<Vec<WithdrawRequestResponse<Lpns>>>
+where
+    Lpn: Currency,
+{
+    let limit = limit
+        .unwrap_or(DEFAULT_REQUESTS_LIMIT)
+        .min(MAX_REQUESTS_LIMIT)
+        .try_into()?;
+
+    WithdrawQueue::<Lpn>::query_requests(storage, &addr, start_after, limit)
+        .map(|requests| requests.into_iter().map(Into::into).collect())
+        .map_err(Into::into)
+}
+
 pub fn query_balance(storage: &dyn Storage, addr: Addr) -> Result<BalanceResponse> {
     let balance: u128 = Deposit::query_balance_nlpn(storage, addr)?
         .unwrap_or_default()
@@ -137,7 +223,10 @@ mod test {
 
     use crate::{borrow::InterestRate, state::Config};
 
-    use super::{query_balance, query_ntoken_price, try_deposit, try_withdraw, LiquidityPool};
+    use super::{
+        query_balance, query_ntoken_price, query_withdraw_requests, try_deposit, try_withdraw,
+        LiquidityPool,
+    };
 
     type TheCurrency = LpnC;
 
@@ -177,8 +266,8 @@ mod test {
         };
 
         use super::{
-            cwcoin, query_balance, query_ntoken_price, setup_storage, try_deposit, try_withdraw,
-            TheCurrency, DEFAULT_MIN_UTILIZATION,
+            cwcoin, query_balance, query_ntoken_price, query_withdraw_requests, setup_storage,
+            try_deposit, try_withdraw, LiquidityPool, TheCurrency, DEFAULT_MIN_UTILIZATION,
         };
 
         const LENDER: &str = "lender";
@@ -344,6 +433,273 @@ mod test {
             }
         }
 
+        mod withdraw_queue {
+            use finance::coin::{Amount, Coin};
+            use sdk::cosmwasm_std::{
+                testing::{mock_info, MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR},
+                Addr, Env, OwnedDeps,
+            };
+
+            use crate::{error::ContractError, msg::WithdrawRequestResponse, state::Config};
+
+            use super::{
+                cwcoin, query_balance, query_withdraw_requests, test_case, try_deposit,
+                try_withdraw, LiquidityPool, TheCurrency, DEPOSIT, LENDER,
+            };
+
+            const BORROWED: Amount = 60;
+            const LENDER2: &str = "lender2";
+            const DEPOSIT2: Amount = 30;
+
+            #[test]
+            fn test_queue_and_fill() {
+                test_case(DEPOSIT, |mut deps, env| {
+                    try_deposit::<TheCurrency>(
+                        deps.as_mut(),
+                        env.clone(),
+                        mock_info(LENDER, &[cwcoin(DEPOSIT)]),
+                    )
+                    .unwrap();
+
+                    LiquidityPool::<TheCurrency>::load(deps.as_ref().storage)
+                        .unwrap()
+                        .try_open_loan(
+                            &mut deps.as_mut(),
+                            &env,
+                            Addr::unchecked("lease"),
+                            BORROWED.into(),
+                        )
+                        .unwrap();
+                    deps.querier
+                        .update_balance(MOCK_CONTRACT_ADDR, vec![cwcoin(DEPOSIT - BORROWED)]);
+
+                    try_withdraw::<TheCurrency>(
+                        deps.as_mut(),
+                        env.clone(),
+                        mock_info(LENDER, &[]),
+                        DEPOSIT.into(),
+                    )
+                    .unwrap();
+
+                    assert_eq!(
+                        query_balance(deps.as_ref().storage, Addr::unchecked(LENDER))
+                            .unwrap()
+                            .balance
+                            .u128(),
+                        0
+                    );
+                    assert_eq!(
+                        vec![WithdrawRequestResponse {
+                            id: 0,
+                            position: 0,
+                            ahead: Coin::<TheCurrency>::new(0).into(),
+                            receipts: DEPOSIT.into(),
+                            amount: Coin::<TheCurrency>::new(DEPOSIT).into(),
+                        }],
+                        query_withdraw_requests::<TheCurrency>(
+                            deps.as_ref().storage,
+                            Addr::unchecked(LENDER),
+                            None,
+                            None
+                        )
+                        .unwrap()
+                    );
+
+                    deps.querier.update_balance(
+                        MOCK_CONTRACT_ADDR,
+                        vec![cwcoin(DEPOSIT - BORROWED + DEPOSIT2)],
+                    );
+                    try_deposit::<TheCurrency>(
+                        deps.as_mut(),
+                        env.clone(),
+                        mock_info(LENDER2, &[cwcoin(DEPOSIT2)]),
+                    )
+                    .unwrap();
+
+                    let left = DEPOSIT - (DEPOSIT - BORROWED + DEPOSIT2);
+                    assert_eq!(
+                        vec![WithdrawRequestResponse {
+                            id: 0,
+                            position: 0,
+                            ahead: Coin::<TheCurrency>::new(0).into(),
+                            receipts: DEPOSIT.into(),
+                            amount: Coin::<TheCurrency>::new(left).into(),
+                        }],
+                        query_withdraw_requests::<TheCurrency>(
+                            deps.as_ref().storage,
+                            Addr::unchecked(LENDER),
+                            None,
+                            None
+                        )
+                        .unwrap()
+                    );
+
+                    // the pool balance is fully used to pay the queue
+                    deps.querier
+                        .update_balance(MOCK_CONTRACT_ADDR, vec![cwcoin(0)]);
+                    assert_eq!(
+                        query_balance(deps.as_ref().storage, Addr::unchecked(LENDER2))
+                            .unwrap()
+                            .balance
+                            .u128(),
+                        DEPOSIT2
+                    );
+                    try_withdraw::<TheCurrency>(
+                        deps.as_mut(),
+                        env,
+                        mock_info(LENDER2, &[]),
+                        DEPOSIT2.into(),
+                    )
+                    .unwrap();
+
+                    assert_eq!(
+                        vec![WithdrawRequestResponse {
+                            id: 1,
+                            position: 1,
+                            ahead: Coin::<TheCurrency>::new(left).into(),
+                            receipts: DEPOSIT2.into(),
+                            amount: Coin::<TheCurrency>::new(DEPOSIT2).into(),
+                        }],
+                        query_withdraw_requests::<TheCurrency>(
+                            deps.as_ref().storage,
+                            Addr::unchecked(LENDER2),
+                            None,
+                            None
+                        )
+                        .unwrap()
+                    );
+                })
+            }
+
+            #[test]
+            fn test_below_minimum_paid_ahead_of_queue() {
+                test_case(DEPOSIT, |mut deps, env| {
+                    setup_queue(&mut deps, &env);
+
+                    // a repayment not used to pay the queue yet
+                    LiquidityPool::<TheCurrency>::load(deps.as_ref().storage)
+                        .unwrap()
+                        .try_repay_loan(
+                            &mut deps.as_mut(),
+                            &env,
+                            Addr::unchecked("lease"),
+                            BORROWED.into(),
+                        )
+                        .unwrap();
+                    deps.querier
+                        .update_balance(MOCK_CONTRACT_ADDR, vec![cwcoin(DEPOSIT + DEPOSIT2)]);
+                    try_withdraw::<TheCurrency>(
+                        deps.as_mut(),
+                        env,
+                        mock_info(LENDER2, &[]),
+                        DEPOSIT2.into(),
+                    )
+                    .unwrap();
+
+                    assert_eq!(
+                        query_balance(deps.as_ref().storage, Addr::unchecked(LENDER2))
+                            .unwrap()
+                            .balance
+                            .u128(),
+                        0
+                    );
+                    assert!(query_withdraw_requests::<TheCurrency>(
+                        deps.as_ref().storage,
+                        Addr::unchecked(LENDER2),
+                        None,
+                        None
+                    )
+                    .unwrap()
+                    .is_empty());
+                })
+            }
+
+            #[test]
+            fn test_below_minimum_queued() {
+                test_case(DEPOSIT, |mut deps, env| {
+                    setup_queue(&mut deps, &env);
+
+                    try_withdraw::<TheCurrency>(
+                        deps.as_mut(),
+                        env,
+                        mock_info(LENDER2, &[]),
+                        DEPOSIT2.into(),
+                    )
+                    .unwrap();
+
+                    assert_eq!(
+                        vec![WithdrawRequestResponse {
+                            id: 1,
+                            position: 1,
+                            ahead: Coin::<TheCurrency>::new(DEPOSIT).into(),
+                            receipts: DEPOSIT2.into(),
+                            amount: Coin::<TheCurrency>::new(DEPOSIT2).into(),
+                        }],
+                        query_withdraw_requests::<TheCurrency>(
+                            deps.as_ref().storage,
+                            Addr::unchecked(LENDER2),
+                            None,
+                            None
+                        )
+                        .unwrap()
+                    );
+                })
+            }
+
+            #[test]
+            fn test_zero_minimum() {
+                test_case(DEPOSIT, |mut deps, _env| {
+                    assert_eq!(
+                        Err(ContractError::ZeroMinQueuedWithdraw),
+                        Config::update_min_queued_withdraw(deps.as_mut().storage, 0)
+                    );
+                })
+            }
+
+            /// Queue the whole deposit of [`LENDER`], leaving no free balance, with
+            /// the deposit of [`LENDER2`] below the minimum queued withdraw
+            fn setup_queue(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, env: &Env) {
+                try_deposit::<TheCurrency>(
+                    deps.as_mut(),
+                    env.clone(),
+                    mock_info(LENDER, &[cwcoin(DEPOSIT)]),
+                )
+                .unwrap();
+                deps.querier
+                    .update_balance(MOCK_CONTRACT_ADDR, vec![cwcoin(DEPOSIT + DEPOSIT2)]);
+                try_deposit::<TheCurrency>(
+                    deps.as_mut(),
+                    env.clone(),
+                    mock_info(LENDER2, &[cwcoin(DEPOSIT2)]),
+                )
+                .unwrap();
+
+                LiquidityPool::<TheCurrency>::load(deps.as_ref().storage)
+                    .unwrap()
+                    .try_open_loan(
+                        &mut deps.as_mut(),
+                        env,
+                        Addr::unchecked("lease"),
+                        BORROWED.into(),
+                    )
+                    .unwrap();
+                deps.querier.update_balance(
+                    MOCK_CONTRACT_ADDR,
+                    vec![cwcoin(DEPOSIT + DEPOSIT2 - BORROWED)],
+                );
+
+                Config::update_min_queued_withdraw(deps.as_mut().storage, DEPOSIT2 + 1).unwrap();
+
+                try_withdraw::<TheCurrency>(
+                    deps.as_mut(),
+                    env.clone(),
+                    mock_info(LENDER, &[]),
+                    DEPOSIT.into(),
+                )
+                .unwrap();
+            }
+        }
+
         mod nlpn_price {
             use finance::{
                 coin::{Amount, Coin},
@@ -63,8 +63,15 @@ pub fn instantiate(
 }
 
 #[entry_point]
-pub fn migrate(deps: DepsMut<'_>, _env: Env, MigrateMsg {}: MigrateMsg) -> Result<CwResponse> {
-    versioning::update_software(deps.storage, CONTRACT_VERSION, Into::into)
+pub fn migrate(
+    deps: DepsMut<'_>,
+    _env: Env,
+    MigrateMsg {
+        min_queued_withdraw,
+    }: MigrateMsg,
+) -> Result<CwResponse> {
+    Config::update_min_queued_withdraw(deps.storage, min_queued_withdraw)
+        .and_then(|()| versioning::update_software(deps.storage, CONTRACT_VERSION, Into::into))
         .and_then(response::response)
         .inspect_err(platform_error::log(deps.api))
 }
@@ -107,6 +114,15 @@ pub fn execute(
             .and_then(|(loan_resp, message_response)| {
                 response::response_with_messages::<_, _, ContractError>(loan_resp, message_response)
             }),
+        ExecuteMsg::IncreaseLoan { amount } => amount
+            .try_into()
+            .map_err(Into::into)
+            .and_then(|amount_lpn| {
+                borrow::try_increase_loan::<LpnCurrency>(deps, env, info, amount_lpn)
+            })
+            .and_then(|(loan_resp, message_response)| {
+                response::response_with_messages::<_, _, ContractError>(loan_resp, message_response)
+            }),
         ExecuteMsg::RepayLoan() => borrow::try_repay_loan::<LpnCurrency>(deps, env, info).and_then(
             |(excess_amount, message_response)| {
                 response::response_with_messages::<_, _, ContractError>(
@@ -115,6 +131,20 @@ pub fn execute(
                 )
             },
         ),
+        ExecuteMsg::RefinanceLoan() => borrow::try_refinance_loan::<LpnCurrency>(deps, env, info)
+            .and_then(|(loan_resp, message_response)| {
+                response::response_with_messages::<_, _, ContractError>(loan_resp, message_response)
+            }),
+        ExecuteMsg::MergeLoan { from, into } => {
+            SingleUserAccess::new(
+                deps.storage.deref_mut(),
+                crate::access_control::LEASE_CODE_ADMIN_KEY,
+            )
+            .check(&info.sender)?;
+
+            borrow::try_merge_loan::<LpnCurrency>(deps, env, from, into)
+                .map(response::response_only_messages)
+        }
         ExecuteMsg::Deposit() => lender::try_deposit::<LpnCurrency>(deps, env, info)
             .map(response::response_only_messages),
         ExecuteMsg::Burn { amount } => lender::try_withdraw::<LpnCurrency>(deps, env, info, amount)
@@ -133,6 +163,12 @@ pub fn sudo(deps: DepsMut<'_>, _env: Env, msg: SudoMsg) -> Result<CwResponse> {
         SudoMsg::MinUtilization { min_utilization } => {
             Config::update_min_utilization(deps.storage, min_utilization)
         }
+        SudoMsg::RefinanceFee { refinance_fee } => {
+            Config::update_refinance_fee(deps.storage, refinance_fee)
+        }
+        SudoMsg::MinQueuedWithdraw {
+            min_queued_withdraw,
+        } => Config::update_min_queued_withdraw(deps.storage, min_queued_withdraw),
     }
     .map(|()| PlatformResponse::default())
     .map(response::response_only_messages)
@@ -155,10 +191,17 @@ pub fn query(deps: Deps<'_>, env: Env, msg: QueryMsg<LpnCurrencies>) -> Result<B
             .map_err(Into::into)
             .and_then(|quote| borrow::query_quote::<LpnCurrency>(&deps, &env, quote))
             .and_then(|ref resp| to_json_binary(resp)),
+        QueryMsg::QuoteDetails { amount } => amount
+            .try_into()
+            .map_err(Into::into)
+            .and_then(|quote| borrow::query_quote_details::<LpnCurrency>(&deps, &env, quote))
+            .and_then(|ref resp| to_json_binary(resp)),
         QueryMsg::Loan { lease_addr } => {
             borrow::query_loan::<LpnCurrency>(deps.storage, lease_addr)
                 .and_then(|ref resp| to_json_binary(resp))
         }
+        QueryMsg::RefinanceQuote() => borrow::query_refinance_quote::<LpnCurrency>(&deps, &env)
+            .and_then(|ref resp| to_json_binary(resp)),
         QueryMsg::LppBalance() => rewards::query_lpp_balance::<LpnCurrency>(deps, env)
             .and_then(|lpp_balances| {
                 rewards::query_total_rewards(deps.storage)
@@ -181,6 +224,17 @@ pub fn query(deps: Deps<'_>, env: Env, msg: QueryMsg<LpnCurrencies>) -> Result<B
         QueryMsg::DepositCapacity() => {
             to_json_binary(&lender::deposit_capacity::<LpnCurrency>(deps, env)?)
         }
+        QueryMsg::WithdrawRequests {
+            address,
+            start_after,
+            limit,
+        } => lender::query_withdraw_requests::<LpnCurrency>(
+            deps.storage,
+            address,
+            start_after,
+            limit,
+        )
+        .and_then(|ref resp| to_json_binary(resp)),
     }
     .inspect_err(platform_error::log(deps.api))
 }
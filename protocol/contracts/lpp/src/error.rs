@@ -1,3 +1,5 @@
+use std::num::TryFromIntError;
+
 use thiserror::Error;
 
 use sdk::cosmwasm_std::StdError;
@@ -7,6 +9,9 @@ pub enum ContractError {
     #[error("[Lpp] [Std] {0}")]
     Std(#[from] StdError),
 
+    #[error("[Lpp] integer conversion {0}")]
+    Conversion(#[from] TryFromIntError),
+
     #[error("[Lpp] Failed to convert query response to binary! Cause: {0}")]
     ConvertToBinary(StdError),
 
@@ -49,12 +54,24 @@ pub enum ContractError {
     #[error("[Lpp] Zero loan amount")]
     ZeroLoanAmount,
 
+    #[error("[Lpp] The current interest rate is not below the loan one")]
+    NoRefinanceGain,
+
+    #[error("[Lpp] Insufficient refinance payment")]
+    InsufficientRefinancePayment,
+
+    #[error("[Lpp] A loan cannot be merged into itself")]
+    SelfLoanMerge,
+
     #[error("[Lpp] Zero deposit")]
     ZeroDepositFunds,
 
     #[error("[Lpp] Zero withdraw amount")]
     ZeroWithdrawFunds,
 
+    #[error("[Lpp] The minimum queued withdraw amount should be positive")]
+    ZeroMinQueuedWithdraw,
+
     #[error("[Lpp] No pending rewards")]
     NoRewards {},
 
@@ -4,6 +4,8 @@ use lpp_platform::NLpn;
 use platform::batch::{Emit, Emitter};
 use sdk::cosmwasm_std::{Addr, Env};
 
+use crate::state::{Fill, RequestId};
+
 pub fn emit_deposit<Lpn>(
     env: Env,
     lender_addr: Addr,
@@ -22,7 +24,7 @@ where
 }
 
 pub fn emit_withdraw<Lpn>(
-    env: Env,
+    env: &Env,
     lender_addr: Addr,
     payment_lpn: Coin<Lpn>,
     receipts: Coin<NLpn>,
@@ -32,10 +34,44 @@ where
     Lpn: ?Sized + Currency,
 {
     Emitter::of_type("lp-withdraw")
-        .emit_tx_info(&env)
+        .emit_tx_info(env)
         .emit("to", lender_addr)
-        .emit("from", env.contract.address)
+        .emit("from", env.contract.address.clone())
         .emit_coin("withdraw", payment_lpn)
         .emit_coin_amount("receipts", receipts)
         .emit_to_string_value("close", close_flag)
 }
+
+pub fn emit_withdraw_queued<Lpn>(
+    env: &Env,
+    lender_addr: Addr,
+    amount_lpn: Coin<Lpn>,
+    receipts: Coin<NLpn>,
+    request_id: RequestId,
+    close_flag: bool,
+) -> Emitter
+where
+    Lpn: ?Sized + Currency,
+{
+    Emitter::of_type("lp-withdraw-queued")
+        .emit_tx_info(env)
+        .emit("to", lender_addr)
+        .emit("from", env.contract.address.clone())
+        .emit_coin("withdraw", amount_lpn)
+        .emit_coin_amount("receipts", receipts)
+        .emit_to_string_value("request", request_id)
+        .emit_to_string_value("close", close_flag)
+}
+
+pub fn emit_withdraw_fill<Lpn>(env: &Env, fill: Fill<Lpn>) -> Emitter
+where
+    Lpn: ?Sized + Currency,
+{
+    Emitter::of_type("lp-withdraw-fill")
+        .emit_tx_info(env)
+        .emit("to", fill.lender)
+        .emit("from", env.contract.address.clone())
+        .emit_coin("payment", fill.amount)
+        .emit_to_string_value("request", fill.id)
+        .emit_to_string_value("completed", fill.completed)
+}
@@ -6,7 +6,7 @@ use sdk::{
 };
 use serde::{Deserialize, Serialize};
 
-use finance::{coin::Coin, duration::Duration, interest, percent::Percent};
+use finance::{coin::Coin, duration::Duration, fraction::Fraction, interest, percent::Percent};
 use sdk::schemars::{self, JsonSchema};
 
 use crate::error::{ContractError, Result};
@@ -69,11 +69,81 @@ where
         }
     }
 
+    /// Re-open the loan at the given annual interest rate
+    ///
+    /// The interest due by then is considered paid and returned. The principal is kept intact.
+    pub fn refinance(&mut self, by: &Timestamp, annual_interest_rate: Percent) -> Coin<Lpn> {
+        let interest_due = self.interest_due(by);
+        self.annual_interest_rate = annual_interest_rate;
+        self.interest_paid = self.interest_paid.max(*by);
+        interest_due
+    }
+
     fn due_period(&self, by: &Timestamp) -> Duration {
         Duration::between(&self.interest_paid, by.max(&self.interest_paid))
     }
 }
 
+impl<Lpn> Loan<Lpn>
+where
+    Lpn: ?Sized + PartialEq,
+{
+    /// Borrow an additional amount at the given annual interest rate
+    ///
+    /// The loan interest rate becomes the average of the current and the new rates weighted
+    /// by the principal. The interest due, or prepaid, by the time of borrowing is preserved
+    /// by moving the `interest_paid` time accordingly.
+    pub fn borrow(&mut self, by: &Timestamp, amount: Coin<Lpn>, annual_interest_rate: Percent) {
+        let period = Duration::between(&self.interest_paid.min(*by), &self.interest_paid.max(*by));
+        let interest = interest::interest(self.annual_interest_rate, self.principal_due, period);
+
+        self.annual_interest_rate = self.blended_rate(amount, annual_interest_rate);
+        self.principal_due += amount;
+
+        let (new_period, _change) = interest::pay(
+            self.annual_interest_rate,
+            self.principal_due,
+            interest,
+            period,
+        );
+        self.interest_paid = if &self.interest_paid < by {
+            by - new_period
+        } else {
+            by + new_period
+        };
+    }
+
+    /// Absorb another loan into this one
+    ///
+    /// The loan interest rate becomes the average of both rates weighted by the principal.
+    /// The sum of the interest due on both loans by then is preserved by moving
+    /// the `interest_paid` time accordingly.
+    pub fn merge(&mut self, by: &Timestamp, other: Self) {
+        let interest = self.interest_due(by) + other.interest_due(by);
+        let period = Duration::between(&self.interest_paid.min(other.interest_paid).min(*by), by);
+
+        self.annual_interest_rate =
+            self.blended_rate(other.principal_due, other.annual_interest_rate);
+        self.principal_due += other.principal_due;
+
+        let (due_period, _change) = interest::pay(
+            self.annual_interest_rate,
+            self.principal_due,
+            interest,
+            period,
+        );
+        self.interest_paid = by - due_period;
+    }
+
+    /// The average of the loan interest rate and `annual_interest_rate` weighted by
+    /// the loan principal and `amount` respectively
+    fn blended_rate(&self, amount: Coin<Lpn>, annual_interest_rate: Percent) -> Percent {
+        let annual_interest =
+            self.annual_interest_rate.of(self.principal_due) + annual_interest_rate.of(amount);
+        Percent::from_ratio(annual_interest, self.principal_due + amount)
+    }
+}
+
 impl<Lpn> Loan<Lpn>
 where
     Lpn: ?Sized,
@@ -99,6 +169,7 @@ where
                 .update(storage, addr, |loaded_loan| {
                     let mut loaded_loan = loaded_loan.ok_or(ContractError::NoLoan {})?;
                     loaded_loan.principal_due = loan.principal_due;
+                    loaded_loan.annual_interest_rate = loan.annual_interest_rate;
                     loaded_loan.interest_paid = loan.interest_paid;
 
                     Ok::<_, ContractError>(loaded_loan)
@@ -107,6 +178,10 @@ where
         }
     }
 
+    pub fn close(storage: &mut dyn Storage, addr: Addr) -> Result<Self> {
+        Self::load(storage, addr.clone()).inspect(|_| Self::STORAGE.remove(storage, addr))
+    }
+
     pub fn query(storage: &dyn Storage, lease_addr: Addr) -> Result<Option<Loan<Lpn>>> {
         Self::STORAGE
             .may_load(storage, lease_addr)
@@ -234,6 +309,124 @@ mod test {
         );
     }
 
+    #[test]
+    fn borrow_no_interest_due() {
+        let start_at = Timestamp::from_nanos(200);
+        let mut l = Loan {
+            principal_due: Coin::<LpnC>::from(1000),
+            annual_interest_rate: Percent::from_percent(10),
+            interest_paid: start_at,
+        };
+
+        l.borrow(&start_at, 1000.into(), Percent::from_percent(20));
+        assert_eq!(
+            Loan {
+                principal_due: 2000.into(),
+                annual_interest_rate: Percent::from_percent(15),
+                interest_paid: start_at
+            },
+            l
+        );
+    }
+
+    #[test]
+    fn borrow_interest_due() {
+        let start_at = Timestamp::from_nanos(200);
+        let mut l = Loan {
+            principal_due: Coin::<LpnC>::from(1000),
+            annual_interest_rate: Percent::from_percent(10),
+            interest_paid: start_at,
+        };
+
+        let borrow_at = start_at + Duration::YEAR;
+        let interest_due = l.interest_due(&borrow_at);
+        assert_eq!(Coin::from(100), interest_due);
+
+        l.borrow(&borrow_at, 3000.into(), Percent::from_percent(30));
+        assert_eq!(Coin::from(4000), l.principal_due);
+        assert_eq!(Percent::from_percent(25), l.annual_interest_rate);
+        assert_eq!(interest_due, l.interest_due(&borrow_at));
+        assert_eq!(
+            interest_due + Coin::from(1000),
+            l.interest_due(&(borrow_at + Duration::YEAR))
+        );
+    }
+
+    #[test]
+    fn borrow_interest_prepaid() {
+        let start_at = Timestamp::from_nanos(200);
+        let prepaid_till = start_at + Duration::YEAR;
+        let mut l = Loan {
+            principal_due: Coin::<LpnC>::from(1000),
+            annual_interest_rate: Percent::from_percent(10),
+            interest_paid: prepaid_till,
+        };
+
+        l.borrow(&start_at, 1000.into(), Percent::from_percent(10));
+        assert_eq!(Coin::from(2000), l.principal_due);
+        assert_eq!(Percent::from_percent(10), l.annual_interest_rate);
+        assert_eq!(
+            start_at + Duration::from_nanos(Duration::YEAR.nanos() / 2),
+            l.interest_paid
+        );
+    }
+
+    #[test]
+    fn refinance() {
+        let start_at = Timestamp::from_nanos(200);
+        let mut l = Loan {
+            principal_due: Coin::<LpnC>::from(1000),
+            annual_interest_rate: Percent::from_percent(10),
+            interest_paid: start_at,
+        };
+
+        let refinance_at = start_at + Duration::YEAR;
+        assert_eq!(
+            Coin::from(100),
+            l.refinance(&refinance_at, Percent::from_percent(5))
+        );
+        assert_eq!(
+            Loan {
+                principal_due: 1000.into(),
+                annual_interest_rate: Percent::from_percent(5),
+                interest_paid: refinance_at
+            },
+            l
+        );
+        assert_eq!(
+            Coin::from(50),
+            l.interest_due(&(refinance_at + Duration::YEAR))
+        );
+    }
+
+    #[test]
+    fn merge() {
+        let start_at = Timestamp::from_nanos(200);
+        let mut l = Loan {
+            principal_due: Coin::<LpnC>::from(1000),
+            annual_interest_rate: Percent::from_percent(10),
+            interest_paid: start_at,
+        };
+        let other = Loan {
+            principal_due: Coin::<LpnC>::from(3000),
+            annual_interest_rate: Percent::from_percent(30),
+            interest_paid: start_at + Duration::YEAR,
+        };
+
+        let merge_at = start_at + Duration::YEAR + Duration::YEAR;
+        let interest_due = l.interest_due(&merge_at) + other.interest_due(&merge_at);
+        assert_eq!(Coin::from(200 + 900), interest_due);
+
+        l.merge(&merge_at, other);
+        assert_eq!(Coin::from(4000), l.principal_due);
+        assert_eq!(Percent::from_percent(25), l.annual_interest_rate);
+        assert_eq!(interest_due, l.interest_due(&merge_at));
+        assert_eq!(
+            interest_due + Coin::from(1000),
+            l.interest_due(&(merge_at + Duration::YEAR))
+        );
+    }
+
     mod persistence {
         use currencies::test::LpnC;
         use finance::{coin::Coin, duration::Duration, percent::Percent, zero::Zero};
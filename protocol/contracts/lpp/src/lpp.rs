@@ -13,10 +13,11 @@ use platform::{bank, contract};
 use sdk::cosmwasm_std::{Addr, Deps, DepsMut, Env, QuerierWrapper, Storage, Timestamp};
 
 use crate::{
+    borrow::RatePosition,
     error::{ContractError, Result},
     loan::Loan,
-    msg::{LppBalanceResponse, PriceResponse},
-    state::{Config, Deposit, Total},
+    msg::{LppBalanceResponse, PriceResponse, QueryRefinanceQuoteResponse},
+    state::{Config, Deposit, Total, WithdrawQueue},
 };
 
 pub struct LppBalances<Lpn>
@@ -83,12 +84,25 @@ where
     }
 }
 
+/// The outcome of a withdraw request
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Withdrawal<Lpn>
+where
+    Lpn: ?Sized,
+{
+    /// The amount could be paid immediately
+    Pay(Coin<Lpn>),
+    /// The amount should be queued until there is enough liquidity
+    Queue(Coin<Lpn>),
+}
+
 pub(crate) struct LiquidityPool<Lpn>
 where
     Lpn: ?Sized,
 {
     config: Config,
     total: Total<Lpn>,
+    queued_withdrawals: Coin<Lpn>,
 }
 
 impl<Lpn> LiquidityPool<Lpn>
@@ -104,8 +118,13 @@ where
     pub fn load(storage: &dyn Storage) -> Result<Self> {
         let config = Config::load(storage)?;
         let total = Total::load(storage)?;
+        let queued_withdrawals = WithdrawQueue::load(storage)?.total();
 
-        Ok(LiquidityPool { config, total })
+        Ok(LiquidityPool {
+            config,
+            total,
+            queued_withdrawals,
+        })
     }
 }
 
@@ -190,20 +209,28 @@ where
             .map_err(ContractError::from)
     }
 
+    /// Determine the amount to pay for the receipts and whether it could be paid immediately
+    ///
+    /// A withdraw is paid if the balance not reserved for the queued withdraws covers it, and
+    /// either there are no queued ones, or it is below the configured minimum. Otherwise, it is
+    /// queued. Hence, small withdraws are not held behind the queue while there is free liquidity.
     pub fn withdraw_lpn(
         &self,
         deps: &Deps<'_>,
         env: &Env,
         amount_nlpn: Coin<NLpn>,
-    ) -> Result<Coin<Lpn>> {
+    ) -> Result<Withdrawal<Lpn>> {
         let price = self.calculate_price(deps, env, Coin::ZERO)?.get();
         let amount_lpn = price::total(amount_nlpn, price);
 
-        if self.balance(&env.contract.address, deps.querier)? < amount_lpn {
-            return Err(ContractError::NoLiquidity {});
-        }
+        let may_skip_queue = self.queued_withdrawals.is_zero()
+            || amount_lpn < Coin::new(self.config.min_queued_withdraw());
 
-        Ok(amount_lpn)
+        if may_skip_queue && self.balance(&env.contract.address, deps.querier)? >= amount_lpn {
+            Ok(Withdrawal::Pay(amount_lpn))
+        } else {
+            Ok(Withdrawal::Queue(amount_lpn))
+        }
     }
 
     pub fn query_quote(
@@ -213,10 +240,45 @@ where
         querier: QuerierWrapper<'_>,
         now: &Timestamp,
     ) -> Result<Option<Percent>> {
-        let balance = self.balance(account, querier)?;
+        self.query_quote_position(quote, account, querier, now)
+            .map(|may_position| may_position.map(|position| position.annual_interest_rate))
+    }
 
+    /// Determine the terms a loan would be refinanced at
+    pub fn query_refinance_quote(
+        &self,
+        account: &Addr,
+        querier: QuerierWrapper<'_>,
+        now: &Timestamp,
+    ) -> Result<QueryRefinanceQuoteResponse> {
+        self.query_quote(Coin::ZERO, account, querier, now)?
+            .ok_or(ContractError::NoLiquidity {})
+            .map(|annual_interest_rate| QueryRefinanceQuoteResponse {
+                annual_interest_rate,
+                fee: self.config.refinance_fee(),
+            })
+    }
+
+    /// Determine the point on the interest rate curve the pool would be at past the quote
+    pub fn query_quote_position(
+        &self,
+        quote: Coin<Lpn>,
+        account: &Addr,
+        querier: QuerierWrapper<'_>,
+        now: &Timestamp,
+    ) -> Result<Option<RatePosition>> {
+        self.balance(account, querier)
+            .map(|balance| self.position_past_quote(quote, balance, now))
+    }
+
+    fn position_past_quote(
+        &self,
+        quote: Coin<Lpn>,
+        balance: Coin<Lpn>,
+        now: &Timestamp,
+    ) -> Option<RatePosition> {
         if quote > balance {
-            return Ok(None);
+            return None;
         }
 
         let total_principal_due = self.total.total_principal_due();
@@ -224,10 +286,11 @@ where
         let total_liability_past_quote = total_principal_due + quote + total_interest;
         let total_balance_past_quote = balance - quote;
 
-        Ok(Some(self.config.borrow_rate().calculate(
-            total_liability_past_quote,
-            total_balance_past_quote,
-        )))
+        Some(
+            self.config
+                .borrow_rate()
+                .position(total_liability_past_quote, total_balance_past_quote),
+        )
     }
 
     pub(super) fn try_open_loan(
@@ -264,6 +327,141 @@ where
         Ok(loan)
     }
 
+    pub(super) fn try_increase_loan(
+        &mut self,
+        deps: &mut DepsMut<'_>,
+        env: &Env,
+        lease_addr: Addr,
+        amount: Coin<Lpn>,
+    ) -> Result<Loan<Lpn>> {
+        if amount.is_zero() {
+            return Err(ContractError::ZeroLoanAmount);
+        }
+
+        let now = env.block.time;
+
+        let annual_interest_rate =
+            match self.query_quote(amount, &env.contract.address, deps.querier, &now)? {
+                Some(rate) => Ok(rate),
+                None => Err(ContractError::NoLiquidity {}),
+            }?;
+
+        let mut loan = Loan::load(deps.storage, lease_addr.clone())?;
+        let loan_principal_before = loan.principal_due;
+        let loan_annual_interest_rate_before = loan.annual_interest_rate;
+        loan.borrow(&now, amount, annual_interest_rate);
+        Loan::save(deps.storage, lease_addr, loan.clone())?;
+
+        // the loan is re-accounted in full since its interest rate has changed
+        self.total.repay(
+            now,
+            Coin::ZERO,
+            loan_principal_before,
+            loan_annual_interest_rate_before,
+        );
+        self.total
+            .borrow(now, loan.principal_due, loan.annual_interest_rate)?
+            .store(deps.storage)?;
+
+        Ok(loan)
+    }
+
+    /// Re-open the loan at the current interest rate
+    ///
+    /// The `payment` should cover the loan interest due and the refinance fee. The rate is
+    /// quoted on the pool as it was before the payment arrived, the same way the lease
+    /// would see it. Return the refinanced loan and the amount to pay back to `lease_addr`.
+    pub(super) fn try_refinance_loan(
+        &mut self,
+        deps: &mut DepsMut<'_>,
+        env: &Env,
+        lease_addr: Addr,
+        payment: Coin<Lpn>,
+    ) -> Result<(Loan<Lpn>, Coin<Lpn>)> {
+        let now = env.block.time;
+
+        let balance_before = self
+            .balance(&env.contract.address, deps.querier)?
+            .saturating_sub(payment);
+        let annual_interest_rate = self
+            .position_past_quote(Coin::ZERO, balance_before, &now)
+            .map(|position| position.annual_interest_rate)
+            .ok_or(ContractError::NoLiquidity {})?;
+
+        let mut loan = Loan::load(deps.storage, lease_addr.clone())?;
+        if annual_interest_rate >= loan.annual_interest_rate {
+            return Err(ContractError::NoRefinanceGain);
+        }
+
+        let interest_due = loan.interest_due(&now);
+        let fee = self.config.refinance_fee().of(loan.principal_due);
+        let payment_due = interest_due + fee;
+        if payment < payment_due {
+            return Err(ContractError::InsufficientRefinancePayment);
+        }
+
+        let loan_annual_interest_rate_before = loan.annual_interest_rate;
+        let interest_paid = loan.refinance(&now, annual_interest_rate);
+        debug_assert_eq!(interest_due, interest_paid);
+        Loan::save(deps.storage, lease_addr, loan.clone())?;
+
+        // the loan is re-accounted in full at the new interest rate
+        self.total.repay(
+            now,
+            interest_due,
+            loan.principal_due,
+            loan_annual_interest_rate_before,
+        );
+        self.total
+            .borrow(now, loan.principal_due, loan.annual_interest_rate)?
+            .store(deps.storage)?;
+
+        Ok((loan, payment - payment_due))
+    }
+
+    /// Merge the loan of `lease_addr` into the loan of `into`
+    ///
+    /// Return the merged loan.
+    pub(super) fn try_merge_loan(
+        &mut self,
+        deps: &mut DepsMut<'_>,
+        env: &Env,
+        lease_addr: Addr,
+        into: Addr,
+    ) -> Result<Loan<Lpn>> {
+        if lease_addr == into {
+            return Err(ContractError::SelfLoanMerge);
+        }
+
+        let now = env.block.time;
+
+        let mut loan = Loan::load(deps.storage, into.clone())?;
+        let absorbed = Loan::close(deps.storage, lease_addr)?;
+        let loan_principal_before = loan.principal_due;
+        let loan_annual_interest_rate_before = loan.annual_interest_rate;
+        self.total.repay(
+            now,
+            Coin::ZERO,
+            absorbed.principal_due,
+            absorbed.annual_interest_rate,
+        );
+        loan.merge(&now, absorbed);
+        Loan::save(deps.storage, into, loan.clone())?;
+
+        // both loans are re-accounted in full as one at the merged interest rate
+        self.total.repay(
+            now,
+            Coin::ZERO,
+            loan_principal_before,
+            loan_annual_interest_rate_before,
+        );
+        self.total
+            .borrow(now, loan.principal_due, loan.annual_interest_rate)?
+            .store(deps.storage)?;
+
+        Ok(loan)
+    }
+
     /// return amount of lpp currency to pay back to lease_addr
     pub(super) fn try_repay_loan(
         &mut self,
@@ -289,8 +487,10 @@ where
         Ok(payment.excess)
     }
 
+    /// The balance not reserved for the queued withdraws
     fn balance(&self, account: &Addr, querier: QuerierWrapper<'_>) -> Result<Coin<Lpn>> {
         self.uncommited_balance(account, querier)
+            .map(|balance| balance.saturating_sub(self.queued_withdrawals))
     }
 
     fn commited_balance(
@@ -324,8 +524,11 @@ where
         now: &Timestamp,
         pending_deposit: Coin<Lpn>,
     ) -> Result<Coin<Lpn>> {
+        // the queued withdraws are owed to lenders whose receipts are already burnt
         self.commited_balance(account, querier, pending_deposit)
-            .map(|balance: Coin<Lpn>| balance + self.total_due(now))
+            .map(|balance: Coin<Lpn>| {
+                (balance + self.total_due(now)).saturating_sub(self.queued_withdrawals)
+            })
     }
 
     fn utilization(&self, balance: Coin<Lpn>, total_due: Coin<Lpn>) -> Percent {
@@ -362,7 +565,7 @@ mod test {
         state::{Config, Deposit, Total},
     };
 
-    use super::LiquidityPool;
+    use super::{LiquidityPool, Withdrawal};
 
     type TheCurrency = LpnC;
 
@@ -710,6 +913,232 @@ mod test {
         assert_eq!(loan_before.interest_paid, loan_after.interest_paid);
     }
 
+    #[test]
+    fn increase_loan() {
+        let balance_mock = [coin_cw(10_000_000)];
+        let mut deps = testing::mock_dependencies_with_balance(&balance_mock);
+        let env = testing::mock_env();
+        let admin = Addr::unchecked("admin");
+        let loan = Addr::unchecked("loan");
+        let lease_code_id = Code::unchecked(123);
+
+        grant_admin_access(deps.as_mut(), &admin);
+        Config::new_unchecked(
+            lease_code_id,
+            InterestRate::new(
+                BASE_INTEREST_RATE,
+                UTILIZATION_OPTIMAL,
+                ADDON_OPTIMAL_INTEREST_RATE,
+            )
+            .expect("Couldn't construct interest rate value!"),
+            DEFAULT_MIN_UTILIZATION,
+        )
+        .store(deps.as_mut().storage)
+        .expect("Failed to store Config!");
+        Total::<TheCurrency>::new()
+            .store(deps.as_mut().storage)
+            .expect("can't initialize Total");
+
+        let mut lpp = LiquidityPool::<TheCurrency>::load(deps.as_mut().storage)
+            .expect("can't load LiquidityPool");
+
+        let err = lpp
+            .try_increase_loan(&mut deps.as_mut(), &env, loan.clone(), Coin::new(1_000))
+            .expect_err("should not increase a non-existent loan");
+        assert!(matches!(err, ContractError::Std(_)));
+
+        lpp.try_open_loan(&mut deps.as_mut(), &env, loan.clone(), Coin::new(5_000))
+            .expect("can't open loan");
+        deps.querier
+            .update_balance(MOCK_CONTRACT_ADDR, vec![coin_cw(10_000_000 - 5_000)]);
+
+        let err = lpp
+            .try_increase_loan(&mut deps.as_mut(), &env, loan.clone(), Coin::new(0))
+            .expect_err("should not increase a loan with zero amount");
+        assert_eq!(ContractError::ZeroLoanAmount, err);
+
+        let loan_before = Loan::<TheCurrency>::load(deps.as_ref().storage, loan.clone())
+            .expect("can't load loan");
+
+        let loan_after = lpp
+            .try_increase_loan(&mut deps.as_mut(), &env, loan.clone(), Coin::new(3_000))
+            .expect("can't increase loan");
+        assert_eq!(
+            loan_before.principal_due + Coin::new(3_000),
+            loan_after.principal_due
+        );
+        assert_eq!(loan_before.interest_paid, loan_after.interest_paid);
+        assert_eq!(
+            loan_after,
+            Loan::load(deps.as_ref().storage, loan).expect("can't load loan")
+        );
+
+        let total = Total::<TheCurrency>::load(deps.as_ref().storage).expect("can't load Total");
+        assert_eq!(loan_after.principal_due, total.total_principal_due());
+    }
+
+    #[test]
+    fn refinance_loan() {
+        let balance_mock = [coin_cw(10_000_000)];
+        let mut deps = testing::mock_dependencies_with_balance(&balance_mock);
+        let env = testing::mock_env();
+        let admin = Addr::unchecked("admin");
+        let loan = Addr::unchecked("loan");
+        let lease_code_id = Code::unchecked(123);
+
+        grant_admin_access(deps.as_mut(), &admin);
+        Config::new_unchecked(
+            lease_code_id,
+            InterestRate::new(
+                BASE_INTEREST_RATE,
+                UTILIZATION_OPTIMAL,
+                ADDON_OPTIMAL_INTEREST_RATE,
+            )
+            .expect("Couldn't construct interest rate value!"),
+            DEFAULT_MIN_UTILIZATION,
+        )
+        .store(deps.as_mut().storage)
+        .expect("Failed to store Config!");
+        Config::update_refinance_fee(deps.as_mut().storage, Percent::from_percent(1))
+            .expect("Failed to update the refinance fee!");
+        Total::<TheCurrency>::new()
+            .store(deps.as_mut().storage)
+            .expect("can't initialize Total");
+
+        let mut lpp = LiquidityPool::<TheCurrency>::load(deps.as_mut().storage)
+            .expect("can't load LiquidityPool");
+
+        lpp.try_open_loan(&mut deps.as_mut(), &env, loan.clone(), Coin::new(5_000_000))
+            .expect("can't open loan");
+        deps.querier
+            .update_balance(MOCK_CONTRACT_ADDR, vec![coin_cw(5_000_000)]);
+
+        let err = lpp
+            .try_refinance_loan(&mut deps.as_mut(), &env, loan.clone(), Coin::ZERO)
+            .expect_err("should not refinance at the same rate");
+        assert_eq!(ContractError::NoRefinanceGain, err);
+
+        let loan_before = Loan::<TheCurrency>::load(deps.as_ref().storage, loan.clone())
+            .expect("can't load loan");
+        let mut env_later = env;
+        env_later.block.time += Duration::YEAR;
+        let payment_due = loan_before.interest_due(&env_later.block.time) + Coin::new(50_000);
+
+        // a lender has deposited in the meantime
+        let balance_later = 20_000_000;
+        deps.querier.update_balance(
+            MOCK_CONTRACT_ADDR,
+            vec![coin_cw(balance_later + Amount::from(payment_due) - 1)],
+        );
+        let err = lpp
+            .try_refinance_loan(
+                &mut deps.as_mut(),
+                &env_later,
+                loan.clone(),
+                payment_due - Coin::new(1),
+            )
+            .expect_err("should not refinance without paying the interest and the fee");
+        assert_eq!(ContractError::InsufficientRefinancePayment, err);
+
+        let excess = Coin::new(100);
+        deps.querier.update_balance(
+            MOCK_CONTRACT_ADDR,
+            vec![coin_cw(balance_later + Amount::from(payment_due + excess))],
+        );
+        let (loan_after, excess_after) = lpp
+            .try_refinance_loan(
+                &mut deps.as_mut(),
+                &env_later,
+                loan.clone(),
+                payment_due + excess,
+            )
+            .expect("can't refinance loan");
+        assert_eq!(excess, excess_after);
+        assert_eq!(loan_before.principal_due, loan_after.principal_due);
+        assert!(loan_after.annual_interest_rate < loan_before.annual_interest_rate);
+        assert_eq!(env_later.block.time, loan_after.interest_paid);
+        assert_eq!(
+            loan_after,
+            Loan::load(deps.as_ref().storage, loan).expect("can't load loan")
+        );
+
+        let total = Total::<TheCurrency>::load(deps.as_ref().storage).expect("can't load Total");
+        assert_eq!(loan_after.principal_due, total.total_principal_due());
+    }
+
+    #[test]
+    fn merge_loan() {
+        let balance_mock = [coin_cw(10_000_000)];
+        let mut deps = testing::mock_dependencies_with_balance(&balance_mock);
+        let env = testing::mock_env();
+        let admin = Addr::unchecked("admin");
+        let loan = Addr::unchecked("loan");
+        let other_loan = Addr::unchecked("other_loan");
+        let lease_code_id = Code::unchecked(123);
+
+        grant_admin_access(deps.as_mut(), &admin);
+        Config::new_unchecked(
+            lease_code_id,
+            InterestRate::new(
+                BASE_INTEREST_RATE,
+                UTILIZATION_OPTIMAL,
+                ADDON_OPTIMAL_INTEREST_RATE,
+            )
+            .expect("Couldn't construct interest rate value!"),
+            DEFAULT_MIN_UTILIZATION,
+        )
+        .store(deps.as_mut().storage)
+        .expect("Failed to store Config!");
+        Total::<TheCurrency>::new()
+            .store(deps.as_mut().storage)
+            .expect("can't initialize Total");
+
+        let mut lpp = LiquidityPool::<TheCurrency>::load(deps.as_mut().storage)
+            .expect("can't load LiquidityPool");
+
+        lpp.try_open_loan(&mut deps.as_mut(), &env, loan.clone(), Coin::new(2_000_000))
+            .expect("can't open loan");
+        deps.querier
+            .update_balance(MOCK_CONTRACT_ADDR, vec![coin_cw(8_000_000)]);
+        lpp.try_open_loan(
+            &mut deps.as_mut(),
+            &env,
+            other_loan.clone(),
+            Coin::new(3_000_000),
+        )
+        .expect("can't open loan");
+
+        let err = lpp
+            .try_merge_loan(&mut deps.as_mut(), &env, loan.clone(), loan.clone())
+            .expect_err("should not merge a loan into itself");
+        assert_eq!(ContractError::SelfLoanMerge, err);
+
+        let mut env_later = env;
+        env_later.block.time += Duration::YEAR;
+
+        let merged = lpp
+            .try_merge_loan(
+                &mut deps.as_mut(),
+                &env_later,
+                other_loan.clone(),
+                loan.clone(),
+            )
+            .expect("can't merge loans");
+        assert_eq!(Coin::new(5_000_000), merged.principal_due);
+        assert_eq!(
+            merged,
+            Loan::load(deps.as_ref().storage, loan).expect("can't load loan")
+        );
+        assert_eq!(
+            None,
+            Loan::<TheCurrency>::query(deps.as_ref().storage, other_loan)
+                .expect("can't query loan")
+        );
+
+        let total = Total::<TheCurrency>::load(deps.as_ref().storage).expect("can't load Total");
+        assert_eq!(merged.principal_due, total.total_principal_due());
+    }
+
     #[test]
     fn try_open_and_close_loan_without_paying_interest() {
         let balance_mock = [coin_cw(10_000_000)];
@@ -903,7 +1332,7 @@ mod test {
         let withdraw = lpp
             .withdraw_lpn(&deps.as_ref(), &env, 1000u128.into())
             .expect("should withdraw");
-        assert_eq!(withdraw, Coin::new(1110));
+        assert_eq!(withdraw, Withdrawal::Pay(Coin::new(1110)));
     }
 
     fn coin_cw<IntoCoin>(into_coin: IntoCoin) -> CwCoin
@@ -961,6 +1390,7 @@ mod test {
                     min_utilization,
                 ),
                 total,
+                queued_withdrawals: Coin::ZERO,
             };
 
             let mock_env: Env = mock_env();
@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 pub use currency::SymbolOwned as LpnResponse;
 use currency::{Group, NlsPlatform, SymbolOwned};
 use finance::{
-    coin::{Coin, CoinDTO},
+    coin::{Amount, Coin, CoinDTO},
     percent::{bound::BoundToHundredPercent, Percent},
     price::Price,
 };
@@ -14,7 +14,10 @@ use sdk::{
     schemars::{self, JsonSchema},
 };
 
-use crate::{borrow::InterestRate, loan::Loan};
+use crate::{
+    borrow::{InterestRate, RatePosition},
+    loan::Loan,
+};
 
 #[derive(Serialize, Deserialize, Clone, Eq, PartialEq, JsonSchema)]
 #[cfg_attr(any(test, feature = "testing"), derive(Debug))]
@@ -26,11 +29,15 @@ pub struct InstantiateMsg {
     pub lease_code: Uint64,
     pub borrow_rate: InterestRate,
     pub min_utilization: BoundToHundredPercent,
+    /// The minimum amount, in LPN, of a withdraw to wait behind the queued ones
+    pub min_queued_withdraw: Amount,
 }
 
 #[derive(Serialize, Deserialize)]
 #[serde(deny_unknown_fields, rename_all = "snake_case")]
-pub struct MigrateMsg {}
+pub struct MigrateMsg {
+    pub min_queued_withdraw: Amount,
+}
 
 #[derive(Serialize, Deserialize, Clone, Eq, PartialEq, JsonSchema)]
 #[cfg_attr(feature = "testing", derive(Debug))]
@@ -51,10 +58,36 @@ where
     OpenLoan {
         amount: CoinDTO<Lpns>,
     },
+    /// Borrow an additional amount against an existing loan
+    ///
+    /// The loan interest rate becomes the average of the current loan rate and the rate quoted
+    /// for the amount, weighted by the principal.
+    IncreaseLoan {
+        amount: CoinDTO<Lpns>,
+    },
     RepayLoan(),
+    /// Re-open the loan at the current pool interest rate
+    ///
+    /// The funds sent should cover the loan interest due by now and the refinance fee.
+    /// The interest is paid, the fee goes to the pool, and the rest is sent back.
+    /// The principal is kept intact. Fails if the current rate is not below the loan's one.
+    RefinanceLoan(),
+    /// Merge the loan of the lease `from` into the loan of the lease `into`
+    ///
+    /// The loan interest rate becomes the average of both rates weighted by the principal.
+    /// The interest due on both loans is preserved. The loan of `from` is closed.
+    /// Only the lease code admin, i.e. the Leaser, is permitted to merge loans.
+    MergeLoan {
+        from: Addr,
+        into: Addr,
+    },
 
     Deposit(),
-    // CW20 interface, withdraw from lender deposit
+    /// CW20 interface, withdraw from lender deposit
+    ///
+    /// The receipts are burnt at the current nLPN price. If the pool does not have enough
+    /// free balance, or there are other withdraws waiting, the amount is queued and paid
+    /// out, in FIFO order, with the next deposits and loan repayments.
     Burn {
         amount: Uint128,
     },
@@ -76,6 +109,16 @@ pub enum SudoMsg {
     MinUtilization {
         min_utilization: BoundToHundredPercent,
     },
+    /// Set the fee charged on refinancing a loan as a percentage of its principal
+    RefinanceFee {
+        refinance_fee: Percent,
+    },
+    /// Set the minimum amount, in LPN, of a withdraw to wait behind the queued ones
+    ///
+    /// Smaller withdraws are paid ahead of the queue if the free balance covers them.
+    MinQueuedWithdraw {
+        min_queued_withdraw: Amount,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Eq, PartialEq, JsonSchema)]
@@ -95,9 +138,19 @@ where
     Quote {
         amount: CoinDTO<Lpns>,
     },
+    /// Return where on the borrow interest rate curve the pool would be past
+    /// borrowing the amount as [QueryQuoteDetailsResponse]
+    ///
+    /// A zero amount gives the current position. The response of [QueryMsg::Quote] is kept
+    /// intact for the sake of the existing leases.
+    QuoteDetails {
+        amount: CoinDTO<Lpns>,
+    },
     Loan {
         lease_addr: Addr,
     },
+    /// Return the terms a loan would be refinanced at now as [QueryRefinanceQuoteResponse]
+    RefinanceQuote(),
     // Deposit
     /// CW20 interface, lender deposit balance
     Balance {
@@ -118,6 +171,18 @@ where
     Rewards {
         address: Addr,
     },
+
+    /// Return the queued withdraw requests of a lender as a [Vec] of [WithdrawRequestResponse]
+    ///
+    /// Up to `limit` requests, past the one with id `start_after` if provided, are returned
+    /// in the queue order. A response with less than `limit` requests is the last page.
+    WithdrawRequests {
+        address: Addr,
+        #[serde(default)]
+        start_after: Option<u64>,
+        #[serde(default)]
+        limit: Option<u32>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
@@ -128,6 +193,23 @@ pub enum QueryQuoteResponse {
     NoLiquidity,
 }
 
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Debug))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub enum QueryQuoteDetailsResponse {
+    Quote(RatePosition),
+    NoLiquidity,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Debug))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct QueryRefinanceQuoteResponse {
+    pub annual_interest_rate: Percent,
+    /// The refinance fee as a percentage of the loan principal
+    pub fee: Percent,
+}
+
 pub type LoanResponse<Lpn> = Loan<Lpn>;
 
 pub type QueryLoanResponse<Lpn> = Option<LoanResponse<Lpn>>;
@@ -166,6 +248,28 @@ pub struct PriceResponse<Lpn>(pub Price<NLpn, Lpn>)
 where
     Lpn: 'static + ?Sized;
 
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Debug))]
+#[serde(
+    deny_unknown_fields,
+    rename_all = "snake_case",
+    bound(serialize = "", deserialize = "")
+)]
+pub struct WithdrawRequestResponse<Lpns>
+where
+    Lpns: Group,
+{
+    pub id: u64,
+    /// The number of requests to be paid before this one
+    pub position: u64,
+    /// The total amount to be paid before this request
+    pub ahead: CoinDTO<Lpns>,
+    /// The burnt receipts
+    pub receipts: Coin<NLpn>,
+    /// The amount still to be paid
+    pub amount: CoinDTO<Lpns>,
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
 #[cfg_attr(any(test, feature = "testing"), derive(Debug))]
 #[serde(deny_unknown_fields, rename_all = "snake_case")]
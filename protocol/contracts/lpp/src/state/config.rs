@@ -3,7 +3,11 @@ use std::mem;
 use serde::{Deserialize, Serialize};
 
 use currency::{Currency, SymbolSlice};
-use finance::{percent::bound::BoundToHundredPercent, price::Price};
+use finance::{
+    coin::Amount,
+    percent::{bound::BoundToHundredPercent, Percent},
+    price::Price,
+};
 use lpp_platform::NLpn;
 use platform::contract::Code;
 use sdk::{cosmwasm_std::Storage, cw_storage_plus::Item};
@@ -19,6 +23,13 @@ pub struct Config {
     lease_code: Code,
     borrow_rate: InterestRate,
     min_utilization: BoundToHundredPercent,
+    /// The fee charged on refinancing a loan, a percentage of its principal
+    #[serde(default)]
+    refinance_fee: Percent,
+    /// The minimum amount, in LPN, of a withdraw to wait behind the queued ones
+    // TODO remove the default once all Lpp contracts have been migrated
+    #[serde(default)]
+    min_queued_withdraw: Amount,
 }
 
 impl Config {
@@ -29,10 +40,14 @@ impl Config {
         Lpn: ?Sized + Currency,
     {
         if msg.lpn_ticker == Self::lpn_ticker::<Lpn>() {
-            Ok(Self {
-                lease_code,
-                borrow_rate: msg.borrow_rate,
-                min_utilization: msg.min_utilization,
+            Self::validate_min_queued_withdraw(msg.min_queued_withdraw).map(|min_queued_withdraw| {
+                Self {
+                    lease_code,
+                    borrow_rate: msg.borrow_rate,
+                    min_utilization: msg.min_utilization,
+                    refinance_fee: Percent::ZERO,
+                    min_queued_withdraw,
+                }
             })
         } else {
             Err(ContractError::InvalidConfigParameter(
@@ -51,6 +66,8 @@ impl Config {
             lease_code,
             borrow_rate,
             min_utilization,
+            refinance_fee: Percent::ZERO,
+            min_queued_withdraw: 0,
         }
     }
 
@@ -73,6 +90,14 @@ impl Config {
         self.min_utilization
     }
 
+    pub const fn refinance_fee(&self) -> Percent {
+        self.refinance_fee
+    }
+
+    pub const fn min_queued_withdraw(&self) -> Amount {
+        self.min_queued_withdraw
+    }
+
     pub fn store(&self, storage: &mut dyn Storage) -> Result<()> {
         Self::STORAGE.save(storage, self).map_err(Into::into)
     }
@@ -112,6 +137,33 @@ impl Config {
         })
     }
 
+    pub fn update_refinance_fee(storage: &mut dyn Storage, refinance_fee: Percent) -> Result<()> {
+        Self::update_field(storage, |config| Self {
+            refinance_fee,
+            ..config
+        })
+    }
+
+    pub fn update_min_queued_withdraw(
+        storage: &mut dyn Storage,
+        min_queued_withdraw: Amount,
+    ) -> Result<()> {
+        Self::validate_min_queued_withdraw(min_queued_withdraw).and_then(|min_queued_withdraw| {
+            Self::update_field(storage, |config| Self {
+                min_queued_withdraw,
+                ..config
+            })
+        })
+    }
+
+    fn validate_min_queued_withdraw(min_queued_withdraw: Amount) -> Result<Amount> {
+        if min_queued_withdraw == 0 {
+            Err(ContractError::ZeroMinQueuedWithdraw)
+        } else {
+            Ok(min_queued_withdraw)
+        }
+    }
+
     fn update_field<F>(storage: &mut dyn Storage, f: F) -> Result<()>
     where
         F: FnOnce(Config) -> Config,
@@ -1,5 +1,10 @@
 #[cfg(any(feature = "contract", test))]
-pub use self::{config::Config, deposit::Deposit, total::Total};
+pub use self::{
+    config::Config,
+    deposit::Deposit,
+    total::Total,
+    withdraw_queue::{Fill, RequestId, WithdrawQueue},
+};
 
 #[cfg(any(feature = "contract", test))]
 mod config;
@@ -7,3 +12,5 @@ mod config;
 mod deposit;
 #[cfg(any(feature = "contract", test))]
 mod total;
+#[cfg(any(feature = "contract", test))]
+mod withdraw_queue;
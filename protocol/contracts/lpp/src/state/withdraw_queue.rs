@@ -0,0 +1,466 @@
+use serde::{Deserialize, Serialize};
+
+use currency::{Currency, Group};
+use finance::{coin::Coin, zero::Zero};
+use lpp_platform::NLpn;
+use sdk::{
+    cosmwasm_std::{Addr, Order, StdResult, Storage},
+    cw_storage_plus::{Item, Map},
+};
+
+use crate::{
+    error::{ContractError, Result},
+    msg::WithdrawRequestResponse,
+};
+
+pub type RequestId = u64;
+
+/// A lender request to withdraw that could not be paid immediately
+///
+/// The receipts are burnt at the time of the request and the amount due is fixed
+/// at the then current nLPN price.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(bound(serialize = "", deserialize = ""))]
+pub struct WithdrawRequest<Lpn>
+where
+    Lpn: ?Sized,
+{
+    pub lender: Addr,
+    pub receipts: Coin<NLpn>,
+    /// The amount still to be paid
+    pub amount: Coin<Lpn>,
+}
+
+/// The position of a request in the queue
+#[derive(Debug, Eq, PartialEq)]
+pub struct QueuedRequest<Lpn>
+where
+    Lpn: ?Sized,
+{
+    pub id: RequestId,
+    /// The number of requests to be paid before this one
+    pub position: u64,
+    /// The total amount to be paid before this request
+    pub ahead: Coin<Lpn>,
+    pub request: WithdrawRequest<Lpn>,
+}
+
+impl<Lpn, Lpns> From<QueuedRequest<Lpn>> for WithdrawRequestResponse<Lpns>
+where
+    Lpn: Currency,
+    Lpns: Group,
+{
+    fn from(queued: QueuedRequest<Lpn>) -> Self {
+        Self {
+            id: queued.id,
+            position: queued.position,
+            ahead: queued.ahead.into(),
+            receipts: queued.request.receipts,
+            amount: queued.request.amount.into(),
+        }
+    }
+}
+
+/// A payment, in full or partial, of a request
+#[derive(Debug, Eq, PartialEq)]
+pub struct Fill<Lpn>
+where
+    Lpn: ?Sized,
+{
+    pub id: RequestId,
+    pub lender: Addr,
+    pub amount: Coin<Lpn>,
+    pub completed: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+#[serde(bound(serialize = "", deserialize = ""))]
+struct QueueGlobals<Lpn>
+where
+    Lpn: ?Sized,
+{
+    next_id: RequestId,
+    total: Coin<Lpn>,
+}
+
+impl<Lpn> Default for QueueGlobals<Lpn>
+where
+    Lpn: ?Sized,
+{
+    fn default() -> Self {
+        Self {
+            next_id: RequestId::default(),
+            total: Coin::ZERO,
+        }
+    }
+}
+
+/// FIFO queue of withdraw requests
+pub struct WithdrawQueue<Lpn>
+where
+    Lpn: ?Sized,
+{
+    globals: QueueGlobals<Lpn>,
+}
+
+impl<Lpn> WithdrawQueue<Lpn>
+where
+    Lpn: ?Sized,
+{
+    const REQUESTS: Map<'static, RequestId, WithdrawRequest<Lpn>> = Map::new("withdraw_requests");
+    const GLOBALS: Item<'static, QueueGlobals<Lpn>> = Item::new("withdraw_queue");
+
+    pub fn load(storage: &dyn Storage) -> StdResult<Self> {
+        Self::GLOBALS
+            .may_load(storage)
+            .map(Option::unwrap_or_default)
+            .map(|globals| Self { globals })
+    }
+
+    /// The total amount due to the queued requests
+    pub fn total(&self) -> Coin<Lpn> {
+        self.globals.total
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.globals.total.is_zero()
+    }
+
+    pub fn push(
+        &mut self,
+        storage: &mut dyn Storage,
+        lender: Addr,
+        receipts: Coin<NLpn>,
+        amount: Coin<Lpn>,
+    ) -> Result<RequestId> {
+        if amount.is_zero() {
+            return Err(ContractError::ZeroWithdrawFunds);
+        }
+
+        let id = self.globals.next_id;
+
+        self.globals.next_id = id
+            .checked_add(1)
+            .ok_or(ContractError::OverflowError("Withdraw request id overflow"))?;
+        self.globals.total = self
+            .globals
+            .total
+            .checked_add(amount)
+            .ok_or(ContractError::OverflowError("Withdraw queue overflow"))?;
+
+        Self::REQUESTS.save(
+            storage,
+            id,
+            &WithdrawRequest {
+                lender,
+                receipts,
+                amount,
+            },
+        )?;
+        Self::GLOBALS.save(storage, &self.globals)?;
+
+        Ok(id)
+    }
+
+    /// Pay up to `max_fills` requests in FIFO order up to the available amount
+    ///
+    /// The last paid request may be filled partially.
+    pub fn fill(
+        &mut self,
+        storage: &mut dyn Storage,
+        available: Coin<Lpn>,
+        max_fills: usize,
+    ) -> Result<Vec<Fill<Lpn>>> {
+        let mut available = available.min(self.globals.total);
+        let mut fills = vec![];
+
+        while !available.is_zero() && fills.len() < max_fills {
+            let Some((id, mut request)) = Self::REQUESTS.first(storage)? else {
+                debug_assert!(false, "the queue total should be covered by requests");
+                break;
+            };
+
+            let amount = available.min(request.amount);
+            request.amount -= amount;
+            available -= amount;
+
+            let completed = request.amount.is_zero();
+            if completed {
+                Self::REQUESTS.remove(storage, id);
+            } else {
+                Self::REQUESTS.save(storage, id, &request)?;
+            }
+
+            fills.push(Fill {
+                id,
+                lender: request.lender,
+                amount,
+                completed,
+            });
+            self.globals.total -= amount;
+        }
+
+        if !fills.is_empty() {
+            Self::GLOBALS.save(storage, &self.globals)?;
+        }
+
+        Ok(fills)
+    }
+
+    /// Up to `limit` queued requests of a lender past the one with id `start_after`
+    pub fn query_requests(
+        storage: &dyn Storage,
+        lender: &Addr,
+        start_after: Option<RequestId>,
+        limit: usize,
+    ) -> StdResult<Vec<QueuedRequest<Lpn>>> {
+        let mut ahead = Coin::ZERO;
+
+        Self::REQUESTS
+            .range(storage, None, None, Order::Ascending)
+            .zip(0..)
+            .filter_map(|(record, position)| match record {
+                Ok((id, request)) => {
+                    let amount = request.amount;
+                    let queued = (request.lender == *lender
+                        && start_after.is_none_or(|start_after| start_after < id))
+                    .then_some(QueuedRequest {
+                        id,
+                        position,
+                        ahead,
+                        request,
+                    });
+                    ahead += amount;
+                    queued.map(Ok)
+                }
+                Err(err) => Some(Err(err)),
+            })
+            .take(limit)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use currencies::test::LpnC;
+    use finance::{coin::Coin, zero::Zero};
+    use sdk::cosmwasm_std::{testing::MockStorage, Addr};
+
+    use crate::error::ContractError;
+
+    use super::{Fill, QueuedRequest, WithdrawQueue, WithdrawRequest};
+
+    type TheCurrency = LpnC;
+
+    #[test]
+    fn fill_fifo() {
+        let mut storage = MockStorage::default();
+        let lender1 = Addr::unchecked("lender1");
+        let lender2 = Addr::unchecked("lender2");
+
+        let mut queue = WithdrawQueue::<TheCurrency>::load(&storage).unwrap();
+        assert!(queue.is_empty());
+        assert_eq!(
+            0,
+            queue
+                .push(&mut storage, lender1.clone(), 100.into(), 110.into())
+                .unwrap()
+        );
+        assert_eq!(
+            1,
+            queue
+                .push(&mut storage, lender2.clone(), 50.into(), 60.into())
+                .unwrap()
+        );
+        assert_eq!(Coin::new(170), queue.total());
+
+        let mut queue = WithdrawQueue::<TheCurrency>::load(&storage).unwrap();
+        assert_eq!(Coin::new(170), queue.total());
+        assert_eq!(
+            vec![
+                Fill {
+                    id: 0,
+                    lender: lender1.clone(),
+                    amount: 110.into(),
+                    completed: true,
+                },
+                Fill {
+                    id: 1,
+                    lender: lender2.clone(),
+                    amount: 20.into(),
+                    completed: false,
+                }
+            ],
+            queue.fill(&mut storage, 130.into(), usize::MAX).unwrap()
+        );
+        assert_eq!(Coin::new(40), queue.total());
+        assert!(
+            WithdrawQueue::<TheCurrency>::query_requests(&storage, &lender1, None, usize::MAX)
+                .unwrap()
+                .is_empty()
+        );
+
+        assert_eq!(
+            vec![Fill {
+                id: 1,
+                lender: lender2.clone(),
+                amount: 40.into(),
+                completed: true,
+            }],
+            queue.fill(&mut storage, 1000.into(), usize::MAX).unwrap()
+        );
+        assert!(queue.is_empty());
+        assert!(queue
+            .fill(&mut storage, 1000.into(), usize::MAX)
+            .unwrap()
+            .is_empty());
+        assert!(WithdrawQueue::<TheCurrency>::load(&storage)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn fill_limited() {
+        let mut storage = MockStorage::default();
+        let lender1 = Addr::unchecked("lender1");
+        let lender2 = Addr::unchecked("lender2");
+
+        let mut queue = WithdrawQueue::<TheCurrency>::load(&storage).unwrap();
+        queue
+            .push(&mut storage, lender1.clone(), 10.into(), 11.into())
+            .unwrap();
+        queue
+            .push(&mut storage, lender2.clone(), 20.into(), 22.into())
+            .unwrap();
+
+        assert_eq!(
+            vec![Fill {
+                id: 0,
+                lender: lender1,
+                amount: 11.into(),
+                completed: true,
+            }],
+            queue.fill(&mut storage, 1000.into(), 1).unwrap()
+        );
+        assert_eq!(Coin::new(22), queue.total());
+        assert_eq!(
+            vec![Fill {
+                id: 1,
+                lender: lender2,
+                amount: 22.into(),
+                completed: true,
+            }],
+            queue.fill(&mut storage, 1000.into(), 1).unwrap()
+        );
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn push_zero() {
+        let mut storage = MockStorage::default();
+
+        let mut queue = WithdrawQueue::<TheCurrency>::load(&storage).unwrap();
+        assert_eq!(
+            Err(ContractError::ZeroWithdrawFunds),
+            queue.push(
+                &mut storage,
+                Addr::unchecked("lender"),
+                10.into(),
+                Coin::ZERO
+            )
+        );
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn query_position() {
+        let mut storage = MockStorage::default();
+        let lender1 = Addr::unchecked("lender1");
+        let lender2 = Addr::unchecked("lender2");
+
+        let mut queue = WithdrawQueue::<TheCurrency>::load(&storage).unwrap();
+        queue
+            .push(&mut storage, lender1.clone(), 100.into(), 110.into())
+            .unwrap();
+        queue
+            .push(&mut storage, lender2.clone(), 50.into(), 60.into())
+            .unwrap();
+        queue
+            .push(&mut storage, lender1.clone(), 10.into(), 11.into())
+            .unwrap();
+        queue.fill(&mut storage, 10.into(), usize::MAX).unwrap();
+
+        assert_eq!(
+            vec![
+                QueuedRequest {
+                    id: 0,
+                    position: 0,
+                    ahead: Coin::ZERO,
+                    request: WithdrawRequest {
+                        lender: lender1.clone(),
+                        receipts: 100.into(),
+                        amount: 100.into(),
+                    },
+                },
+                QueuedRequest {
+                    id: 2,
+                    position: 2,
+                    ahead: 160.into(),
+                    request: WithdrawRequest {
+                        lender: lender1.clone(),
+                        receipts: 10.into(),
+                        amount: 11.into(),
+                    },
+                }
+            ],
+            WithdrawQueue::<TheCurrency>::query_requests(&storage, &lender1, None, usize::MAX)
+                .unwrap()
+        );
+        assert_eq!(
+            vec![QueuedRequest {
+                id: 1,
+                position: 1,
+                ahead: 100.into(),
+                request: WithdrawRequest {
+                    lender: lender2.clone(),
+                    receipts: 50.into(),
+                    amount: 60.into(),
+                },
+            }],
+            WithdrawQueue::<TheCurrency>::query_requests(&storage, &lender2, None, usize::MAX)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn query_page() {
+        let mut storage = MockStorage::default();
+        let lender1 = Addr::unchecked("lender1");
+        let lender2 = Addr::unchecked("lender2");
+
+        let mut queue = WithdrawQueue::<TheCurrency>::load(&storage).unwrap();
+        queue
+            .push(&mut storage, lender1.clone(), 10.into(), 10.into())
+            .unwrap();
+        queue
+            .push(&mut storage, lender2, 20.into(), 20.into())
+            .unwrap();
+        queue
+            .push(&mut storage, lender1.clone(), 30.into(), 30.into())
+            .unwrap();
+        queue
+            .push(&mut storage, lender1.clone(), 40.into(), 40.into())
+            .unwrap();
+
+        let page = |start_after, limit| {
+            WithdrawQueue::<TheCurrency>::query_requests(&storage, &lender1, start_after, limit)
+                .unwrap()
+                .into_iter()
+                .map(|queued| (queued.id, queued.position, queued.ahead))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(vec![(0, 0, Coin::ZERO), (2, 2, 30.into())], page(None, 2));
+        assert_eq!(vec![(3, 3, 60.into())], page(Some(2), 2));
+        assert!(page(Some(3), 2).is_empty());
+    }
+}
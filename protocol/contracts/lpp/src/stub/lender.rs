@@ -10,7 +10,7 @@ use sdk::cosmwasm_std::{Addr, QuerierWrapper, Reply};
 
 use crate::{
     error::{ContractError, Result},
-    msg::{ExecuteMsg, LoanResponse, QueryMsg, QueryQuoteResponse},
+    msg::{ExecuteMsg, LoanResponse, QueryMsg, QueryQuoteResponse, QueryRefinanceQuoteResponse},
 };
 
 use super::{LppBatch, LppRef};
@@ -25,6 +25,8 @@ where
     fn open_loan_resp(&self, resp: Reply) -> Result<LoanResponse<Lpn>>;
 
     fn quote(&self, amount: Coin<Lpn>) -> Result<QueryQuoteResponse>;
+
+    fn refinance_quote(&self) -> Result<QueryRefinanceQuoteResponse>;
 }
 
 pub trait WithLppLender<Lpn, Lpns>
@@ -104,6 +106,12 @@ where
             .query_wasm_smart(self.id(), &msg)
             .map_err(ContractError::from)
     }
+
+    fn refinance_quote(&self) -> Result<QueryRefinanceQuoteResponse> {
+        self.querier
+            .query_wasm_smart(self.id(), &QueryMsg::<Lpns>::RefinanceQuote())
+            .map_err(ContractError::from)
+    }
 }
 
 impl<'a, Lpn, Lpns> From<LppLenderStub<'a, Lpn, Lpns>> for LppBatch<LppRef<Lpn, Lpns>>
@@ -27,6 +27,22 @@ where
     /// and then, if there is any remaining amount, to repay the principal.
     /// Amount 0 is acceptable although does not change the loan.
     fn repay(&mut self, by: &Timestamp, repayment: Coin<Lpn>) -> RepayShares<Lpn>;
+    /// Increase the loan principal with the specified amount
+    ///
+    /// The 'annual_interest_rate' is the rate quoted by the Lpp for the amount.
+    /// The loan interest rate becomes the average of the current and the quoted rates
+    /// weighted by the principal.
+    fn borrow(&mut self, by: &Timestamp, amount: Coin<Lpn>, annual_interest_rate: Percent);
+    /// Re-open the loan at the specified annual interest rate by the specified time
+    ///
+    /// The 'annual_interest_rate' is the rate quoted by the Lpp for refinancing.
+    /// The interest due is paid along with the refinance 'fee'. Return the paid interest.
+    fn refinance(
+        &mut self,
+        by: &Timestamp,
+        annual_interest_rate: Percent,
+        fee: Coin<Lpn>,
+    ) -> Coin<Lpn>;
     fn annual_interest_rate(&self) -> Percent;
 }
 
@@ -51,6 +67,8 @@ where
     lpn: PhantomData<Lpn>,
     loan: Loan<Lpn>,
     repayment: Coin<Lpn>,
+    borrowed: Coin<Lpn>,
+    refinance_payment: Option<Coin<Lpn>>,
 }
 
 impl<Lpn, Lpns> LppLoanImpl<Lpn, Lpns>
@@ -64,6 +82,8 @@ where
             lpn: PhantomData,
             loan,
             repayment: Default::default(),
+            borrowed: Default::default(),
+            refinance_payment: None,
         }
     }
 }
@@ -85,6 +105,26 @@ where
         self.loan.repay(by, repayment)
     }
 
+    fn borrow(&mut self, by: &Timestamp, amount: Coin<Lpn>, annual_interest_rate: Percent) {
+        self.borrowed += amount;
+        self.loan.borrow(by, amount, annual_interest_rate)
+    }
+
+    fn refinance(
+        &mut self,
+        by: &Timestamp,
+        annual_interest_rate: Percent,
+        fee: Coin<Lpn>,
+    ) -> Coin<Lpn> {
+        debug_assert!(
+            self.refinance_payment.is_none(),
+            "The loan should be refinanced at most once"
+        );
+        let interest = self.loan.refinance(by, annual_interest_rate);
+        self.refinance_payment = Some(interest + fee);
+        interest
+    }
+
     fn annual_interest_rate(&self) -> Percent {
         self.loan.annual_interest_rate
     }
@@ -106,6 +146,21 @@ where
                 Some(stub.repayment),
             )?;
         }
+        if !stub.borrowed.is_zero() {
+            batch.schedule_execute_wasm_no_reply_no_funds(
+                stub.lpp_ref.addr().clone(),
+                &ExecuteMsg::<Lpns>::IncreaseLoan {
+                    amount: stub.borrowed.into(),
+                },
+            )?;
+        }
+        if let Some(payment) = stub.refinance_payment {
+            batch.schedule_execute_wasm_no_reply(
+                stub.lpp_ref.addr().clone(),
+                &ExecuteMsg::<Lpns>::RefinanceLoan(),
+                Some(payment),
+            )?;
+        }
         Ok(Self {
             lpp_ref: stub.lpp_ref,
             batch,
@@ -177,4 +232,45 @@ mod test {
             assert_eq!(exp, batch.batch);
         }
     }
+
+    #[test]
+    fn try_from_borrow() {
+        let lpp_ref = LppRef::<LpnC, _>::unchecked("lpp_address");
+        let start = Timestamp::from_seconds(0);
+        let mut loan = LppLoanImpl::new(
+            lpp_ref.clone(),
+            Loan {
+                principal_due: Coin::<LpnC>::new(100),
+                annual_interest_rate: Percent::from_percent(12),
+                interest_paid: start,
+            },
+        );
+        let payment = 8.into();
+        let amount1 = 40.into();
+        let amount2 = 60.into();
+        loan.repay(&start, payment);
+        loan.borrow(&start, amount1, Percent::from_percent(10));
+        loan.borrow(&start, amount2, Percent::from_percent(10));
+        assert_eq!(Coin::new(100 - 8 + 40 + 60), loan.principal_due());
+        let batch: LppBatch<LppRef<LpnC, Lpns>> = loan.try_into().unwrap();
+
+        assert_eq!(lpp_ref, batch.lpp_ref);
+        {
+            let mut exp = Batch::default();
+            exp.schedule_execute_wasm_no_reply(
+                lpp_ref.addr().clone(),
+                &ExecuteMsg::<Lpns>::RepayLoan(),
+                Some(payment),
+            )
+            .unwrap();
+            exp.schedule_execute_wasm_no_reply_no_funds(
+                lpp_ref.addr().clone(),
+                &ExecuteMsg::<Lpns>::IncreaseLoan {
+                    amount: (amount1 + amount2).into(),
+                },
+            )
+            .unwrap();
+            assert_eq!(exp, batch.batch);
+        }
+    }
 }
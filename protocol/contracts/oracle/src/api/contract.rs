@@ -1,11 +1,11 @@
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use currencies::PaymentGroup;
-use currency::SymbolOwned;
-use finance::price::dto::PriceDTO;
-use marketprice::config::Config as PriceConfig;
+use currency::{NlsPlatform, SymbolOwned};
+use finance::{coin::Coin, duration::Duration, price::dto::PriceDTO};
+use marketprice::{config::Config as PriceConfig, feeders::FeederStats};
 use sdk::{
-    cosmwasm_std::Addr,
+    cosmwasm_std::{Addr, Timestamp},
     schemars::{self, JsonSchema},
 };
 use tree::HumanReadableTree;
@@ -15,6 +15,7 @@ use super::{alarms::AlarmCurrencies, swap::SwapTarget, BaseCurrencies};
 
 pub type PriceCurrencies = PaymentGroup;
 pub type AlarmsCount = platform::dispatcher::AlarmsCount;
+pub type DispatchRewardsResponse = platform::dispatch_rewards::DispatchRewardsResponse;
 
 #[derive(Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[cfg_attr(any(test, feature = "testing"), derive(Debug, Clone))]
@@ -46,10 +47,29 @@ pub enum ExecuteMsg {
 #[cfg_attr(any(test, feature = "testing"), derive(Debug, Clone))]
 #[serde(deny_unknown_fields, rename_all = "snake_case")]
 pub enum SudoMsg {
-    RegisterFeeder { feeder_address: String },
-    RemoveFeeder { feeder_address: String },
+    RegisterFeeder {
+        feeder_address: String,
+    },
+    RemoveFeeder {
+        feeder_address: String,
+    },
     UpdateConfig(PriceConfig),
-    SwapTree { tree: HumanReadableTree<SwapTarget> },
+    SwapTree {
+        tree: HumanReadableTree<SwapTarget>,
+    },
+    /// Set the reward paid per alarm delivered successfully to a lease and top up the budget
+    ///
+    /// The contract NLS balance should cover the resulting budget. Only the alarms
+    /// of the leases verified by one of the `leasers` are rewarded.
+    DispatchRewards {
+        per_alarm: Coin<NlsPlatform>,
+        top_up: Coin<NlsPlatform>,
+        leasers: Vec<Addr>,
+    },
+    /// Resume the price alarms on a currency halted due to an abnormal price move
+    ClearPriceHalt {
+        currency: SymbolOwned,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
@@ -73,6 +93,11 @@ pub enum QueryMsg {
         address: Addr,
     },
 
+    /// Provides the track record of all registered feeders
+    ///
+    /// Returns [`Vec<FeederStatsResponse>`]
+    FeedersStats {},
+
     /// Provides all supported prices
     Prices {},
 
@@ -88,6 +113,23 @@ pub enum QueryMsg {
         currency: SymbolOwned,
     },
 
+    /// Provides the samples the price of a currency against the base currency is calculated from
+    ///
+    /// Returns [`PriceSamplesResponse`]
+    BasePriceSamples {
+        currency: SymbolOwned,
+    },
+
+    /// Provides the time-weighted average price of a currency against the base currency
+    ///
+    /// The period [`from`, `to`] should be within the price feeds validity window.
+    /// Returns [`PriceDTO`]
+    BasePriceTwap {
+        currency: SymbolOwned,
+        from: Timestamp,
+        to: Timestamp,
+    },
+
     /// Implementation of [oracle_platform::msg::QueryMsg::StableCurrency]
     StableCurrency {},
 
@@ -112,6 +154,14 @@ pub enum QueryMsg {
     },
     /// Returns [`Status`] as response data.
     AlarmsStatus {},
+    /// Provides the reward per delivered alarm and the remaining budget
+    ///
+    /// Returns [`DispatchRewardsResponse`]
+    DispatchRewards {},
+    /// Provides the currencies whose price alarms are halted due to an abnormal price move
+    ///
+    /// Returns [`PriceHaltsResponse`]
+    PriceHalts {},
 }
 
 #[derive(Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
@@ -180,6 +230,14 @@ pub struct SwapTreeResponse {
     pub tree: HumanReadableTree<SwapTarget>,
 }
 
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Debug))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct FeederStatsResponse {
+    pub feeder: Addr,
+    pub stats: FeederStats,
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
 #[cfg_attr(any(test, feature = "testing"), derive(Debug))]
 #[serde(deny_unknown_fields, rename_all = "snake_case")]
@@ -187,6 +245,20 @@ pub struct PricesResponse {
     pub prices: Vec<PriceDTO<PriceCurrencies, BaseCurrencies>>,
 }
 
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Debug))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct PriceSamplesResponse {
+    /// The start of the first sample period, exclusive
+    pub valid_since: Timestamp,
+    pub sample_period: Duration,
+    /// The sample prices, ordered from the oldest to the newest
+    ///
+    /// The i-th sample spans (valid_since + i * sample_period, valid_since + (i + 1) * sample_period].
+    /// A sample has no price if there have not been enough observations so far.
+    pub samples: Vec<Option<PriceDTO<PriceCurrencies, BaseCurrencies>>>,
+}
+
 #[derive(Serialize, Deserialize, JsonSchema)]
 #[cfg_attr(any(test, feature = "testing"), derive(Debug))]
 #[serde(deny_unknown_fields, rename_all = "snake_case")]
@@ -194,6 +266,17 @@ pub struct AlarmsStatusResponse {
     pub remaining_alarms: bool,
 }
 
+pub type PriceHaltsResponse = Vec<PriceHalt>;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Debug))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct PriceHalt {
+    pub currency: SymbolOwned,
+    /// The price alarms on the currency are resumed at this time
+    pub until: Timestamp,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct SwapLeg {
     pub from: SymbolOwned,
@@ -29,6 +29,15 @@ pub type Result<T> = StdResult<T, Error>;
 pub enum Error {
     #[error("[Oracle; Stub] Failed to query swap path! Cause: {0}")]
     StubSwapPathQuery(StdError),
+
+    #[error("[Oracle; Stub] {0}")]
+    Currency(#[from] currency::error::Error),
+
+    #[error("[Oracle; Stub] {0}")]
+    Finance(#[from] finance::error::Error),
+
+    #[error("[Oracle; Stub] Failed to estimate the swap output! Cause: {0}")]
+    StubPriceQuery(#[from] oracle_platform::error::Error),
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, JsonSchema)]
@@ -10,6 +10,11 @@ use crate::{contract::alarms::PriceResult, error::ContractError, result::Contrac
 type AlarmIterMapFn = fn(Result<Addr, AlarmError>) -> ContractResult<Addr>;
 type AlarmIter<'alarms, G> = iter::Map<AlarmsIterator<'alarms, G>, AlarmIterMapFn>;
 
+/// Iterates over the alarms triggered by the prices, one price after another
+///
+/// The prices are visited lazily, so a dispatch limited to a few alarms does not
+/// walk the alarms of all prices. The alarms of each price come below first,
+/// the most severe first.
 pub struct Iter<'storage, 'alarms, S, I, PriceG, BaseC>
 where
     S: Deref<Target = (dyn Storage + 'storage)>,
@@ -108,7 +108,7 @@ where
         self.alarms.out_for_delivery(subscriber).map_err(Into::into)
     }
 
-    pub fn last_delivered(&mut self) -> ContractResult<()> {
+    pub fn last_delivered(&mut self) -> ContractResult<Addr> {
         self.alarms.last_delivered().map_err(Into::into)
     }
 
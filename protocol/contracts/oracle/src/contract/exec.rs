@@ -1,7 +1,9 @@
+use std::ops::DerefMut;
+
 use finance::price::dto::PriceDTO;
 
 use currency::{Currency, Group};
-use platform::{contract, response};
+use platform::{contract, dispatch_rewards, response};
 use sdk::{
     cosmwasm_ext::Response as CwResponse,
     cosmwasm_std::{Addr, DepsMut, Env, Storage, Timestamp},
@@ -14,12 +16,13 @@ use crate::{
     contract::{alarms::MarketAlarms, oracle::Oracle},
     error::ContractError,
     result::ContractResult,
+    state::{price_halts, supported_pairs::SupportedPairs},
 };
 
 use super::oracle::{feed::Feeds, feeder::Feeders};
 
 pub fn do_executute(
-    deps: DepsMut<'_>,
+    mut deps: DepsMut<'_>,
     env: Env,
     msg: ExecuteMsg,
     sender: Addr,
@@ -39,9 +42,14 @@ pub fn do_executute(
             .map(|()| Default::default())
         }
         ExecuteMsg::DispatchAlarms { max_count } => {
-            Oracle::<_, PriceCurrencies, BaseCurrency, BaseCurrencies>::load(deps.storage)?
-                .try_notify_alarms(env.block.time, max_count)
-                .and_then(|(total, resp)| {
+            let (total, resp) = Oracle::<_, PriceCurrencies, BaseCurrency, BaseCurrencies>::load(
+                deps.storage.deref_mut(),
+            )?
+            .try_notify_alarms(env.block.time, max_count)?;
+
+            dispatch_rewards::dispatched(deps.storage, sender, total)
+                .map_err(Into::into)
+                .and_then(|()| {
                     response::response_with_messages(DispatchAlarmsResponse(total), resp)
                 })
         }
@@ -66,7 +74,26 @@ where
     BaseC: Currency,
     QuoteG: Group,
 {
-    Config::load(storage)
-        .map(|cfg| Feeds::<G, BaseC, QuoteG>::with(cfg.price_config))
-        .and_then(|oracle| oracle.feed_prices(storage, block_time, &sender, &prices))
+    let price_config = Config::load(storage)?.price_config;
+    let circuit_breaker = price_config.circuit_breaker();
+    let sample_period = price_config.sample_period();
+    let feeds = Feeds::<G, BaseC, QuoteG>::with(price_config);
+
+    feeds
+        .feed_prices(storage, block_time, &sender, &prices)
+        .and_then(|deviations| Feeders::record_feed(storage, &sender, prices.len(), deviations))?;
+
+    circuit_breaker.map_or(Ok(()), |circuit_breaker| {
+        let tree = SupportedPairs::<BaseC>::load(storage)?;
+        let total_feeders =
+            Feeders::total_registered(storage).map_err(ContractError::LoadFeeders)?;
+        let halted_until = block_time + circuit_breaker.cool_down();
+
+        feeds
+            .jumped_currencies(storage, &tree, &prices, block_time, total_feeders)?
+            .into_iter()
+            .try_for_each(|currency| {
+                price_halts::halt(storage, currency, block_time, sample_period, halted_until)
+            })
+    })
 }
@@ -1,7 +1,11 @@
+use std::ops::DerefMut;
+
 use currency::Currency;
 use platform::{
     batch::{Emit, Emitter},
-    error as platform_error, response,
+    dispatch_rewards, error as platform_error,
+    message::Response as MessageResponse,
+    response,
 };
 use sdk::{
     cosmwasm_ext::Response as CwResponse,
@@ -14,13 +18,15 @@ use versioning::{package_version, version, SemVer, Version, VersionSegment};
 
 use crate::{
     api::{
-        BaseCurrencies, BaseCurrency, Config, ExecuteMsg, InstantiateMsg, MigrateMsg,
-        PriceCurrencies, PricesResponse, QueryMsg, StableCurrency, SudoMsg, SwapTreeResponse,
+        BaseCurrencies, BaseCurrency, Config, DispatchRewardsResponse, ExecuteMsg,
+        FeederStatsResponse, InstantiateMsg, MigrateMsg, PriceCurrencies, PriceHalt,
+        PriceHaltsResponse, PriceSamplesResponse, PricesResponse, QueryMsg, StableCurrency,
+        SudoMsg, SwapTreeResponse,
     },
     contract::{alarms::MarketAlarms, oracle::Oracle},
     error::ContractError,
     result::ContractResult,
-    state::supported_pairs::SupportedPairs,
+    state::{price_halts, supported_pairs::SupportedPairs},
 };
 
 use self::{config::query_config, oracle::feeder::Feeders};
@@ -83,6 +89,16 @@ pub fn query(deps: Deps<'_>, env: Env, msg: QueryMsg) -> ContractResult<Binary>
         QueryMsg::IsFeeder { address } => Feeders::is_feeder(deps.storage, &address)
             .map_err(ContractError::LoadFeeders)
             .and_then(|ref f| to_json_binary(&f)),
+        QueryMsg::FeedersStats {} => Feeders::stats(deps.storage)
+            .map_err(ContractError::LoadFeeders)
+            .and_then(|stats| {
+                to_json_binary(
+                    &stats
+                        .into_iter()
+                        .map(|(feeder, stats)| FeederStatsResponse { feeder, stats })
+                        .collect::<Vec<_>>(),
+                )
+            }),
         QueryMsg::BaseCurrency {} => to_json_binary(BaseCurrency::TICKER),
         QueryMsg::StableCurrency {} => to_json_binary(StableCurrency::TICKER),
         QueryMsg::SupportedCurrencyPairs {} => to_json_binary(
@@ -98,6 +114,25 @@ pub fn query(deps: Deps<'_>, env: Env, msg: QueryMsg) -> ContractResult<Binary>
         QueryMsg::BasePrice { currency } => to_json_binary(
             &QueryOracle::load(deps.storage)?.try_query_base_price(env.block.time, &currency)?,
         ),
+        QueryMsg::BasePriceSamples { currency } => {
+            let price_config = query_config(deps.storage)?.price_config;
+            let samples = QueryOracle::load(deps.storage)?
+                .try_query_base_price_samples(env.block.time, &currency)?;
+
+            to_json_binary(&PriceSamplesResponse {
+                valid_since: price_config.feed_valid_since(env.block.time),
+                sample_period: price_config.sample_period(),
+                samples,
+            })
+        }
+        QueryMsg::BasePriceTwap { currency, from, to } => {
+            to_json_binary(&QueryOracle::load(deps.storage)?.try_query_base_twap(
+                env.block.time,
+                &currency,
+                from,
+                to,
+            )?)
+        }
         QueryMsg::StablePrice { currency } => to_json_binary(
             &QueryOracle::load(deps.storage)?.try_query_stable_price(env.block.time, &currency)?,
         ),
@@ -117,6 +152,19 @@ pub fn query(deps: Deps<'_>, env: Env, msg: QueryMsg) -> ContractResult<Binary>
         QueryMsg::AlarmsStatus {} => {
             to_json_binary(&QueryOracle::load(deps.storage)?.try_query_alarms(env.block.time)?)
         }
+        QueryMsg::DispatchRewards {} => dispatch_rewards::load(deps.storage)
+            .map_err(Into::into)
+            .and_then(|rewards| to_json_binary(&DispatchRewardsResponse::from(rewards))),
+        QueryMsg::PriceHalts {} => {
+            price_halts::active(deps.storage, env.block.time).and_then(|halts| {
+                to_json_binary(
+                    &halts
+                        .into_iter()
+                        .map(|(currency, until)| PriceHalt { currency, until })
+                        .collect::<PriceHaltsResponse>(),
+                )
+            })
+        }
     }
 }
 
@@ -131,7 +179,7 @@ pub fn execute(
 }
 
 #[entry_point]
-pub fn sudo(deps: DepsMut<'_>, _env: Env, msg: SudoMsg) -> ContractResult<CwResponse> {
+pub fn sudo(deps: DepsMut<'_>, env: Env, msg: SudoMsg) -> ContractResult<CwResponse> {
     match msg {
         SudoMsg::UpdateConfig(price_config) => Config::update(deps.storage, price_config),
         SudoMsg::RegisterFeeder { feeder_address } => Feeders::try_register(deps, feeder_address),
@@ -140,31 +188,57 @@ pub fn sudo(deps: DepsMut<'_>, _env: Env, msg: SudoMsg) -> ContractResult<CwResp
             SupportedPairs::<BaseCurrency>::new::<StableCurrency>(tree.into_tree())
                 .and_then(|supported_pairs| supported_pairs.save(deps.storage))
         }
+        SudoMsg::DispatchRewards {
+            per_alarm,
+            top_up,
+            leasers,
+        } => {
+            dispatch_rewards::try_fund(deps.storage, deps.querier, &env, per_alarm, top_up, leasers)
+                .map_err(Into::into)
+        }
+        SudoMsg::ClearPriceHalt { currency } => SupportedPairs::<BaseCurrency>::load(deps.storage)
+            .and_then(|tree| tree.load_path(&currency).map(|_| ()))
+            .and_then(|()| price_halts::clear(deps.storage, currency, env.block.time)),
     }
     .map(|()| response::empty_response())
 }
 
 // TODO: compare gas usage of this solution vs reply on error
 #[entry_point]
-pub fn reply(deps: DepsMut<'_>, _env: Env, msg: Reply) -> ContractResult<CwResponse> {
+pub fn reply(mut deps: DepsMut<'_>, env: Env, msg: Reply) -> ContractResult<CwResponse> {
     const EVENT_TYPE: &str = "market-alarm";
     const KEY_DELIVERED: &str = "delivered";
     const KEY_DETAILS: &str = "details";
 
-    let mut alarms: MarketAlarms<'_, &mut (dyn Storage + '_), PriceCurrencies> =
-        MarketAlarms::new(deps.storage);
-
     let emitter: Emitter = Emitter::of_type(EVENT_TYPE);
 
     match msg.result {
-        SubMsgResult::Ok(_) => alarms
-            .last_delivered()
-            .map(|()| emitter.emit(KEY_DELIVERED, "success")),
-        SubMsgResult::Err(error) => alarms.last_failed().map(|()| {
-            emitter
-                .emit(KEY_DELIVERED, "error")
-                .emit(KEY_DETAILS, error)
-        }),
+        SubMsgResult::Ok(_) => {
+            let subscriber = MarketAlarms::<'_, &mut (dyn Storage + '_), PriceCurrencies>::new(
+                deps.storage.deref_mut(),
+            )
+            .last_delivered()?;
+
+            dispatch_rewards::delivered(
+                deps.storage,
+                deps.querier,
+                &env,
+                &subscriber,
+                emitter.emit(KEY_DELIVERED, "success"),
+            )
+            .map_err(Into::into)
+        }
+        SubMsgResult::Err(error) => {
+            MarketAlarms::<'_, &mut (dyn Storage + '_), PriceCurrencies>::new(deps.storage)
+                .last_failed()
+                .map(|()| {
+                    MessageResponse::from(
+                        emitter
+                            .emit(KEY_DELIVERED, "error")
+                            .emit(KEY_DETAILS, error),
+                    )
+                })
+        }
     }
     .map(response::response_only_messages)
 }
@@ -182,15 +256,23 @@ mod tests {
         test::{LeaseC1, LpnC, PaymentC1, PaymentC5},
         LeaseGroup, Lpns,
     };
-    use currency::Currency;
-    use finance::{duration::Duration, percent::Percent, price};
-    use sdk::cosmwasm_std::{self, testing::mock_env};
+    use currency::{Currency, NlsPlatform};
+    use finance::{coin::Coin, duration::Duration, percent::Percent, price};
+    use platform::error::Error as PlatformError;
+    use sdk::cosmwasm_std::{
+        self, coins,
+        testing::{mock_env, MOCK_CONTRACT_ADDR},
+    };
 
     use crate::{
-        api::{swap::SwapTarget, Alarm, Config, ExecuteMsg, QueryMsg, SwapLeg},
-        contract::query,
+        api::{
+            swap::SwapTarget, Alarm, Config, DispatchRewardsResponse, ExecuteMsg, QueryMsg,
+            SudoMsg, SwapLeg,
+        },
+        contract::{query, sudo},
         swap_tree,
         tests::{dummy_instantiate_msg, setup_test},
+        ContractError,
     };
 
     #[test]
@@ -276,4 +358,54 @@ mod tests {
         .unwrap();
         assert_eq!(ExecuteMsgApi::AddPriceAlarm { alarm }, query_api);
     }
+
+    #[test]
+    fn fund_dispatch_rewards() {
+        let msg = dummy_instantiate_msg(
+            60,
+            Percent::from_percent(50),
+            swap_tree!({ base: LpnC::TICKER }, (1, PaymentC5::TICKER)),
+        );
+        let (mut deps, _info) = setup_test(msg);
+        deps.querier
+            .update_balance(MOCK_CONTRACT_ADDR, coins(15, NlsPlatform::BANK_SYMBOL));
+
+        assert_eq!(
+            ContractError::Platform(PlatformError::insufficient_rewards_balance(
+                Coin::new(20),
+                Coin::new(15),
+            )),
+            sudo(
+                deps.as_mut(),
+                mock_env(),
+                SudoMsg::DispatchRewards {
+                    per_alarm: Coin::new(10),
+                    top_up: Coin::new(20),
+                    leasers: vec![],
+                },
+            )
+            .unwrap_err()
+        );
+
+        sudo(
+            deps.as_mut(),
+            mock_env(),
+            SudoMsg::DispatchRewards {
+                per_alarm: Coin::new(10),
+                top_up: Coin::new(15),
+                leasers: vec![],
+            },
+        )
+        .unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::DispatchRewards {}).unwrap();
+        assert_eq!(
+            DispatchRewardsResponse {
+                per_alarm: Coin::new(10),
+                budget: Coin::new(15),
+                leasers: vec![],
+            },
+            cosmwasm_std::from_json(res).unwrap()
+        );
+    }
 }
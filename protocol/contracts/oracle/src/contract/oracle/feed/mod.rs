@@ -1,8 +1,8 @@
 use std::marker::PhantomData;
 
-use currency::{Currency, Group, SymbolSlice};
-use finance::price::dto::PriceDTO;
-use marketprice::{config::Config, market_price::PriceFeeds};
+use currency::{Currency, Group, SymbolOwned, SymbolSlice};
+use finance::{period::Period, price::dto::PriceDTO};
+use marketprice::{config::Config, error::PriceFeedsError, market_price::PriceFeeds};
 use sdk::cosmwasm_std::{Addr, Storage, Timestamp};
 
 use crate::{
@@ -38,13 +38,16 @@ where
         }
     }
 
+    /// Add the prices fed by a feeder
+    ///
+    /// Return the number of prices deviating more than the configured maximum.
     pub(crate) fn feed_prices(
         &self,
         storage: &mut dyn Storage,
         block_time: Timestamp,
         sender_raw: &Addr,
         prices: &[PriceDTO<PriceG, PriceG>],
-    ) -> Result<(), ContractError> {
+    ) -> Result<usize, ContractError> {
         let tree = SupportedPairs::<BaseC>::load(storage)?;
         if prices.iter().any(|price| {
             !tree.swap_pairs_df().any(
@@ -59,9 +62,55 @@ where
             return Err(ContractError::UnsupportedDenomPairs {});
         }
 
-        self.feeds.feed(storage, block_time, sender_raw, prices)?;
+        self.feeds
+            .feed(storage, block_time, sender_raw, prices)
+            .map_err(Into::into)
+    }
 
-        Ok(())
+    /// Provide the currencies whose base price has moved abnormally due to the fed prices
+    ///
+    /// Only the currencies whose path to the root goes through a fed price are sampled,
+    /// including the ones derived from an intermediate fed price.
+    pub(crate) fn jumped_currencies(
+        &self,
+        storage: &dyn Storage,
+        tree: &SupportedPairs<BaseC>,
+        fed_prices: &[PriceDTO<PriceG, PriceG>],
+        at: Timestamp,
+        total_feeders: usize,
+    ) -> Result<Vec<SymbolOwned>, ContractError> {
+        let is_fed = |ticker: &SymbolSlice| {
+            fed_prices
+                .iter()
+                .any(|price| price.base().ticker() == ticker)
+        };
+
+        tree.swap_pairs_df()
+            .map(|SwapLeg { from, .. }| from)
+            .filter_map(|currency| {
+                tree.load_path(&currency)
+                    .map(Iterator::collect::<Vec<_>>)
+                    .and_then(|leaf_to_root| {
+                        if leaf_to_root.iter().any(|ticker| is_fed(ticker)) {
+                            self.feeds
+                                .sample_jumps::<BaseC, _>(
+                                    storage,
+                                    at,
+                                    total_feeders,
+                                    leaf_to_root.into_iter(),
+                                )
+                                .or_else(|error| match error {
+                                    PriceFeedsError::NoPrice() => Ok(false),
+                                    error => Err(error.into()),
+                                })
+                        } else {
+                            Ok(false)
+                        }
+                    })
+                    .map(|jumped| jumped.then_some(currency))
+                    .transpose()
+            })
+            .collect()
     }
 
     pub fn all_prices_iter<'r, 'self_, 'storage, I>(
@@ -110,6 +159,39 @@ where
             .price::<BaseC, _, _>(storage, at, total_feeders, tree.load_path(currency)?)
             .map_err(Into::into)
     }
+
+    pub fn calc_base_price_samples(
+        &self,
+        storage: &dyn Storage,
+        tree: &SupportedPairs<BaseC>,
+        currency: &SymbolSlice,
+        at: Timestamp,
+        total_feeders: usize,
+    ) -> Result<Vec<Option<PriceDTO<PriceG, BaseG>>>, ContractError> {
+        self.feeds
+            .price_samples::<BaseC, _, _>(storage, at, total_feeders, tree.load_path(currency)?)
+            .map_err(Into::into)
+    }
+
+    pub fn calc_base_twap(
+        &self,
+        storage: &dyn Storage,
+        tree: &SupportedPairs<BaseC>,
+        currency: &SymbolSlice,
+        at: Timestamp,
+        total_feeders: usize,
+        period: Period,
+    ) -> Result<PriceDTO<PriceG, BaseG>, ContractError> {
+        self.feeds
+            .twap::<BaseC, _, _>(
+                storage,
+                at,
+                total_feeders,
+                tree.load_path(currency)?,
+                period,
+            )
+            .map_err(Into::into)
+    }
 }
 
 #[cfg(test)]
@@ -2,7 +2,7 @@ use std::collections::HashSet;
 
 use serde::{Deserialize, Serialize};
 
-use marketprice::feeders::PriceFeeders;
+use marketprice::feeders::{FeederStats, PriceFeeders};
 use sdk::cosmwasm_std::{Addr, DepsMut, StdResult, Storage};
 
 use crate::{api::Config, result::ContractResult, ContractError};
@@ -13,7 +13,7 @@ pub struct Feeders {
 }
 
 impl Feeders {
-    const FEEDERS: PriceFeeders<'static> = PriceFeeders::new("feeders");
+    const FEEDERS: PriceFeeders<'static> = PriceFeeders::new("feeders", "feeder_stats");
 
     pub(crate) fn get(storage: &dyn Storage) -> StdResult<HashSet<Addr>> {
         Self::FEEDERS.get(storage)
@@ -48,6 +48,21 @@ impl Feeders {
     pub(crate) fn total_registered(storage: &dyn Storage) -> StdResult<usize> {
         Self::get(storage).map(|c| c.len())
     }
+
+    pub(crate) fn record_feed(
+        storage: &mut dyn Storage,
+        feeder: &Addr,
+        prices: usize,
+        deviations: usize,
+    ) -> ContractResult<()> {
+        Self::FEEDERS
+            .record(storage, feeder, prices.try_into()?, deviations.try_into()?)
+            .map_err(ContractError::StoreFeederStats)
+    }
+
+    pub(crate) fn stats(storage: &dyn Storage) -> StdResult<Vec<(Addr, FeederStats)>> {
+        Self::FEEDERS.stats(storage)
+    }
 }
 
 #[cfg(test)]
@@ -4,11 +4,15 @@ use std::{
 };
 
 use currency::{Currency, Group, SymbolOwned, SymbolSlice};
-use finance::price::{
-    base::BasePrice,
-    dto::{with_quote, PriceDTO, WithQuote},
-    Price,
+use finance::{
+    period::Period,
+    price::{
+        base::BasePrice,
+        dto::{with_quote, PriceDTO, WithQuote},
+        Price,
+    },
 };
+use marketprice::error::PriceFeedsError;
 use platform::{
     dispatcher::{AlarmsDispatcher, Id},
     message::Response as MessageResponse,
@@ -20,7 +24,7 @@ use crate::{
     contract::{alarms::MarketAlarms, oracle::feed::Feeds},
     error::ContractError,
     result::ContractResult,
-    state::supported_pairs::SupportedPairs,
+    state::{price_halts, supported_pairs::SupportedPairs},
 };
 
 use self::feeder::Feeders;
@@ -70,7 +74,7 @@ where
         block_time: Timestamp,
     ) -> Result<AlarmsStatusResponse, ContractError> {
         MarketAlarms::new(self.storage.deref())
-            .try_query_alarms::<_, BaseC>(self.calc_all_prices(block_time))
+            .try_query_alarms::<_, BaseC>(self.calc_alarm_prices(block_time))
             .map(|remaining_alarms| AlarmsStatusResponse { remaining_alarms })
     }
 
@@ -100,6 +104,41 @@ where
             .calc_base_price(self.storage.deref(), &self.tree, currency, at, self.feeders)
     }
 
+    pub(super) fn try_query_base_price_samples(
+        &self,
+        at: Timestamp,
+        currency: &SymbolSlice,
+    ) -> Result<Vec<Option<PriceDTO<PriceG, BaseG>>>, ContractError> {
+        self.feeds.calc_base_price_samples(
+            self.storage.deref(),
+            &self.tree,
+            currency,
+            at,
+            self.feeders,
+        )
+    }
+
+    pub(super) fn try_query_base_twap(
+        &self,
+        at: Timestamp,
+        currency: &SymbolSlice,
+        from: Timestamp,
+        to: Timestamp,
+    ) -> Result<PriceDTO<PriceG, BaseG>, ContractError> {
+        if to < from {
+            return Err(PriceFeedsError::InvalidPeriod(format!("{from} is after {to}")).into());
+        }
+
+        self.feeds.calc_base_twap(
+            self.storage.deref(),
+            &self.tree,
+            currency,
+            at,
+            self.feeders,
+            Period::from_till(from, &to),
+        )
+    }
+
     pub(super) fn try_query_stable_price(
         &self,
         at: Timestamp,
@@ -156,6 +195,23 @@ where
             self.feeders,
         )
     }
+
+    /// Calculate the prices of the currencies whose price alarms are not halted
+    fn calc_alarm_prices(
+        &self,
+        at: Timestamp,
+    ) -> impl Iterator<Item = PriceResult<PriceG, BaseC>> + '_ {
+        let storage: &dyn Storage = self.storage.deref();
+
+        self.calc_all_prices(at).filter_map(move |price| {
+            price
+                .and_then(|price| {
+                    price_halts::is_halted(storage, price.base_ticker(), at)
+                        .map(|halted| (!halted).then_some(price))
+                })
+                .transpose()
+        })
+    }
 }
 
 impl<'storage, S, PriceG, BaseC, BaseG> Oracle<'storage, S, PriceG, BaseC, BaseG>
@@ -175,7 +231,7 @@ where
     ) -> ContractResult<(u32, MessageResponse)> {
         let subscribers: Vec<Addr> = MarketAlarms::new(self.storage.deref())
             .ensure_no_in_delivery()?
-            .notify_alarms_iter::<_, BaseC>(self.calc_all_prices(block_time))?
+            .notify_alarms_iter::<_, BaseC>(self.calc_alarm_prices(block_time))?
             .take(max_count.try_into()?)
             .collect::<ContractResult<Vec<Addr>>>()?;
 
@@ -28,6 +28,9 @@ pub enum ContractError {
     #[error("[Oracle] Failed to load feeders! Cause: {0}")]
     LoadFeeders(StdError),
 
+    #[error("[Oracle] Failed to store feeder statistics! Cause: {0}")]
+    StoreFeederStats(StdError),
+
     #[error("[Oracle] Failed to load configuration! Cause: {0}")]
     LoadConfig(StdError),
 
@@ -43,6 +46,12 @@ pub enum ContractError {
     #[error("[Oracle] Failed to store supported pairs! Cause: {0}")]
     StoreSupportedPairs(StdError),
 
+    #[error("[Oracle] Failed to load the price halts! Cause: {0}")]
+    LoadPriceHalts(StdError),
+
+    #[error("[Oracle] Failed to store the price halts! Cause: {0}")]
+    StorePriceHalts(StdError),
+
     #[error("[Oracle] Failed to convert query response to binary! Cause: {0}")]
     ConvertToBinary(StdError),
 
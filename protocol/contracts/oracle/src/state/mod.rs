@@ -1,2 +1,3 @@
 pub mod config;
+pub mod price_halts;
 pub mod supported_pairs;
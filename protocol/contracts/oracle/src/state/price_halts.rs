@@ -0,0 +1,104 @@
+use serde::{Deserialize, Serialize};
+
+use currency::{SymbolOwned, SymbolSlice};
+use finance::duration::Duration;
+use sdk::{
+    cosmwasm_std::{Order, Storage, Timestamp},
+    cw_storage_plus::Map,
+};
+
+use crate::{error::Result as ContractResult, ContractError};
+
+const STORAGE: Map<'static, SymbolOwned, Halt> = Map::new("price_halts");
+
+/// The time until which the price alarms on a currency are halted
+///
+/// The time the halt was last set or cleared at prevents halting the currency
+/// again on the same samples, for example, right after a governance clear.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+struct Halt {
+    until: Timestamp,
+    since: Timestamp,
+}
+
+/// Halt the price alarms on a currency until a time, unless already halted for longer
+///
+/// A currency is halted at most once per sample period. The same applies
+/// after a clear so that the samples that triggered the halt cannot re-trigger it.
+pub fn halt(
+    storage: &mut dyn Storage,
+    currency: SymbolOwned,
+    now: Timestamp,
+    sample_period: Duration,
+    until: Timestamp,
+) -> ContractResult<()> {
+    load(storage, &currency).and_then(|halt| {
+        if halt
+            .as_ref()
+            .is_none_or(|halt| halt.since + sample_period <= now)
+        {
+            save(
+                storage,
+                currency,
+                &Halt {
+                    until: halt.map_or(until, |halt| halt.until.max(until)),
+                    since: now,
+                },
+            )
+        } else {
+            Ok(())
+        }
+    })
+}
+
+/// Resume the price alarms on a currency
+///
+/// The currency may not be halted again until the next sample period.
+pub fn clear(
+    storage: &mut dyn Storage,
+    currency: SymbolOwned,
+    now: Timestamp,
+) -> ContractResult<()> {
+    save(
+        storage,
+        currency,
+        &Halt {
+            until: now,
+            since: now,
+        },
+    )
+}
+
+pub fn is_halted(
+    storage: &dyn Storage,
+    currency: &SymbolSlice,
+    now: Timestamp,
+) -> ContractResult<bool> {
+    load(storage, currency).map(|halt| halt.is_some_and(|halt| now < halt.until))
+}
+
+/// Provide the currencies with price alarms halted as of `now` along with the time they are halted until
+pub fn active(
+    storage: &dyn Storage,
+    now: Timestamp,
+) -> ContractResult<Vec<(SymbolOwned, Timestamp)>> {
+    STORAGE
+        .range(storage, None, None, Order::Ascending)
+        .filter(|halt| halt.as_ref().ok().is_none_or(|(_, halt)| now < halt.until))
+        .map(|halt| halt.map(|(currency, halt)| (currency, halt.until)))
+        .collect::<Result<_, _>>()
+        .map_err(ContractError::LoadPriceHalts)
+}
+
+fn load(storage: &dyn Storage, currency: &SymbolSlice) -> ContractResult<Option<Halt>> {
+    STORAGE
+        .may_load(storage, currency.into())
+        .map_err(ContractError::LoadPriceHalts)
+}
+
+fn save(storage: &mut dyn Storage, currency: SymbolOwned, halt: &Halt) -> ContractResult<()> {
+    STORAGE
+        .save(storage, currency, halt)
+        .map_err(ContractError::StorePriceHalts)
+}
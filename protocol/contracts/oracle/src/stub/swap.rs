@@ -1,5 +1,10 @@
-use currency::SymbolOwned;
-use oracle_platform::OracleRef;
+use std::marker::PhantomData;
+
+use currency::{
+    AnyVisitor, AnyVisitorResult, Currency, Group, GroupVisit, SymbolOwned, SymbolSlice, Tickers,
+};
+use finance::coin::{Amount, Coin, CoinDTO, WithCoin, WithCoinResult};
+use oracle_platform::{convert, Oracle, OracleRef, WithOracle};
 use sdk::cosmwasm_std::QuerierWrapper;
 
 use crate::api::swap::{Error, QueryMsg, Result, SwapTarget};
@@ -11,9 +16,26 @@ pub trait SwapPath {
         to: SymbolOwned,
         querier: QuerierWrapper<'_>,
     ) -> Result<Vec<SwapTarget>>;
+
+    /// Estimate the amount of `currency_out` that `amount_in` is worth at the current market prices
+    ///
+    /// `GIn` - the group of the input token
+    /// `GSwap` - the group common for all tokens in the swap path, and the oracle base currency
+    fn estimate_amount_out<GIn, GSwap>(
+        &self,
+        amount_in: &CoinDTO<GIn>,
+        currency_out: &SymbolSlice,
+        querier: QuerierWrapper<'_>,
+    ) -> Result<Amount>
+    where
+        GIn: Group,
+        GSwap: Group;
 }
 
-impl<OracleBase> SwapPath for OracleRef<OracleBase> {
+impl<OracleBase> SwapPath for OracleRef<OracleBase>
+where
+    OracleBase: Currency,
+{
     fn swap_path(
         &self,
         from: SymbolOwned,
@@ -26,4 +48,122 @@ impl<OracleBase> SwapPath for OracleRef<OracleBase> {
                 .map_err(Error::StubSwapPathQuery)
         }
     }
+
+    fn estimate_amount_out<GIn, GSwap>(
+        &self,
+        amount_in: &CoinDTO<GIn>,
+        currency_out: &SymbolSlice,
+        querier: QuerierWrapper<'_>,
+    ) -> Result<Amount>
+    where
+        GIn: Group,
+        GSwap: Group,
+    {
+        amount_in.with_coin(EstimateIn::<'_, _, GIn, GSwap> {
+            oracle: self,
+            currency_out,
+            querier,
+            _in_group: PhantomData,
+            _swap_group: PhantomData,
+        })
+    }
+}
+
+struct EstimateIn<'a, OracleBase, GIn, GSwap> {
+    oracle: &'a OracleRef<OracleBase>,
+    currency_out: &'a SymbolSlice,
+    querier: QuerierWrapper<'a>,
+    _in_group: PhantomData<GIn>,
+    _swap_group: PhantomData<GSwap>,
+}
+
+impl<'a, OracleBase, GIn, GSwap> WithCoin for EstimateIn<'a, OracleBase, GIn, GSwap>
+where
+    OracleBase: Currency,
+    GIn: Group,
+    GSwap: Group,
+{
+    type Output = Amount;
+    type Error = Error;
+
+    fn on<InC>(self, amount_in: Coin<InC>) -> WithCoinResult<Self>
+    where
+        InC: Currency,
+    {
+        Tickers.visit_any::<GSwap, _>(
+            self.currency_out,
+            EstimateOut::<'_, _, _, GIn, GSwap> {
+                oracle: self.oracle,
+                amount_in,
+                querier: self.querier,
+                _in_group: PhantomData,
+                _swap_group: PhantomData,
+            },
+        )
+    }
+}
+
+struct EstimateOut<'a, OracleBase, InC, GIn, GSwap> {
+    oracle: &'a OracleRef<OracleBase>,
+    amount_in: Coin<InC>,
+    querier: QuerierWrapper<'a>,
+    _in_group: PhantomData<GIn>,
+    _swap_group: PhantomData<GSwap>,
+}
+
+impl<'a, OracleBase, InC, GIn, GSwap> AnyVisitor for EstimateOut<'a, OracleBase, InC, GIn, GSwap>
+where
+    OracleBase: Currency,
+    InC: Currency,
+    GIn: Group,
+    GSwap: Group,
+{
+    type Output = Amount;
+    type Error = Error;
+
+    fn on<OutC>(self) -> AnyVisitorResult<Self>
+    where
+        OutC: Currency,
+    {
+        self.oracle
+            .clone()
+            .execute_as_oracle::<GSwap, _>(
+                Convert::<InC, GIn, OutC, GSwap> {
+                    amount_in: self.amount_in,
+                    _in_group: PhantomData,
+                    _out: PhantomData,
+                    _out_group: PhantomData,
+                },
+                self.querier,
+            )
+            .map(Into::into)
+    }
+}
+
+struct Convert<InC, GIn, OutC, GOut> {
+    amount_in: Coin<InC>,
+    _in_group: PhantomData<GIn>,
+    _out: PhantomData<OutC>,
+    _out_group: PhantomData<GOut>,
+}
+
+impl<OracleBase, InC, GIn, OutC, GOut> WithOracle<OracleBase> for Convert<InC, GIn, OutC, GOut>
+where
+    OracleBase: Currency,
+    InC: Currency,
+    GIn: Group,
+    OutC: Currency,
+    GOut: Group,
+{
+    type Output = Coin<OutC>;
+    type Error = Error;
+
+    fn exec<OracleImpl>(self, oracle: OracleImpl) -> Result<Self::Output>
+    where
+        OracleImpl: Oracle<OracleBase>,
+    {
+        convert::to_quote::<_, GIn, _, _>(&oracle, self.amount_in)
+            .and_then(|amount_base| convert::from_quote::<_, _, OutC, GOut>(&oracle, amount_base))
+            .map_err(Into::into)
+    }
 }
@@ -1,6 +1,10 @@
 use currencies::test::{LpnC, PaymentC3, PaymentC4, PaymentC5, PaymentC7};
 use currency::{Currency, Group};
-use finance::{coin::Coin, price, price::dto::PriceDTO};
+use finance::{coin::Coin, duration::Duration, percent::Percent, price, price::dto::PriceDTO};
+use marketprice::{
+    config::{CircuitBreaker, Config as PriceConfig},
+    feeders::FeederStats,
+};
 use platform::{contract, tests};
 use sdk::{
     cosmwasm_ext::Response as CwResponse,
@@ -12,8 +16,11 @@ use sdk::{
 };
 
 use crate::{
-    api::{Alarm, AlarmsCount, DispatchAlarmsResponse, ExecuteMsg, QueryMsg},
-    contract::{execute, query},
+    api::{
+        Alarm, AlarmsCount, Config, DispatchAlarmsResponse, ExecuteMsg, FeederStatsResponse,
+        InstantiateMsg, PriceHalt, PriceHaltsResponse, QueryMsg, SudoMsg,
+    },
+    contract::{execute, query, sudo},
     tests::{dummy_default_instantiate_msg, setup_test, PriceGroup, TheStableGroup},
     ContractError,
 };
@@ -62,6 +69,36 @@ fn feed_direct_price() {
     assert_eq!(generate_price(), value);
 }
 
+#[test]
+fn feeder_stats() {
+    let (mut deps, info) = setup_test(dummy_default_instantiate_msg());
+
+    let msg = ExecuteMsg::FeedPrices {
+        prices: vec![
+            price::total_of(Coin::<PaymentC4>::new(10))
+                .is(Coin::<LpnC>::new(120))
+                .into(),
+            price::total_of(Coin::<PaymentC5>::new(10))
+                .is(Coin::<PaymentC3>::new(120))
+                .into(),
+        ],
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::FeedersStats {}).unwrap();
+    let value: Vec<FeederStatsResponse> = from_json(res).unwrap();
+    assert_eq!(
+        vec![FeederStatsResponse {
+            feeder: info.sender,
+            stats: FeederStats {
+                observations: 2,
+                deviations: 0,
+            },
+        }],
+        value
+    );
+}
+
 #[test]
 fn feed_indirect_price() {
     let (mut deps, info) = setup_test(dummy_default_instantiate_msg());
@@ -176,6 +213,173 @@ fn deliver_alarm() {
     }
 }
 
+#[test]
+fn halt_alarms_on_price_jump() {
+    const COOL_DOWN: Duration = Duration::HOUR;
+
+    let (mut deps, info) = setup_test(InstantiateMsg {
+        config: Config {
+            price_config: PriceConfig::new(
+                Percent::from_percent(50),
+                Duration::from_secs(60),
+                3,
+                Percent::from_percent(88),
+            )
+            .with_circuit_breaker(CircuitBreaker::new(Percent::from_percent(30), COOL_DOWN)),
+        },
+        ..dummy_default_instantiate_msg()
+    });
+    setup_receiver(&mut deps.querier);
+
+    let feed_price = |total_of, is| ExecuteMsg::FeedPrices {
+        prices: vec![price::total_of(Coin::<PaymentC7>::new(total_of))
+            .is(Coin::<LpnC>::new(is))
+            .into()],
+    };
+    let dispatch_alarms = ExecuteMsg::DispatchAlarms { max_count: 10 };
+
+    let env = mock_env();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        feed_price(10, 23451),
+    )
+    .unwrap();
+
+    let mut env_jump = env.clone();
+    env_jump.block.time = env.block.time.plus_seconds(60);
+    execute(
+        deps.as_mut(),
+        env_jump.clone(),
+        info.clone(),
+        feed_price(10, 46902),
+    )
+    .unwrap();
+
+    execute(
+        deps.as_mut(),
+        env_jump.clone(),
+        info.clone(),
+        ExecuteMsg::AddPriceAlarm {
+            alarm: Alarm::new(
+                price::total_of(Coin::<PaymentC7>::new(10)).is(Coin::<LpnC>::new(1)),
+                Some(price::total_of(Coin::<PaymentC7>::new(10)).is(Coin::<LpnC>::new(30000))),
+            ),
+        },
+    )
+    .unwrap();
+
+    let halts: PriceHaltsResponse =
+        from_json(query(deps.as_ref(), env_jump.clone(), QueryMsg::PriceHalts {}).unwrap())
+            .unwrap();
+    assert_eq!(
+        vec![PriceHalt {
+            currency: PaymentC7::TICKER.into(),
+            until: env_jump.block.time + COOL_DOWN,
+        }],
+        halts
+    );
+
+    let dispatch_alarms_resp = execute(
+        deps.as_mut(),
+        env_jump.clone(),
+        info.clone(),
+        dispatch_alarms.clone(),
+    )
+    .unwrap();
+    assert_eq!(sent_alarms(&dispatch_alarms_resp), Some(0));
+
+    sudo(
+        deps.as_mut(),
+        env_jump.clone(),
+        SudoMsg::ClearPriceHalt {
+            currency: PaymentC7::TICKER.into(),
+        },
+    )
+    .unwrap();
+
+    // the same samples do not halt the currency again
+    execute(
+        deps.as_mut(),
+        env_jump.clone(),
+        info.clone(),
+        feed_price(10, 46902),
+    )
+    .unwrap();
+
+    let halts: PriceHaltsResponse =
+        from_json(query(deps.as_ref(), env_jump.clone(), QueryMsg::PriceHalts {}).unwrap())
+            .unwrap();
+    assert!(halts.is_empty());
+
+    sudo(
+        deps.as_mut(),
+        env_jump.clone(),
+        SudoMsg::ClearPriceHalt {
+            currency: "UNKNOWN".into(),
+        },
+    )
+    .unwrap_err();
+
+    let dispatch_alarms_resp = execute(deps.as_mut(), env_jump, info, dispatch_alarms).unwrap();
+    assert!(!any_error(&dispatch_alarms_resp));
+    assert_eq!(sent_alarms(&dispatch_alarms_resp), Some(1));
+}
+
+#[test]
+fn halt_derived_currencies_on_intermediate_price_jump() {
+    let (mut deps, info) = setup_test(InstantiateMsg {
+        config: Config {
+            price_config: PriceConfig::new(
+                Percent::from_percent(50),
+                Duration::from_secs(60),
+                3,
+                Percent::from_percent(88),
+            )
+            .with_circuit_breaker(CircuitBreaker::new(
+                Percent::from_percent(30),
+                Duration::HOUR,
+            )),
+        },
+        ..dummy_default_instantiate_msg()
+    });
+
+    let env = mock_env();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        dummy_feed_prices_msg(),
+    )
+    .unwrap();
+
+    // only the intermediate price is fed
+    let mut env_jump = env.clone();
+    env_jump.block.time = env.block.time.plus_seconds(60);
+    execute(
+        deps.as_mut(),
+        env_jump.clone(),
+        info,
+        ExecuteMsg::FeedPrices {
+            prices: vec![price::total_of(Coin::<PaymentC7>::new(10))
+                .is(Coin::<LpnC>::new(24))
+                .into()],
+        },
+    )
+    .unwrap();
+
+    let halts: PriceHaltsResponse =
+        from_json(query(deps.as_ref(), env_jump, QueryMsg::PriceHalts {}).unwrap()).unwrap();
+    let mut halted: Vec<_> = halts.into_iter().map(|halt| halt.currency).collect();
+    halted.sort();
+    let mut expected: Vec<String> = [PaymentC3::TICKER, PaymentC5::TICKER, PaymentC7::TICKER]
+        .map(Into::into)
+        .into();
+    expected.sort();
+    assert_eq!(expected, halted);
+}
+
 fn setup_receiver(querier: &mut MockQuerier) {
     querier.update_wasm(contract::testing::valid_contract_handler);
 }
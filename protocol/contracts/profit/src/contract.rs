@@ -1,7 +1,7 @@
 use std::ops::{Deref, DerefMut};
 
 use access_control::{ContractOwnerAccess, SingleUserAccess};
-use dex::{ContinueResult as DexResult, Handler as _, Response as DexResponse};
+use dex::{ContinueResult as DexResult, Contract as _, Handler as _, Response as DexResponse};
 use oracle_platform::OracleRef;
 use platform::{
     message::Response as MessageResponse, response, state_machine::Response as StateMachineResponse,
@@ -18,7 +18,7 @@ use versioning::{package_version, version, SemVer, Version, VersionSegment};
 
 use crate::{
     error::ContractError,
-    msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
+    msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, CADENCE_ALARM_ID},
     profit::Profit,
     result::ContractResult,
     state::{Config, ConfigManagement as _, State},
@@ -32,7 +32,7 @@ const CONTRACT_VERSION: Version = version!(CONTRACT_STORAGE_VERSION, PACKAGE_VER
 #[entry_point]
 pub fn instantiate(
     mut deps: DepsMut<'_>,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> ContractResult<CwResponse> {
@@ -50,29 +50,48 @@ pub fn instantiate(
     )
     .grant_to(&msg.timealarms)?;
 
-    let (state, response) = State::start(
-        Config::new(
-            msg.cadence_hours,
-            msg.treasury,
-            OracleRef::try_from_base(msg.oracle, deps.querier)?,
-            TimeAlarmsRef::new(msg.timealarms, deps.querier)?,
-        ),
-        msg.dex,
-    );
+    let config = Config::new(
+        msg.cadence_hours,
+        msg.treasury,
+        OracleRef::try_from_base(msg.oracle, deps.querier)?,
+        TimeAlarmsRef::new(msg.timealarms, deps.querier)?,
+        msg.max_slippage,
+    )?;
+    let cadence_alarm = config.setup_cadence_alarm(env.block.time)?;
+
+    let (state, response) = State::start(config, msg.dex);
 
     state
         .store(deps.storage)
-        .map(|()| response::response_only_messages(response))
+        .map(|()| response::response_only_messages(response.merge(cadence_alarm)))
 }
 
 #[entry_point]
 pub fn migrate(
     deps: DepsMut<'_>,
-    _env: Env,
+    env: Env,
     MigrateMsg {}: MigrateMsg,
 ) -> ContractResult<CwResponse> {
-    versioning::update_software(deps.storage, CONTRACT_VERSION, Into::into)
-        .and_then(response::response)
+    versioning::update_software(deps.storage, CONTRACT_VERSION, Into::into).and_then(
+        |release_label| {
+            // replace the pending one-shot alarm with a recurring one keeping the cadence
+            let time_alarms = SingleUserAccess::new(
+                deps.storage.deref(),
+                crate::access_control::TIMEALARMS_NAMESPACE,
+            )
+            .granted_to()?;
+            let cadence_hours = State::load(deps.storage)?
+                .state(env.block.time, deps.querier)
+                .cadence_hours;
+
+            TimeAlarmsRef::new(time_alarms, deps.querier)
+                .and_then(|time_alarms| {
+                    Config::cadence_alarm(&time_alarms, cadence_hours, env.block.time)
+                })
+                .map_err(Into::into)
+                .and_then(|alarm| response::response_with_messages(release_label, alarm))
+        },
+    )
 }
 
 #[entry_point]
@@ -83,15 +102,20 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> ContractResult<CwResponse> {
     match msg {
-        ExecuteMsg::TimeAlarm {} => {
+        ExecuteMsg::TimeAlarm { id } => {
             SingleUserAccess::new(
                 deps.storage.deref(),
                 crate::access_control::TIMEALARMS_NAMESPACE,
             )
             .check(&info.sender)?;
 
-            try_handle_execute_message(deps, env, State::on_time_alarm)
-                .map(response::response_only_messages)
+            if id == CADENCE_ALARM_ID {
+                try_handle_execute_message(deps, env, State::on_cadence)
+                    .map(response::response_only_messages)
+            } else {
+                try_handle_execute_message(deps, env, State::on_time_alarm)
+                    .map(response::response_only_messages)
+            }
         }
         ExecuteMsg::Config { cadence_hours } => {
             ContractOwnerAccess::new(deps.storage.deref()).check(&info.sender)?;
@@ -105,6 +129,22 @@ pub fn execute(
 
             Ok(response::response_only_messages(response))
         }
+        ExecuteMsg::DexIbc { ibc } => {
+            ContractOwnerAccess::new(deps.storage.deref()).check(&info.sender)?;
+
+            State::load(deps.storage)?
+                .try_update_dex_ibc(ibc)?
+                .store(deps.storage)
+                .map(|()| response::empty_response())
+        }
+        ExecuteMsg::MaxSlippage { max_slippage } => {
+            ContractOwnerAccess::new(deps.storage.deref()).check(&info.sender)?;
+
+            State::load(deps.storage)?
+                .try_update_max_slippage(max_slippage)?
+                .store(deps.storage)
+                .map(|()| response::empty_response())
+        }
         ExecuteMsg::DexCallback() => {
             access_control::check(&env.contract.address, &info.sender)?;
 
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
-use dex::ConnectionParams;
+use dex::{ConnectionParams, IbcParams};
+use finance::percent::Percent;
 use sdk::{
     cosmwasm_std::Addr,
     schemars::{self, JsonSchema},
@@ -8,6 +9,11 @@ use sdk::{
 
 use crate::typedefs::CadenceHours;
 
+/// The id of the recurring alarm driving the buy-back cadence
+///
+/// Kept apart from the default id used by the DEX transfer polling alarms.
+pub const CADENCE_ALARM_ID: &str = "cadence";
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
 #[cfg_attr(any(test, feature = "testing"), derive(Debug))]
 #[serde(deny_unknown_fields, rename_all = "snake_case")]
@@ -17,6 +23,8 @@ pub struct InstantiateMsg {
     pub oracle: Addr,
     pub timealarms: Addr,
     pub dex: ConnectionParams,
+    /// The maximum deviation of a buy-back swap output from the one estimated at the oracle prices
+    pub max_slippage: Percent,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -27,10 +35,25 @@ pub struct MigrateMsg {}
 #[cfg_attr(any(test, feature = "testing"), derive(Debug))]
 #[serde(deny_unknown_fields, rename_all = "snake_case")]
 pub enum ExecuteMsg {
-    TimeAlarm {},
+    TimeAlarm {
+        #[serde(default)]
+        id: String,
+    },
     Config {
         cadence_hours: CadenceHours,
     },
+    /// Update the maximum deviation of a buy-back swap output from the one estimated at the oracle prices
+    ///
+    /// Allowed only while no buy-back is in progress.
+    MaxSlippage {
+        max_slippage: Percent,
+    },
+    /// Update the relayer tips and timeout of the IBC transactions to the DEX
+    ///
+    /// Allowed only while no buy-back is in progress.
+    DexIbc {
+        ibc: IbcParams,
+    },
 
     /// An entry point for safe delivery of a Dex response
     ///
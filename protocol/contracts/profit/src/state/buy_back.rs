@@ -8,7 +8,10 @@ use dex::{
     Account, CoinVisitor, ContractInSwap, Enterable, IterNext, IterState, Response as DexResponse,
     StateLocalOut, SwapTask,
 };
-use finance::coin::{Coin, CoinDTO};
+use finance::{
+    coin::{Coin, CoinDTO},
+    percent::Percent,
+};
 use oracle::stub::SwapPath;
 use platform::{
     bank::{self, BankAccountView},
@@ -88,6 +91,10 @@ impl SwapTask for BuyBack {
         Nls::TICKER
     }
 
+    fn max_slippage(&self) -> Percent {
+        self.config.max_slippage()
+    }
+
     fn on_coins<Visitor>(&self, visitor: &mut Visitor) -> Result<IterState, Visitor::Error>
     where
         Visitor: CoinVisitor<Result = IterNext>,
@@ -182,12 +189,15 @@ mod tests {
     };
     use currency::never::Never;
     use dex::{CoinVisitor, IterNext, IterState, SwapTask as _};
-    use finance::coin::{Coin, CoinDTO};
+    use finance::{
+        coin::{Coin, CoinDTO},
+        percent::Percent,
+    };
 
     use super::BuyBack;
 
     fn buy_back_instance(coins: Vec<CoinDTO<PaymentGroup>>) -> BuyBack {
-        use dex::{Account, ConnectionParams, Ics20Channel};
+        use dex::{Account, ConnectionParams, IbcParams, Ics20Channel};
         use oracle_platform::OracleRef;
         use platform::ica::HostAccount;
         use sdk::cosmwasm_std::Addr;
@@ -202,7 +212,9 @@ mod tests {
                 Addr::unchecked("DEADCODE"),
                 OracleRef::<LpnC>::unchecked(Addr::unchecked("DEADCODE")),
                 TimeAlarmsRef::unchecked("DEADCODE"),
-            ),
+                Percent::from_percent(5),
+            )
+            .unwrap(),
             Account::unchecked(
                 Addr::unchecked("DEADCODE"),
                 HostAccount::try_from(String::from("DEADCODE")).unwrap(),
@@ -212,6 +224,7 @@ mod tests {
                         local_endpoint: String::from("DEADCODE"),
                         remote_endpoint: String::from("DEADCODE"),
                     },
+                    ibc: IbcParams::default(),
                 },
             ),
             coins,
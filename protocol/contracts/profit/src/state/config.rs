@@ -1,12 +1,19 @@
 use serde::{Deserialize, Serialize};
 
 use currencies::Lpn as QuoteC;
+use finance::{
+    duration::Duration,
+    percent::{bound::BoundToHundredPercent, Percent},
+};
 use oracle::stub::SwapPath;
 use oracle_platform::OracleRef;
-use sdk::cosmwasm_std::Addr;
-use timealarms::stub::TimeAlarmsRef;
+use platform::batch::Batch;
+use sdk::cosmwasm_std::{Addr, Timestamp};
+use timealarms::{
+    msg::RecurringAlarm, result::ContractResult as TimeAlarmsResult, stub::TimeAlarmsRef,
+};
 
-use crate::typedefs::CadenceHours;
+use crate::{msg::CADENCE_ALARM_ID, result::ContractResult, typedefs::CadenceHours};
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub(crate) struct Config {
@@ -14,6 +21,9 @@ pub(crate) struct Config {
     treasury: Addr,
     oracle: OracleRef<QuoteC>,
     time_alarms: TimeAlarmsRef,
+    // TODO remove the default once all profit contracts have been migrated
+    #[serde(default = "Config::no_max_slippage")]
+    max_slippage: Percent,
 }
 
 impl Config {
@@ -22,13 +32,15 @@ impl Config {
         treasury: Addr,
         oracle: OracleRef<QuoteC>,
         time_alarms: TimeAlarmsRef,
-    ) -> Self {
-        Self {
+        max_slippage: Percent,
+    ) -> ContractResult<Self> {
+        Self::validate_max_slippage(max_slippage).map(|max_slippage| Self {
             cadence_hours,
             treasury,
             oracle,
             time_alarms,
-        }
+            max_slippage,
+        })
     }
 
     pub fn update(self, cadence_hours: CadenceHours) -> Self {
@@ -38,6 +50,13 @@ impl Config {
         }
     }
 
+    pub fn update_max_slippage(self, max_slippage: Percent) -> ContractResult<Self> {
+        Self::validate_max_slippage(max_slippage).map(|max_slippage| Self {
+            max_slippage,
+            ..self
+        })
+    }
+
     pub fn cadence_hours(&self) -> CadenceHours {
         self.cadence_hours
     }
@@ -53,4 +72,39 @@ impl Config {
     pub fn time_alarms(&self) -> &TimeAlarmsRef {
         &self.time_alarms
     }
+
+    pub fn max_slippage(&self) -> Percent {
+        self.max_slippage
+    }
+
+    /// (Re-)register the recurring cadence alarm starting a cadence after `now`
+    pub fn setup_cadence_alarm(&self, now: Timestamp) -> TimeAlarmsResult<Batch> {
+        Self::cadence_alarm(&self.time_alarms, self.cadence_hours, now)
+    }
+
+    pub fn cadence_alarm(
+        time_alarms: &TimeAlarmsRef,
+        cadence_hours: CadenceHours,
+        now: Timestamp,
+    ) -> TimeAlarmsResult<Batch> {
+        let cadence = Duration::from_hours(cadence_hours);
+
+        time_alarms.setup_recurring_alarm(RecurringAlarm {
+            start: now + cadence,
+            period: cadence,
+            until: None,
+            id: CADENCE_ALARM_ID.into(),
+            payload: None,
+        })
+    }
+
+    fn validate_max_slippage(max_slippage: Percent) -> ContractResult<Percent> {
+        BoundToHundredPercent::try_from(max_slippage)
+            .map(|max_slippage| max_slippage.percent())
+            .map_err(Into::into)
+    }
+
+    const fn no_max_slippage() -> Percent {
+        Percent::HUNDRED
+    }
 }
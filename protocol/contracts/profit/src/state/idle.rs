@@ -8,12 +8,12 @@ use serde::{Deserialize, Serialize};
 use currencies::{Nls, PaymentGroup};
 use currency::{Currency, Group};
 use dex::{
-    Account, Contract, Enterable, Error as DexError, Handler, Response as DexResponse,
+    Account, Contract, Enterable, Error as DexError, Handler, IbcParams, Response as DexResponse,
     Result as DexResult, StartLocalLocalState,
 };
 use finance::{
     coin::{Coin, CoinDTO, WithCoin, WithCoinResult},
-    duration::Duration,
+    percent::Percent,
 };
 use platform::{
     bank::{self, Aggregate, BankAccount, BankAccountView, BankStub, BankView},
@@ -22,7 +22,6 @@ use platform::{
     state_machine::Response as StateMachineResponse,
 };
 use sdk::cosmwasm_std::{Addr, Env, QuerierWrapper, Timestamp};
-use timealarms::result::ContractResult as TimeAlarmsResult;
 
 use crate::{
     error::ContractError, msg::ConfigResponse, profit::Profit, result::ContractResult,
@@ -65,7 +64,7 @@ impl Idle {
             .map_err(Into::into)
     }
 
-    fn on_time_alarm(
+    pub fn on_cadence(
         self,
         querier: QuerierWrapper<'_>,
         env: Env,
@@ -116,17 +115,12 @@ impl Idle {
             })
             .map_err(Into::into)
     }
-
-    fn setup_time_alarm(config: &Config, now: Timestamp) -> TimeAlarmsResult<Batch> {
-        config
-            .time_alarms()
-            .setup_alarm(now + Duration::from_hours(config.cadence_hours()))
-    }
 }
 
 impl Enterable for Idle {
-    fn enter(&self, now: Timestamp, _: QuerierWrapper<'_>) -> Result<Batch, DexError> {
-        Self::setup_time_alarm(&self.config, now).map_err(DexError::TimeAlarmError)
+    fn enter(&self, _: Timestamp, _: QuerierWrapper<'_>) -> Result<Batch, DexError> {
+        // the next buy-back is triggered by the recurring cadence alarm
+        Ok(Batch::default())
     }
 }
 
@@ -148,7 +142,8 @@ impl ConfigManagement for Idle {
     ) -> ContractResult<StateMachineResponse<Self>> {
         let config: Config = self.config.update(cadence_hours);
 
-        Self::setup_time_alarm(&config, now)
+        config
+            .setup_cadence_alarm(now)
             .map(PlatformResponse::messages_only)
             .map(|response: PlatformResponse| StateMachineResponse {
                 response,
@@ -156,6 +151,19 @@ impl ConfigManagement for Idle {
             })
             .map_err(Into::into)
     }
+
+    fn try_update_dex_ibc(self, ibc: IbcParams) -> ContractResult<Self> {
+        Ok(Self {
+            account: self.account.with_ibc(ibc),
+            ..self
+        })
+    }
+
+    fn try_update_max_slippage(self, max_slippage: Percent) -> ContractResult<Self> {
+        self.config
+            .update_max_slippage(max_slippage)
+            .map(|config| Self { config, ..self })
+    }
 }
 
 impl Handler for Idle {
@@ -163,7 +171,7 @@ impl Handler for Idle {
     type SwapResult = ContractResult<DexResponse<State>>;
 
     fn on_time_alarm(self, querier: QuerierWrapper<'_>, env: Env) -> DexResult<Self> {
-        DexResult::Finished(self.on_time_alarm(querier, env))
+        DexResult::Finished(self.on_cadence(querier, env))
     }
 }
 
@@ -4,9 +4,10 @@ use currencies::PaymentGroup;
 use serde::{Deserialize, Serialize};
 
 use dex::{
-    ConnectionParams, ContinueResult, Contract, Handler, Response as DexResponse,
+    ConnectionParams, ContinueResult, Contract, Handler, IbcParams, Response as DexResponse,
     Result as DexResult, StateLocalOut,
 };
+use finance::percent::Percent;
 use platform::{
     batch::Batch,
     state_machine::{self, Response as StateMachineResponse},
@@ -54,6 +55,18 @@ where
             "Configuration changes are not allowed in this state!",
         ))
     }
+
+    fn try_update_dex_ibc(self, _: IbcParams) -> ContractResult<Self> {
+        Err(ContractError::unsupported_operation(
+            "IBC parameters changes are not allowed in this state!",
+        ))
+    }
+
+    fn try_update_max_slippage(self, _: Percent) -> ContractResult<Self> {
+        Err(ContractError::unsupported_operation(
+            "Maximum slippage changes are not allowed in this state!",
+        ))
+    }
 }
 
 #[derive(Serialize, Deserialize)]
@@ -94,6 +107,24 @@ impl ConfigManagement for State {
                 .map(state_machine::from),
         }
     }
+
+    fn try_update_dex_ibc(self, ibc: IbcParams) -> ContractResult<Self> {
+        match self.0 {
+            StateEnum::OpenIca(ica) => ica.try_update_dex_ibc(ibc).map(Into::into),
+            StateEnum::Idle(idle) => idle.try_update_dex_ibc(ibc).map(Into::into),
+            StateEnum::BuyBack(buy_back) => buy_back.try_update_dex_ibc(ibc).map(Into::into),
+        }
+    }
+
+    fn try_update_max_slippage(self, max_slippage: Percent) -> ContractResult<Self> {
+        match self.0 {
+            StateEnum::OpenIca(ica) => ica.try_update_max_slippage(max_slippage).map(Into::into),
+            StateEnum::Idle(idle) => idle.try_update_max_slippage(max_slippage).map(Into::into),
+            StateEnum::BuyBack(buy_back) => buy_back
+                .try_update_max_slippage(max_slippage)
+                .map(Into::into),
+        }
+    }
 }
 
 impl State {
@@ -105,6 +136,20 @@ impl State {
         (state, response)
     }
 
+    /// Start a buy-back on a cadence alarm if idle, otherwise skip it
+    pub fn on_cadence(
+        self,
+        querier: QuerierWrapper<'_>,
+        env: Env,
+    ) -> ContractResult<DexResponse<Self>> {
+        match self.0 {
+            StateEnum::Idle(idle) => idle.on_cadence(querier, env),
+            state @ (StateEnum::OpenIca(_) | StateEnum::BuyBack(_)) => {
+                Ok(DexResponse::<Self>::no_msgs(Self(state)))
+            }
+        }
+    }
+
     pub fn load(storage: &dyn Storage) -> ContractResult<Self> {
         STATE.load(storage).map_err(Into::into)
     }
@@ -40,7 +40,7 @@ pub fn execute(
     msg: ExecuteAlarmMsg,
 ) -> Result<CwResponse, Infallible> {
     match msg {
-        ExecuteAlarmMsg::TimeAlarm {} => Ok(response::empty_response()), // we just consume the time alarm
+        ExecuteAlarmMsg::TimeAlarm { .. } => Ok(response::empty_response()), // we just consume the time alarm
     }
 }
 
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use currency::NlsPlatform;
+use finance::{coin::Coin, duration::Duration};
 use sdk::schemars::{self, JsonSchema};
 
 /// Parameters needed to operate with the Dex network
@@ -11,6 +13,10 @@ pub struct ConnectionParams {
     /// The IBC ICS-20 channel used to transfer assets in/out.
     /// It must be established over the same connection.
     pub transfer_channel: Ics20Channel,
+    /// The relayer tips and timeout of the IBC transactions.
+    /// Missing on connections established before it was introduced.
+    #[serde(default)]
+    pub ibc: IbcParams,
 }
 
 /// IBC ICS-20 channel parameters
@@ -22,3 +28,80 @@ pub struct Ics20Channel {
     /// The id of the remote endpoint
     pub remote_endpoint: String,
 }
+
+/// IBC transactions parameters
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct IbcParams {
+    /// The period after which a transaction not relayed to the DEX times out
+    ///
+    /// It should be less than the total amount of time 100 blocks take,
+    /// a Hermes setting to look back for packets.
+    pub timeout: Duration,
+    /// The tip paid to the relayer of an ICS-20 transfer out acknowledgement
+    pub transfer_ack_tip: Coin<NlsPlatform>,
+    /// The tip paid to the relayer of an ICS-20 transfer out timeout
+    pub transfer_timeout_tip: Coin<NlsPlatform>,
+    /// The tip paid to the relayer of an ICA transaction acknowledgement
+    pub ica_ack_tip: Coin<NlsPlatform>,
+    /// The tip paid to the relayer of an ICA transaction timeout
+    pub ica_timeout_tip: Coin<NlsPlatform>,
+}
+
+impl Default for IbcParams {
+    fn default() -> Self {
+        const TIP: Coin<NlsPlatform> = Coin::new(1);
+
+        Self {
+            timeout: Duration::from_minutes(7),
+            transfer_ack_tip: TIP,
+            transfer_timeout_tip: TIP,
+            ica_ack_tip: TIP,
+            ica_timeout_tip: TIP,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use finance::{coin::Coin, duration::Duration};
+    use sdk::cosmwasm_std;
+
+    use super::{ConnectionParams, IbcParams, Ics20Channel};
+
+    #[test]
+    fn without_ibc() {
+        let params: ConnectionParams = cosmwasm_std::from_json(
+            r#"{"connection_id":"connection-0","transfer_channel":{"local_endpoint":"channel-0","remote_endpoint":"channel-1"}}"#,
+        )
+        .unwrap();
+        assert_eq!(connection(IbcParams::default()), params);
+    }
+
+    #[test]
+    fn with_ibc() {
+        let ibc = IbcParams {
+            timeout: Duration::from_minutes(5),
+            transfer_ack_tip: Coin::new(10),
+            transfer_timeout_tip: Coin::new(20),
+            ica_ack_tip: Coin::new(30),
+            ica_timeout_tip: Coin::new(40),
+        };
+        let params = connection(ibc);
+        assert_eq!(
+            params,
+            cosmwasm_std::from_json(cosmwasm_std::to_json_vec(&params).unwrap()).unwrap()
+        );
+    }
+
+    fn connection(ibc: IbcParams) -> ConnectionParams {
+        ConnectionParams {
+            connection_id: "connection-0".into(),
+            transfer_channel: Ics20Channel {
+                local_endpoint: "channel-0".into(),
+                remote_endpoint: "channel-1".into(),
+            },
+            ibc,
+        }
+    }
+}
@@ -7,7 +7,7 @@ use platform::{
 };
 use sdk::cosmwasm_std::{Addr, QuerierWrapper, Timestamp};
 
-use crate::{error::Result, ConnectionParams};
+use crate::{error::Result, ConnectionParams, IbcParams};
 
 use super::{
     trx::{SwapTrx, TransferInTrx, TransferOutTrx},
@@ -50,6 +50,7 @@ impl Account {
             &self.dex.transfer_channel.local_endpoint,
             &self.owner,
             &self.host,
+            &self.dex.ibc,
             now,
             format!(
                 "Transfer out: {sender} -> {receiver}",
@@ -59,12 +60,21 @@ impl Account {
         )
     }
 
-    pub(super) fn swap<'a>(
+    pub(super) fn swap<'a, Oracle>(
         &'a self,
-        swap_path: &'a dyn SwapPath,
+        oracle: &'a Oracle,
         querier: QuerierWrapper<'a>,
-    ) -> SwapTrx<'a> {
-        SwapTrx::new(&self.dex.connection_id, &self.host, swap_path, querier)
+    ) -> SwapTrx<'a, Oracle>
+    where
+        Oracle: SwapPath,
+    {
+        SwapTrx::new(
+            &self.dex.connection_id,
+            &self.host,
+            &self.dex.ibc,
+            oracle,
+            querier,
+        )
     }
 
     pub(super) fn transfer_from(&self, now: Timestamp) -> TransferInTrx<'_> {
@@ -73,10 +83,19 @@ impl Account {
             &self.dex.transfer_channel.remote_endpoint,
             &self.host,
             &self.owner,
+            &self.dex.ibc,
             now,
         )
     }
 
+    /// Replace the parameters of the IBC transactions sent from now on
+    pub fn with_ibc(self, ibc: IbcParams) -> Self {
+        Self {
+            dex: ConnectionParams { ibc, ..self.dex },
+            ..self
+        }
+    }
+
     #[cfg(feature = "testing")]
     pub fn unchecked(owner: Addr, host: HostAccount, dex: ConnectionParams) -> Self {
         Self { owner, host, dex }
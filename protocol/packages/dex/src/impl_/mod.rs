@@ -17,7 +17,12 @@ pub use self::{
         start_local_local, start_remote_local, StartLocalLocalState, StartRemoteLocalState,
         StartTransferInState, State as StateLocalOut,
     },
-    out_remote::{start as start_local_remote, StartLocalRemoteState, State as StateRemoteOut},
+    out_remote::{
+        start as start_local_remote, start_swap as start_local_remote_swap,
+        start_transfer_out as start_local_remote_transfer_out, StartLocalRemoteState,
+        StartSwapState as StartLocalRemoteSwapState,
+        StartTransferOutState as StartLocalRemoteTransferOutState, State as StateRemoteOut,
+    },
     resp_delivery::{ICAOpenResponseDelivery, ResponseDelivery},
     response::{ContinueResult, Handler, Response, Result},
     swap_coins::{on_coin, on_coins},
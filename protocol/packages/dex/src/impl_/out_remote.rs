@@ -55,6 +55,86 @@ where
     StartLocalRemoteState::<OpenIca, SwapTask>::new(connectee)
 }
 
+pub type StartTransferOutState<
+    OpenIca,
+    SwapTask,
+    SwapGroup,
+    SwapClient,
+    ForwardToInnerMsg,
+    ForwardToInnerContinueMsg,
+> = TransferOut<
+    SwapTask,
+    State<OpenIca, SwapTask, SwapGroup, SwapClient, ForwardToInnerMsg, ForwardToInnerContinueMsg>,
+    SwapGroup,
+    SwapClient,
+>;
+
+/// Start a swap on an already open ICA
+///
+/// The result is left on the ICA as with `start`.
+pub fn start_transfer_out<
+    OpenIca,
+    SwapTask,
+    SwapGroup,
+    SwapClient,
+    ForwardToInnerMsg,
+    ForwardToInnerContinueMsg,
+>(
+    spec: SwapTask,
+) -> StartTransferOutState<
+    OpenIca,
+    SwapTask,
+    SwapGroup,
+    SwapClient,
+    ForwardToInnerMsg,
+    ForwardToInnerContinueMsg,
+>
+where
+    SwapTask: SwapTaskT,
+{
+    StartTransferOutState::new(spec)
+}
+
+pub type StartSwapState<
+    OpenIca,
+    SwapTask,
+    SwapGroup,
+    SwapClient,
+    ForwardToInnerMsg,
+    ForwardToInnerContinueMsg,
+> = SwapExactIn<
+    SwapTask,
+    State<OpenIca, SwapTask, SwapGroup, SwapClient, ForwardToInnerMsg, ForwardToInnerContinueMsg>,
+    SwapGroup,
+    SwapClient,
+>;
+
+/// Start a swap of funds already on an open ICA
+///
+/// The result is left on the ICA as with `start`.
+pub fn start_swap<
+    OpenIca,
+    SwapTask,
+    SwapGroup,
+    SwapClient,
+    ForwardToInnerMsg,
+    ForwardToInnerContinueMsg,
+>(
+    spec: SwapTask,
+) -> StartSwapState<
+    OpenIca,
+    SwapTask,
+    SwapGroup,
+    SwapClient,
+    ForwardToInnerMsg,
+    ForwardToInnerContinueMsg,
+>
+where
+    SwapTask: SwapTaskT,
+{
+    StartSwapState::new(spec)
+}
+
 mod impl_into {
     use crate::impl_::{
         swap_task::SwapTask as SwapTaskT, IcaConnector, SwapExactIn, SwapExactInRecoverIca,
@@ -8,9 +8,17 @@ use serde::{Deserialize, Serialize};
 use currency::{Group, SymbolSlice};
 use finance::{
     coin::{self, Amount, CoinDTO},
+    duration::Duration,
+    percent::Percent,
     zero::Zero,
 };
-use platform::{batch::Batch, trx};
+use oracle::stub::SwapPath;
+use platform::{
+    batch::{Batch, Emit, Emitter},
+    message::Response as MessageResponse,
+    state_machine::Response as StateMachineResponse,
+    trx,
+};
 use sdk::{
     cosmos_sdk_proto::Any,
     cosmwasm_std::{Binary, Env, QuerierWrapper, Timestamp},
@@ -40,9 +48,19 @@ use crate::{InspectSpec, MigrateSpec};
 
 use super::{Contract, SwapState};
 
+/// The delay of the first retry of a failed swap, each next one is twice as long
+const ERROR_RETRY_DELAY: Duration = Duration::from_secs(30);
+/// The number of retries of a failed swap before waiting for a heal
+const MAX_ERROR_RETRIES: u8 = 5;
+
 #[derive(Serialize, Deserialize)]
 pub struct SwapExactIn<SwapTask, SEnum, SwapGroup, SwapClient> {
     spec: SwapTask,
+    #[serde(default)]
+    error_retries: u8,
+    /// The time of the next retry of a failed swap, if one is scheduled
+    #[serde(default)]
+    retry_at: Option<Timestamp>,
     #[serde(skip)]
     _state_enum: PhantomData<SEnum>,
     #[serde(skip)]
@@ -56,8 +74,14 @@ where
     Self: Into<SEnum>,
 {
     pub(super) fn new(spec: SwapTask) -> Self {
+        Self::with_retries(spec, 0, None)
+    }
+
+    fn with_retries(spec: SwapTask, error_retries: u8, retry_at: Option<Timestamp>) -> Self {
         Self {
             spec,
+            error_retries,
+            retry_at,
             _state_enum: PhantomData,
             _swap_group: PhantomData,
             _swap_client: PhantomData,
@@ -78,14 +102,17 @@ where
     ) -> Result<Batch> {
         let swap_trx = self.spec.dex_account().swap(self.spec.oracle(), querier);
         // TODO apply nls_swap_fee on the downpayment only!
-        struct SwapWorker<'a, SwapGroup, SwapClient>(
-            SwapTrx<'a>,
+        struct SwapWorker<'a, Oracle, SwapGroup, SwapClient>(
+            SwapTrx<'a, Oracle>,
             &'a SymbolSlice,
+            Percent,
             PhantomData<SwapGroup>,
             PhantomData<SwapClient>,
         );
-        impl<'a, SwapGroup, SwapClient> CoinVisitor for SwapWorker<'a, SwapGroup, SwapClient>
+        impl<'a, Oracle, SwapGroup, SwapClient> CoinVisitor
+            for SwapWorker<'a, Oracle, SwapGroup, SwapClient>
         where
+            Oracle: SwapPath,
             SwapGroup: Group,
             SwapClient: ExactAmountIn,
         {
@@ -97,7 +124,7 @@ where
                 G: Group,
             {
                 self.0
-                    .swap_exact_in::<_, SwapGroup, SwapClient>(coin, self.1)?;
+                    .swap_exact_in::<_, SwapGroup, SwapClient>(coin, self.1, self.2)?;
                 Ok(IterNext::Continue)
             }
         }
@@ -105,6 +132,7 @@ where
         let mut swapper = SwapWorker(
             swap_trx,
             self.spec.out_currency(),
+            self.spec.max_slippage(),
             PhantomData::<SwapGroup>,
             PhantomData::<SwapClient>,
         );
@@ -178,6 +206,65 @@ where
         let state_label = self.spec.label();
         timeout::on_timeout_retry(self, state_label, querier, env).into()
     }
+
+    /// Retry the swap from scratch, for example, on a heal
+    fn reset_and_retry(self, querier: QuerierWrapper<'_>, env: Env) -> HandlerResult<Self> {
+        Self::with_retries(self.spec, 0, None).retry(querier, env)
+    }
+
+    /// A swap with an output below the minimum amount fails on the DEX
+    ///
+    /// Retry it on a time alarm with a minimum amount re-estimated at the then current prices.
+    /// The retries back off exponentially and, once exhausted, the swap waits for a heal.
+    fn on_swap_error(self, env: Env) -> ContinueResult<Self> {
+        let emitter = Emitter::of_type(self.spec.label()).emit("id", env.contract.address);
+
+        if self.error_retries < MAX_ERROR_RETRIES {
+            let retry_at = env.block.time + Self::retry_delay(self.error_retries);
+
+            self.spec
+                .time_alarm()
+                .setup_alarm(retry_at)
+                .map_err(Into::into)
+                .map(|batch| {
+                    StateMachineResponse::from(
+                        MessageResponse::messages_with_events(
+                            batch,
+                            emitter.emit("error", "retry-scheduled"),
+                        ),
+                        Self::with_retries(self.spec, self.error_retries + 1, Some(retry_at)),
+                    )
+                })
+        } else {
+            Ok(StateMachineResponse::from(
+                MessageResponse::from(emitter.emit("error", "retries-exhausted")),
+                self,
+            ))
+        }
+    }
+
+    fn on_retry_alarm(self, querier: QuerierWrapper<'_>, env: Env) -> HandlerResult<Self> {
+        if self
+            .retry_at
+            .is_some_and(|retry_at| retry_at <= env.block.time)
+        {
+            let next_state = Self::with_retries(self.spec, self.error_retries, None);
+
+            next_state
+                .enter_state(env.block.time, querier)
+                .and_then(|batch| response::res_continue::<_, _, Self>(batch, next_state))
+                .into()
+        } else {
+            // not a due retry, e.g. a stale or an unrelated alarm, so keep waiting for the swap
+            response::res_continue::<_, _, Self>(MessageResponse::default(), self).into()
+        }
+    }
+
+    fn retry_delay(error_retries: u8) -> Duration {
+        ERROR_RETRY_DELAY
+            .checked_mul(1 << error_retries)
+            .expect("the retry delay should not overflow")
+    }
 }
 
 impl<SwapTask, SEnum, SwapGroup, SwapClient> Enterable
@@ -247,6 +334,10 @@ where
             .into()
     }
 
+    fn on_error(self, _querier: QuerierWrapper<'_>, env: Env) -> ContinueResult<Self> {
+        self.on_swap_error(env)
+    }
+
     fn on_timeout(self, _querier: QuerierWrapper<'_>, env: Env) -> ContinueResult<Self> {
         let state_label = self.spec.label();
         let timealarms = self.spec.time_alarm().clone();
@@ -254,7 +345,11 @@ where
     }
 
     fn heal(self, querier: QuerierWrapper<'_>, env: Env) -> HandlerResult<Self> {
-        self.retry(querier, env)
+        self.reset_and_retry(querier, env)
+    }
+
+    fn on_time_alarm(self, querier: QuerierWrapper<'_>, env: Env) -> HandlerResult<Self> {
+        self.on_retry_alarm(querier, env)
     }
 }
 
@@ -301,6 +396,10 @@ where
             )
     }
 
+    fn on_error(self, _querier: QuerierWrapper<'_>, env: Env) -> ContinueResult<Self> {
+        self.on_swap_error(env)
+    }
+
     fn on_timeout(self, _querier: QuerierWrapper<'_>, env: Env) -> ContinueResult<Self> {
         let state_label = self.spec.label();
         let timealarms = self.spec.time_alarm().clone();
@@ -308,7 +407,11 @@ where
     }
 
     fn heal(self, querier: QuerierWrapper<'_>, env: Env) -> HandlerResult<Self> {
-        self.retry(querier, env)
+        self.reset_and_retry(querier, env)
+    }
+
+    fn on_time_alarm(self, querier: QuerierWrapper<'_>, env: Env) -> HandlerResult<Self> {
+        self.on_retry_alarm(querier, env)
     }
 }
 
@@ -359,7 +462,7 @@ where
     where
         MigrateFn: FnOnce(SwapTask) -> SwapTaskNew,
     {
-        Self::Out::new(migrate_fn(self.spec))
+        Self::Out::with_retries(migrate_fn(self.spec), self.error_retries, self.retry_at)
     }
 }
 
@@ -1,5 +1,5 @@
 use currency::{Group, SymbolSlice};
-use finance::coin::CoinDTO;
+use finance::{coin::CoinDTO, percent::Percent};
 use oracle::stub::SwapPath;
 use sdk::cosmwasm_std::{Env, QuerierWrapper};
 use timealarms::stub::TimeAlarmsRef;
@@ -23,6 +23,9 @@ pub trait SwapTask {
     fn time_alarm(&self) -> &TimeAlarmsRef;
     fn out_currency(&self) -> &SymbolSlice;
 
+    /// The maximum deviation of the swap output from the one estimated at the oracle prices
+    fn max_slippage(&self) -> Percent;
+
     /// Call back the worker with each coin this swap is about.
     /// The iteration is done over the coins always in the same order.
     /// It continues either until there are no more coins or the worker has responded
@@ -16,7 +16,6 @@ use super::{
     swap_task::SwapTask as SwapTaskT,
     timeout,
     transfer_in_finish::TransferInFinish,
-    trx::IBC_TIMEOUT,
     Contract, ContractInSwap, DexConnectable, Enterable, TimeAlarm, TransferInInitRecoverIca,
     TransferInInitState,
 };
@@ -97,8 +96,9 @@ where
     SEnum: From<TransferInInitRecoverIca<SwapTask, SEnum>>,
 {
     fn on_response(self, querier: QuerierWrapper<'_>, env: Env) -> HandlerResult<Self> {
+        let timeout = env.block.time + self.dex().ibc.timeout;
         let finish: TransferInFinish<SwapTask, SEnum> =
-            TransferInFinish::new(self.spec, self.amount_in, env.block.time + IBC_TIMEOUT);
+            TransferInFinish::new(self.spec, self.amount_in, timeout);
         finish.try_complete(querier, env).map_into()
     }
 }
@@ -10,7 +10,10 @@ use currency::{
     never::{self, Never},
     Group,
 };
-use finance::{coin::CoinDTO, zero::Zero};
+use finance::{
+    coin::{self, CoinDTO},
+    zero::Zero,
+};
 use platform::{
     batch::{Batch, Emitter},
     message::Response as MessageResponse,
@@ -26,6 +29,7 @@ use crate::{InspectSpec, MigrateSpec};
 
 use super::{
     coin_index,
+    filter::CurrencyFilter,
     ica_connector::Enterable,
     response::{self, ContinueResult, Handler, Result as HandlerResult},
     swap_exact_in::SwapExactIn,
@@ -37,7 +41,8 @@ use super::{
 
 /// Transfer out a list of coins to DEX
 ///
-/// Supports up to `CoinsNb::MAX` number of coins.
+/// Supports up to `CoinsNb::MAX` number of coins. If all of them are already
+/// in the out currency, the task finishes right after the transfer without a swap.
 #[derive(Serialize, Deserialize)]
 pub struct TransferOut<SwapTask, SEnum, SwapGroup, SwapClient> {
     spec: SwapTask,
@@ -138,6 +143,21 @@ where
         }
     }
 
+    /// The total amount of the coins provided they are all in the out currency
+    fn out_currency_only(&self) -> Result<Option<CoinDTO<SwapTask::OutG>>> {
+        let mut no_swap = NoSwap;
+        let mut filter = CurrencyFilter::new(&mut no_swap, self.spec.out_currency());
+        let _res = never::safe_unwrap(self.spec.on_coins(&mut filter));
+
+        if filter.passed_any() {
+            Ok(None)
+        } else {
+            coin::from_amount_ticker(filter.filtered(), self.spec.out_currency().into())
+                .map(Some)
+                .map_err(Into::into)
+        }
+    }
+
     fn last_coin_index(spec: &SwapTask) -> CoinsNb {
         let mut counter = Counter::default();
         let _res = never::safe_unwrap(spec.on_coins(&mut counter));
@@ -183,11 +203,18 @@ where
         let label = self.spec.label();
         let now = env.block.time;
         if self.last_coin() {
-            Self::on_response(SwapExactIn::new(self.spec), label, now, querier)
+            match self.out_currency_only() {
+                Ok(Some(amount_out)) => {
+                    response::res_finished(self.spec.finish(amount_out, &env, querier))
+                }
+                Ok(None) => {
+                    Self::on_response(SwapExactIn::new(self.spec), label, now, querier).into()
+                }
+                Err(err) => HandlerResult::Continue(Err(err)),
+            }
         } else {
-            Self::on_response(self.next(), label, now, querier)
+            Self::on_response(self.next(), label, now, querier).into()
         }
-        .into()
     }
 
     fn on_timeout(self, querier: QuerierWrapper<'_>, env: Env) -> ContinueResult<Self> {
@@ -291,6 +318,19 @@ impl CoinVisitor for Counter {
     }
 }
 
+struct NoSwap;
+impl CoinVisitor for NoSwap {
+    type Result = IterNext;
+    type Error = Never;
+
+    fn visit<G>(&mut self, _coin: &CoinDTO<G>) -> StdResult<Self::Result, Self::Error>
+    where
+        G: Group,
+    {
+        Ok(IterNext::Continue)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use currency::test::{SuperGroup, SuperGroupTestC1};
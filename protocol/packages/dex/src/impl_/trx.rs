@@ -1,7 +1,8 @@
-use currency::{Group, NlsPlatform, SymbolSlice};
+use currency::{Group, SymbolSlice};
 use finance::{
-    coin::{Coin, CoinDTO},
-    duration::Duration,
+    coin::{Amount, CoinDTO},
+    fraction::Fraction,
+    percent::Percent,
 };
 use oracle::stub::SwapPath;
 use platform::{
@@ -12,17 +13,7 @@ use platform::{
 };
 use sdk::cosmwasm_std::{Addr, QuerierWrapper, Timestamp};
 
-use crate::{error::Result, swap::ExactAmountIn};
-
-pub(super) const IBC_TIMEOUT: Duration = Duration::from_minutes(7); // less than the total amount of time 100 blocks take (a Hermes setting to look back for packets)
-
-//TODO take them as input from the client
-const ICA_TRANSFER_ACK_TIP: Coin<NlsPlatform> = Coin::new(1);
-const ICA_TRANSFER_TIMEOUT_TIP: Coin<NlsPlatform> = ICA_TRANSFER_ACK_TIP;
-
-//TODO take them as input from the client
-const ICA_SWAP_ACK_TIP: Coin<NlsPlatform> = Coin::new(1);
-const ICA_SWAP_TIMEOUT_TIP: Coin<NlsPlatform> = ICA_SWAP_ACK_TIP;
+use crate::{error::Result, swap::ExactAmountIn, IbcParams};
 
 pub(super) struct TransferOutTrx<'a> {
     sender: LocalSender<'a>,
@@ -33,6 +24,7 @@ impl<'a> TransferOutTrx<'a> {
         channel: &'a str,
         sender: &Addr,
         receiver: &HostAccount,
+        ibc: &IbcParams,
         now: Timestamp,
         memo: String,
     ) -> Self {
@@ -40,9 +32,9 @@ impl<'a> TransferOutTrx<'a> {
             channel,
             sender.clone(),
             receiver.clone(),
-            now + IBC_TIMEOUT,
-            ICA_TRANSFER_ACK_TIP,
-            ICA_TRANSFER_TIMEOUT_TIP,
+            now + ibc.timeout,
+            ibc.transfer_ack_tip,
+            ibc.transfer_timeout_tip,
             memo,
         );
 
@@ -63,35 +55,46 @@ impl<'r> From<TransferOutTrx<'r>> for LocalBatch {
     }
 }
 
-pub(super) struct SwapTrx<'a> {
+pub(super) struct SwapTrx<'a, Oracle> {
     conn: &'a str,
     ica_account: &'a HostAccount,
+    ibc: &'a IbcParams,
     trx: Transaction,
-    oracle: &'a dyn SwapPath,
+    oracle: &'a Oracle,
     querier: QuerierWrapper<'a>,
 }
 
-impl<'a> SwapTrx<'a> {
+impl<'a, Oracle> SwapTrx<'a, Oracle>
+where
+    Oracle: SwapPath,
+{
     pub(super) fn new(
         conn: &'a str,
         ica_account: &'a HostAccount,
-        swap_path: &'a dyn SwapPath,
+        ibc: &'a IbcParams,
+        oracle: &'a Oracle,
         querier: QuerierWrapper<'a>,
     ) -> Self {
         let trx = Transaction::default();
         Self {
             conn,
             ica_account,
+            ibc,
             trx,
-            oracle: swap_path,
+            oracle,
             querier,
         }
     }
 
+    /// Add a swap of `amount` into `currency_out` to the transaction
+    ///
+    /// The swap would fail on the DEX if the output amount is less than
+    /// the one estimated at the current oracle prices decreased by `max_slippage`.
     pub fn swap_exact_in<GIn, GSwap, SwapClient>(
         &mut self,
         amount: &CoinDTO<GIn>,
         currency_out: &SymbolSlice,
+        max_slippage: Percent,
     ) -> Result<()>
     where
         GIn: Group,
@@ -100,12 +103,21 @@ impl<'a> SwapTrx<'a> {
     {
         self.oracle
             .swap_path(amount.ticker().into(), currency_out.into(), self.querier)
-            .map_err(Into::into)
             .and_then(|swap_path| {
+                self.oracle
+                    .estimate_amount_out::<GIn, GSwap>(amount, currency_out, self.querier)
+                    .map(|amount_out| (swap_path, amount_out))
+            })
+            .map_err(Into::into)
+            .and_then(|(swap_path, amount_out)| {
+                min_amount_out(amount_out, max_slippage).map(|min_out| (swap_path, min_out))
+            })
+            .and_then(|(swap_path, min_out)| {
                 SwapClient::build_request::<GIn, GSwap>(
                     &mut self.trx,
                     self.ica_account.clone(),
                     amount,
+                    min_out,
                     &swap_path,
                 )
                 .map_err(Into::into)
@@ -113,21 +125,29 @@ impl<'a> SwapTrx<'a> {
     }
 }
 
-impl From<SwapTrx<'_>> for LocalBatch {
-    fn from(value: SwapTrx<'_>) -> Self {
+fn min_amount_out(amount_out: Amount, max_slippage: Percent) -> Result<Amount> {
+    Percent::HUNDRED
+        .checked_sub(max_slippage)
+        .map(|min_out_ratio| min_out_ratio.of(amount_out))
+        .map_err(Into::into)
+}
+
+impl<Oracle> From<SwapTrx<'_, Oracle>> for LocalBatch {
+    fn from(value: SwapTrx<'_, Oracle>) -> Self {
         ica::submit_transaction(
             value.conn,
             value.trx,
             "memo",
-            IBC_TIMEOUT,
-            ICA_SWAP_ACK_TIP,
-            ICA_SWAP_TIMEOUT_TIP,
+            value.ibc.timeout,
+            value.ibc.ica_ack_tip,
+            value.ibc.ica_timeout_tip,
         )
     }
 }
 
 pub(super) struct TransferInTrx<'a> {
     conn: &'a str,
+    ibc: &'a IbcParams,
     sender: RemoteSender<'a>,
 }
 
@@ -137,11 +157,12 @@ impl<'a> TransferInTrx<'a> {
         channel: &'a str,
         sender: &HostAccount,
         receiver: &Addr,
+        ibc: &'a IbcParams,
         now: Timestamp,
     ) -> Self {
         let sender =
-            RemoteSender::new(channel, sender.clone(), receiver.clone(), now + IBC_TIMEOUT);
-        TransferInTrx { conn, sender }
+            RemoteSender::new(channel, sender.clone(), receiver.clone(), now + ibc.timeout);
+        TransferInTrx { conn, ibc, sender }
     }
 
     pub fn send<G>(&mut self, amount: &CoinDTO<G>) -> Result<()>
@@ -158,9 +179,9 @@ impl<'r> From<TransferInTrx<'r>> for LocalBatch {
             value.conn,
             value.sender.into(),
             "memo",
-            IBC_TIMEOUT,
-            ICA_SWAP_ACK_TIP,
-            ICA_SWAP_TIMEOUT_TIP,
+            value.ibc.timeout,
+            value.ibc.ica_ack_tip,
+            value.ibc.ica_timeout_tip,
         )
     }
 }
@@ -1,4 +1,4 @@
-pub use self::connection::{ConnectionParams, Ics20Channel};
+pub use self::connection::{ConnectionParams, IbcParams, Ics20Channel};
 #[cfg(feature = "impl")]
 pub use self::error::Error;
 #[cfg(feature = "impl")]
@@ -9,12 +9,16 @@ use sdk::{cosmos_sdk_proto::Any, cosmwasm_std::StdError};
 pub trait ExactAmountIn {
     /// `swap_path` should be a non-empty list
     ///
+    /// `min_token_out` is the minimum amount of the output token the swap
+    /// should obtain, otherwise the DEX should fail the transaction
+    ///
     /// `GIn` - the group of the input token
     /// `GSwap` - the group common for all tokens in the swap path
     fn build_request<GIn, GSwap>(
         trx: &mut Transaction,
         sender: HostAccount,
         token_in: &CoinDTO<GIn>,
+        min_token_out: Amount,
         swap_path: &SwapPath,
     ) -> Result<()>
     where
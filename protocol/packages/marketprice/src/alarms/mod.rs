@@ -151,6 +151,12 @@ where
         }
     }
 
+    /// Provides the alarms triggered by a price
+    ///
+    /// All alarms below go first, the most severe first. Their index is ordered by
+    /// the normalized thresholds, which are inversely proportional to the prices,
+    /// so the thresholds crossed the most come first with no alarm loaded in advance.
+    /// The alarms above or equal follow in their storage order.
     pub fn alarms<C, BaseC>(&self, price: Price<C, BaseC>) -> AlarmsIterator<'_, G>
     where
         C: Currency,
@@ -284,12 +290,13 @@ where
             .map_err(AlarmError::InDeliveryAppend)
     }
 
-    pub fn last_delivered(&mut self) -> Result<(), AlarmError> {
+    /// Confirm the delivery of the first alarm out for delivery, returning its subscriber
+    pub fn last_delivered(&mut self) -> Result<Addr, AlarmError> {
         self.pop_front_in_delivery(
             AlarmError::LastDeliveredRemove,
             "Received success reply status",
         )
-        .map(|_: AlarmWithSubscriber<G>| ())
+        .map(|alarm: AlarmWithSubscriber<G>| alarm.subscriber)
     }
 
     pub fn last_failed(&mut self) -> Result<(), AlarmError> {
@@ -619,6 +626,42 @@ pub mod tests {
         assert_eq!(resp, vec![Ok(addr3), Ok(addr4)]);
     }
 
+    #[test]
+    fn test_alarms_below_first() {
+        let mut storage = MockStorage::new();
+        let mut alarms = alarms(&mut storage);
+
+        let slightly_below = Addr::unchecked("slightly_below");
+        let far_below = Addr::unchecked("far_below");
+        let slightly_above = Addr::unchecked("slightly_above");
+        let far_above = Addr::unchecked("far_above");
+
+        let price = |quote| {
+            price::total_of(Coin::<SuperGroupTestC4>::new(1)).is(Coin::<BaseCurrency>::new(quote))
+        };
+
+        alarms
+            .add_alarm(slightly_below.clone(), price(110), None)
+            .unwrap();
+        alarms
+            .add_alarm(far_above.clone(), price(1), Some(price(50)))
+            .unwrap();
+        alarms
+            .add_alarm(far_below.clone(), price(150), None)
+            .unwrap();
+        alarms
+            .add_alarm(slightly_above.clone(), price(1), Some(price(90)))
+            .unwrap();
+
+        assert_eq!(
+            alarms
+                .alarms(price(100))
+                .map(Result::unwrap)
+                .collect::<Vec<_>>(),
+            vec![far_below, slightly_below, slightly_above, far_above]
+        );
+    }
+
     #[test]
     fn test_delivered() {
         let mut storage = MockStorage::new();
@@ -22,6 +22,60 @@ pub struct Config {
     /// transient property equals to `sample_period` * `samples_number`
     feed_validity: Duration,
     discount_factor: Percent,
+    /// The maximum deviation of an observation from the median of a sample
+    ///
+    /// Observations deviating more are not taken into account on the sample price calculation.
+    /// If not set, all observations are.
+    max_deviation: Option<Percent>,
+    /// Guards against abnormal price moves
+    ///
+    /// If not set, no price move halts the price alarms.
+    circuit_breaker: Option<CircuitBreaker>,
+}
+
+/// The price alarms on a currency are halted once its sample price moves abnormally
+#[derive(Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Debug))]
+pub struct CircuitBreaker {
+    /// The maximum deviation of a sample price from the previous one
+    max_sample_deviation: Percent,
+    /// How long the price alarms stay halted
+    cool_down: Duration,
+}
+
+impl CircuitBreaker {
+    #[cfg(any(test, feature = "testing"))]
+    pub fn new(max_sample_deviation: Percent, cool_down: Duration) -> Self {
+        Self::new_private(max_sample_deviation, cool_down).expect("reasonable input test data")
+    }
+
+    fn new_private(
+        max_sample_deviation: Percent,
+        cool_down: Duration,
+    ) -> Result<Self, PriceFeedsError> {
+        error::config_error_if(
+            max_sample_deviation == Percent::ZERO,
+            "The maximum sample deviation should be greater than 0",
+        )?;
+
+        error::config_error_if(
+            cool_down == Duration::default(),
+            "The cool-down should be longer than zero",
+        )?;
+
+        Ok(Self {
+            max_sample_deviation,
+            cool_down,
+        })
+    }
+
+    pub fn max_sample_deviation(&self) -> Percent {
+        self.max_sample_deviation
+    }
+
+    pub fn cool_down(&self) -> Duration {
+        self.cool_down
+    }
 }
 
 impl Config {
@@ -32,8 +86,33 @@ impl Config {
         samples_number: u16,
         discount_factor: Percent,
     ) -> Self {
-        Self::new_private(min_feeders, sample_period, samples_number, discount_factor)
-            .expect("reasonable input test data")
+        Self::new_private(
+            min_feeders,
+            sample_period,
+            samples_number,
+            discount_factor,
+            None,
+            None,
+        )
+        .expect("reasonable input test data")
+    }
+
+    #[cfg(any(test, feature = "testing"))]
+    pub fn with_max_deviation(self, max_deviation: Percent) -> Self {
+        Self {
+            max_deviation: Some(max_deviation),
+            ..self
+        }
+        .check_invariant()
+        .expect("reasonable input test data")
+    }
+
+    #[cfg(any(test, feature = "testing"))]
+    pub fn with_circuit_breaker(self, circuit_breaker: CircuitBreaker) -> Self {
+        Self {
+            circuit_breaker: Some(circuit_breaker),
+            ..self
+        }
     }
 
     fn new_private(
@@ -41,6 +120,8 @@ impl Config {
         sample_period: Duration,
         samples_number: u16,
         discount_factor: Percent,
+        max_deviation: Option<Percent>,
+        circuit_breaker: Option<CircuitBreaker>,
     ) -> Result<Self, PriceFeedsError> {
         if let Some(feed_validity) = sample_period.checked_mul(samples_number) {
             Self {
@@ -49,6 +130,8 @@ impl Config {
                 samples_number,
                 feed_validity,
                 discount_factor,
+                max_deviation,
+                circuit_breaker,
             }
             .check_invariant()
         } else {
@@ -86,6 +169,14 @@ impl Config {
         self.discount_factor
     }
 
+    pub fn max_deviation(&self) -> Option<Percent> {
+        self.max_deviation
+    }
+
+    pub fn circuit_breaker(&self) -> Option<CircuitBreaker> {
+        self.circuit_breaker
+    }
+
     fn check_invariant(self) -> Result<Self, PriceFeedsError> {
         error::config_error_if(
             self.min_feeders == Percent::ZERO || self.min_feeders > Percent::HUNDRED,
@@ -110,6 +201,11 @@ impl Config {
             "The discounting factor should be greater than 0 and less or equal to 100%",
         )?;
 
+        error::config_error_if(
+            self.max_deviation == Some(Percent::ZERO),
+            "The maximum deviation should be greater than 0",
+        )?;
+
         Ok(self)
     }
 }
@@ -121,7 +217,7 @@ mod unchecked {
 
     use crate::error::PriceFeedsError;
 
-    use super::Config as ValidatedConfig;
+    use super::{CircuitBreaker as ValidatedCircuitBreaker, Config as ValidatedConfig};
 
     #[derive(Serialize, Deserialize)]
     pub(super) struct Config {
@@ -129,6 +225,16 @@ mod unchecked {
         sample_period_secs: u32,
         samples_number: u16,
         discount_factor: Percent,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_deviation: Option<Percent>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        circuit_breaker: Option<CircuitBreaker>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub(super) struct CircuitBreaker {
+        max_sample_deviation: Percent,
+        cool_down_secs: u32,
     }
 
     impl From<ValidatedConfig> for Config {
@@ -141,6 +247,20 @@ mod unchecked {
                 ),
                 samples_number: o.samples_number,
                 discount_factor: o.discount_factor,
+                max_deviation: o.max_deviation,
+                circuit_breaker: o.circuit_breaker.map(Into::into),
+            }
+        }
+    }
+
+    impl From<ValidatedCircuitBreaker> for CircuitBreaker {
+        fn from(o: ValidatedCircuitBreaker) -> Self {
+            Self {
+                max_sample_deviation: o.max_sample_deviation,
+                cool_down_secs: expect_u32_secs(
+                    o.cool_down,
+                    "Programming error! The cool-down has been increased!",
+                ),
             }
         }
     }
@@ -154,6 +274,19 @@ mod unchecked {
                 Duration::from_secs(dto.sample_period_secs),
                 dto.samples_number,
                 dto.discount_factor,
+                dto.max_deviation,
+                dto.circuit_breaker.map(TryInto::try_into).transpose()?,
+            )
+        }
+    }
+
+    impl TryFrom<CircuitBreaker> for ValidatedCircuitBreaker {
+        type Error = PriceFeedsError;
+
+        fn try_from(dto: CircuitBreaker) -> Result<Self, Self::Error> {
+            Self::new_private(
+                dto.max_sample_deviation,
+                Duration::from_secs(dto.cool_down_secs),
             )
         }
     }
@@ -168,7 +301,7 @@ mod test {
     use finance::{duration::Duration, percent::Percent};
     use sdk::cosmwasm_std::{from_json, to_json_vec, StdError, Timestamp};
 
-    use crate::config::Config;
+    use crate::config::{CircuitBreaker, Config};
 
     #[test]
     fn feed_valid_since() {
@@ -258,6 +391,71 @@ mod test {
         serde_impl(351, 13522, 13522, 750);
     }
 
+    #[test]
+    fn max_deviation() {
+        let c = Config::new(
+            Percent::from_permille(650),
+            Duration::from_secs(5),
+            60,
+            Percent::from_permille(750),
+        );
+        assert_eq!(None, c.max_deviation());
+        assert_eq!(Ok(c.clone()), deserialize(650, 5, 60, 750));
+
+        let c = c.with_max_deviation(Percent::from_percent(5));
+        assert_eq!(Some(Percent::from_percent(5)), c.max_deviation());
+        assert_eq!(from_json(to_json_vec(&c).unwrap()), Ok(c.clone()));
+        assert_eq!(
+            Ok(c),
+            from_json(
+                r#"{"min_feeders": 650, "sample_period_secs": 5,"samples_number": 60, "discount_factor": 750, "max_deviation": 50}"#
+            )
+        );
+        assert!(matches!(
+            from_json::<Config>(
+                r#"{"min_feeders": 650, "sample_period_secs": 5,"samples_number": 60, "discount_factor": 750, "max_deviation": 0}"#
+            )
+            .unwrap_err(),
+            StdError::ParseErr { .. }
+        ));
+    }
+
+    #[test]
+    fn circuit_breaker() {
+        let c = Config::new(
+            Percent::from_permille(650),
+            Duration::from_secs(5),
+            60,
+            Percent::from_permille(750),
+        );
+        assert_eq!(None, c.circuit_breaker());
+
+        let breaker = CircuitBreaker::new(Percent::from_percent(30), Duration::HOUR);
+        let c = c.with_circuit_breaker(breaker);
+        assert_eq!(Some(breaker), c.circuit_breaker());
+        assert_eq!(from_json(to_json_vec(&c).unwrap()), Ok(c.clone()));
+        assert_eq!(
+            Ok(c),
+            from_json(
+                r#"{"min_feeders": 650, "sample_period_secs": 5,"samples_number": 60, "discount_factor": 750, "circuit_breaker": {"max_sample_deviation": 300, "cool_down_secs": 3600}}"#
+            )
+        );
+        assert!(matches!(
+            from_json::<Config>(
+                r#"{"min_feeders": 650, "sample_period_secs": 5,"samples_number": 60, "discount_factor": 750, "circuit_breaker": {"max_sample_deviation": 0, "cool_down_secs": 3600}}"#
+            )
+            .unwrap_err(),
+            StdError::ParseErr { .. }
+        ));
+        assert!(matches!(
+            from_json::<Config>(
+                r#"{"min_feeders": 650, "sample_period_secs": 5,"samples_number": 60, "discount_factor": 750, "circuit_breaker": {"max_sample_deviation": 300, "cool_down_secs": 0}}"#
+            )
+            .unwrap_err(),
+            StdError::ParseErr { .. }
+        ));
+    }
+
     fn serde_impl(min_feeders: u32, sample_period: u32, samples_number: u16, discount_factor: u32) {
         let c = Config::new(
             Percent::from_permille(min_feeders),
@@ -26,6 +26,9 @@ pub enum PriceFeedsError {
 
     #[error("[Market Price; Feeds] {0}")]
     FeedSerdeError(String),
+
+    #[error("[Market Price; Feeds] Invalid averaging period: {0}")]
+    InvalidPeriod(String),
 }
 
 impl From<postcard::Error> for PriceFeedsError {
@@ -0,0 +1,103 @@
+use std::cmp::Ordering;
+
+use currency::Currency;
+use finance::{fraction::Fraction, percent::Percent, price::Price, ratio::Rational};
+
+/// Calculate the median of prices
+///
+/// On an even number of prices, the median is the average of the two middle ones.
+pub fn median<C, QuoteC>(mut prices: Vec<Price<C, QuoteC>>) -> Option<Price<C, QuoteC>>
+where
+    C: Currency,
+    QuoteC: Currency,
+{
+    prices.sort_unstable_by(|a, b| {
+        a.partial_cmp(b)
+            .expect("prices of the same currencies should be comparable")
+    });
+
+    let middle = prices.len() / 2;
+    match prices.len() {
+        0 => None,
+        len if len % 2 == 1 => Some(prices[middle]),
+        _ => Some(Fraction::<usize>::of(
+            &Rational::<usize>::new(1, 2),
+            prices[middle - 1] + prices[middle],
+        )),
+    }
+}
+
+/// Build a predicate checking whether a price is within a maximum deviation from a median
+pub fn within<C, QuoteC>(
+    median: Price<C, QuoteC>,
+    max_deviation: Percent,
+) -> impl Fn(&Price<C, QuoteC>) -> bool
+where
+    C: Currency,
+    QuoteC: Currency,
+{
+    let upper_bound = median + max_deviation.of(median);
+    let lower_bound =
+        (max_deviation < Percent::HUNDRED).then(|| (Percent::HUNDRED - max_deviation).of(median));
+
+    move |price: &Price<C, QuoteC>| {
+        price.partial_cmp(&upper_bound) != Some(Ordering::Greater)
+            && lower_bound
+                .is_none_or(|lower_bound| price.partial_cmp(&lower_bound) != Some(Ordering::Less))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use currency::test::{SuperGroupTestC4, SuperGroupTestC5};
+    use finance::{
+        coin::{Amount, Coin},
+        percent::Percent,
+        price::{self, Price},
+    };
+
+    #[test]
+    fn median() {
+        assert_eq!(
+            None,
+            super::median::<SuperGroupTestC4, SuperGroupTestC5>(vec![])
+        );
+        assert_eq!(Some(price(1, 10)), super::median(vec![price(1, 10)]));
+        assert_eq!(
+            Some(price(1, 15)),
+            super::median(vec![price(1, 20), price(1, 10)])
+        );
+        assert_eq!(
+            Some(price(1, 12)),
+            super::median(vec![price(1, 1000), price(1, 10), price(1, 12)])
+        );
+        assert_eq!(
+            Some(price(1, 13)),
+            super::median(vec![
+                price(1, 14),
+                price(1, 1),
+                price(1, 1000),
+                price(1, 12)
+            ])
+        );
+    }
+
+    #[test]
+    fn within() {
+        let within = super::within(price(1, 100), Percent::from_percent(10));
+        assert!(within(&price(1, 100)));
+        assert!(within(&price(1, 110)));
+        assert!(within(&price(1, 90)));
+        assert!(!within(&price(1, 111)));
+        assert!(!within(&price(1, 89)));
+
+        let within = super::within(price(1, 100), Percent::from_percent(150));
+        assert!(within(&price(1, 1)));
+        assert!(within(&price(1, 250)));
+        assert!(!within(&price(1, 251)));
+    }
+
+    fn price(c: Amount, q: Amount) -> Price<SuperGroupTestC4, SuperGroupTestC5> {
+        price::total_of(Coin::from(c)).is(Coin::from(q))
+    }
+}
@@ -1,4 +1,7 @@
-use std::collections::HashSet;
+use std::{
+    collections::{HashMap, HashSet},
+    iter,
+};
 
 use serde::{Deserialize, Serialize};
 
@@ -10,6 +13,7 @@ use crate::{config::Config, error::PriceFeedsError, feed::sample::Sample};
 
 use self::observation::Observation;
 
+pub(crate) mod deviation;
 mod observation;
 mod sample;
 
@@ -47,6 +51,32 @@ where
         self
     }
 
+    /// Check whether a new observation deviates from the median of the most recent ones
+    ///
+    /// The median is taken over the last observations per feeder within the last sample period,
+    /// the new observation replacing the feeder's previous one.
+    /// Nothing deviates if there is no configured maximum deviation.
+    pub fn deviates(
+        &self,
+        config: &Config,
+        from: &Addr,
+        at: Timestamp,
+        price: Price<C, QuoteC>,
+    ) -> bool {
+        config.max_deviation().is_some_and(|max_deviation| {
+            let period_start =
+                Timestamp::from_nanos(at.nanos().saturating_sub(config.sample_period().nanos()));
+            let last_prices: HashMap<&Addr, Price<C, QuoteC>> = self
+                .valid_observations(period_start)
+                .filter(|observation| observation.feeder() != from)
+                .map(|observation| (observation.feeder(), observation.price()))
+                .collect();
+
+            deviation::median(last_prices.into_values().chain(iter::once(price)).collect())
+                .is_some_and(|median| !deviation::within(median, max_deviation)(&price))
+        })
+    }
+
     /// Calculate the price of this feed
     ///
     /// Provide no price if there are no observations from at least configurable percentage * <number_of_whitelisted_feeders>.
@@ -59,22 +89,9 @@ where
         at: Timestamp,
         total_feeders: usize,
     ) -> Result<Price<C, QuoteC>, PriceFeedsError> {
-        let valid_since = config.feed_valid_since(at);
-        if !self.has_enough_feeders(valid_since, config, total_feeders) {
-            return Err(PriceFeedsError::NoPrice {});
-        }
-
-        let observations = self.valid_observations(valid_since);
-
-        let samples = sample::from_observations(observations, valid_since, config.sample_period());
-
         let discount_factor = config.discount_factor();
 
-        let samples_nb = config.samples_number().into();
-
-        samples
-            .take(samples_nb)
-            .map(Sample::into_maybe_price)
+        self.valid_samples(config, at, total_feeders)?
             .skip_while(Option::is_none)
             .map(|price| Option::expect(price, "sample prices should keep being present"))
             .reduce(|acc, sample_price| {
@@ -83,6 +100,45 @@ where
             .ok_or(PriceFeedsError::NoPrice {})
     }
 
+    /// Provide the samples the price of this feed is calculated from
+    ///
+    /// The samples are ordered from the oldest to the newest. The i-th sample
+    /// spans the period (valid_since + i * sample_period, valid_since + (i + 1) * sample_period],
+    /// where valid_since = `config.feed_valid_since(at)`.
+    /// The same feeders' constraint as of [`Self::calc_price`] applies.
+    pub fn samples(
+        &self,
+        config: &Config,
+        at: Timestamp,
+        total_feeders: usize,
+    ) -> Result<Vec<Option<Price<C, QuoteC>>>, PriceFeedsError> {
+        self.valid_samples(config, at, total_feeders)
+            .map(Iterator::collect)
+    }
+
+    fn valid_samples<'a>(
+        &'a self,
+        config: &Config,
+        at: Timestamp,
+        total_feeders: usize,
+    ) -> Result<impl Iterator<Item = Option<Price<C, QuoteC>>> + 'a, PriceFeedsError> {
+        let valid_since = config.feed_valid_since(at);
+        if !self.has_enough_feeders(valid_since, config, total_feeders) {
+            return Err(PriceFeedsError::NoPrice {});
+        }
+
+        let observations = self.valid_observations(valid_since);
+
+        Ok(sample::from_observations(
+            observations,
+            valid_since,
+            config.sample_period(),
+            config.max_deviation(),
+        )
+        .take(config.samples_number().into())
+        .map(Sample::into_maybe_price))
+    }
+
     fn has_enough_feeders(&self, since: Timestamp, config: &Config, total_feeders: usize) -> bool {
         self.count_unique_feeders(since) >= config.min_feeders(total_feeders)
     }
@@ -111,6 +167,7 @@ mod test {
     use finance::{
         coin::{Amount, Coin},
         duration::Duration,
+        fraction::Fraction,
         percent::Percent,
         price::{self, Price},
     };
@@ -307,6 +364,93 @@ mod test {
         );
     }
 
+    #[test]
+    fn samples() {
+        let block_time = Timestamp::from_seconds(100);
+        let config = Config::new(
+            Percent::HUNDRED,
+            SAMPLE_PERIOD,
+            SAMPLES_NUMBER,
+            DISCOUNTING_FACTOR,
+        );
+
+        let s1 = block_time - Duration::from_secs(12);
+        let s2 = block_time - Duration::from_secs(2);
+
+        let feeder1 = Addr::unchecked("feeder1");
+
+        let mut feed = PriceFeed::new();
+        assert_eq!(
+            Err(PriceFeedsError::NoPrice()),
+            feed.samples(&config, block_time, ONE_FEEDER)
+        );
+
+        feed = feed.add_observation(
+            feeder1.clone(),
+            s1,
+            price(19, 5160),
+            config.feed_valid_since(s1),
+        );
+        feed = feed.add_observation(feeder1, s2, price(19, 5000), config.feed_valid_since(s2));
+
+        let samples = feed.samples(&config, block_time, ONE_FEEDER).unwrap();
+        assert_eq!(usize::from(SAMPLES_NUMBER), samples.len());
+        assert!(samples[..9].iter().all(Option::is_none));
+        assert_eq!(
+            vec![
+                Some(price(19, 5160)),
+                Some(price(19, 5160)),
+                Some(price(19, 5000))
+            ],
+            samples[9..]
+        );
+
+        let ema = samples.into_iter().flatten().reduce(|acc, sample_price| {
+            DISCOUNTING_FACTOR.of(sample_price) + (Percent::HUNDRED - DISCOUNTING_FACTOR).of(acc)
+        });
+        assert_eq!(ema, feed.calc_price(&config, block_time, ONE_FEEDER).ok());
+    }
+
+    #[test]
+    fn deviates() {
+        let block_time = Timestamp::from_seconds(100);
+        let config = Config::new(
+            Percent::HUNDRED,
+            SAMPLE_PERIOD,
+            SAMPLES_NUMBER,
+            DISCOUNTING_FACTOR,
+        );
+        let feeder1 = Addr::unchecked("feeder1");
+        let feeder2 = Addr::unchecked("feeder2");
+        let feeder3 = Addr::unchecked("feeder3");
+
+        let t1 = block_time - Duration::from_secs(3);
+        let t2 = block_time - Duration::from_secs(2);
+        let mut feed = PriceFeed::new();
+        feed = feed.add_observation(
+            feeder1.clone(),
+            t1,
+            price(1, 100),
+            config.feed_valid_since(t1),
+        );
+        feed = feed.add_observation(feeder2, t2, price(1, 102), config.feed_valid_since(t2));
+
+        assert!(!feed.deviates(&config, &feeder3, block_time, price(1, 1000)));
+
+        let config = config.with_max_deviation(Percent::from_percent(10));
+        assert!(!feed.deviates(&config, &feeder3, block_time, price(1, 110)));
+        assert!(feed.deviates(&config, &feeder3, block_time, price(1, 1000)));
+        // the previous observation of the same feeder is replaced
+        assert!(!feed.deviates(&config, &feeder1, block_time, price(1, 110)));
+        // observations out of the last sample period are not taken into account
+        assert!(!feed.deviates(
+            &config,
+            &feeder3,
+            block_time + SAMPLE_PERIOD,
+            price(1, 1000)
+        ));
+    }
+
     fn price(c: Amount, q: Amount) -> Price<SuperGroupTestC4, SuperGroupTestC5> {
         price::total_of(Coin::from(c)).is(Coin::from(q))
     }
@@ -1,28 +1,33 @@
 use std::collections::HashMap;
 
 use currency::Currency;
-use finance::{duration::Duration, fraction::Fraction, price::Price, ratio::Rational};
+use finance::{
+    duration::Duration, fraction::Fraction, percent::Percent, price::Price, ratio::Rational,
+};
 use sdk::cosmwasm_std::{Addr, Timestamp};
 
-use super::observation::Observation;
+use super::{deviation, observation::Observation};
 
 /// Builds an infinite iterator of samples
 ///
 /// It loops over #Observation instances, groups them by time in periods,
 /// takes the last by feeder, and computes an average for each period.
-/// If there are no observations for a period, the sample from the last
-/// period is yielded again.
+/// If a maximum deviation is provided, the prices deviating more from
+/// the median of the period are left out of the average.
+/// If there are no observations for a period, or all of them are left out,
+/// the sample from the last period is yielded again.
 pub fn from_observations<'a, IterO, C, QuoteC>(
     observations: IterO,
     start_from: Timestamp,
     sample_span: Duration,
+    max_deviation: Option<Percent>,
 ) -> impl Iterator<Item = Sample<C, QuoteC>> + 'a
 where
     IterO: Iterator<Item = &'a Observation<C, QuoteC>> + 'a,
     C: Currency,
     QuoteC: Currency,
 {
-    SampleBuilder::from(observations, start_from, sample_span)
+    SampleBuilder::from(observations, start_from, sample_span, max_deviation)
 }
 
 #[derive(Default, Copy, Clone)]
@@ -56,6 +61,7 @@ where
     observations: IterO,
     sample_start: Timestamp,
     sample_span: Duration,
+    max_deviation: Option<Percent>,
     consumed: Option<IterO::Item>,
     sample_prices: HashMap<&'a Addr, Price<C, QuoteC>>,
     last_sample: <Self as Iterator>::Item,
@@ -67,11 +73,17 @@ where
     QuoteC: Currency,
     IterO: Iterator<Item = &'a Observation<C, QuoteC>>,
 {
-    fn from(observations: IterO, start_from: Timestamp, sample_span: Duration) -> Self {
+    fn from(
+        observations: IterO,
+        start_from: Timestamp,
+        sample_span: Duration,
+        max_deviation: Option<Percent>,
+    ) -> Self {
         Self {
             observations,
             sample_start: start_from,
             sample_span,
+            max_deviation,
             consumed: None,
             sample_prices: HashMap::default(),
             last_sample: <Self as Iterator>::Item::default(),
@@ -79,19 +91,26 @@ where
     }
 
     fn end_of_period(&mut self) {
-        let prices_number = self.sample_prices.len();
+        let mut prices: Vec<Price<C, QuoteC>> =
+            self.sample_prices.drain().map(|(_, price)| price).collect();
+        if let Some(max_deviation) = self.max_deviation {
+            if let Some(median) = deviation::median(prices.clone()) {
+                prices.retain(deviation::within(median, max_deviation));
+            }
+        }
+
+        let prices_number = prices.len();
         if prices_number > 0 {
-            let mut values = self.sample_prices.values();
+            let mut values = prices.into_iter();
             let first = values
                 .next()
                 .expect("should have been checked that there is at least one member");
 
-            let sum = values.fold(*first, |acc, current| acc + *current);
+            let sum = values.fold(first, |acc, current| acc + current);
             let part = Rational::new(1, prices_number);
             let avg = Fraction::<usize>::of(&part, sum);
             self.last_sample = Sample { price: Some(avg) }
         }
-        self.sample_prices.clear();
         self.sample_start += self.sample_span;
     }
 }
@@ -125,7 +144,7 @@ where
 #[cfg(test)]
 mod test {
     use currency::test::{SuperGroupTestC4, SuperGroupTestC5};
-    use finance::{coin::Amount, duration::Duration, price};
+    use finance::{coin::Amount, duration::Duration, percent::Percent, price};
     use sdk::cosmwasm_std::{Addr, Timestamp};
 
     use crate::feed::{
@@ -144,7 +163,7 @@ mod test {
         let obs = [Observation::new(feeder1(), t1, p1)];
 
         let mut samples =
-            sample::from_observations(obs.iter(), start_from, Duration::from_secs(25));
+            sample::from_observations(obs.iter(), start_from, Duration::from_secs(25), None);
         assert_eq!(Some(Sample::default()), samples.next());
         assert_eq!(Some(Sample { price: Some(p1) }), samples.next());
         assert_eq!(Some(Sample { price: Some(p1) }), samples.next());
@@ -168,7 +187,7 @@ mod test {
         ];
 
         let mut samples =
-            sample::from_observations(obs.iter(), start_from, Duration::from_secs(25));
+            sample::from_observations(obs.iter(), start_from, Duration::from_secs(25), None);
         assert_eq!(Some(Sample { price: Some(p1) }), samples.next());
         assert_eq!(Some(Sample { price: Some(p3) }), samples.next());
         assert_eq!(Some(Sample { price: Some(p3) }), samples.next());
@@ -202,13 +221,60 @@ mod test {
         ];
 
         let mut samples =
-            sample::from_observations(obs.iter(), start_from, Duration::from_secs(25));
+            sample::from_observations(obs.iter(), start_from, Duration::from_secs(25), None);
         assert_eq!(Some(Sample { price: Some(p13) }), samples.next());
         assert_eq!(Some(Sample { price: Some(p23) }), samples.next());
         assert_eq!(Some(Sample { price: Some(p1) }), samples.next());
         assert_eq!(Some(Sample { price: Some(p1) }), samples.next());
     }
 
+    #[test]
+    fn outlier_observations() {
+        let start_from = Timestamp::from_seconds(150);
+        let t1 = Timestamp::from_seconds(160);
+        let t2 = Timestamp::from_seconds(180);
+
+        let p1 = price(1, 12000);
+        let p2 = price(1, 12200);
+        let p3 = price(1, 24000);
+        let obs = [
+            Observation::new(feeder1(), t1, p1), // first period
+            Observation::new(feeder2(), t1, p2),
+            Observation::new(feeder3(), t1, p3),
+            Observation::new(feeder1(), t2, p2), // second period
+            Observation::new(feeder3(), t2, p3),
+        ];
+
+        let mut samples = sample::from_observations(
+            obs.iter(),
+            start_from,
+            Duration::from_secs(25),
+            Some(Percent::from_percent(5)),
+        );
+        assert_eq!(
+            Some(Sample {
+                price: Some(price(1, 12100))
+            }),
+            samples.next()
+        );
+        // no consensus, the last sample is repeated
+        assert_eq!(
+            Some(Sample {
+                price: Some(price(1, 12100))
+            }),
+            samples.next()
+        );
+
+        let mut samples =
+            sample::from_observations(obs.iter(), start_from, Duration::from_secs(25), None);
+        assert_eq!(
+            Some(Sample {
+                price: Some(price(3, 48200))
+            }),
+            samples.next()
+        );
+    }
+
     fn price(of: Amount, is: Amount) -> price::Price<TheCurrency, TheQuote> {
         price::total_of(of.into()).is(is.into())
     }
@@ -220,4 +286,8 @@ mod test {
     fn feeder2() -> Addr {
         Addr::unchecked("feeder2")
     }
+
+    fn feeder3() -> Addr {
+        Addr::unchecked("feeder3")
+    }
 }
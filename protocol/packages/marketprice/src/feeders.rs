@@ -1,10 +1,12 @@
 use std::collections::HashSet;
 
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use sdk::{
     cosmwasm_std::{Addr, DepsMut, StdError, StdResult, Storage},
-    cw_storage_plus::Item,
+    cw_storage_plus::{Item, Map},
+    schemars::{self, JsonSchema},
 };
 
 /// Errors returned from Feeders
@@ -23,29 +25,48 @@ pub enum PriceFeedersError {
     Unauthorized {},
 }
 
+/// Track record of a feeder
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Debug))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct FeederStats {
+    /// The number of prices fed
+    pub observations: u64,
+    /// The number of prices fed that deviated from the median more than the configured maximum
+    pub deviations: u64,
+}
+
 // state/logic
-pub struct PriceFeeders<'f>(Item<'f, HashSet<Addr>>);
+pub struct PriceFeeders<'f> {
+    feeders: Item<'f, HashSet<Addr>>,
+    stats: Map<'f, Addr, FeederStats>,
+}
 
 // this is the core business logic we expose
 impl<'f> PriceFeeders<'f> {
-    pub const fn new(namespace: &'f str) -> Self {
-        Self(Item::new(namespace))
+    pub const fn new(namespace: &'f str, stats_namespace: &'f str) -> Self {
+        Self {
+            feeders: Item::new(namespace),
+            stats: Map::new(stats_namespace),
+        }
     }
 
     pub fn get(&self, storage: &dyn Storage) -> StdResult<HashSet<Addr>> {
-        self.0.may_load(storage).map(Option::unwrap_or_default)
+        self.feeders
+            .may_load(storage)
+            .map(Option::unwrap_or_default)
     }
 
     pub fn is_registered(&self, storage: &dyn Storage, address: &Addr) -> StdResult<bool> {
-        self.0
+        self.feeders
             .may_load(storage)
             .map(|maybe_addrs: Option<HashSet<Addr>>| {
-                maybe_addrs.map_or(false, |addrs: HashSet<Addr>| addrs.contains(address))
+                maybe_addrs.is_some_and(|addrs: HashSet<Addr>| addrs.contains(address))
             })
     }
 
     pub fn register(&self, deps: DepsMut<'_>, address: Addr) -> Result<(), PriceFeedersError> {
-        let mut db = self.0.may_load(deps.storage)?.unwrap_or_default();
+        let mut db = self.feeders.may_load(deps.storage)?.unwrap_or_default();
 
         if db.contains(&address) {
             return Err(PriceFeedersError::FeederAlreadyRegistered {});
@@ -53,7 +74,7 @@ impl<'f> PriceFeeders<'f> {
 
         db.insert(address);
 
-        self.0.save(deps.storage, &db)?;
+        self.feeders.save(deps.storage, &db)?;
 
         Ok(())
     }
@@ -64,27 +85,66 @@ impl<'f> PriceFeeders<'f> {
             feeders
         };
 
-        if let Some(feeders) = self.0.may_load(deps.storage).transpose() {
+        self.stats.remove(deps.storage, feeder.clone());
+
+        if let Some(feeders) = self.feeders.may_load(deps.storage).transpose() {
             feeders
                 .map(remove_address)
-                .and_then(|new_feeders| self.0.save(deps.storage, &new_feeders))
+                .and_then(|new_feeders| self.feeders.save(deps.storage, &new_feeders))
                 .map_err(Into::into)
         } else {
             Ok(())
         }
     }
+
+    /// Account for newly fed prices
+    pub fn record(
+        &self,
+        storage: &mut dyn Storage,
+        feeder: &Addr,
+        observations: u64,
+        deviations: u64,
+    ) -> StdResult<()> {
+        debug_assert!(deviations <= observations);
+
+        self.stats
+            .update(storage, feeder.clone(), |stats: Option<FeederStats>| {
+                let stats = stats.unwrap_or_default();
+
+                Ok(FeederStats {
+                    observations: stats.observations.saturating_add(observations),
+                    deviations: stats.deviations.saturating_add(deviations),
+                })
+            })
+            .map(|_| ())
+    }
+
+    /// Provide the track record of all registered feeders
+    pub fn stats(&self, storage: &dyn Storage) -> StdResult<Vec<(Addr, FeederStats)>> {
+        let mut feeders: Vec<Addr> = self.get(storage)?.into_iter().collect();
+        feeders.sort();
+
+        feeders
+            .into_iter()
+            .map(|feeder| {
+                self.stats
+                    .may_load(storage, feeder.clone())
+                    .map(|stats| (feeder, stats.unwrap_or_default()))
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use sdk::cosmwasm_std::{testing, Addr};
 
-    use crate::feeders::PriceFeeders;
+    use crate::feeders::{FeederStats, PriceFeeders};
 
     #[test]
     fn remove_empty() {
         let mut deps = testing::mock_dependencies();
-        let feeders = PriceFeeders::new("storage_namespace");
+        let feeders = PriceFeeders::new("storage_namespace", "stats_namespace");
         feeders
             .remove(deps.as_mut(), &Addr::unchecked("test_feeder"))
             .unwrap();
@@ -93,7 +153,7 @@ mod tests {
     #[test]
     fn remove_existing() {
         let mut deps = testing::mock_dependencies();
-        let feeders = PriceFeeders::new("storage_namespace");
+        let feeders = PriceFeeders::new("storage_namespace", "stats_namespace");
         let new_feeder = Addr::unchecked("feeder34");
         feeders.register(deps.as_mut(), new_feeder.clone()).unwrap();
         assert_eq!(Ok(true), feeders.is_registered(&deps.storage, &new_feeder));
@@ -102,4 +162,40 @@ mod tests {
 
         assert_eq!(Ok(false), feeders.is_registered(&deps.storage, &new_feeder));
     }
+
+    #[test]
+    fn record_stats() {
+        let mut deps = testing::mock_dependencies();
+        let feeders = PriceFeeders::new("storage_namespace", "stats_namespace");
+        let feeder1 = Addr::unchecked("feeder1");
+        let feeder2 = Addr::unchecked("feeder2");
+        feeders.register(deps.as_mut(), feeder1.clone()).unwrap();
+        feeders.register(deps.as_mut(), feeder2.clone()).unwrap();
+
+        feeders.record(&mut deps.storage, &feeder1, 3, 1).unwrap();
+        feeders.record(&mut deps.storage, &feeder1, 2, 0).unwrap();
+        assert_eq!(
+            Ok(vec![
+                (
+                    feeder1.clone(),
+                    FeederStats {
+                        observations: 5,
+                        deviations: 1
+                    }
+                ),
+                (feeder2.clone(), FeederStats::default())
+            ]),
+            feeders.stats(&deps.storage)
+        );
+
+        feeders.remove(deps.as_mut(), &feeder1).unwrap();
+        feeders.register(deps.as_mut(), feeder1.clone()).unwrap();
+        assert_eq!(
+            Ok(vec![
+                (feeder1, FeederStats::default()),
+                (feeder2, FeederStats::default())
+            ]),
+            feeders.stats(&deps.storage)
+        );
+    }
 }
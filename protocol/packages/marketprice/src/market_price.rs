@@ -4,16 +4,27 @@ use currency::{
     self, AnyVisitor, AnyVisitorResult, Currency, Group, GroupVisit, SymbolOwned, SymbolSlice,
     Tickers,
 };
-use finance::price::{
-    dto::{with_price, PriceDTO, WithPrice},
-    Price,
+use finance::{
+    duration::Duration,
+    fraction::Fraction,
+    percent::Percent,
+    period::Period,
+    price::{
+        dto::{with_price, PriceDTO, WithPrice},
+        Price,
+    },
+    ratio::Rational,
 };
 use sdk::{
     cosmwasm_std::{Addr, Storage, Timestamp},
     cw_storage_plus::Map,
 };
 
-use crate::{config::Config, error::PriceFeedsError, feed::PriceFeed};
+use crate::{
+    config::Config,
+    error::PriceFeedsError,
+    feed::{deviation, PriceFeed},
+};
 
 pub type PriceFeedBin = Vec<u8>;
 pub struct PriceFeeds<'m, G> {
@@ -34,33 +45,33 @@ where
         }
     }
 
+    /// Add observations of a feeder
+    ///
+    /// Return the number of the observed prices that deviate from the median of
+    /// the most recent observations more than the configured maximum.
     pub fn feed(
         &self,
         storage: &mut dyn Storage,
         at: Timestamp,
         sender_raw: &Addr,
         prices: &[PriceDTO<G, G>],
-    ) -> Result<(), PriceFeedsError> {
-        for price in prices {
-            self.storage.update(
-                storage,
-                (
-                    price.base().ticker().to_string(),
-                    price.quote().ticker().to_string(),
-                ),
-                |feed: Option<PriceFeedBin>| -> Result<PriceFeedBin, PriceFeedsError> {
-                    add_observation(
-                        feed,
-                        sender_raw,
-                        at,
-                        price,
-                        self.config.feed_valid_since(at),
-                    )
-                },
-            )?;
-        }
+    ) -> Result<usize, PriceFeedsError> {
+        prices.iter().try_fold(0, |deviations, price| {
+            let key = (
+                price.base().ticker().to_string(),
+                price.quote().ticker().to_string(),
+            );
+            let feed = self.storage.may_load(storage, key.clone())?;
 
-        Ok(())
+            add_observation(feed, sender_raw, at, price, &self.config).and_then(
+                |(feed, deviates)| {
+                    self.storage
+                        .save(storage, key, &feed)
+                        .map(|()| deviations + usize::from(deviates))
+                        .map_err(Into::into)
+                },
+            )
+        })
     }
 
     pub fn price<'a, QuoteC, QuoteG, Iter>(
@@ -90,6 +101,147 @@ where
         )
     }
 
+    /// Provide the sample prices along the path, ordered from the oldest to the newest
+    ///
+    /// Each sample price is the product of the corresponding sample prices of the feeds
+    /// on the path. A sample has no price if any of them is missing.
+    pub fn price_samples<'a, QuoteC, QuoteG, Iter>(
+        &'m self,
+        storage: &'a dyn Storage,
+        at: Timestamp,
+        total_feeders: usize,
+        leaf_to_root: Iter,
+    ) -> Result<Vec<Option<PriceDTO<G, QuoteG>>>, PriceFeedsError>
+    where
+        'm: 'a,
+        G: Group,
+        QuoteC: Currency,
+        QuoteG: Group,
+        Iter: Iterator<Item = &'a SymbolSlice> + DoubleEndedIterator,
+    {
+        self.collect_samples::<QuoteC, _, _>(
+            storage,
+            at,
+            total_feeders,
+            leaf_to_root,
+            SamplesDTO(PhantomData),
+        )
+    }
+
+    /// Calculate the time-weighted average price along the path over a period
+    ///
+    /// The period should not be empty and should be within the validity window of the feeds
+    /// as of `at`. All samples span the same amount of time so the average is taken over
+    /// the samples with a price whose periods intersect the given one.
+    pub fn twap<'a, QuoteC, QuoteG, Iter>(
+        &'m self,
+        storage: &'a dyn Storage,
+        at: Timestamp,
+        total_feeders: usize,
+        leaf_to_root: Iter,
+        period: Period,
+    ) -> Result<PriceDTO<G, QuoteG>, PriceFeedsError>
+    where
+        'm: 'a,
+        G: Group,
+        QuoteC: Currency,
+        QuoteG: Group,
+        Iter: Iterator<Item = &'a SymbolSlice> + DoubleEndedIterator,
+    {
+        let valid_since = self.config.feed_valid_since(at);
+        if period.zero_length() || period.start() < valid_since || at < period.till() {
+            return Err(PriceFeedsError::InvalidPeriod(format!(
+                "[{from}, {till}] is not within the validity window [{valid_since}, {at}]",
+                from = period.start(),
+                till = period.till(),
+            )));
+        }
+
+        let sample_period = self.config.sample_period().nanos();
+        let last_sample = u64::from(self.config.samples_number()).saturating_sub(1);
+        let sample_index = |till: Timestamp| {
+            let index = (Duration::between(&valid_since, &till)
+                .nanos()
+                .saturating_sub(1)
+                / sample_period)
+                .min(last_sample);
+            usize::try_from(index).expect("the sample index should not exceed the samples number")
+        };
+
+        self.collect_samples::<QuoteC, _, _>(
+            storage,
+            at,
+            total_feeders,
+            leaf_to_root,
+            Twap {
+                first: sample_index(period.start()),
+                last: sample_index(period.till()),
+                _quote_g: PhantomData,
+            },
+        )
+    }
+
+    /// Check whether the newest sample price along the path moved abnormally
+    ///
+    /// It has if it deviates from the previous sample price more than the maximum
+    /// configured with the circuit breaker. Nothing moves abnormally if there is none.
+    pub fn sample_jumps<'a, QuoteC, Iter>(
+        &'m self,
+        storage: &'a dyn Storage,
+        at: Timestamp,
+        total_feeders: usize,
+        leaf_to_root: Iter,
+    ) -> Result<bool, PriceFeedsError>
+    where
+        'm: 'a,
+        G: Group,
+        QuoteC: Currency,
+        Iter: Iterator<Item = &'a SymbolSlice> + DoubleEndedIterator,
+    {
+        self.config
+            .circuit_breaker()
+            .map_or(Ok(false), |circuit_breaker| {
+                self.collect_samples::<QuoteC, _, _>(
+                    storage,
+                    at,
+                    total_feeders,
+                    leaf_to_root,
+                    SampleJump {
+                        max_deviation: circuit_breaker.max_sample_deviation(),
+                    },
+                )
+            })
+    }
+
+    fn collect_samples<'a, QuoteC, Iter, Cmd>(
+        &'m self,
+        storage: &'a dyn Storage,
+        at: Timestamp,
+        total_feeders: usize,
+        leaf_to_root: Iter,
+        cmd: Cmd,
+    ) -> Result<Cmd::Output, PriceFeedsError>
+    where
+        'm: 'a,
+        G: Group,
+        QuoteC: Currency,
+        Iter: Iterator<Item = &'a SymbolSlice> + DoubleEndedIterator,
+        Cmd: WithSamples<G>,
+    {
+        let mut root_to_leaf = leaf_to_root.rev();
+        let _root = root_to_leaf.next();
+        debug_assert_eq!(_root, Some(QuoteC::TICKER));
+        SamplesCollect::do_collect(
+            root_to_leaf,
+            self,
+            storage,
+            at,
+            total_feeders,
+            vec![Some(Price::<QuoteC, QuoteC>::identity()); self.config.samples_number().into()],
+            cmd,
+        )
+    }
+
     pub fn price_of_feed<C, QuoteC>(
         &self,
         storage: &dyn Storage,
@@ -198,27 +350,216 @@ where
     }
 }
 
+trait WithSamples<G> {
+    type Output;
+
+    fn exec<C, QuoteC>(
+        self,
+        samples: Vec<Option<Price<C, QuoteC>>>,
+    ) -> Result<Self::Output, PriceFeedsError>
+    where
+        C: Currency,
+        QuoteC: Currency;
+}
+
+struct SamplesDTO<QuoteG>(PhantomData<QuoteG>);
+impl<G, QuoteG> WithSamples<G> for SamplesDTO<QuoteG>
+where
+    G: Group,
+    QuoteG: Group,
+{
+    type Output = Vec<Option<PriceDTO<G, QuoteG>>>;
+
+    fn exec<C, QuoteC>(
+        self,
+        samples: Vec<Option<Price<C, QuoteC>>>,
+    ) -> Result<Self::Output, PriceFeedsError>
+    where
+        C: Currency,
+        QuoteC: Currency,
+    {
+        Ok(samples
+            .into_iter()
+            .map(|sample| sample.map(Into::into))
+            .collect())
+    }
+}
+
+struct Twap<QuoteG> {
+    first: usize,
+    last: usize,
+    _quote_g: PhantomData<QuoteG>,
+}
+impl<G, QuoteG> WithSamples<G> for Twap<QuoteG>
+where
+    G: Group,
+    QuoteG: Group,
+{
+    type Output = PriceDTO<G, QuoteG>;
+
+    fn exec<C, QuoteC>(
+        self,
+        samples: Vec<Option<Price<C, QuoteC>>>,
+    ) -> Result<Self::Output, PriceFeedsError>
+    where
+        C: Currency,
+        QuoteC: Currency,
+    {
+        debug_assert!(self.first <= self.last);
+        debug_assert!(self.last < samples.len());
+
+        let (prices_number, sum): (usize, Option<Price<C, QuoteC>>) = samples
+            [self.first..=self.last]
+            .iter()
+            .flatten()
+            .fold((0, None), |(count, sum), &price| {
+                (count + 1, Some(sum.map_or(price, |sum| sum + price)))
+            });
+
+        sum.map(|sum| Fraction::<usize>::of(&Rational::new(1, prices_number), sum).into())
+            .ok_or(PriceFeedsError::NoPrice())
+    }
+}
+
+struct SampleJump {
+    max_deviation: Percent,
+}
+impl<G> WithSamples<G> for SampleJump {
+    type Output = bool;
+
+    fn exec<C, QuoteC>(
+        self,
+        samples: Vec<Option<Price<C, QuoteC>>>,
+    ) -> Result<Self::Output, PriceFeedsError>
+    where
+        C: Currency,
+        QuoteC: Currency,
+    {
+        let mut newest_first = samples.into_iter().rev().flatten();
+
+        Ok(newest_first
+            .next()
+            .zip(newest_first.next())
+            .is_some_and(|(newest, previous)| {
+                !deviation::within(previous, self.max_deviation)(&newest)
+            }))
+    }
+}
+
+struct SamplesCollect<'a, Iter, C, G, QuoteC, Cmd>
+where
+    Iter: Iterator<Item = &'a SymbolSlice>,
+    C: Currency,
+    QuoteC: Currency,
+{
+    currency_path: Iter,
+    feeds: &'a PriceFeeds<'a, G>,
+    storage: &'a dyn Storage,
+    at: Timestamp,
+    total_feeders: usize,
+    samples: Vec<Option<Price<C, QuoteC>>>,
+    cmd: Cmd,
+}
+impl<'a, Iter, C, G, QuoteC, Cmd> SamplesCollect<'a, Iter, C, G, QuoteC, Cmd>
+where
+    Iter: Iterator<Item = &'a SymbolSlice>,
+    C: Currency,
+    QuoteC: Currency,
+{
+    fn do_collect(
+        mut currency_path: Iter,
+        feeds: &'a PriceFeeds<'a, G>,
+        storage: &'a dyn Storage,
+        at: Timestamp,
+        total_feeders: usize,
+        samples: Vec<Option<Price<C, QuoteC>>>,
+        cmd: Cmd,
+    ) -> Result<Cmd::Output, PriceFeedsError>
+    where
+        G: Group,
+        Cmd: WithSamples<G>,
+    {
+        if let Some(next_currency) = currency_path.next() {
+            let next_collect = SamplesCollect {
+                currency_path,
+                feeds,
+                storage,
+                at,
+                total_feeders,
+                samples,
+                cmd,
+            };
+            Tickers.visit_any::<G, _>(next_currency, next_collect)
+        } else {
+            cmd.exec(samples)
+        }
+    }
+}
+impl<'a, Iter, QuoteC, G, QuoteQuoteC, Cmd> AnyVisitor
+    for SamplesCollect<'a, Iter, QuoteC, G, QuoteQuoteC, Cmd>
+where
+    Iter: Iterator<Item = &'a SymbolSlice>,
+    QuoteC: Currency,
+    G: Group,
+    QuoteQuoteC: Currency,
+    Cmd: WithSamples<G>,
+{
+    type Output = Cmd::Output;
+    type Error = PriceFeedsError;
+
+    fn on<C>(self) -> AnyVisitorResult<Self>
+    where
+        C: Currency,
+    {
+        let feed_bin = self
+            .feeds
+            .storage
+            .may_load(self.storage, (C::TICKER.into(), QuoteC::TICKER.into()))?;
+        let next_samples = load_feed::<C, QuoteC>(feed_bin)
+            .and_then(|feed| feed.samples(&self.feeds.config, self.at, self.total_feeders))?;
+        debug_assert_eq!(next_samples.len(), self.samples.len());
+
+        let total_samples = next_samples
+            .into_iter()
+            .zip(self.samples)
+            .map(|(next_sample, sample)| {
+                next_sample
+                    .zip(sample)
+                    .map(|(next_price, price)| next_price * price)
+            })
+            .collect();
+        SamplesCollect::do_collect(
+            self.currency_path,
+            self.feeds,
+            self.storage,
+            self.at,
+            self.total_feeders,
+            total_samples,
+            self.cmd,
+        )
+    }
+}
+
 fn add_observation<G, QuoteG>(
     feed_bin: Option<PriceFeedBin>,
     from: &Addr,
     at: Timestamp,
     price: &PriceDTO<G, QuoteG>,
-    valid_since: Timestamp,
-) -> Result<PriceFeedBin, PriceFeedsError>
+    config: &Config,
+) -> Result<(PriceFeedBin, bool), PriceFeedsError>
 where
     G: Group,
     QuoteG: Group,
 {
-    debug_assert!(valid_since < at);
     struct AddObservation<'a> {
         feed_bin: Option<PriceFeedBin>,
         from: &'a Addr,
         at: Timestamp,
-        valid_since: Timestamp,
+        config: &'a Config,
     }
 
     impl<'a> WithPrice for AddObservation<'a> {
-        type Output = PriceFeedBin;
+        type Output = (PriceFeedBin, bool);
         type Error = PriceFeedsError;
 
         fn exec<C, QuoteC>(self, price: Price<C, QuoteC>) -> Result<Self::Output, Self::Error>
@@ -227,9 +568,16 @@ where
             QuoteC: Currency,
         {
             load_feed(self.feed_bin).and_then(|feed| {
-                let feed =
-                    feed.add_observation(self.from.clone(), self.at, price, self.valid_since);
-                postcard::to_allocvec(&feed).map_err(Into::into)
+                let deviates = feed.deviates(self.config, self.from, self.at, price);
+                let feed = feed.add_observation(
+                    self.from.clone(),
+                    self.at,
+                    price,
+                    self.config.feed_valid_since(self.at),
+                );
+                postcard::to_allocvec(&feed)
+                    .map(|feed_bin| (feed_bin, deviates))
+                    .map_err(Into::into)
             })
         }
     }
@@ -239,7 +587,7 @@ where
             feed_bin,
             from,
             at,
-            valid_since,
+            config,
         },
     )
 }
@@ -255,11 +603,12 @@ mod test {
         coin::Coin,
         duration::Duration,
         percent::Percent,
+        period::Period,
         price::{self, dto::PriceDTO, Price},
     };
     use sdk::cosmwasm_std::{Addr, MemoryStorage, Timestamp};
 
-    use crate::{error::PriceFeedsError, market_price::Config};
+    use crate::{config::CircuitBreaker, error::PriceFeedsError, market_price::Config};
 
     use super::PriceFeeds;
 
@@ -428,6 +777,199 @@ mod test {
         );
     }
 
+    #[test]
+    fn price_samples() {
+        let feeds = PriceFeeds::<SuperGroup>::new(FEEDS_NAMESPACE, config());
+        let mut storage = MemoryStorage::new();
+        let (price53_1, price53_2, price34) = feed_samples(&feeds, &mut storage);
+
+        let samples53: Vec<Option<PriceDTO<SuperGroup, SuperGroup>>> = [
+            price53_1, price53_1, price53_1, price53_2, price53_2, price53_2,
+        ]
+        .into_iter()
+        .map(|price| Some(price.into()))
+        .collect();
+        assert_eq!(
+            Ok(samples53),
+            feeds.price_samples::<SuperGroupTestC3, SuperGroup, _>(
+                &storage,
+                NOW,
+                TOTAL_FEEDERS,
+                [SuperGroupTestC5::TICKER, SuperGroupTestC3::TICKER].into_iter()
+            )
+        );
+
+        let samples54: Vec<Option<PriceDTO<SuperGroup, SuperGroup>>> = [
+            None,
+            None,
+            Some(price53_1 * price34),
+            Some(price53_2 * price34),
+            Some(price53_2 * price34),
+            Some(price53_2 * price34),
+        ]
+        .into_iter()
+        .map(|price| price.map(Into::into))
+        .collect();
+        assert_eq!(
+            Ok(samples54),
+            feeds.price_samples::<SuperGroupTestC4, SuperGroup, _>(
+                &storage,
+                NOW,
+                TOTAL_FEEDERS,
+                [
+                    SuperGroupTestC5::TICKER,
+                    SuperGroupTestC3::TICKER,
+                    SuperGroupTestC4::TICKER
+                ]
+                .into_iter()
+            )
+        );
+
+        assert_eq!(
+            Err(PriceFeedsError::NoPrice()),
+            feeds.price_samples::<SuperGroupTestC2, SuperGroup, _>(
+                &storage,
+                NOW,
+                TOTAL_FEEDERS,
+                [SuperGroupTestC5::TICKER, SuperGroupTestC2::TICKER].into_iter()
+            )
+        );
+    }
+
+    #[test]
+    fn twap() {
+        let feeds = PriceFeeds::<SuperGroup>::new(FEEDS_NAMESPACE, config());
+        let mut storage = MemoryStorage::new();
+        feed_samples(&feeds, &mut storage);
+
+        let twap53 = |from: u64, till: u64| {
+            feeds.twap::<SuperGroupTestC3, SuperGroup, _>(
+                &storage,
+                NOW,
+                TOTAL_FEEDERS,
+                [SuperGroupTestC5::TICKER, SuperGroupTestC3::TICKER].into_iter(),
+                Period::from_till(
+                    Timestamp::from_seconds(from),
+                    &Timestamp::from_seconds(till),
+                ),
+            )
+        };
+        let price53 = |c, q| -> Result<PriceDTO<SuperGroup, SuperGroup>, PriceFeedsError> {
+            Ok(price::total_of(Coin::<SuperGroupTestC5>::new(c))
+                .is(Coin::<SuperGroupTestC3>::new(q))
+                .into())
+        };
+
+        assert_eq!(price53(1, 3), twap53(30, 60));
+        assert_eq!(price53(3, 8), twap53(40, 50));
+        assert_eq!(price53(2, 7), twap53(45, 60));
+        assert_eq!(price53(1, 2), twap53(31, 32));
+
+        assert!(matches!(
+            twap53(29, 60),
+            Err(PriceFeedsError::InvalidPeriod(_))
+        ));
+        assert!(matches!(
+            twap53(40, 61),
+            Err(PriceFeedsError::InvalidPeriod(_))
+        ));
+        assert!(matches!(
+            twap53(40, 40),
+            Err(PriceFeedsError::InvalidPeriod(_))
+        ));
+
+        assert_eq!(
+            Err(PriceFeedsError::NoPrice()),
+            feeds.twap::<SuperGroupTestC4, SuperGroup, _>(
+                &storage,
+                NOW,
+                TOTAL_FEEDERS,
+                [
+                    SuperGroupTestC5::TICKER,
+                    SuperGroupTestC3::TICKER,
+                    SuperGroupTestC4::TICKER
+                ]
+                .into_iter(),
+                Period::from_till(Timestamp::from_seconds(30), &Timestamp::from_seconds(40)),
+            )
+        );
+    }
+
+    #[test]
+    fn sample_jumps() {
+        let sample_jumps = |config: Config, at: u64| {
+            let feeds = PriceFeeds::<SuperGroup>::new(FEEDS_NAMESPACE, config);
+            let mut storage = MemoryStorage::new();
+            feed_samples(&feeds, &mut storage);
+
+            feeds.sample_jumps::<SuperGroupTestC3, _>(
+                &storage,
+                Timestamp::from_seconds(at),
+                TOTAL_FEEDERS,
+                [SuperGroupTestC5::TICKER, SuperGroupTestC3::TICKER].into_iter(),
+            )
+        };
+        let circuit_breaker = |max_sample_deviation| {
+            config().with_circuit_breaker(CircuitBreaker::new(
+                Percent::from_percent(max_sample_deviation),
+                Duration::HOUR,
+            ))
+        };
+
+        // the newest sample doubles the previous one
+        assert_eq!(Ok(false), sample_jumps(config(), 50));
+        assert_eq!(Ok(true), sample_jumps(circuit_breaker(50), 50));
+        assert_eq!(Ok(false), sample_jumps(circuit_breaker(100), 50));
+
+        // the newest sample repeats the previous one
+        assert_eq!(Ok(false), sample_jumps(circuit_breaker(50), NOW.seconds()));
+    }
+
+    /// Feed C5/C3 at the first and the fourth sample periods, and C3/C4 at the third one
+    fn feed_samples(
+        feeds: &PriceFeeds<'_, SuperGroup>,
+        storage: &mut MemoryStorage,
+    ) -> (
+        Price<SuperGroupTestC5, SuperGroupTestC3>,
+        Price<SuperGroupTestC5, SuperGroupTestC3>,
+        Price<SuperGroupTestC3, SuperGroupTestC4>,
+    ) {
+        let price53_1 =
+            price::total_of(Coin::<SuperGroupTestC5>::new(1)).is(Coin::<SuperGroupTestC3>::new(2));
+        let price53_2 =
+            price::total_of(Coin::<SuperGroupTestC5>::new(1)).is(Coin::<SuperGroupTestC3>::new(4));
+        let price34 =
+            price::total_of(Coin::<SuperGroupTestC3>::new(1)).is(Coin::<SuperGroupTestC4>::new(3));
+        let feeder = Addr::unchecked(FEEDER);
+
+        feeds
+            .feed(
+                storage,
+                Timestamp::from_seconds(32),
+                &feeder,
+                &[price53_1.into()],
+            )
+            .unwrap();
+        feeds
+            .feed(
+                storage,
+                Timestamp::from_seconds(42),
+                &feeder,
+                &[price34.into()],
+            )
+            .unwrap();
+        feeds
+            .feed(
+                storage,
+                Timestamp::from_seconds(47),
+                &feeder,
+                &[price53_2.into()],
+            )
+            .unwrap();
+
+        (price53_1, price53_2, price34)
+    }
+
     fn config() -> Config {
         Config::new(
             Percent::HUNDRED,
@@ -26,7 +26,7 @@ const DISCOUNTING_FACTOR: Percent = Percent::from_permille(750);
 fn register_feeder() {
     let mut deps = mock_dependencies();
 
-    let control = PriceFeeders::new("foo");
+    let control = PriceFeeders::new("foo", "bar");
     let f_address = deps.api.addr_validate("address1").unwrap();
     let resp = control.is_registered(&deps.storage, &f_address).unwrap();
     assert!(!resp);
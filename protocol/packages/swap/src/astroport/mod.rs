@@ -68,6 +68,7 @@ where
         trx: &mut Transaction,
         sender: HostAccount,
         token_in: &CoinDTO<GIn>,
+        min_token_out: Amount,
         swap_path: &SwapPath,
     ) -> Result<()>
     where
@@ -80,8 +81,8 @@ where
         to_operations::<GSwap>(&token_in.denom, swap_path)
             .map(|operations| ExecuteMsg::ExecuteSwapOperations {
                 operations,
-                minimum_receive: None, // disable checks on the received amount
-                to: None,              // means the sender
+                minimum_receive: Some(min_token_out.into()),
+                to: None,                           // means the sender
                 max_spread: Some(Self::MAX_IMPACT), // if None that would be equivalent to `astroport::pair::DEFAULT_SLIPPAGE`, i.e. 0.5%
             })
             .and_then(|swap_msg| cosmwasm_std::to_json_vec(&swap_msg).map_err(Into::into))
@@ -49,7 +49,7 @@ where
 
         let ExecuteMsg::ExecuteSwapOperations {
             operations,
-            minimum_receive: None,
+            minimum_receive: Some(min_token_out),
             to: None,
             max_spread: Some(Self::MAX_IMPACT),
         } = cosmwasm_std::from_json(msg).unwrap_or_else(|_| {
@@ -66,6 +66,7 @@ where
 
         SwapRequest {
             token_in,
+            min_token_out: min_token_out.into(),
             swap_path,
         }
     }
@@ -30,21 +30,18 @@ impl ExactAmountIn for Impl {
         trx: &mut Transaction,
         sender: HostAccount,
         token_in: &CoinDTO<GIn>,
+        min_token_out: Amount,
         swap_path: &SwapPath,
     ) -> Result<()>
     where
         GIn: Group,
         GSwap: Group,
     {
-        // TODO bring the token balances, weights and swapFee-s from the DEX pools
-        // into the oracle in order to calculate the tokenOut as per the formula at
-        // https://docs.osmosis.zone/osmosis-core/modules/gamm/#swap.
-        // Then apply the parameterized maximum slippage to get the minimum amount.
-        // For the first version, we accept whatever price impact and slippage.
-        const MIN_OUT_AMOUNT: &str = "1";
+        // Osmosis requires a positive minimum amount of the output token
+        const MIN_OUT_AMOUNT: Amount = 1;
         let routes = to_route::<GSwap>(swap_path)?;
         let token_in = Some(to_dex_cwcoin(token_in)?);
-        let token_out_min_amount = MIN_OUT_AMOUNT.into();
+        let token_out_min_amount = min_token_out.max(MIN_OUT_AMOUNT).to_string();
         let msg = RequestMsg {
             sender: sender.into(),
             routes,
@@ -27,12 +27,13 @@ impl ExactAmountInSkel for Impl {
             pattern_match_else(type_name::<RequestMsg>())
         };
 
-        assert_eq!({ token_out_min_amount }, "1");
-
         let token_in = parse_dex_token(&token_in.amount, &token_in.denom);
 
         SwapRequest {
             token_in,
+            min_token_out: token_out_min_amount
+                .parse()
+                .expect("Expected minimum swap-out amount to be an unsigned integer!"),
             swap_path: routes
                 .into_iter()
                 .map(
@@ -24,6 +24,7 @@ where
     GIn: Group,
 {
     pub token_in: CoinDTO<GIn>,
+    pub min_token_out: Amount,
     pub swap_path: SwapPath,
 }
 
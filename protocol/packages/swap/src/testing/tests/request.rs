@@ -3,7 +3,7 @@ use currency::{
     Currency as _,
 };
 use dex::swap::ExactAmountIn;
-use finance::coin::{Coin, CoinDTO};
+use finance::coin::{Amount, Coin, CoinDTO};
 use oracle::api::swap::SwapTarget;
 use platform::trx::Transaction;
 use sdk::{
@@ -19,6 +19,7 @@ use crate::{
 #[test]
 fn build_and_parse() {
     let expected_token_in: CoinDTO<SubGroup> = Coin::<SubGroupTestC1>::new(20).into();
+    let expected_min_token_out: Amount = 15;
 
     let expected_swap_path = vec![
         SwapTarget {
@@ -31,19 +32,26 @@ fn build_and_parse() {
         },
     ];
 
-    let request: CosmosAny = build_request(expected_token_in.clone(), expected_swap_path.clone());
+    let request: CosmosAny = build_request(
+        expected_token_in.clone(),
+        expected_min_token_out,
+        expected_swap_path.clone(),
+    );
 
     let SwapRequest {
         token_in,
+        min_token_out,
         swap_path,
     } = <Impl as ExactAmountInSkel>::parse_request::<SubGroup, SuperGroup>(request);
 
     assert_eq!(token_in, expected_token_in);
+    assert_eq!(min_token_out, expected_min_token_out);
     assert_eq!(swap_path, expected_swap_path);
 }
 
 fn build_request(
     expected_token_in: CoinDTO<SubGroup>,
+    expected_min_token_out: Amount,
     expected_swap_path: Vec<SwapTarget>,
 ) -> CosmosAny {
     let mut tx = Transaction::default();
@@ -52,6 +60,7 @@ fn build_request(
         &mut tx,
         String::from("host_account").try_into().unwrap(),
         &expected_token_in,
+        expected_min_token_out,
         &expected_swap_path,
     )
     .unwrap();
@@ -4,7 +4,7 @@ use finance::{coin::Coin, duration::Duration, liability::Liability, percent::Per
 use lease::{
     api::{
         open::{
-            ConnectionParams, Ics20Channel, LoanForm, NewLeaseContract, NewLeaseForm,
+            ConnectionParams, IbcParams, Ics20Channel, LoanForm, NewLeaseContract, NewLeaseForm,
             PositionSpecDTO,
         },
         query::{StateQuery, StateResponse},
@@ -106,6 +106,8 @@ impl Instantiator {
                     annual_margin_interest: config.annual_margin_interest,
                     due_period: config.lease_due_period,
                 },
+                max_slippage: config.max_slippage,
+                max_duration: config.max_duration,
                 reserve: addresses.reserve,
                 time_alarms: addresses.time_alarms,
                 market_price_oracle: addresses.oracle,
@@ -154,6 +156,8 @@ pub(crate) struct InstantiatorConfig {
     pub lease_due_period: Duration,
     // Dex
     pub dex: ConnectionParams,
+    pub max_slippage: Percent,
+    pub max_duration: Option<Duration>,
 }
 
 impl Default for InstantiatorConfig {
@@ -177,7 +181,10 @@ impl Default for InstantiatorConfig {
                     local_endpoint: "channel-0".into(),
                     remote_endpoint: "channel-2048".into(),
                 },
+                ibc: IbcParams::default(),
             },
+            max_slippage: Percent::from_percent(5),
+            max_duration: None,
         }
     }
 }
@@ -3,7 +3,7 @@ use std::collections::HashSet;
 use currency::Currency;
 use finance::{coin::Coin, duration::Duration, liability::Liability, percent::Percent, test};
 use lease::api::{
-    open::{ConnectionParams, Ics20Channel, PositionSpecDTO},
+    open::{ConnectionParams, IbcParams, Ics20Channel, PositionSpecDTO},
     LpnCoinDTO,
 };
 use leaser::{
@@ -26,6 +26,8 @@ impl Instantiator {
 
     pub const REPAYMENT_PERIOD: Duration = Duration::from_days(90);
 
+    pub const MAX_SLIPPAGE: Percent = Percent::from_percent(5);
+
     pub const FIRST_LIQ_WARN: Percent = Percent::from_permille(730);
     pub const SECOND_LIQ_WARN: Percent = Percent::from_permille(750);
     pub const THIRD_LIQ_WARN: Percent = Percent::from_permille(780);
@@ -88,7 +90,10 @@ impl Instantiator {
             protocols_registry,
             lease_interest_rate_margin: Self::INTEREST_RATE_MARGIN,
             lease_position_spec: Self::position_spec(),
+            lease_asset_specs: Default::default(),
             lease_due_period: Self::REPAYMENT_PERIOD,
+            lease_max_slippage: Self::MAX_SLIPPAGE,
+            lease_max_duration: None,
             time_alarms: alarms.time_alarm,
             market_price_oracle: alarms.market_price_oracle,
             dex: ConnectionParams {
@@ -97,6 +102,7 @@ impl Instantiator {
                     local_endpoint: TestCase::LEASER_IBC_CHANNEL.into(),
                     remote_endpoint: "channel-422".into(),
                 },
+                ibc: IbcParams::default(),
             },
         };
 
@@ -72,6 +72,7 @@ impl Instantiator {
             lease_code: CodeId::from(lease_code).into(),
             borrow_rate,
             min_utilization,
+            min_queued_withdraw: 1,
         };
 
         app.instantiate(
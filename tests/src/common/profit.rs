@@ -1,4 +1,5 @@
-use dex::{ConnectionParams, Ics20Channel};
+use dex::{ConnectionParams, IbcParams, Ics20Channel};
+use finance::percent::Percent;
 use profit::{
     contract::{execute, instantiate, query, reply, sudo},
     msg::InstantiateMsg,
@@ -16,6 +17,8 @@ use super::{
 pub(crate) struct Instantiator;
 
 impl Instantiator {
+    pub const MAX_SLIPPAGE: Percent = Percent::from_percent(5);
+
     #[track_caller]
     pub fn instantiate(
         app: &mut App,
@@ -42,7 +45,9 @@ impl Instantiator {
                     local_endpoint: TestCase::PROFIT_IBC_CHANNEL.into(),
                     remote_endpoint: "channel-262".into(),
                 },
+                ibc: IbcParams::default(),
             },
+            max_slippage: Self::MAX_SLIPPAGE,
         };
 
         app.instantiate(code_id, Addr::unchecked(ADMIN), &msg, &[], "profit", None)
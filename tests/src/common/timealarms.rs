@@ -1,10 +1,10 @@
 use sdk::cosmwasm_std::Addr;
 use timealarms::{
-    contract::{execute, instantiate, reply},
+    contract::{execute, instantiate, query, reply, sudo},
     msg::InstantiateMsg,
 };
 
-use super::{dummy_query, test_case::app::App, CwContractWrapper, ADMIN};
+use super::{test_case::app::App, CwContractWrapper, ADMIN};
 
 pub(crate) struct Instantiator;
 
@@ -12,7 +12,9 @@ impl Instantiator {
     #[track_caller]
     pub fn instantiate(app: &mut App) -> Addr {
         // TODO [Rust 1.70] Convert to static item with OnceCell
-        let endpoints = CwContractWrapper::new(execute, instantiate, dummy_query).with_reply(reply);
+        let endpoints = CwContractWrapper::new(execute, instantiate, query)
+            .with_reply(reply)
+            .with_sudo(sudo);
 
         let code_id = app.store_code(Box::new(endpoints));
 
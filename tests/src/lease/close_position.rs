@@ -2,6 +2,7 @@ use currencies::PaymentGroup;
 use currency::Currency;
 use finance::{
     coin::{Amount, Coin},
+    duration::Duration,
     price,
     zero::Zero,
 };
@@ -13,6 +14,7 @@ use lease::{
     },
     error::ContractError,
 };
+use leaser::msg::SudoMsg as LeaserSudoMsg;
 use platform::coin_legacy::to_cosmwasm_on_dex;
 use sdk::{
     cosmwasm_std::{Addr, Event},
@@ -50,6 +52,69 @@ fn close_by_another_user() {
     );
 }
 
+#[test]
+fn close_by_operator() {
+    let mut test_case = super::create_test_case::<PaymentCurrency>();
+    let lease = super::open_lease(&mut test_case, DOWNPAYMENT, None);
+    let operator = Addr::unchecked(ADMIN);
+    let close_msg = ExecuteMsg::ClosePosition(PositionClose::FullClose(FullClose {}));
+
+    assert_unauthorized(
+        &mut test_case,
+        lease.clone(),
+        ExecuteMsg::AddOperator(operator.clone()),
+    );
+
+    send_close(
+        &mut test_case,
+        lease.clone(),
+        &ExecuteMsg::AddOperator(operator.clone()),
+    )
+    .unwrap_response();
+    assert!(matches!(
+        super::state_query(&test_case, lease.as_str()),
+        StateResponse::Opened { operators, .. } if operators == [operator.clone()]
+    ));
+    assert_unauthorized(
+        &mut test_case,
+        lease.clone(),
+        ExecuteMsg::RemoveOperator(operator.clone()),
+    );
+
+    send_close(
+        &mut test_case,
+        lease.clone(),
+        &ExecuteMsg::RemoveOperator(operator.clone()),
+    )
+    .unwrap_response();
+    assert_unauthorized(&mut test_case, lease.clone(), close_msg.clone());
+
+    send_close(
+        &mut test_case,
+        lease.clone(),
+        &ExecuteMsg::AddOperator(operator.clone()),
+    )
+    .unwrap_response();
+    let mut response_close: ResponseWithInterChainMsgs<'_, ()> = test_case
+        .app
+        .execute(operator, lease.clone(), &close_msg, &[])
+        .unwrap()
+        .ignore_response();
+    let _requests: Vec<SwapRequest<PaymentGroup>> = common::swap::expect_swap(
+        &mut response_close,
+        TestCase::DEX_CONNECTION_ID,
+        TestCase::LEASE_ICA_ID,
+    );
+
+    assert!(matches!(
+        super::state_query(&test_case, lease.as_str()),
+        StateResponse::Opened {
+            in_progress: Some(_),
+            ..
+        }
+    ));
+}
+
 #[test]
 fn full_close() {
     let lease_amount: LeaseCoin = lease_amount();
@@ -87,6 +152,94 @@ fn full_close() {
     );
 }
 
+#[test]
+fn close_on_expiry() {
+    let lease_amount: LeaseCoin = lease_amount();
+    let lease_amount_in_lpn: LpnCoin = price::total(lease_amount, super::price_lpn_of());
+    let customer = Addr::unchecked(USER);
+    let max_duration = Duration::from_days(1);
+    let mut test_case = super::create_test_case::<PaymentCurrency>();
+
+    let _: AppResponse = test_case
+        .app
+        .sudo(
+            test_case.address_book.leaser().clone(),
+            &LeaserSudoMsg::LeaseMaxDuration {
+                max_duration: Some(max_duration),
+            },
+        )
+        .unwrap()
+        .unwrap_response();
+
+    let lease = super::open_lease(&mut test_case, DOWNPAYMENT, None);
+
+    test_case.app.time_shift(max_duration);
+    super::feed_price(&mut test_case);
+
+    let mut response_alarm: ResponseWithInterChainMsgs<'_, AppResponse> = test_case
+        .app
+        .execute(
+            test_case.address_book.time_alarms().clone(),
+            lease.clone(),
+            &ExecuteMsg::TimeAlarm {},
+            &[],
+        )
+        .unwrap();
+
+    let requests: Vec<SwapRequest<PaymentGroup>> = common::swap::expect_swap(
+        &mut response_alarm,
+        TestCase::DEX_CONNECTION_ID,
+        TestCase::LEASE_ICA_ID,
+    );
+
+    assert!(response_alarm
+        .unwrap_response()
+        .events
+        .iter()
+        .any(|event| event.ty == "wasm-ls-expired"));
+
+    let mut response_swap: ResponseWithInterChainMsgs<'_, ()> = common::swap::do_swap(
+        &mut test_case.app,
+        lease.clone(),
+        TestCase::ica_addr(lease.as_str(), TestCase::LEASE_ICA_ID),
+        requests.into_iter(),
+        |amount: Amount, _, _| {
+            assert_eq!(amount, lease_amount.into());
+
+            lease_amount_in_lpn.into()
+        },
+    )
+    .ignore_response();
+
+    let transfer_amount: CwCoin = ibc::expect_remote_transfer(
+        &mut response_swap,
+        TestCase::DEX_CONNECTION_ID,
+        TestCase::LEASE_ICA_ID,
+    );
+
+    assert_eq!(transfer_amount, to_cosmwasm_on_dex(lease_amount_in_lpn));
+
+    let _: AppResponse = ibc::do_transfer(
+        &mut test_case.app,
+        TestCase::ica_addr(lease.as_str(), TestCase::LEASE_ICA_ID),
+        lease.clone(),
+        true,
+        &transfer_amount,
+    )
+    .unwrap_response();
+
+    assert_eq!(
+        StateResponse::Closed(),
+        super::state_query(&test_case, lease.as_str())
+    );
+
+    leaser::assert_no_leases(
+        &test_case.app,
+        test_case.address_book.leaser().clone(),
+        customer,
+    );
+}
+
 #[test]
 fn partial_close_loan_not_closed() {
     let lease_amount: LeaseCoin = lease_amount();
@@ -166,6 +319,7 @@ fn partial_close_loan_closed() {
     assert_eq!(
         StateResponse::Paid {
             amount: (lease_amount - close_amount).into(),
+            operators: vec![],
             in_progress: None
         },
         state
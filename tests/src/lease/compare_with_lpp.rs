@@ -1,4 +1,4 @@
-use ::lease::api::query::StateResponse;
+use ::lease::api::{position::ClosePolicy, query::StateResponse};
 use finance::{
     coin::{Amount, Coin},
     duration::Duration,
@@ -41,6 +41,9 @@ fn manual_calculation() {
         due_margin: LpnCoin::new(13_737_769_080).into(),
         due_interest: LpnCoin::new(32_054_794_520).into(),
         validity: super::block_time(&test_case),
+        expiry: None,
+        close_policy: ClosePolicy::default(),
+        operators: vec![],
         in_progress: None,
     };
 
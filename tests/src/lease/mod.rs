@@ -7,7 +7,10 @@ use finance::{
     percent::Percent,
     price::{self, Price},
 };
-use lease::api::query::{StateQuery, StateResponse};
+use lease::api::{
+    position::ClosePolicy,
+    query::{StateQuery, StateResponse},
+};
 use leaser::msg::QuoteResponse;
 use sdk::cosmwasm_std::{coin, Addr, Timestamp};
 
@@ -26,6 +29,7 @@ mod heal;
 mod liquidation;
 mod open;
 mod repay;
+mod transfer_ownership;
 
 type LpnCurrency = LpnC;
 type LpnCoin = Coin<LpnCurrency>;
@@ -415,6 +419,9 @@ where
         )
         .into(),
         validity: block_time(test_case),
+        expiry: None,
+        close_policy: ClosePolicy::default(),
+        operators: vec![],
         in_progress: None,
     }
 }
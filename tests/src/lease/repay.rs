@@ -1,6 +1,10 @@
 use std::slice;
 
-use ::lease::api::{query::StateResponse, ExecuteMsg};
+use ::lease::api::{
+    position::ClosePolicy,
+    query::{HistoryQuery, HistoryRecord, PaymentKind, StateResponse},
+    ExecuteMsg,
+};
 use ::swap::testing::SwapRequest;
 use currencies::PaymentGroup;
 use currency::Currency;
@@ -150,6 +154,7 @@ fn full_repay() {
     );
     let expected_result = StateResponse::Paid {
         amount: LeaseCoin::into(expected_amount),
+        operators: vec![],
         in_progress: None,
     };
     let query_result = super::state_query(&test_case, lease_address.as_str());
@@ -157,6 +162,46 @@ fn full_repay() {
     assert_eq!(query_result, expected_result);
 }
 
+#[test]
+fn repay_history() {
+    let mut test_case = super::create_test_case::<PaymentCurrency>();
+    let downpayment: PaymentCoin = DOWNPAYMENT;
+    let lease_address = super::open_lease(&mut test_case, downpayment, None);
+    let borrowed_lpn = super::quote_borrow(&test_case, downpayment);
+    let borrowed: PaymentCoin = price::total(borrowed_lpn, super::price_lpn_of().inv());
+    let partial_payment: PaymentCoin = Fraction::<PaymentCoin>::of(&Rational::new(1, 2), borrowed);
+
+    repay(&mut test_case, lease_address.clone(), partial_payment);
+    repay(&mut test_case, lease_address.clone(), borrowed);
+
+    let history: Vec<HistoryRecord> = test_case
+        .app
+        .query()
+        .query_wasm_smart(
+            lease_address,
+            &HistoryQuery::History {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(2, history.len());
+    assert!(history
+        .iter()
+        .all(|record| record.kind == PaymentKind::Repayment));
+    assert_eq!(
+        vec![(0, false), (1, true)],
+        history
+            .iter()
+            .map(|record| (record.id, record.loan_close))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        LpnCoin::try_from(&history[0].payment).unwrap(),
+        price::total(partial_payment, super::price_lpn_of())
+    );
+}
+
 #[test]
 fn full_repay_with_max_ltd() {
     let mut test_case = super::create_test_case::<PaymentCurrency>();
@@ -180,6 +225,9 @@ fn full_repay_with_max_ltd() {
         due_margin: LpnCoin::ZERO.into(),
         due_interest: LpnCoin::ZERO.into(),
         validity: Timestamp::from_nanos(1537237459879305533),
+        expiry: None,
+        close_policy: ClosePolicy::default(),
+        operators: vec![],
         in_progress: None,
     };
     let query_result = super::state_query(&test_case, lease_address.as_str());
@@ -197,6 +245,7 @@ fn full_repay_with_max_ltd() {
     );
     let expected_result = StateResponse::Paid {
         amount: LeaseCoin::into(expected_amount),
+        operators: vec![],
         in_progress: None,
     };
     let query_result = super::state_query(&test_case, lease_address.as_str());
@@ -252,6 +301,7 @@ fn full_repay_with_excess() {
                 price::total(downpayment + borrowed, price_lpn_of()),
                 price_lpn_of().inv(),
             )),
+            operators: vec![],
             in_progress: None,
         }
     );
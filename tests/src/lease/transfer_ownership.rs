@@ -0,0 +1,82 @@
+use lease::api::{
+    position::{FullClose, PositionClose},
+    ExecuteMsg,
+};
+use sdk::{
+    cosmwasm_std::{Addr, Event},
+    cw_multi_test::AppResponse,
+};
+
+use crate::common::{leaser, ADMIN, USER};
+
+use super::{LeaseTestCase, PaymentCurrency, DOWNPAYMENT};
+
+#[test]
+fn transfer_by_another_user() {
+    let mut test_case = super::create_test_case::<PaymentCurrency>();
+    let lease = super::open_lease(&mut test_case, DOWNPAYMENT, None);
+
+    assert_unauthorized(
+        &mut test_case,
+        Addr::unchecked(ADMIN),
+        lease,
+        ExecuteMsg::TransferOwnership {
+            new_customer: Addr::unchecked(ADMIN),
+        },
+    );
+}
+
+#[test]
+fn transfer() {
+    let mut test_case = super::create_test_case::<PaymentCurrency>();
+    let lease = super::open_lease(&mut test_case, DOWNPAYMENT, None);
+    let customer = Addr::unchecked(USER);
+    let new_customer = Addr::unchecked(ADMIN);
+
+    let response: AppResponse = test_case
+        .app
+        .execute(
+            customer.clone(),
+            lease.clone(),
+            &ExecuteMsg::TransferOwnership {
+                new_customer: new_customer.clone(),
+            },
+            &[],
+        )
+        .unwrap()
+        .unwrap_response();
+    response.assert_event(
+        &Event::new("wasm-ls-transfer-ownership")
+            .add_attribute("id", lease.clone())
+            .add_attribute("from", customer.clone())
+            .add_attribute("to", new_customer.clone()),
+    );
+
+    leaser::assert_no_leases(
+        &test_case.app,
+        test_case.address_book.leaser().clone(),
+        customer.clone(),
+    );
+    leaser::assert_lease(
+        &test_case.app,
+        test_case.address_book.leaser().clone(),
+        new_customer,
+        &lease,
+    );
+
+    assert_unauthorized(
+        &mut test_case,
+        customer,
+        lease,
+        ExecuteMsg::ClosePosition(PositionClose::FullClose(FullClose {})),
+    );
+}
+
+fn assert_unauthorized(test_case: &mut LeaseTestCase, sender: Addr, lease: Addr, msg: ExecuteMsg) {
+    let err = test_case.app.execute(sender, lease, &msg, &[]).unwrap_err();
+    assert_eq!(
+        err.root_cause()
+            .downcast_ref::<access_control::error::Error>(),
+        Some(&access_control::error::Error::Unauthorized {})
+    );
+}
@@ -310,6 +310,8 @@ fn wrong_timealarms_addr() {
 
     let alarm_msg = timealarms::msg::ExecuteMsg::AddAlarm {
         time: Timestamp::from_seconds(100),
+        id: timealarms::msg::AlarmId::default(),
+        payload: None,
     };
 
     () = test_case
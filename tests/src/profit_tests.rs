@@ -2,6 +2,7 @@ use std::slice;
 
 use currencies::test::LpnC;
 use currency::Currency;
+use dex::IbcParams;
 use finance::{
     coin::{Amount, Coin},
     duration::Duration,
@@ -9,7 +10,7 @@ use finance::{
 };
 use platform::bank;
 use profit::{
-    msg::{ConfigResponse, ExecuteMsg, QueryMsg},
+    msg::{ConfigResponse, ExecuteMsg, QueryMsg, CADENCE_ALARM_ID},
     typedefs::CadenceHours,
 };
 use sdk::{
@@ -126,6 +127,28 @@ fn update_config_unauthorized() {
         .contains("Unauthorized"));
 }
 
+#[test]
+fn update_dex_ibc_unauthorized() {
+    type Lpn = LpnC;
+
+    let mut test_case = test_case::<Lpn>();
+
+    assert!(test_case
+        .app
+        .execute(
+            Addr::unchecked(USER),
+            test_case.address_book.profit().clone(),
+            &ExecuteMsg::DexIbc {
+                ibc: IbcParams::default(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .root_cause()
+        .to_string()
+        .contains("Unauthorized"));
+}
+
 #[test]
 fn on_alarm_from_unknown() {
     type Lpn = LpnC;
@@ -146,7 +169,9 @@ fn on_alarm_from_unknown() {
         .execute(
             user_addr,
             test_case.address_book.profit().clone(),
-            &profit::msg::ExecuteMsg::TimeAlarm {},
+            &profit::msg::ExecuteMsg::TimeAlarm {
+                id: CADENCE_ALARM_ID.into(),
+            },
             &[cwcoin::<Lpn, _>(40)],
         )
         .unwrap_err();
@@ -176,7 +201,9 @@ fn on_alarm_zero_balance() {
         .execute(
             test_case.address_book.time_alarms().clone(),
             test_case.address_book.profit().clone(),
-            &profit::msg::ExecuteMsg::TimeAlarm {},
+            &profit::msg::ExecuteMsg::TimeAlarm {
+                id: CADENCE_ALARM_ID.into(),
+            },
             &[],
         )
         .unwrap()
@@ -228,7 +255,9 @@ where
         .execute(
             test_case.address_book.time_alarms().clone(),
             test_case.address_book.profit().clone(),
-            &profit::msg::ExecuteMsg::TimeAlarm {},
+            &profit::msg::ExecuteMsg::TimeAlarm {
+                id: CADENCE_ALARM_ID.into(),
+            },
             &[],
         )
         .unwrap();
@@ -345,7 +374,8 @@ fn expect_transfer_events<ProtocolsRegistry, Reserve, Leaser, Lpp, Oracle>(
         );
     }
 
-    assert_eq!(response.events.len(), 4, "{:?}", response.events);
+    // no re-scheduling since the cadence alarm is a recurring one
+    assert_eq!(response.events.len(), 3, "{:?}", response.events);
 
     let profit_exec = &response.events[0];
     assert_eq!(profit_exec.ty.as_str(), "execute");
@@ -377,12 +407,7 @@ fn expect_transfer_events<ProtocolsRegistry, Reserve, Leaser, Lpp, Oracle>(
         ]
     );
 
-    let [transfer, time_alarms_exec] = if has_swap {
-        [&response.events[3], &response.events[2]]
-    } else {
-        [&response.events[2], &response.events[3]]
-    };
-
+    let transfer = &response.events[2];
     assert_eq!(transfer.ty.as_str(), "transfer", "{transfer:?}");
     assert_eq!(
         transfer.attributes,
@@ -399,16 +424,6 @@ fn expect_transfer_events<ProtocolsRegistry, Reserve, Leaser, Lpp, Oracle>(
             )
         ]
     );
-
-    assert_eq!(
-        time_alarms_exec.ty.as_str(),
-        "execute",
-        "{time_alarms_exec:?}"
-    );
-    assert_eq!(
-        time_alarms_exec.attributes,
-        [("_contract_address", test_case.address_book.time_alarms())]
-    );
 }
 
 fn expect_balances<Lpn, ProtocolsRegistry, Reserve, Leaser, Lpp, Oracle, TimeAlarms>(
@@ -585,4 +600,32 @@ fn integration_with_time_alarms() {
             .u128(),
         ::profit::profit::Profit::IBC_FEE_RESERVE.into(),
     );
+
+    // the cadence alarm recurs with no re-scheduling by the profit contract
+    test_case
+        .app
+        .time_shift(Duration::from_hours(CADENCE_HOURS));
+
+    test_case.send_funds_from_admin(
+        test_case.address_book.profit().clone(),
+        &[cwcoin::<Native, _>(500)],
+    );
+
+    let resp = test_case
+        .app
+        .execute(
+            Addr::unchecked(ADMIN),
+            test_case.address_book.time_alarms().clone(),
+            &timealarms::msg::ExecuteMsg::DispatchAlarms { max_count: 10 },
+            &[],
+        )
+        .unwrap()
+        .unwrap_response();
+
+    assert_eq!(
+        from_json(resp.data.clone().unwrap()),
+        Ok(DispatchAlarmsResponse(1))
+    );
+
+    resp.assert_event(&Event::new("wasm-time-alarm").add_attribute("delivered", "success"));
 }
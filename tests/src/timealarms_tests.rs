@@ -1,20 +1,22 @@
 use std::array::from_fn;
 
 use currencies::test::{LpnC, NativeC};
-use currency::Currency;
+use currency::{Currency, NlsPlatform};
 use finance::{coin::Coin, duration::Duration};
-use platform::tests;
+use platform::{bank, tests};
 use sdk::{
     cosmwasm_std::{coin, Addr, Attribute, Event, Timestamp},
     cw_multi_test::AppResponse,
 };
-use timealarms::msg::{AlarmsCount, DispatchAlarmsResponse};
+use timealarms::msg::{
+    AlarmId, AlarmsCount, DispatchAlarmsResponse, DispatchRewardsResponse, QueryMsg, SudoMsg,
+};
 
 use crate::common::{
     cwcoin,
     protocols::Registry,
     test_case::{builder::BlankBuilder as TestCaseBuilder, TestCase},
-    ADMIN,
+    Native, ADMIN,
 };
 
 use self::mock_lease::*;
@@ -189,6 +191,51 @@ mod mock_lease {
     }
 }
 
+/// The mock for leaser SC. It vouches for a single lease.
+mod mock_leaser {
+    use leaser::msg::QueryMsg;
+    use sdk::{
+        cosmwasm_ext::Response,
+        cosmwasm_std::{
+            to_json_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, StdError, StdResult,
+        },
+        cw_storage_plus::Item,
+        testing::CwContractWrapper,
+    };
+
+    use crate::common::{test_case::app::App, ADMIN};
+
+    const LEASE: Item<'static, Addr> = Item::new("lease");
+
+    fn instantiate(deps: DepsMut<'_>, _: Env, _: MessageInfo, lease: Addr) -> StdResult<Response> {
+        LEASE.save(deps.storage, &lease).map(|()| Response::new())
+    }
+
+    fn execute(_: DepsMut<'_>, _: Env, _: MessageInfo, _: ()) -> StdResult<Response> {
+        unimplemented!()
+    }
+
+    fn query(deps: Deps<'_>, _: Env, msg: QueryMsg) -> StdResult<Binary> {
+        match msg {
+            QueryMsg::IsLease { address } => LEASE
+                .load(deps.storage)
+                .and_then(|lease| to_json_binary(&(lease == address))),
+            _ => Err(StdError::generic_err("not implemented")),
+        }
+    }
+
+    pub(crate) fn instantiate_leaser(app: &mut App, lease: Addr) -> Addr {
+        let code_id = app.store_code(Box::new(CwContractWrapper::new(
+            execute,
+            instantiate,
+            query,
+        )));
+        app.instantiate(code_id, Addr::unchecked(ADMIN), &lease, &[], "leaser", None)
+            .unwrap()
+            .unwrap_response()
+    }
+}
+
 type Lpn = LpnC;
 
 #[test]
@@ -197,14 +244,21 @@ fn test_lease_serde() {
     use sdk::cosmwasm_std;
     use timealarms::msg::ExecuteAlarmMsg::TimeAlarm;
 
-    let LeaseTimeAlarm {} =
-        cosmwasm_std::from_json(cosmwasm_std::to_json_vec(&TimeAlarm {}).unwrap()).unwrap()
-    else {
+    let LeaseTimeAlarm {} = cosmwasm_std::from_json(
+        cosmwasm_std::to_json_vec(&TimeAlarm {
+            id: AlarmId::default(),
+            payload: None,
+        })
+        .unwrap(),
+    )
+    .unwrap() else {
         unreachable!()
     };
 
-    let TimeAlarm {} =
+    let TimeAlarm { id, payload } =
         cosmwasm_std::from_json(cosmwasm_std::to_json_vec(&LeaseTimeAlarm {}).unwrap()).unwrap();
+    assert_eq!(AlarmId::default(), id);
+    assert_eq!(None, payload);
 }
 
 fn test_case() -> TestCase<(), (), (), (), (), (), (), Addr> {
@@ -235,9 +289,29 @@ fn add_alarm<ProtocolsRegistry, Treasury, Profit, Reserve, Leaser, Lpp, Oracle>(
     >,
     recv: &Addr,
     time_secs: u64,
+) {
+    add_keyed_alarm(test_case, recv, AlarmId::default(), time_secs)
+}
+
+fn add_keyed_alarm<ProtocolsRegistry, Treasury, Profit, Reserve, Leaser, Lpp, Oracle>(
+    test_case: &mut TestCase<
+        ProtocolsRegistry,
+        Treasury,
+        Profit,
+        Reserve,
+        Leaser,
+        Lpp,
+        Oracle,
+        Addr,
+    >,
+    recv: &Addr,
+    id: AlarmId,
+    time_secs: u64,
 ) {
     let alarm_msg = timealarms::msg::ExecuteMsg::AddAlarm {
         time: Timestamp::from_seconds(time_secs),
+        id,
+        payload: None,
     };
     () = test_case
         .app
@@ -302,6 +376,123 @@ fn fired_alarms_are_removed() {
     assert_eq!(sent_alarms(&resp), Some(0));
 }
 
+#[test]
+fn keyed_alarms_are_independent() {
+    let mut test_case = test_case();
+    let lease1 = instantiate_may_fail_contract(&mut test_case.app);
+
+    add_keyed_alarm(&mut test_case, &lease1, "due".into(), 1);
+    add_keyed_alarm(&mut test_case, &lease1, "overdue".into(), 2);
+    //overwritten
+    add_keyed_alarm(&mut test_case, &lease1, "due".into(), 3);
+    add_keyed_alarm(&mut test_case, &lease1, "expiry".into(), 4);
+
+    () = test_case
+        .app
+        .execute(
+            lease1.clone(),
+            test_case.address_book.time_alarms().clone(),
+            &timealarms::msg::ExecuteMsg::RemoveAlarm {
+                id: "expiry".into(),
+            },
+            &[],
+        )
+        .unwrap()
+        .ignore_response()
+        .unwrap_response();
+
+    test_case.app.time_shift(Duration::from_secs(5));
+
+    let resp = dispatch(&mut test_case, 100);
+    assert!(!any_error(&resp));
+    assert_eq!(sent_alarms(&resp), Some(2));
+
+    // try to resend same alarms
+    let resp = dispatch(&mut test_case, 100);
+    assert!(!any_error(&resp));
+    assert_eq!(sent_alarms(&resp), Some(0));
+}
+
+#[test]
+fn dispatch_rewards() {
+    let mut test_case = test_case();
+    let lease1 = instantiate_no_reschedule_contract(&mut test_case.app);
+    let lease2 = instantiate_no_reschedule_contract(&mut test_case.app);
+    let leaser = mock_leaser::instantiate_leaser(&mut test_case.app, lease2.clone());
+    let keeper = Addr::unchecked("keeper");
+
+    test_case.send_funds_from_admin(
+        test_case.address_book.time_alarms().clone(),
+        &[cwcoin::<Native, _>(15)],
+    );
+
+    // the budget is not covered by the balance
+    _ = test_case
+        .app
+        .sudo(
+            test_case.address_book.time_alarms().clone(),
+            &SudoMsg::DispatchRewards {
+                per_alarm: Coin::new(10),
+                top_up: Coin::new(20),
+                leasers: vec![leaser.clone()],
+            },
+        )
+        .unwrap_err();
+
+    () = test_case
+        .app
+        .sudo(
+            test_case.address_book.time_alarms().clone(),
+            &SudoMsg::DispatchRewards {
+                per_alarm: Coin::new(10),
+                top_up: Coin::new(15),
+                leasers: vec![leaser.clone()],
+            },
+        )
+        .unwrap()
+        .ignore_response()
+        .unwrap_response();
+
+    add_alarm(&mut test_case, &lease1, 1);
+    add_alarm(&mut test_case, &lease2, 2);
+
+    test_case.app.time_shift(Duration::from_secs(5));
+
+    let resp = test_case
+        .app
+        .execute(
+            keeper.clone(),
+            test_case.address_book.time_alarms().clone(),
+            &timealarms::msg::ExecuteMsg::DispatchAlarms { max_count: 100 },
+            &[],
+        )
+        .unwrap()
+        .unwrap_response();
+    assert!(!any_error(&resp));
+    assert_eq!(sent_alarms(&resp), Some(2));
+
+    // only the delivery to the lease the leaser vouches for is rewarded
+    assert_eq!(
+        Coin::<NlsPlatform>::new(10),
+        bank::balance(&keeper, test_case.app.query()).unwrap()
+    );
+    assert_eq!(
+        DispatchRewardsResponse {
+            per_alarm: Coin::new(10),
+            budget: Coin::new(5),
+            leasers: vec![leaser],
+        },
+        test_case
+            .app
+            .query()
+            .query_wasm_smart(
+                test_case.address_book.time_alarms().clone(),
+                &QueryMsg::DispatchRewards {},
+            )
+            .unwrap()
+    );
+}
+
 #[test]
 fn no_reschedule_alarm() {
     let mut test_case = test_case();
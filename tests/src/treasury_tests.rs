@@ -79,7 +79,14 @@ fn test_config() {
     assert_eq!(response.data, None);
     assert_eq!(
         &response.events,
-        &[Event::new("sudo").add_attribute("_contract_address", "contract4"),]
+        &[
+            Event::new("sudo").add_attribute("_contract_address", "contract4"),
+            // the recurring alarm is re-registered with the new cadence
+            Event::new("execute").add_attribute(
+                "_contract_address",
+                test_case.address_book.time_alarms().clone()
+            ),
+        ]
     );
 
     let resp = query_config(&test_case);
@@ -183,7 +190,8 @@ fn check_events(
     protocols_nb: usize,
     exp_reward: Coin<NlsPlatform>,
 ) {
-    assert_eq!(events.len(), 2 + protocols_nb * 2, "{:?}", events);
+    // the cadence alarm is recurring, no re-scheduling
+    assert_eq!(events.len(), 1 + protocols_nb * 2, "{:?}", events);
 
     let mut event_index = 0;
     {
@@ -232,12 +240,7 @@ fn check_events(
         });
     }
 
-    let time_alarms_exec = &events[event_index];
-    assert_eq!(time_alarms_exec.ty.as_str(), "execute");
-    assert_eq!(
-        time_alarms_exec.attributes,
-        [("_contract_address", &test_case.address_book.time_alarms())]
-    );
+    assert_eq!(event_index, events.len());
 }
 
 fn query_config(test_case: &DispatcherTestCase) -> ConfigResponse {